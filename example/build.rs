@@ -29,5 +29,49 @@ fn main() -> Result<()> {
     .output("src/shader_bindings.rs")
     .build()?
     .generate()
+    .into_diagnostic()?;
+
+  // Only compiled, to check the code generated with the opt-in options.
+  WgslBindgenOptionBuilder::default()
+    .workspace_root("shaders/options")
+    .add_entry_point("shaders/options/bytemuck_options.wgsl")
+    .skip_hash_check(true)
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .derive_default(true)
+    .add_custom_derive(Regex::new("::(Light|Padded)$").unwrap(), quote!(#[derive(Default)]))
+    .add_custom_padding_field_regexp(Regex::new("^_padding$").unwrap())
+    .struct_size_constants(true)
+    .array_length_constants(true)
+    .field_offset_constants(true)
+    .emit_layout_tests(true)
+    .always_generate_init_struct(true)
+    .struct_buffer_helpers(true)
+    .buffer_wrappers(true)
+    .named_bind_group_resources(true)
+    .bind_group_entries_builders(true)
+    .binding_reflection_table(true)
+    .cache_layouts(true)
+    .dyn_entry_structs(true)
+    .output("src/bytemuck_options.rs")
+    .build()?
+    .generate()
+    .into_diagnostic()?;
+
+  WgslBindgenOptionBuilder::default()
+    .workspace_root("shaders/options")
+    .add_entry_point("shaders/options/encase_options.wgsl")
+    .skip_hash_check(true)
+    .serialization_strategy(WgslTypeSerializeStrategy::Encase)
+    .type_map(GlamWgslTypeMap)
+    .derive_default(true)
+    .struct_size_constants(true)
+    .array_length_constants(true)
+    .field_offset_constants(true)
+    .emit_layout_tests(true)
+    .struct_buffer_helpers(true)
+    .output("src/encase_options.rs")
+    .build()?
+    .generate()
     .into_diagnostic()
 }
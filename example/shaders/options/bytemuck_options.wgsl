@@ -0,0 +1,56 @@
+// Bindings generated with most of the opt-in options enabled, only compiled to check
+// that the generated code builds.
+
+struct Light {
+  position: vec4<f32>,
+  intensity: f32,
+  colors: array<vec4<f32>, 4>,
+}
+
+struct Lights {
+  count: u32,
+  lights: array<Light, 8>,
+}
+
+// Implements `Default` manually, since the array is too long to derive it.
+struct Padded {
+  value: vec4<f32>,
+  _padding: vec4<u32>,
+  history: array<vec4<f32>, 40>,
+}
+
+struct VertexInput {
+  @location(0) position: vec4<f32>,
+  @location(1) uv: vec2<f32>,
+}
+
+struct VertexOutput {
+  @builtin(position) position: vec4<f32>,
+  @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0) var<uniform> lights: Lights;
+@group(0) @binding(1) var<storage, read_write> counts: array<u32>;
+@group(1) @binding(0) var color_texture: texture_2d<f32>;
+@group(1) @binding(1) var color_sampler: sampler;
+@group(1) @binding(2) var<uniform> padded: Padded;
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+  var out: VertexOutput;
+  out.position = in.position * lights.lights[0].intensity;
+  out.uv = in.uv;
+  return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+  let color = textureSample(color_texture, color_sampler, in.uv);
+  return color * lights.lights[0].colors[0] * padded.value;
+}
+
+@compute
+@workgroup_size(64)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+  counts[id.x] = lights.count;
+}
@@ -0,0 +1,19 @@
+// Bindings serialized with encase, only compiled to check that the generated code
+// builds.
+
+// `_pad0` is a regular field, since `padding_fields_by_name` is off by default.
+struct Params {
+  scale: f32,
+  _pad0: u32,
+  offset: vec2<f32>,
+  colors: array<vec4<f32>, 40>,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> output: array<vec4<f32>>;
+
+@compute
+@workgroup_size(64)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+  output[id.x] = params.colors[id.x % 40u] * params.scale + vec4(params.offset, 0.0, 0.0);
+}
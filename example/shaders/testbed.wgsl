@@ -35,13 +35,13 @@ var color_texture: texture_2d<f32>;
 @group(0) @binding(1)
 var color_sampler: sampler;
 
-struct Uniforms {
+struct TestbedUniforms {
   color_rgb: vec4<f32>,
   scalars: Scalars
 }
 
 @group(1) @binding(0)
-var<uniform> uniforms: Uniforms;
+var<uniform> testbed_uniforms: TestbedUniforms;
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
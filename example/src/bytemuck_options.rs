@@ -0,0 +1,1774 @@
+// File automatically generated by wgsl_bindgen^
+//
+// ^ wgsl_bindgen version 0.15.1
+// Changes made to this file will not be saved.
+// SourceHash: 203775956889701852c58e8bed9d037942a12e4f6e7e332d7714b13f9adabcbf
+
+#![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderEntry {
+    BytemuckOptions,
+}
+impl ShaderEntry {
+    pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
+        match self {
+            Self::BytemuckOptions => bytemuck_options::create_pipeline_layout(device),
+        }
+    }
+    pub fn create_shader_module_embed_source(
+        &self,
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        match self {
+            Self::BytemuckOptions => {
+                bytemuck_options::create_shader_module_embed_source(device)
+            }
+        }
+    }
+}
+/// The names that couldn't be used to create bind groups from named resources.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamedResourcesError {
+    /// The names without a resource.
+    pub missing: Vec<&'static str>,
+    /// The names whose resource is of the wrong kind for the binding.
+    pub wrong_kind: Vec<&'static str>,
+}
+/// Fallback resources for the bindings left unset in the bind group entries builders.
+/// Binding arrays have no fallback.
+#[derive(Debug, Clone, Default)]
+pub struct BindGroupEntryDefaults<'a> {
+    pub buffer: Option<wgpu::BufferBinding<'a>>,
+    pub sampler: Option<&'a wgpu::Sampler>,
+    pub texture_view: Option<&'a wgpu::TextureView>,
+}
+/// The names of the bindings that were neither set nor covered by a default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingBindGroupEntries(pub Vec<&'static str>);
+/// A binding of a module, with the layout entry it's generated with.
+#[derive(Debug, Clone, Copy)]
+pub struct BindingDesc {
+    /// The name of the module the binding belongs to.
+    pub module: &'static str,
+    /// The fully qualified name of the binding.
+    pub name: &'static str,
+    pub group: u32,
+    pub binding: u32,
+    pub entry: wgpu::BindGroupLayoutEntry,
+}
+/// The `BINDINGS` of every module. Bindings with the same name in several modules are
+/// all kept.
+pub const ALL_BINDINGS: &[&[BindingDesc]] = &[bytemuck_options::BINDINGS];
+/// Iterates over the bindings of all the modules.
+pub fn all_bindings() -> impl Iterator<Item = &'static BindingDesc> {
+    ALL_BINDINGS.iter().flat_map(|bindings| bindings.iter())
+}
+mod _root {
+    pub use super::*;
+}
+pub mod layout_asserts {
+    use super::{_root, _root::*};
+    const WGSL_BASE_TYPE_ASSERTS: () = {
+        assert!(std::mem::size_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::align_of:: < glam::Vec4 > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat3A > () == 48);
+        assert!(std::mem::align_of:: < glam::Mat3A > () == 16);
+        assert!(std::mem::size_of:: < glam::Mat4 > () == 64);
+        assert!(std::mem::align_of:: < glam::Mat4 > () == 16);
+    };
+    const BYTEMUCK_OPTIONS_LIGHT_ASSERTS: () = {
+        assert!(std::mem::offset_of!(bytemuck_options::Light, position) == 0);
+        assert!(std::mem::offset_of!(bytemuck_options::Light, intensity) == 16);
+        assert!(std::mem::offset_of!(bytemuck_options::Light, colors) == 32);
+        assert!(std::mem::size_of:: < bytemuck_options::Light > () == 96);
+    };
+    const BYTEMUCK_OPTIONS_LIGHTS_ASSERTS: () = {
+        assert!(std::mem::offset_of!(bytemuck_options::Lights, count) == 0);
+        assert!(std::mem::offset_of!(bytemuck_options::Lights, lights) == 16);
+        assert!(std::mem::size_of:: < bytemuck_options::Lights > () == 784);
+    };
+    const BYTEMUCK_OPTIONS_PADDED_ASSERTS: () = {
+        assert!(std::mem::offset_of!(bytemuck_options::Padded, value) == 0);
+        assert!(std::mem::offset_of!(bytemuck_options::Padded, history) == 32);
+        assert!(std::mem::size_of:: < bytemuck_options::Padded > () == 672);
+    };
+}
+pub mod bytemuck_options {
+    use super::{_root, _root::*};
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Light {
+        /// size: 16, offset: 0x0, type: `vec4<f32>`
+        pub position: glam::Vec4,
+        /// size: 4, offset: 0x10, type: `f32`
+        pub intensity: f32,
+        pub _pad_intensity: [u8; 0x10 - core::mem::size_of::<f32>()],
+        /// size: 64, offset: 0x20, type: `array<vec4<f32>, 4>`
+        pub colors: [glam::Vec4; 4],
+        pub _pad_colors: [u8; 0x40 - core::mem::size_of::<[glam::Vec4; 4]>()],
+    }
+    impl Light {
+        pub const fn new(
+            position: glam::Vec4,
+            intensity: f32,
+            colors: [glam::Vec4; 4],
+        ) -> Self {
+            Self {
+                position,
+                intensity,
+                _pad_intensity: [0; 0x10 - core::mem::size_of::<f32>()],
+                colors,
+                _pad_colors: [0; 0x40 - core::mem::size_of::<[glam::Vec4; 4]>()],
+            }
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy, Default)]
+    pub struct LightInit {
+        pub position: glam::Vec4,
+        pub intensity: f32,
+        pub colors: [glam::Vec4; 4],
+    }
+    impl LightInit {
+        pub const fn build(&self) -> Light {
+            Light {
+                position: self.position,
+                intensity: self.intensity,
+                _pad_intensity: [0; 0x10 - core::mem::size_of::<f32>()],
+                colors: self.colors,
+                _pad_colors: [0; 0x40 - core::mem::size_of::<[glam::Vec4; 4]>()],
+            }
+        }
+    }
+    impl From<LightInit> for Light {
+        fn from(data: LightInit) -> Self {
+            data.build()
+        }
+    }
+    impl From<Light> for LightInit {
+        fn from(data: Light) -> Self {
+            Self {
+                position: data.position,
+                intensity: data.intensity,
+                colors: data.colors,
+            }
+        }
+    }
+    impl Light {
+        /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+        pub fn update_from(&mut self, init: &LightInit) {
+            self.position = init.position;
+            self.intensity = init.intensity;
+            self.colors = init.colors;
+        }
+    }
+    impl Default for Light {
+        fn default() -> Self {
+            Self {
+                position: Default::default(),
+                intensity: Default::default(),
+                _pad_intensity: [0; 0x10 - core::mem::size_of::<f32>()],
+                colors: [Default::default(); 4],
+                _pad_colors: [0; 0x40 - core::mem::size_of::<[glam::Vec4; 4]>()],
+            }
+        }
+    }
+    impl Light {
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const SIZE: u64 = 96;
+        /// The size of the struct rounded up to 256 bytes, the default
+        /// `min_uniform_buffer_offset_alignment` and `min_storage_buffer_offset_alignment`.
+        pub const ALIGNED_SIZE_256: u64 = Self::aligned_size(256);
+        /// The size of the struct rounded up to a multiple of `alignment`, i.e. the stride
+        /// of instances placed at aligned offsets in a buffer.
+        pub const fn aligned_size(alignment: u64) -> u64 {
+            Self::SIZE.next_multiple_of(alignment)
+        }
+        /// The offset of the instance at `index` in a buffer of instances placed at
+        /// offsets aligned to `alignment`.
+        pub const fn offset_of_index(index: u64, alignment: u64) -> u64 {
+            index * Self::aligned_size(alignment)
+        }
+    }
+    impl Light {
+        /// The length of the `colors` array.
+        pub const COLORS_LEN: usize = 4;
+    }
+    impl Light {
+        /// The names and byte offsets of the fields with the WGSL layout.
+        pub const FIELD_OFFSETS: &[(&str, u64)] = &[
+            ("position", 0),
+            ("intensity", 16),
+            ("colors", 32),
+        ];
+        /// The byte offset of `position` with the WGSL layout.
+        pub const POSITION_OFFSET: u64 = 0;
+        /// The byte offset of `intensity` with the WGSL layout.
+        pub const INTENSITY_OFFSET: u64 = 16;
+        /// The byte offset of `colors` with the WGSL layout.
+        pub const COLORS_OFFSET: u64 = 32;
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const GPU_SIZE: u64 = 96;
+    }
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Lights {
+        /// size: 4, offset: 0x0, type: `u32`
+        pub count: u32,
+        pub _pad_count: [u8; 0x10 - core::mem::size_of::<u32>()],
+        /// size: 768, offset: 0x10, type: `array<Light, 8>`
+        pub lights: [Light; 8],
+        pub _pad_lights: [u8; 0x300 - core::mem::size_of::<[Light; 8]>()],
+    }
+    impl Lights {
+        pub const fn new(count: u32, lights: [Light; 8]) -> Self {
+            Self {
+                count,
+                _pad_count: [0; 0x10 - core::mem::size_of::<u32>()],
+                lights,
+                _pad_lights: [0; 0x300 - core::mem::size_of::<[Light; 8]>()],
+            }
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct LightsInit {
+        pub count: u32,
+        pub lights: [Light; 8],
+    }
+    impl LightsInit {
+        pub const fn build(&self) -> Lights {
+            Lights {
+                count: self.count,
+                _pad_count: [0; 0x10 - core::mem::size_of::<u32>()],
+                lights: self.lights,
+                _pad_lights: [0; 0x300 - core::mem::size_of::<[Light; 8]>()],
+            }
+        }
+    }
+    impl From<LightsInit> for Lights {
+        fn from(data: LightsInit) -> Self {
+            data.build()
+        }
+    }
+    impl From<Lights> for LightsInit {
+        fn from(data: Lights) -> Self {
+            Self {
+                count: data.count,
+                lights: data.lights,
+            }
+        }
+    }
+    impl Lights {
+        /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+        pub fn update_from(&mut self, init: &LightsInit) {
+            self.count = init.count;
+            self.lights = init.lights;
+        }
+    }
+    impl Default for Lights {
+        fn default() -> Self {
+            Self {
+                count: Default::default(),
+                _pad_count: [0; 0x10 - core::mem::size_of::<u32>()],
+                lights: [Default::default(); 8],
+                _pad_lights: [0; 0x300 - core::mem::size_of::<[Light; 8]>()],
+            }
+        }
+    }
+    impl Lights {
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const SIZE: u64 = 784;
+        /// The size of the struct rounded up to 256 bytes, the default
+        /// `min_uniform_buffer_offset_alignment` and `min_storage_buffer_offset_alignment`.
+        pub const ALIGNED_SIZE_256: u64 = Self::aligned_size(256);
+        /// The size of the struct rounded up to a multiple of `alignment`, i.e. the stride
+        /// of instances placed at aligned offsets in a buffer.
+        pub const fn aligned_size(alignment: u64) -> u64 {
+            Self::SIZE.next_multiple_of(alignment)
+        }
+        /// The offset of the instance at `index` in a buffer of instances placed at
+        /// offsets aligned to `alignment`.
+        pub const fn offset_of_index(index: u64, alignment: u64) -> u64 {
+            index * Self::aligned_size(alignment)
+        }
+    }
+    impl Lights {
+        /// The length of the `lights` array.
+        pub const LIGHTS_LEN: usize = 8;
+    }
+    impl Lights {
+        /// The names and byte offsets of the fields with the WGSL layout.
+        pub const FIELD_OFFSETS: &[(&str, u64)] = &[("count", 0), ("lights", 16)];
+        /// The byte offset of `count` with the WGSL layout.
+        pub const COUNT_OFFSET: u64 = 0;
+        /// The byte offset of `lights` with the WGSL layout.
+        pub const LIGHTS_OFFSET: u64 = 16;
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const GPU_SIZE: u64 = 784;
+    }
+    impl Lights {
+        /// The usages of the buffers created for the struct by its bindings.
+        pub const BUFFER_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::COPY_DST
+            .union(wgpu::BufferUsages::UNIFORM);
+        /// Creates an uninitialized buffer the size of the struct.
+        pub fn create_buffer(
+            device: &wgpu::Device,
+            label: Option<&str>,
+        ) -> wgpu::Buffer {
+            device
+                .create_buffer(
+                    &wgpu::BufferDescriptor {
+                        label,
+                        size: std::mem::size_of::<Self>() as u64,
+                        usage: Self::BUFFER_USAGES,
+                        mapped_at_creation: false,
+                    },
+                )
+        }
+        /// Creates a buffer initialized with the struct.
+        pub fn create_buffer_init(
+            &self,
+            device: &wgpu::Device,
+            label: Option<&str>,
+        ) -> wgpu::Buffer {
+            wgpu::util::DeviceExt::create_buffer_init(
+                device,
+                &wgpu::util::BufferInitDescriptor {
+                    label,
+                    contents: bytemuck::bytes_of(self),
+                    usage: Self::BUFFER_USAGES,
+                },
+            )
+        }
+        /// Writes the struct into `buffer` at `offset`.
+        pub fn write(&self, queue: &wgpu::Queue, buffer: &wgpu::Buffer, offset: u64) {
+            queue.write_buffer(buffer, offset, bytemuck::bytes_of(self));
+        }
+    }
+    #[repr(C, align(16))]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Padded {
+        /// size: 16, offset: 0x0, type: `vec4<f32>`
+        pub value: glam::Vec4,
+        pub _padding: [u8; 0x10],
+        /// size: 640, offset: 0x20, type: `array<vec4<f32>, 40>`
+        pub history: [glam::Vec4; 40],
+        pub _pad_history: [u8; 0x280 - core::mem::size_of::<[glam::Vec4; 40]>()],
+    }
+    impl Padded {
+        pub const fn new(value: glam::Vec4, history: [glam::Vec4; 40]) -> Self {
+            Self {
+                value,
+                _padding: [0; 0x10],
+                history,
+                _pad_history: [0; 0x280 - core::mem::size_of::<[glam::Vec4; 40]>()],
+            }
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct PaddedInit {
+        pub value: glam::Vec4,
+        pub history: [glam::Vec4; 40],
+    }
+    impl Default for PaddedInit {
+        fn default() -> Self {
+            Self {
+                value: Default::default(),
+                history: [Default::default(); 40],
+            }
+        }
+    }
+    impl PaddedInit {
+        pub const fn build(&self) -> Padded {
+            Padded {
+                value: self.value,
+                _padding: [0; 0x10],
+                history: self.history,
+                _pad_history: [0; 0x280 - core::mem::size_of::<[glam::Vec4; 40]>()],
+            }
+        }
+    }
+    impl From<PaddedInit> for Padded {
+        fn from(data: PaddedInit) -> Self {
+            data.build()
+        }
+    }
+    impl From<Padded> for PaddedInit {
+        fn from(data: Padded) -> Self {
+            Self {
+                value: data.value,
+                history: data.history,
+            }
+        }
+    }
+    impl Padded {
+        /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+        pub fn update_from(&mut self, init: &PaddedInit) {
+            self.value = init.value;
+            self.history = init.history;
+        }
+    }
+    impl Default for Padded {
+        fn default() -> Self {
+            Self {
+                value: Default::default(),
+                _padding: [0; 0x10],
+                history: [Default::default(); 40],
+                _pad_history: [0; 0x280 - core::mem::size_of::<[glam::Vec4; 40]>()],
+            }
+        }
+    }
+    impl Padded {
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const SIZE: u64 = 672;
+        /// The size of the struct rounded up to 256 bytes, the default
+        /// `min_uniform_buffer_offset_alignment` and `min_storage_buffer_offset_alignment`.
+        pub const ALIGNED_SIZE_256: u64 = Self::aligned_size(256);
+        /// The size of the struct rounded up to a multiple of `alignment`, i.e. the stride
+        /// of instances placed at aligned offsets in a buffer.
+        pub const fn aligned_size(alignment: u64) -> u64 {
+            Self::SIZE.next_multiple_of(alignment)
+        }
+        /// The offset of the instance at `index` in a buffer of instances placed at
+        /// offsets aligned to `alignment`.
+        pub const fn offset_of_index(index: u64, alignment: u64) -> u64 {
+            index * Self::aligned_size(alignment)
+        }
+    }
+    impl Padded {
+        /// The length of the `history` array.
+        pub const HISTORY_LEN: usize = 40;
+    }
+    impl Padded {
+        /// The names and byte offsets of the fields with the WGSL layout.
+        pub const FIELD_OFFSETS: &[(&str, u64)] = &[("value", 0), ("history", 32)];
+        /// The byte offset of `value` with the WGSL layout.
+        pub const VALUE_OFFSET: u64 = 0;
+        /// The byte offset of `history` with the WGSL layout.
+        pub const HISTORY_OFFSET: u64 = 32;
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const GPU_SIZE: u64 = 672;
+    }
+    impl Padded {
+        /// The usages of the buffers created for the struct by its bindings.
+        pub const BUFFER_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::COPY_DST
+            .union(wgpu::BufferUsages::UNIFORM);
+        /// Creates an uninitialized buffer the size of the struct.
+        pub fn create_buffer(
+            device: &wgpu::Device,
+            label: Option<&str>,
+        ) -> wgpu::Buffer {
+            device
+                .create_buffer(
+                    &wgpu::BufferDescriptor {
+                        label,
+                        size: std::mem::size_of::<Self>() as u64,
+                        usage: Self::BUFFER_USAGES,
+                        mapped_at_creation: false,
+                    },
+                )
+        }
+        /// Creates a buffer initialized with the struct.
+        pub fn create_buffer_init(
+            &self,
+            device: &wgpu::Device,
+            label: Option<&str>,
+        ) -> wgpu::Buffer {
+            wgpu::util::DeviceExt::create_buffer_init(
+                device,
+                &wgpu::util::BufferInitDescriptor {
+                    label,
+                    contents: bytemuck::bytes_of(self),
+                    usage: Self::BUFFER_USAGES,
+                },
+            )
+        }
+        /// Writes the struct into `buffer` at `offset`.
+        pub fn write(&self, queue: &wgpu::Queue, buffer: &wgpu::Buffer, offset: u64) {
+            queue.write_buffer(buffer, offset, bytemuck::bytes_of(self));
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy, Default)]
+    pub struct VertexInput {
+        pub position: glam::Vec4,
+        pub uv: [f32; 2],
+    }
+    impl VertexInput {
+        pub const fn new(position: glam::Vec4, uv: [f32; 2]) -> Self {
+            Self { position, uv }
+        }
+    }
+    impl VertexInput {
+        /// The names and byte offsets of the fields with the WGSL layout.
+        pub const FIELD_OFFSETS: &[(&str, u64)] = &[("position", 0), ("uv", 16)];
+        /// The byte offset of `position` with the WGSL layout.
+        pub const POSITION_OFFSET: u64 = 0;
+        /// The byte offset of `uv` with the WGSL layout.
+        pub const UV_OFFSET: u64 = 16;
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const GPU_SIZE: u64 = 32;
+    }
+    impl VertexInput {
+        pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 2] = [
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x4,
+                offset: std::mem::offset_of!(Self, position) as u64,
+                shader_location: 0,
+            },
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x2,
+                offset: std::mem::offset_of!(Self, uv) as u64,
+                shader_location: 1,
+            },
+        ];
+        pub const fn vertex_buffer_layout(
+            step_mode: wgpu::VertexStepMode,
+        ) -> wgpu::VertexBufferLayout<'static> {
+            wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<Self>() as u64,
+                step_mode,
+                attributes: &Self::VERTEX_ATTRIBUTES,
+            }
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0EntriesParams<'a> {
+        pub lights: wgpu::BufferBinding<'a>,
+        pub counts: wgpu::BufferBinding<'a>,
+    }
+    #[derive(Clone, Debug)]
+    pub struct WgpuBindGroup0Entries<'a> {
+        pub lights: wgpu::BindGroupEntry<'a>,
+        pub counts: wgpu::BindGroupEntry<'a>,
+    }
+    impl<'a> WgpuBindGroup0Entries<'a> {
+        pub fn new(params: WgpuBindGroup0EntriesParams<'a>) -> Self {
+            Self {
+                lights: wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(params.lights),
+                },
+                counts: wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Buffer(params.counts),
+                },
+            }
+        }
+        pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 2] {
+            [self.lights, self.counts]
+        }
+        pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+            self.as_array().into_iter().collect()
+        }
+    }
+    /// Builds [`WgpuBindGroup0Entries`] one binding at a time, using the defaults for the bindings that aren't set.
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0EntriesBuilder<'a> {
+        defaults: BindGroupEntryDefaults<'a>,
+        lights: Option<wgpu::BufferBinding<'a>>,
+        counts: Option<wgpu::BufferBinding<'a>>,
+    }
+    impl<'a> WgpuBindGroup0EntriesBuilder<'a> {
+        pub fn new(defaults: BindGroupEntryDefaults<'a>) -> Self {
+            Self {
+                defaults,
+                lights: None,
+                counts: None,
+            }
+        }
+        pub fn lights(mut self, lights: wgpu::BufferBinding<'a>) -> Self {
+            self.lights = Some(lights);
+            self
+        }
+        pub fn counts(mut self, counts: wgpu::BufferBinding<'a>) -> Self {
+            self.counts = Some(counts);
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<WgpuBindGroup0Entries<'a>, MissingBindGroupEntries> {
+            let mut missing = Vec::new();
+            let lights = self.lights.or_else(|| self.defaults.buffer.clone());
+            if lights.is_none() {
+                missing.push("lights");
+            }
+            let counts = self.counts.or_else(|| self.defaults.buffer.clone());
+            if counts.is_none() {
+                missing.push("counts");
+            }
+            let (Some(lights), Some(counts)) = (lights, counts) else {
+                return Err(MissingBindGroupEntries(missing));
+            };
+            Ok(
+                WgpuBindGroup0Entries::new(WgpuBindGroup0EntriesParams {
+                    lights,
+                    counts,
+                }),
+            )
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0(wgpu::BindGroup);
+    impl WgpuBindGroup0 {
+        pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+            label: Some("BytemuckOptions::BindGroup0::LayoutDescriptor"),
+            entries: &[
+                /// @binding(0): "lights"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX
+                        .union(wgpu::ShaderStages::FRAGMENT)
+                        .union(wgpu::ShaderStages::COMPUTE),
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<_root::bytemuck_options::Lights>() as _,
+                        ),
+                    },
+                    count: None,
+                },
+                /// @binding(1): "counts"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage {
+                            read_only: false,
+                        },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        };
+        pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+            device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+        }
+        pub fn from_bindings(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup0Entries,
+        ) -> Self {
+            let bind_group_layout = Self::get_bind_group_layout(&device);
+            let entries = bindings.as_array();
+            let bind_group = device
+                .create_bind_group(
+                    &wgpu::BindGroupDescriptor {
+                        label: Some("BytemuckOptions::BindGroup0"),
+                        layout: &bind_group_layout,
+                        entries: &entries,
+                    },
+                );
+            Self(bind_group)
+        }
+        /// Returns the layout created on the first call, with the device of that call.
+        pub fn get_cached_bind_group_layout(
+            device: &wgpu::Device,
+        ) -> &'static wgpu::BindGroupLayout {
+            static LAYOUT: std::sync::OnceLock<wgpu::BindGroupLayout> = std::sync::OnceLock::new();
+            LAYOUT.get_or_init(|| Self::get_bind_group_layout(device))
+        }
+        pub fn from_bindings_with_cached_layout(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup0Entries,
+        ) -> Self {
+            let entries = bindings.as_array();
+            let bind_group = device
+                .create_bind_group(
+                    &wgpu::BindGroupDescriptor {
+                        label: Some("BytemuckOptions::BindGroup0"),
+                        layout: Self::get_cached_bind_group_layout(device),
+                        entries: &entries,
+                    },
+                );
+            Self(bind_group)
+        }
+        pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+            render_pass.set_bind_group(0, &self.0, &[]);
+        }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(0, &self.0, offsets);
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup1EntriesParams<'a> {
+        pub color_texture: &'a wgpu::TextureView,
+        pub color_sampler: &'a wgpu::Sampler,
+        pub padded: wgpu::BufferBinding<'a>,
+    }
+    #[derive(Clone, Debug)]
+    pub struct WgpuBindGroup1Entries<'a> {
+        pub color_texture: wgpu::BindGroupEntry<'a>,
+        pub color_sampler: wgpu::BindGroupEntry<'a>,
+        pub padded: wgpu::BindGroupEntry<'a>,
+    }
+    impl<'a> WgpuBindGroup1Entries<'a> {
+        pub fn new(params: WgpuBindGroup1EntriesParams<'a>) -> Self {
+            Self {
+                color_texture: wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(params.color_texture),
+                },
+                color_sampler: wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(params.color_sampler),
+                },
+                padded: wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Buffer(params.padded),
+                },
+            }
+        }
+        pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 3] {
+            [self.color_texture, self.color_sampler, self.padded]
+        }
+        pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+            self.as_array().into_iter().collect()
+        }
+    }
+    /// Builds [`WgpuBindGroup1Entries`] one binding at a time, using the defaults for the bindings that aren't set.
+    #[derive(Debug)]
+    pub struct WgpuBindGroup1EntriesBuilder<'a> {
+        defaults: BindGroupEntryDefaults<'a>,
+        color_texture: Option<&'a wgpu::TextureView>,
+        color_sampler: Option<&'a wgpu::Sampler>,
+        padded: Option<wgpu::BufferBinding<'a>>,
+    }
+    impl<'a> WgpuBindGroup1EntriesBuilder<'a> {
+        pub fn new(defaults: BindGroupEntryDefaults<'a>) -> Self {
+            Self {
+                defaults,
+                color_texture: None,
+                color_sampler: None,
+                padded: None,
+            }
+        }
+        pub fn color_texture(mut self, color_texture: &'a wgpu::TextureView) -> Self {
+            self.color_texture = Some(color_texture);
+            self
+        }
+        pub fn color_sampler(mut self, color_sampler: &'a wgpu::Sampler) -> Self {
+            self.color_sampler = Some(color_sampler);
+            self
+        }
+        pub fn padded(mut self, padded: wgpu::BufferBinding<'a>) -> Self {
+            self.padded = Some(padded);
+            self
+        }
+        pub fn build(
+            self,
+        ) -> Result<WgpuBindGroup1Entries<'a>, MissingBindGroupEntries> {
+            let mut missing = Vec::new();
+            let color_texture = self.color_texture.or(self.defaults.texture_view);
+            if color_texture.is_none() {
+                missing.push("color_texture");
+            }
+            let color_sampler = self.color_sampler.or(self.defaults.sampler);
+            if color_sampler.is_none() {
+                missing.push("color_sampler");
+            }
+            let padded = self.padded.or_else(|| self.defaults.buffer.clone());
+            if padded.is_none() {
+                missing.push("padded");
+            }
+            let (Some(color_texture), Some(color_sampler), Some(padded)) = (
+                color_texture,
+                color_sampler,
+                padded,
+            ) else {
+                return Err(MissingBindGroupEntries(missing));
+            };
+            Ok(
+                WgpuBindGroup1Entries::new(WgpuBindGroup1EntriesParams {
+                    color_texture,
+                    color_sampler,
+                    padded,
+                }),
+            )
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup1(wgpu::BindGroup);
+    impl WgpuBindGroup1 {
+        pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+            label: Some("BytemuckOptions::BindGroup1::LayoutDescriptor"),
+            entries: &[
+                /// @binding(0): "color_texture"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float {
+                            filterable: true,
+                        },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                /// @binding(1): "color_sampler"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                /// @binding(2): "padded"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<_root::bytemuck_options::Padded>() as _,
+                        ),
+                    },
+                    count: None,
+                },
+            ],
+        };
+        pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+            device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+        }
+        pub fn from_bindings(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup1Entries,
+        ) -> Self {
+            let bind_group_layout = Self::get_bind_group_layout(&device);
+            let entries = bindings.as_array();
+            let bind_group = device
+                .create_bind_group(
+                    &wgpu::BindGroupDescriptor {
+                        label: Some("BytemuckOptions::BindGroup1"),
+                        layout: &bind_group_layout,
+                        entries: &entries,
+                    },
+                );
+            Self(bind_group)
+        }
+        /// Returns the layout created on the first call, with the device of that call.
+        pub fn get_cached_bind_group_layout(
+            device: &wgpu::Device,
+        ) -> &'static wgpu::BindGroupLayout {
+            static LAYOUT: std::sync::OnceLock<wgpu::BindGroupLayout> = std::sync::OnceLock::new();
+            LAYOUT.get_or_init(|| Self::get_bind_group_layout(device))
+        }
+        pub fn from_bindings_with_cached_layout(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup1Entries,
+        ) -> Self {
+            let entries = bindings.as_array();
+            let bind_group = device
+                .create_bind_group(
+                    &wgpu::BindGroupDescriptor {
+                        label: Some("BytemuckOptions::BindGroup1"),
+                        layout: Self::get_cached_bind_group_layout(device),
+                        entries: &entries,
+                    },
+                );
+            Self(bind_group)
+        }
+        pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+            render_pass.set_bind_group(1, &self.0, &[]);
+        }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(1, &self.0, offsets);
+        }
+    }
+    #[derive(Debug, Copy, Clone)]
+    pub struct WgpuBindGroups<'a> {
+        pub bind_group0: &'a WgpuBindGroup0,
+        pub bind_group1: &'a WgpuBindGroup1,
+    }
+    impl<'a> WgpuBindGroups<'a> {
+        pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
+            self.bind_group0.set(pass);
+            self.bind_group1.set(pass);
+        }
+        /// Sets all the bind groups with the given dynamic offsets, in group order.
+        pub fn set_with_offsets(
+            &self,
+            pass: &mut wgpu::RenderPass<'a>,
+            offsets: [&[wgpu::DynamicOffset]; 2],
+        ) {
+            self.bind_group0.set_with_offsets(pass, offsets[0]);
+            self.bind_group1.set_with_offsets(pass, offsets[1]);
+        }
+    }
+    /// The number of bind group slots in the pipeline layout.
+    pub const BIND_GROUP_COUNT: u32 = 2;
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(u32)]
+    pub enum BindGroupIndex {
+        Group0 = 0,
+        Group1 = 1,
+    }
+    impl BindGroupIndex {
+        pub const ALL: [Self; 2] = [Self::Group0, Self::Group1];
+        pub fn layout_descriptor(
+            self,
+        ) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+            match self {
+                Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+                Self::Group1 => &WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+            }
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut wgpu::RenderPass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+        bind_group1: &'a WgpuBindGroup1,
+    ) {
+        bind_group0.set(pass);
+        bind_group1.set(pass);
+    }
+    pub fn set_bind_groups_with_offsets<'a>(
+        pass: &mut wgpu::RenderPass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+        bind_group0_offsets: &[wgpu::DynamicOffset],
+        bind_group1: &'a WgpuBindGroup1,
+        bind_group1_offsets: &[wgpu::DynamicOffset],
+    ) {
+        bind_group0.set_with_offsets(pass, bind_group0_offsets);
+        bind_group1.set_with_offsets(pass, bind_group1_offsets);
+    }
+    /// Creates the layouts and all the bind groups from their entries, in group order.
+    pub fn create_bind_groups(
+        device: &wgpu::Device,
+        bind_group0: WgpuBindGroup0Entries,
+        bind_group1: WgpuBindGroup1Entries,
+    ) -> (WgpuBindGroup0, WgpuBindGroup1) {
+        (
+            WgpuBindGroup0::from_bindings(device, bind_group0),
+            WgpuBindGroup1::from_bindings(device, bind_group1),
+        )
+    }
+    pub const LIGHTS_GROUP: u32 = 0;
+    pub const LIGHTS_BINDING: u32 = 0;
+    pub const COUNTS_GROUP: u32 = 0;
+    pub const COUNTS_BINDING: u32 = 1;
+    pub const COLOR_TEXTURE_GROUP: u32 = 1;
+    pub const COLOR_TEXTURE_BINDING: u32 = 0;
+    pub const COLOR_SAMPLER_GROUP: u32 = 1;
+    pub const COLOR_SAMPLER_BINDING: u32 = 1;
+    pub const PADDED_GROUP: u32 = 1;
+    pub const PADDED_BINDING: u32 = 2;
+    /// Returns the group and binding index of the binding with the given name.
+    pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+        match name {
+            "lights" => Some((0, 0)),
+            "counts" => Some((0, 1)),
+            "color_texture" => Some((1, 0)),
+            "color_sampler" => Some((1, 1)),
+            "padded" => Some((1, 2)),
+            _ => None,
+        }
+    }
+    /// The fully qualified names of all the bindings, in group and binding order.
+    pub const BIND_GROUP_ENTRY_NAMES: [&str; 5] = [
+        "bytemuck_options::lights",
+        "bytemuck_options::counts",
+        "bytemuck_options::color_texture",
+        "bytemuck_options::color_sampler",
+        "bytemuck_options::padded",
+    ];
+    /// Creates all the bind groups from resources keyed by the names in
+    /// [`BIND_GROUP_ENTRY_NAMES`], reporting every name that is missing or has a resource
+    /// of the wrong kind.
+    pub fn bind_groups_from_named<'a>(
+        device: &wgpu::Device,
+        resources: &std::collections::HashMap<String, wgpu::BindingResource<'a>>,
+    ) -> Result<(WgpuBindGroup0, WgpuBindGroup1), NamedResourcesError> {
+        let mut error = NamedResourcesError::default();
+        let group0_lights = match resources.get("bytemuck_options::lights") {
+            Some(wgpu::BindingResource::Buffer(resource)) => Some(resource.clone()),
+            Some(_) => {
+                error.wrong_kind.push("bytemuck_options::lights");
+                None
+            }
+            None => {
+                error.missing.push("bytemuck_options::lights");
+                None
+            }
+        };
+        let group0_counts = match resources.get("bytemuck_options::counts") {
+            Some(wgpu::BindingResource::Buffer(resource)) => Some(resource.clone()),
+            Some(_) => {
+                error.wrong_kind.push("bytemuck_options::counts");
+                None
+            }
+            None => {
+                error.missing.push("bytemuck_options::counts");
+                None
+            }
+        };
+        let group1_color_texture = match resources.get("bytemuck_options::color_texture")
+        {
+            Some(wgpu::BindingResource::TextureView(resource)) => Some(*resource),
+            Some(_) => {
+                error.wrong_kind.push("bytemuck_options::color_texture");
+                None
+            }
+            None => {
+                error.missing.push("bytemuck_options::color_texture");
+                None
+            }
+        };
+        let group1_color_sampler = match resources.get("bytemuck_options::color_sampler")
+        {
+            Some(wgpu::BindingResource::Sampler(resource)) => Some(*resource),
+            Some(_) => {
+                error.wrong_kind.push("bytemuck_options::color_sampler");
+                None
+            }
+            None => {
+                error.missing.push("bytemuck_options::color_sampler");
+                None
+            }
+        };
+        let group1_padded = match resources.get("bytemuck_options::padded") {
+            Some(wgpu::BindingResource::Buffer(resource)) => Some(resource.clone()),
+            Some(_) => {
+                error.wrong_kind.push("bytemuck_options::padded");
+                None
+            }
+            None => {
+                error.missing.push("bytemuck_options::padded");
+                None
+            }
+        };
+        let (
+            Some(group0_lights),
+            Some(group0_counts),
+            Some(group1_color_texture),
+            Some(group1_color_sampler),
+            Some(group1_padded),
+        ) = (
+            group0_lights,
+            group0_counts,
+            group1_color_texture,
+            group1_color_sampler,
+            group1_padded,
+        ) else {
+            return Err(error);
+        };
+        Ok((
+            WgpuBindGroup0::from_bindings(
+                device,
+                WgpuBindGroup0Entries::new(WgpuBindGroup0EntriesParams {
+                    lights: group0_lights,
+                    counts: group0_counts,
+                }),
+            ),
+            WgpuBindGroup1::from_bindings(
+                device,
+                WgpuBindGroup1Entries::new(WgpuBindGroup1EntriesParams {
+                    color_texture: group1_color_texture,
+                    color_sampler: group1_color_sampler,
+                    padded: group1_padded,
+                }),
+            ),
+        ))
+    }
+    /// A buffer for the `bytemuck_options::lights` binding.
+    #[derive(Debug)]
+    pub struct LightsBuffer(pub wgpu::Buffer);
+    impl LightsBuffer {
+        pub const USAGES: wgpu::BufferUsages = wgpu::BufferUsages::UNIFORM
+            .union(wgpu::BufferUsages::COPY_DST);
+        pub fn new(
+            device: &wgpu::Device,
+            data: &_root::bytemuck_options::Lights,
+        ) -> Self {
+            Self(
+                wgpu::util::DeviceExt::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("bytemuck_options::lights"),
+                        contents: bytemuck::bytes_of(data),
+                        usage: Self::USAGES,
+                    },
+                ),
+            )
+        }
+        pub fn write(
+            &self,
+            queue: &wgpu::Queue,
+            data: &_root::bytemuck_options::Lights,
+        ) {
+            queue.write_buffer(&self.0, 0, bytemuck::bytes_of(data));
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.0.as_entire_buffer_binding()
+        }
+    }
+    /// A buffer for the `bytemuck_options::counts` binding.
+    #[derive(Debug)]
+    pub struct CountsBuffer(pub wgpu::Buffer);
+    impl CountsBuffer {
+        pub const USAGES: wgpu::BufferUsages = wgpu::BufferUsages::STORAGE
+            .union(wgpu::BufferUsages::COPY_DST);
+        pub fn new(device: &wgpu::Device, data: &[u32]) -> Self {
+            Self(
+                wgpu::util::DeviceExt::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("bytemuck_options::counts"),
+                        contents: bytemuck::cast_slice(data),
+                        usage: Self::USAGES,
+                    },
+                ),
+            )
+        }
+        /// Creates an uninitialized buffer with room for `len` elements.
+        pub fn new_with_capacity(device: &wgpu::Device, len: u64) -> Self {
+            Self(
+                device
+                    .create_buffer(
+                        &wgpu::BufferDescriptor {
+                            label: Some("bytemuck_options::counts"),
+                            size: len * 4,
+                            usage: Self::USAGES,
+                            mapped_at_creation: false,
+                        },
+                    ),
+            )
+        }
+        pub fn write(&self, queue: &wgpu::Queue, data: &[u32]) {
+            queue.write_buffer(&self.0, 0, bytemuck::cast_slice(data));
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.0.as_entire_buffer_binding()
+        }
+    }
+    /// A buffer for the `bytemuck_options::padded` binding.
+    #[derive(Debug)]
+    pub struct PaddedBuffer(pub wgpu::Buffer);
+    impl PaddedBuffer {
+        pub const USAGES: wgpu::BufferUsages = wgpu::BufferUsages::UNIFORM
+            .union(wgpu::BufferUsages::COPY_DST);
+        pub fn new(
+            device: &wgpu::Device,
+            data: &_root::bytemuck_options::Padded,
+        ) -> Self {
+            Self(
+                wgpu::util::DeviceExt::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("bytemuck_options::padded"),
+                        contents: bytemuck::bytes_of(data),
+                        usage: Self::USAGES,
+                    },
+                ),
+            )
+        }
+        pub fn write(
+            &self,
+            queue: &wgpu::Queue,
+            data: &_root::bytemuck_options::Padded,
+        ) {
+            queue.write_buffer(&self.0, 0, bytemuck::bytes_of(data));
+        }
+        pub fn as_entire_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.0.as_entire_buffer_binding()
+        }
+    }
+    pub mod compute {
+        pub const CS_MAIN_WORKGROUP_SIZE: [u32; 3] = [64, 1, 1];
+        /// Returns the number of workgroups needed to cover `work_items`, rounding up.
+        pub const fn cs_main_dispatch_count(work_items: [u32; 3]) -> [u32; 3] {
+            [
+                work_items[0].div_ceil(CS_MAIN_WORKGROUP_SIZE[0]),
+                work_items[1].div_ceil(CS_MAIN_WORKGROUP_SIZE[1]),
+                work_items[2].div_ceil(CS_MAIN_WORKGROUP_SIZE[2]),
+            ]
+        }
+        /// Dispatches enough workgroups to cover `work_items` with the pipeline set on `pass`.
+        pub fn dispatch_cs_main(pass: &mut wgpu::ComputePass<'_>, work_items: [u32; 3]) {
+            let [x, y, z] = cs_main_dispatch_count(work_items);
+            pass.dispatch_workgroups(x, y, z);
+        }
+        /// Dispatches the workgroup counts stored in `buffer` at `offset`, laid out like
+        /// `wgpu::util::DispatchIndirectArgs`, with the pipeline set on `pass`.
+        pub fn dispatch_indirect_cs_main(
+            pass: &mut wgpu::ComputePass<'_>,
+            buffer: &wgpu::Buffer,
+            offset: wgpu::BufferAddress,
+        ) {
+            pass.dispatch_workgroups_indirect(buffer, offset);
+        }
+        pub fn create_cs_main_pipeline_embed_source(
+            device: &wgpu::Device,
+        ) -> wgpu::ComputePipeline {
+            let module = super::create_shader_module_embed_source(device);
+            let layout = super::create_pipeline_layout(device);
+            device
+                .create_compute_pipeline(
+                    &wgpu::ComputePipelineDescriptor {
+                        label: Some("Compute Pipeline cs_main"),
+                        layout: Some(&layout),
+                        module: &module,
+                        entry_point: "cs_main",
+                        compilation_options: wgpu::PipelineCompilationOptions {
+                            zero_initialize_workgroup_memory: true,
+                            ..Default::default()
+                        },
+                        cache: None,
+                    },
+                )
+        }
+    }
+    pub const ENTRY_VS_MAIN: &str = "vs_main";
+    pub const ENTRY_FS_MAIN: &str = "fs_main";
+    pub const ENTRY_CS_MAIN: &str = "cs_main";
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum EntryPoint {
+        VsMain,
+        FsMain,
+        CsMain,
+    }
+    impl EntryPoint {
+        pub const ALL: &'static [Self] = &[Self::VsMain, Self::FsMain, Self::CsMain];
+        pub fn name(self) -> &'static str {
+            match self {
+                Self::VsMain => ENTRY_VS_MAIN,
+                Self::FsMain => ENTRY_FS_MAIN,
+                Self::CsMain => ENTRY_CS_MAIN,
+            }
+        }
+        pub fn stage(self) -> wgpu::ShaderStages {
+            match self {
+                Self::VsMain => wgpu::ShaderStages::VERTEX,
+                Self::FsMain => wgpu::ShaderStages::FRAGMENT,
+                Self::CsMain => wgpu::ShaderStages::COMPUTE,
+            }
+        }
+        /// The workgroup size of compute entry points.
+        pub fn workgroup_size(self) -> Option<[u32; 3]> {
+            match self {
+                Self::VsMain => None,
+                Self::FsMain => None,
+                Self::CsMain => Some(compute::CS_MAIN_WORKGROUP_SIZE),
+            }
+        }
+    }
+    #[derive(Debug)]
+    pub struct VertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
+    }
+    pub fn vertex_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntry<N>,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: entry.entry_point,
+            buffers: &entry.buffers,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                ..Default::default()
+            },
+        }
+    }
+    pub fn vs_main_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
+        VertexEntry {
+            entry_point: ENTRY_VS_MAIN,
+            buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
+            constants: Default::default(),
+            zero_initialize_workgroup_memory: true,
+        }
+    }
+    /// Calls [`vs_main_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:
+    /// - `VertexInput`: `Vertex`
+    pub fn vs_main_entry_default() -> VertexEntry<1> {
+        vs_main_entry(wgpu::VertexStepMode::Vertex)
+    }
+    #[derive(Debug)]
+    pub struct FragmentEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub targets: [Option<wgpu::ColorTargetState>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
+    }
+    pub fn fragment_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a FragmentEntry<N>,
+    ) -> wgpu::FragmentState<'a> {
+        wgpu::FragmentState {
+            module,
+            entry_point: entry.entry_point,
+            targets: &entry.targets,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                ..Default::default()
+            },
+        }
+    }
+    pub fn fs_main_entry(
+        targets: [Option<wgpu::ColorTargetState>; 1],
+    ) -> FragmentEntry<1> {
+        FragmentEntry {
+            entry_point: ENTRY_FS_MAIN,
+            targets,
+            constants: Default::default(),
+            zero_initialize_workgroup_memory: true,
+        }
+    }
+    /// Calls [`fs_main_entry`] with a single color target of `format`, without blending and writing all channels.
+    pub fn fs_main_entry_single(format: wgpu::TextureFormat) -> FragmentEntry<1> {
+        fs_main_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// Calls [`fs_main_entry`] with a single color target of `format` blended with `blend`, writing all channels.
+    pub fn fs_main_entry_single_blend(
+        format: wgpu::TextureFormat,
+        blend: wgpu::BlendState,
+    ) -> FragmentEntry<1> {
+        fs_main_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// The output locations of the entry point in ascending order, with their scalar kinds:
+    /// - `0`: float
+    pub const FS_MAIN_OUTPUT_LOCATIONS: [u32; 1] = [0];
+    /// Returns the color targets of the entry point with the same format for every output,
+    /// without blending and writing all channels.
+    pub fn fs_main_targets(
+        format: wgpu::TextureFormat,
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        fs_main_targets_with_formats([format; 1])
+    }
+    /// Returns the color targets of the entry point with one format per output, in the order
+    /// of the output locations, without blending and writing all channels.
+    pub fn fs_main_targets_with_formats(
+        formats: [wgpu::TextureFormat; 1],
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        [
+            Some(wgpu::ColorTargetState {
+                format: formats[0],
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ]
+    }
+    /// Whether the entry point writes `@builtin(frag_depth)`.
+    pub const FS_MAIN_WRITES_DEPTH: bool = false;
+    #[derive(Debug)]
+    pub struct VertexEntryDyn {
+        pub entry_point: &'static str,
+        pub buffers: Vec<wgpu::VertexBufferLayout<'static>>,
+        pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
+    }
+    impl<const N: usize> From<VertexEntry<N>> for VertexEntryDyn {
+        fn from(entry: VertexEntry<N>) -> Self {
+            Self {
+                entry_point: entry.entry_point,
+                buffers: entry.buffers.into(),
+                constants: entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+            }
+        }
+    }
+    pub fn vertex_state_dyn<'a>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntryDyn,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: entry.entry_point,
+            buffers: &entry.buffers,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                ..Default::default()
+            },
+        }
+    }
+    #[derive(Debug)]
+    pub struct FragmentEntryDyn {
+        pub entry_point: &'static str,
+        pub targets: Vec<Option<wgpu::ColorTargetState>>,
+        pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
+    }
+    impl<const N: usize> From<FragmentEntry<N>> for FragmentEntryDyn {
+        fn from(entry: FragmentEntry<N>) -> Self {
+            Self {
+                entry_point: entry.entry_point,
+                targets: entry.targets.into(),
+                constants: entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+            }
+        }
+    }
+    pub fn fragment_state_dyn<'a>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a FragmentEntryDyn,
+    ) -> wgpu::FragmentState<'a> {
+        wgpu::FragmentState {
+            module,
+            entry_point: entry.entry_point,
+            targets: &entry.targets,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                ..Default::default()
+            },
+        }
+    }
+    /// Calls [`create_cs_main_pipeline_with_zero_init`] with `zero_initialize_workgroup_memory` set to `true`.
+    pub fn create_cs_main_pipeline(
+        device: &wgpu::Device,
+        module: &wgpu::ShaderModule,
+    ) -> wgpu::ComputePipeline {
+        create_cs_main_pipeline_with_zero_init(device, module, true)
+    }
+    pub fn create_cs_main_pipeline_with_zero_init(
+        device: &wgpu::Device,
+        module: &wgpu::ShaderModule,
+        zero_initialize_workgroup_memory: bool,
+    ) -> wgpu::ComputePipeline {
+        let layout = create_pipeline_layout(device);
+        let constants: std::collections::HashMap<String, f64> = Default::default();
+        device
+            .create_compute_pipeline(
+                &wgpu::ComputePipelineDescriptor {
+                    label: Some("BytemuckOptions::cs_main::ComputePipeline"),
+                    layout: Some(&layout),
+                    module,
+                    entry_point: ENTRY_CS_MAIN,
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: &constants,
+                        zero_initialize_workgroup_memory,
+                        ..Default::default()
+                    },
+                    cache: None,
+                },
+            )
+    }
+    /// The entries and states of a render pipeline. The optional states default to a
+    /// triangle list without culling, no depth stencil and no multisampling.
+    #[derive(Debug)]
+    pub struct CreatePipelineArgs<const N: usize, const M: usize> {
+        pub vertex: VertexEntry<N>,
+        pub fragment: FragmentEntry<M>,
+        pub primitive: Option<wgpu::PrimitiveState>,
+        pub depth_stencil: Option<wgpu::DepthStencilState>,
+        pub multisample: Option<wgpu::MultisampleState>,
+    }
+    pub fn render_pipeline_descriptor<'a, const N: usize, const M: usize>(
+        layout: &'a wgpu::PipelineLayout,
+        module: &'a wgpu::ShaderModule,
+        args: &'a CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipelineDescriptor<'a> {
+        wgpu::RenderPipelineDescriptor {
+            label: Some("BytemuckOptions::RenderPipeline"),
+            layout: Some(layout),
+            vertex: vertex_state(module, &args.vertex),
+            primitive: args.primitive.unwrap_or_default(),
+            depth_stencil: args.depth_stencil.clone(),
+            multisample: args.multisample.unwrap_or_default(),
+            fragment: Some(fragment_state(module, &args.fragment)),
+            multiview: None,
+            cache: None,
+        }
+    }
+    pub fn create_pipeline_embed_source<const N: usize, const M: usize>(
+        device: &wgpu::Device,
+        args: CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipeline {
+        let module = create_shader_module_embed_source(device);
+        let layout = create_pipeline_layout(device);
+        device
+            .create_render_pipeline(&render_pipeline_descriptor(&layout, &module, &args))
+    }
+    #[derive(Debug)]
+    pub struct WgpuPipelineLayout;
+    impl WgpuPipelineLayout {
+        pub fn bind_group_layout_entries(
+            entries: [wgpu::BindGroupLayout; 2],
+        ) -> [wgpu::BindGroupLayout; 2] {
+            entries
+        }
+    }
+    /// The descriptor of the pipeline layout of the module with the given bind group layouts,
+    /// for callers that create or share the bind group layouts themselves.
+    pub fn pipeline_layout_descriptor<'a>(
+        bind_group_layouts: &'a [&'a wgpu::BindGroupLayout],
+    ) -> wgpu::PipelineLayoutDescriptor<'a> {
+        wgpu::PipelineLayoutDescriptor {
+            label: Some("BytemuckOptions::PipelineLayout"),
+            bind_group_layouts,
+            push_constant_ranges: &[],
+        }
+    }
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &pipeline_layout_descriptor(
+                    &[
+                        &WgpuBindGroup0::get_bind_group_layout(device),
+                        &WgpuBindGroup1::get_bind_group_layout(device),
+                    ],
+                ),
+            )
+    }
+    /// Creates a pipeline layout with only the bind groups used by `vs_main`.
+    pub fn create_pipeline_layout_for_vs_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &pipeline_layout_descriptor(
+                    &[&WgpuBindGroup0::get_bind_group_layout(device)],
+                ),
+            )
+    }
+    /// Creates a pipeline layout with only the bind groups used by `fs_main`.
+    pub fn create_pipeline_layout_for_fs_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &pipeline_layout_descriptor(
+                    &[
+                        &WgpuBindGroup0::get_bind_group_layout(device),
+                        &WgpuBindGroup1::get_bind_group_layout(device),
+                    ],
+                ),
+            )
+    }
+    /// Creates a pipeline layout with only the bind groups used by `cs_main`.
+    pub fn create_pipeline_layout_for_cs_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &pipeline_layout_descriptor(
+                    &[&WgpuBindGroup0::get_bind_group_layout(device)],
+                ),
+            )
+    }
+    /// The bind group layouts and the pipeline layout of the module, created on first use
+    /// with the device of that call.
+    #[derive(Debug, Clone, Copy)]
+    pub struct CachedLayouts {
+        pub bind_group_layouts: [&'static wgpu::BindGroupLayout; 2],
+        pub pipeline_layout: &'static wgpu::PipelineLayout,
+    }
+    impl CachedLayouts {
+        pub fn get(device: &wgpu::Device) -> Self {
+            static PIPELINE_LAYOUT: std::sync::OnceLock<wgpu::PipelineLayout> = std::sync::OnceLock::new();
+            let bind_group_layouts = [
+                WgpuBindGroup0::get_cached_bind_group_layout(device),
+                WgpuBindGroup1::get_cached_bind_group_layout(device),
+            ];
+            let pipeline_layout = PIPELINE_LAYOUT
+                .get_or_init(|| {
+                    device
+                        .create_pipeline_layout(
+                            &pipeline_layout_descriptor(&bind_group_layouts),
+                        )
+                });
+            Self {
+                bind_group_layouts,
+                pipeline_layout,
+            }
+        }
+    }
+    /// Every binding of the module, in group and binding order.
+    pub const BINDINGS: &[BindingDesc] = &[
+        BindingDesc {
+            module: "bytemuck_options",
+            name: "bytemuck_options::lights",
+            group: 0,
+            binding: 0,
+            entry: WgpuBindGroup0::LAYOUT_DESCRIPTOR.entries[0],
+        },
+        BindingDesc {
+            module: "bytemuck_options",
+            name: "bytemuck_options::counts",
+            group: 0,
+            binding: 1,
+            entry: WgpuBindGroup0::LAYOUT_DESCRIPTOR.entries[1],
+        },
+        BindingDesc {
+            module: "bytemuck_options",
+            name: "bytemuck_options::color_texture",
+            group: 1,
+            binding: 0,
+            entry: WgpuBindGroup1::LAYOUT_DESCRIPTOR.entries[0],
+        },
+        BindingDesc {
+            module: "bytemuck_options",
+            name: "bytemuck_options::color_sampler",
+            group: 1,
+            binding: 1,
+            entry: WgpuBindGroup1::LAYOUT_DESCRIPTOR.entries[1],
+        },
+        BindingDesc {
+            module: "bytemuck_options",
+            name: "bytemuck_options::padded",
+            group: 1,
+            binding: 2,
+            entry: WgpuBindGroup1::LAYOUT_DESCRIPTOR.entries[2],
+        },
+    ];
+    pub fn create_shader_module_embed_source(
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("bytemuck_options.wgsl"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    pub const SHADER_STRING: &'static str = r#"
+struct Light {
+    position: vec4<f32>,
+    intensity: f32,
+    colors: array<vec4<f32>, 4>,
+}
+
+struct Lights {
+    count: u32,
+    lights: array<Light, 8>,
+}
+
+struct Padded {
+    value: vec4<f32>,
+    _padding: vec4<u32>,
+    history: array<vec4<f32>, 40>,
+}
+
+struct VertexInput {
+    @location(0) position: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0) 
+var<uniform> lights: Lights;
+@group(0) @binding(1) 
+var<storage, read_write> counts: array<u32>;
+@group(1) @binding(0) 
+var color_texture: texture_2d<f32>;
+@group(1) @binding(1) 
+var color_sampler: sampler;
+@group(1) @binding(2) 
+var<uniform> padded: Padded;
+
+@vertex 
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    let _e8 = lights.lights[0].intensity;
+    out.position = (in.position * _e8);
+    out.uv = in.uv;
+    let _e12 = out;
+    return _e12;
+}
+
+@fragment 
+fn fs_main(in_1: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(color_texture, color_sampler, in_1.uv);
+    let _e10 = lights.lights[0].colors[0];
+    let _e14 = padded.value;
+    return ((color * _e10) * _e14);
+}
+
+@compute @workgroup_size(64, 1, 1) 
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let _e6 = lights.count;
+    counts[id.x] = _e6;
+    return;
+}
+"#;
+}
+pub mod bytemuck_impls {
+    use super::{_root, _root::*};
+    unsafe impl bytemuck::Zeroable for bytemuck_options::Light {}
+    unsafe impl bytemuck::Pod for bytemuck_options::Light {}
+    unsafe impl bytemuck::Zeroable for bytemuck_options::Lights {}
+    unsafe impl bytemuck::Pod for bytemuck_options::Lights {}
+    unsafe impl bytemuck::Zeroable for bytemuck_options::Padded {}
+    unsafe impl bytemuck::Pod for bytemuck_options::Padded {}
+    unsafe impl bytemuck::Zeroable for bytemuck_options::VertexInput {}
+    unsafe impl bytemuck::Pod for bytemuck_options::VertexInput {}
+}
+#[cfg(test)]
+mod generated_layout_tests {
+    use super::{_root, _root::*};
+    #[test]
+    fn bytemuck_options_light_layout() {
+        assert_eq!(std::mem::size_of:: < bytemuck_options::Light > (), 96);
+        assert_eq!(std::mem::align_of:: < bytemuck_options::Light > (), 16);
+        assert_eq!(std::mem::offset_of!(bytemuck_options::Light, position), 0);
+        assert_eq!(std::mem::offset_of!(bytemuck_options::Light, intensity), 16);
+        assert_eq!(std::mem::offset_of!(bytemuck_options::Light, colors), 32);
+        let value: bytemuck_options::Light = bytemuck::Zeroable::zeroed();
+        let bytes = bytemuck::bytes_of(&value);
+        assert_eq!(bytes.len(), 96);
+        let read = bytemuck::pod_read_unaligned::<bytemuck_options::Light>(bytes);
+        assert_eq!(bytemuck::bytes_of(& read), bytes);
+    }
+    #[test]
+    fn bytemuck_options_lights_layout() {
+        assert_eq!(std::mem::size_of:: < bytemuck_options::Lights > (), 784);
+        assert_eq!(std::mem::align_of:: < bytemuck_options::Lights > (), 16);
+        assert_eq!(std::mem::offset_of!(bytemuck_options::Lights, count), 0);
+        assert_eq!(std::mem::offset_of!(bytemuck_options::Lights, lights), 16);
+        let value: bytemuck_options::Lights = bytemuck::Zeroable::zeroed();
+        let bytes = bytemuck::bytes_of(&value);
+        assert_eq!(bytes.len(), 784);
+        let read = bytemuck::pod_read_unaligned::<bytemuck_options::Lights>(bytes);
+        assert_eq!(bytemuck::bytes_of(& read), bytes);
+    }
+    #[test]
+    fn bytemuck_options_padded_layout() {
+        assert_eq!(std::mem::size_of:: < bytemuck_options::Padded > (), 672);
+        assert_eq!(std::mem::align_of:: < bytemuck_options::Padded > (), 16);
+        assert_eq!(std::mem::offset_of!(bytemuck_options::Padded, value), 0);
+        assert_eq!(std::mem::offset_of!(bytemuck_options::Padded, history), 32);
+        let value: bytemuck_options::Padded = bytemuck::Zeroable::zeroed();
+        let bytes = bytemuck::bytes_of(&value);
+        assert_eq!(bytes.len(), 672);
+        let read = bytemuck::pod_read_unaligned::<bytemuck_options::Padded>(bytes);
+        assert_eq!(bytemuck::bytes_of(& read), bytes);
+    }
+}
@@ -0,0 +1,453 @@
+// File automatically generated by wgsl_bindgen^
+//
+// ^ wgsl_bindgen version 0.15.1
+// Changes made to this file will not be saved.
+// SourceHash: 44e79e1ab99e10d2d5c28d97f42627c7dfffa57259c5880ccfdeec6eefdcb5c8
+
+#![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderEntry {
+    EncaseOptions,
+}
+impl ShaderEntry {
+    pub fn create_pipeline_layout(&self, device: &wgpu::Device) -> wgpu::PipelineLayout {
+        match self {
+            Self::EncaseOptions => encase_options::create_pipeline_layout(device),
+        }
+    }
+    pub fn create_shader_module_embed_source(
+        &self,
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        match self {
+            Self::EncaseOptions => {
+                encase_options::create_shader_module_embed_source(device)
+            }
+        }
+    }
+}
+mod _root {
+    pub use super::*;
+}
+pub mod encase_options {
+    use super::{_root, _root::*};
+    #[repr(C)]
+    #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
+    pub struct Params {
+        pub scale: f32,
+        pub _pad0: u32,
+        pub offset: glam::Vec2,
+        pub colors: [glam::Vec4; 40],
+    }
+    impl Params {
+        pub const fn new(
+            scale: f32,
+            _pad0: u32,
+            offset: glam::Vec2,
+            colors: [glam::Vec4; 40],
+        ) -> Self {
+            Self {
+                scale,
+                _pad0,
+                offset,
+                colors,
+            }
+        }
+    }
+    impl Default for Params {
+        fn default() -> Self {
+            Self {
+                scale: Default::default(),
+                _pad0: Default::default(),
+                offset: Default::default(),
+                colors: [Default::default(); 40],
+            }
+        }
+    }
+    impl Params {
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const SIZE: u64 = 656;
+        /// The size of the struct rounded up to 256 bytes, the default
+        /// `min_uniform_buffer_offset_alignment` and `min_storage_buffer_offset_alignment`.
+        pub const ALIGNED_SIZE_256: u64 = Self::aligned_size(256);
+        /// The size of the struct rounded up to a multiple of `alignment`, i.e. the stride
+        /// of instances placed at aligned offsets in a buffer.
+        pub const fn aligned_size(alignment: u64) -> u64 {
+            Self::SIZE.next_multiple_of(alignment)
+        }
+        /// The offset of the instance at `index` in a buffer of instances placed at
+        /// offsets aligned to `alignment`.
+        pub const fn offset_of_index(index: u64, alignment: u64) -> u64 {
+            index * Self::aligned_size(alignment)
+        }
+    }
+    impl Params {
+        /// The length of the `colors` array.
+        pub const COLORS_LEN: usize = 40;
+    }
+    impl Params {
+        /// The names and byte offsets of the fields with the WGSL layout.
+        pub const FIELD_OFFSETS: &[(&str, u64)] = &[
+            ("scale", 0),
+            ("_pad0", 4),
+            ("offset", 8),
+            ("colors", 16),
+        ];
+        /// The byte offset of `scale` with the WGSL layout.
+        pub const SCALE_OFFSET: u64 = 0;
+        /// The byte offset of `_pad0` with the WGSL layout.
+        pub const _PAD0_OFFSET: u64 = 4;
+        /// The byte offset of `offset` with the WGSL layout.
+        pub const OFFSET_OFFSET: u64 = 8;
+        /// The byte offset of `colors` with the WGSL layout.
+        pub const COLORS_OFFSET: u64 = 16;
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const GPU_SIZE: u64 = 656;
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0EntriesParams<'a> {
+        pub params: wgpu::BufferBinding<'a>,
+        pub output: wgpu::BufferBinding<'a>,
+    }
+    #[derive(Clone, Debug)]
+    pub struct WgpuBindGroup0Entries<'a> {
+        pub params: wgpu::BindGroupEntry<'a>,
+        pub output: wgpu::BindGroupEntry<'a>,
+    }
+    impl<'a> WgpuBindGroup0Entries<'a> {
+        pub fn new(params: WgpuBindGroup0EntriesParams<'a>) -> Self {
+            Self {
+                params: wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(params.params),
+                },
+                output: wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Buffer(params.output),
+                },
+            }
+        }
+        pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 2] {
+            [self.params, self.output]
+        }
+        pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
+            self.as_array().into_iter().collect()
+        }
+    }
+    #[derive(Debug)]
+    pub struct WgpuBindGroup0(wgpu::BindGroup);
+    impl WgpuBindGroup0 {
+        pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
+            label: Some("EncaseOptions::BindGroup0::LayoutDescriptor"),
+            entries: &[
+                /// @binding(0): "params"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<_root::encase_options::Params>() as _,
+                        ),
+                    },
+                    count: None,
+                },
+                /// @binding(1): "output"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage {
+                            read_only: false,
+                        },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        };
+        pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+            device.create_bind_group_layout(&Self::LAYOUT_DESCRIPTOR)
+        }
+        pub fn from_bindings(
+            device: &wgpu::Device,
+            bindings: WgpuBindGroup0Entries,
+        ) -> Self {
+            let bind_group_layout = Self::get_bind_group_layout(&device);
+            let entries = bindings.as_array();
+            let bind_group = device
+                .create_bind_group(
+                    &wgpu::BindGroupDescriptor {
+                        label: Some("EncaseOptions::BindGroup0"),
+                        layout: &bind_group_layout,
+                        entries: &entries,
+                    },
+                );
+            Self(bind_group)
+        }
+        pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
+            render_pass.set_bind_group(0, &self.0, &[]);
+        }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::ComputePass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(0, &self.0, offsets);
+        }
+    }
+    #[derive(Debug, Copy, Clone)]
+    pub struct WgpuBindGroups<'a> {
+        pub bind_group0: &'a WgpuBindGroup0,
+    }
+    impl<'a> WgpuBindGroups<'a> {
+        pub fn set(&self, pass: &mut wgpu::ComputePass<'a>) {
+            self.bind_group0.set(pass);
+        }
+        /// Sets all the bind groups with the given dynamic offsets, in group order.
+        pub fn set_with_offsets(
+            &self,
+            pass: &mut wgpu::ComputePass<'a>,
+            offsets: [&[wgpu::DynamicOffset]; 1],
+        ) {
+            self.bind_group0.set_with_offsets(pass, offsets[0]);
+        }
+    }
+    /// The number of bind group slots in the pipeline layout.
+    pub const BIND_GROUP_COUNT: u32 = 1;
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(u32)]
+    pub enum BindGroupIndex {
+        Group0 = 0,
+    }
+    impl BindGroupIndex {
+        pub const ALL: [Self; 1] = [Self::Group0];
+        pub fn layout_descriptor(
+            self,
+        ) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+            match self {
+                Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+            }
+        }
+    }
+    pub fn set_bind_groups<'a>(
+        pass: &mut wgpu::ComputePass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+    ) {
+        bind_group0.set(pass);
+    }
+    pub fn set_bind_groups_with_offsets<'a>(
+        pass: &mut wgpu::ComputePass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+        bind_group0_offsets: &[wgpu::DynamicOffset],
+    ) {
+        bind_group0.set_with_offsets(pass, bind_group0_offsets);
+    }
+    /// Creates the layouts and all the bind groups from their entries, in group order.
+    pub fn create_bind_groups(
+        device: &wgpu::Device,
+        bind_group0: WgpuBindGroup0Entries,
+    ) -> (WgpuBindGroup0,) {
+        (WgpuBindGroup0::from_bindings(device, bind_group0),)
+    }
+    pub const PARAMS_GROUP: u32 = 0;
+    pub const PARAMS_BINDING: u32 = 0;
+    pub const OUTPUT_GROUP: u32 = 0;
+    pub const OUTPUT_BINDING: u32 = 1;
+    /// Returns the group and binding index of the binding with the given name.
+    pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+        match name {
+            "params" => Some((0, 0)),
+            "output" => Some((0, 1)),
+            _ => None,
+        }
+    }
+    pub mod compute {
+        pub const CS_MAIN_WORKGROUP_SIZE: [u32; 3] = [64, 1, 1];
+        /// Returns the number of workgroups needed to cover `work_items`, rounding up.
+        pub const fn cs_main_dispatch_count(work_items: [u32; 3]) -> [u32; 3] {
+            [
+                work_items[0].div_ceil(CS_MAIN_WORKGROUP_SIZE[0]),
+                work_items[1].div_ceil(CS_MAIN_WORKGROUP_SIZE[1]),
+                work_items[2].div_ceil(CS_MAIN_WORKGROUP_SIZE[2]),
+            ]
+        }
+        /// Dispatches enough workgroups to cover `work_items` with the pipeline set on `pass`.
+        pub fn dispatch_cs_main(pass: &mut wgpu::ComputePass<'_>, work_items: [u32; 3]) {
+            let [x, y, z] = cs_main_dispatch_count(work_items);
+            pass.dispatch_workgroups(x, y, z);
+        }
+        /// Dispatches the workgroup counts stored in `buffer` at `offset`, laid out like
+        /// `wgpu::util::DispatchIndirectArgs`, with the pipeline set on `pass`.
+        pub fn dispatch_indirect_cs_main(
+            pass: &mut wgpu::ComputePass<'_>,
+            buffer: &wgpu::Buffer,
+            offset: wgpu::BufferAddress,
+        ) {
+            pass.dispatch_workgroups_indirect(buffer, offset);
+        }
+        pub fn create_cs_main_pipeline_embed_source(
+            device: &wgpu::Device,
+        ) -> wgpu::ComputePipeline {
+            let module = super::create_shader_module_embed_source(device);
+            let layout = super::create_pipeline_layout(device);
+            device
+                .create_compute_pipeline(
+                    &wgpu::ComputePipelineDescriptor {
+                        label: Some("Compute Pipeline cs_main"),
+                        layout: Some(&layout),
+                        module: &module,
+                        entry_point: "cs_main",
+                        compilation_options: wgpu::PipelineCompilationOptions {
+                            zero_initialize_workgroup_memory: true,
+                            ..Default::default()
+                        },
+                        cache: None,
+                    },
+                )
+        }
+    }
+    pub const ENTRY_CS_MAIN: &str = "cs_main";
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum EntryPoint {
+        CsMain,
+    }
+    impl EntryPoint {
+        pub const ALL: &'static [Self] = &[Self::CsMain];
+        pub fn name(self) -> &'static str {
+            match self {
+                Self::CsMain => ENTRY_CS_MAIN,
+            }
+        }
+        pub fn stage(self) -> wgpu::ShaderStages {
+            match self {
+                Self::CsMain => wgpu::ShaderStages::COMPUTE,
+            }
+        }
+        /// The workgroup size of compute entry points.
+        pub fn workgroup_size(self) -> Option<[u32; 3]> {
+            match self {
+                Self::CsMain => Some(compute::CS_MAIN_WORKGROUP_SIZE),
+            }
+        }
+    }
+    /// Calls [`create_cs_main_pipeline_with_zero_init`] with `zero_initialize_workgroup_memory` set to `true`.
+    pub fn create_cs_main_pipeline(
+        device: &wgpu::Device,
+        module: &wgpu::ShaderModule,
+    ) -> wgpu::ComputePipeline {
+        create_cs_main_pipeline_with_zero_init(device, module, true)
+    }
+    pub fn create_cs_main_pipeline_with_zero_init(
+        device: &wgpu::Device,
+        module: &wgpu::ShaderModule,
+        zero_initialize_workgroup_memory: bool,
+    ) -> wgpu::ComputePipeline {
+        let layout = create_pipeline_layout(device);
+        let constants: std::collections::HashMap<String, f64> = Default::default();
+        device
+            .create_compute_pipeline(
+                &wgpu::ComputePipelineDescriptor {
+                    label: Some("EncaseOptions::cs_main::ComputePipeline"),
+                    layout: Some(&layout),
+                    module,
+                    entry_point: ENTRY_CS_MAIN,
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: &constants,
+                        zero_initialize_workgroup_memory,
+                        ..Default::default()
+                    },
+                    cache: None,
+                },
+            )
+    }
+    #[derive(Debug)]
+    pub struct WgpuPipelineLayout;
+    impl WgpuPipelineLayout {
+        pub fn bind_group_layout_entries(
+            entries: [wgpu::BindGroupLayout; 1],
+        ) -> [wgpu::BindGroupLayout; 1] {
+            entries
+        }
+    }
+    /// The descriptor of the pipeline layout of the module with the given bind group layouts,
+    /// for callers that create or share the bind group layouts themselves.
+    pub fn pipeline_layout_descriptor<'a>(
+        bind_group_layouts: &'a [&'a wgpu::BindGroupLayout],
+    ) -> wgpu::PipelineLayoutDescriptor<'a> {
+        wgpu::PipelineLayoutDescriptor {
+            label: Some("EncaseOptions::PipelineLayout"),
+            bind_group_layouts,
+            push_constant_ranges: &[],
+        }
+    }
+    pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &pipeline_layout_descriptor(
+                    &[&WgpuBindGroup0::get_bind_group_layout(device)],
+                ),
+            )
+    }
+    /// Creates a pipeline layout with only the bind groups used by `cs_main`.
+    pub fn create_pipeline_layout_for_cs_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &pipeline_layout_descriptor(
+                    &[&WgpuBindGroup0::get_bind_group_layout(device)],
+                ),
+            )
+    }
+    pub fn create_shader_module_embed_source(
+        device: &wgpu::Device,
+    ) -> wgpu::ShaderModule {
+        let source = std::borrow::Cow::Borrowed(SHADER_STRING);
+        device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("encase_options.wgsl"),
+                source: wgpu::ShaderSource::Wgsl(source),
+            })
+    }
+    pub const SHADER_STRING: &'static str = r#"
+struct Params {
+    scale: f32,
+    _pad0_: u32,
+    offset: vec2<f32>,
+    colors: array<vec4<f32>, 40>,
+}
+
+@group(0) @binding(0) 
+var<uniform> params: Params;
+@group(0) @binding(1) 
+var<storage, read_write> output: array<vec4<f32>>;
+
+@compute @workgroup_size(64, 1, 1) 
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let _e10 = params.colors[(id.x % 40u)];
+    let _e13 = params.scale;
+    let _e17 = params.offset;
+    output[id.x] = ((_e10 * _e13) + vec4<f32>(_e17, 0f, 0f));
+    return;
+}
+"#;
+}
+pub mod layout_asserts {
+    use super::{_root, _root::*};
+}
+pub mod bytemuck_impls {
+    use super::{_root, _root::*};
+}
+#[cfg(test)]
+mod generated_layout_tests {
+    use super::{_root, _root::*};
+    #[test]
+    fn encase_options_params_layout() {
+        assert_eq!(
+            < encase_options::Params as encase::ShaderSize > ::SHADER_SIZE.get(), 656
+        );
+    }
+}
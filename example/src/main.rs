@@ -21,6 +21,8 @@ pub struct MyScalars {
 }
 
 // Include the bindings generated by build.rs.
+mod bytemuck_options;
+mod encase_options;
 mod shader_bindings;
 
 struct State {
@@ -1,8 +1,8 @@
 // File automatically generated by wgsl_bindgen^
 //
-// ^ wgsl_bindgen version 0.15.0
+// ^ wgsl_bindgen version 0.15.1
 // Changes made to this file will not be saved.
-// SourceHash: ab0ee9f9f13b9eeef9a51ab610bad34a9a9c52d6a13924e69caa9be2905aa833
+// SourceHash: d7240bc98b49f85e126cfc3ebc0c12c0bc0a99b8f6fd7df36d3e0faeb56d977f
 
 #![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -120,10 +120,10 @@ pub mod layout_asserts {
         assert!(std::mem::offset_of!(utils::types::Nested, b) == 368);
         assert!(std::mem::size_of:: < utils::types::Nested > () == 416);
     };
-    const TESTBED_UNIFORMS_ASSERTS: () = {
-        assert!(std::mem::offset_of!(testbed::Uniforms, color_rgb) == 0);
-        assert!(std::mem::offset_of!(testbed::Uniforms, scalars) == 16);
-        assert!(std::mem::size_of:: < testbed::Uniforms > () == 32);
+    const TESTBED_TESTBED_UNIFORMS_ASSERTS: () = {
+        assert!(std::mem::offset_of!(testbed::TestbedUniforms, color_rgb) == 0);
+        assert!(std::mem::offset_of!(testbed::TestbedUniforms, scalars) == 16);
+        assert!(std::mem::size_of:: < testbed::TestbedUniforms > () == 32);
     };
     const TRIANGLE_UNIFORMS_ASSERTS: () = {
         assert!(std::mem::offset_of!(triangle::Uniforms, color_rgb) == 0);
@@ -187,6 +187,23 @@ pub mod utils {
                 data.build()
             }
         }
+        impl From<VectorsU32> for VectorsU32Init {
+            fn from(data: VectorsU32) -> Self {
+                Self {
+                    a: data.a,
+                    b: data.b,
+                    c: data.c,
+                }
+            }
+        }
+        impl VectorsU32 {
+            /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+            pub fn update_from(&mut self, init: &VectorsU32Init) {
+                self.a = init.a;
+                self.b = init.b;
+                self.c = init.c;
+            }
+        }
         #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct VectorsI32 {
@@ -230,6 +247,23 @@ pub mod utils {
                 data.build()
             }
         }
+        impl From<VectorsI32> for VectorsI32Init {
+            fn from(data: VectorsI32) -> Self {
+                Self {
+                    a: data.a,
+                    b: data.b,
+                    c: data.c,
+                }
+            }
+        }
+        impl VectorsI32 {
+            /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+            pub fn update_from(&mut self, init: &VectorsI32Init) {
+                self.a = init.a;
+                self.b = init.b;
+                self.c = init.c;
+            }
+        }
         #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct VectorsF32 {
@@ -273,6 +307,23 @@ pub mod utils {
                 data.build()
             }
         }
+        impl From<VectorsF32> for VectorsF32Init {
+            fn from(data: VectorsF32) -> Self {
+                Self {
+                    a: data.a,
+                    b: data.b,
+                    c: data.c,
+                }
+            }
+        }
+        impl VectorsF32 {
+            /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+            pub fn update_from(&mut self, init: &VectorsF32Init) {
+                self.a = init.a;
+                self.b = init.b;
+                self.c = init.c;
+            }
+        }
         #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct MatricesF32 {
@@ -356,6 +407,35 @@ pub mod utils {
                 data.build()
             }
         }
+        impl From<MatricesF32> for MatricesF32Init {
+            fn from(data: MatricesF32) -> Self {
+                Self {
+                    a: data.a,
+                    b: data.b,
+                    c: data.c,
+                    d: data.d,
+                    e: data.e,
+                    f: data.f,
+                    g: data.g,
+                    h: data.h,
+                    i: data.i,
+                }
+            }
+        }
+        impl MatricesF32 {
+            /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+            pub fn update_from(&mut self, init: &MatricesF32Init) {
+                self.a = init.a;
+                self.b = init.b;
+                self.c = init.c;
+                self.d = init.d;
+                self.e = init.e;
+                self.f = init.f;
+                self.g = init.g;
+                self.h = init.h;
+                self.i = init.i;
+            }
+        }
         #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct StaticArrays {
@@ -418,6 +498,25 @@ pub mod utils {
                 data.build()
             }
         }
+        impl From<StaticArrays> for StaticArraysInit {
+            fn from(data: StaticArrays) -> Self {
+                Self {
+                    a: data.a,
+                    b: data.b,
+                    c: data.c,
+                    d: data.d,
+                }
+            }
+        }
+        impl StaticArrays {
+            /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+            pub fn update_from(&mut self, init: &StaticArraysInit) {
+                self.a = init.a;
+                self.b = init.b;
+                self.c = init.c;
+                self.d = init.d;
+            }
+        }
         #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct Nested {
@@ -476,8 +575,8 @@ pub mod bytemuck_impls {
     unsafe impl bytemuck::Pod for utils::types::Nested {}
     unsafe impl bytemuck::Zeroable for utils::types::VertexIn {}
     unsafe impl bytemuck::Pod for utils::types::VertexIn {}
-    unsafe impl bytemuck::Zeroable for testbed::Uniforms {}
-    unsafe impl bytemuck::Pod for testbed::Uniforms {}
+    unsafe impl bytemuck::Zeroable for testbed::TestbedUniforms {}
+    unsafe impl bytemuck::Pod for testbed::TestbedUniforms {}
     unsafe impl bytemuck::Zeroable for triangle::Uniforms {}
     unsafe impl bytemuck::Pod for triangle::Uniforms {}
     unsafe impl bytemuck::Zeroable for triangle::VertexInput {}
@@ -489,14 +588,20 @@ pub mod testbed {
     use super::{_root, _root::*};
     #[repr(C, align(16))]
     #[derive(Debug, PartialEq, Clone, Copy)]
-    pub struct Uniforms {
+    pub struct TestbedUniforms {
         /// size: 16, offset: 0x0, type: `vec4<f32>`
         pub color_rgb: glam::Vec4,
         /// size: 16, offset: 0x10, type: `struct`
         pub scalars: crate::MyScalars,
     }
-    pub const fn Uniforms(color_rgb: glam::Vec4, scalars: crate::MyScalars) -> Uniforms {
-        Uniforms { color_rgb, scalars }
+    pub const fn TestbedUniforms(
+        color_rgb: glam::Vec4,
+        scalars: crate::MyScalars,
+    ) -> TestbedUniforms {
+        TestbedUniforms {
+            color_rgb,
+            scalars,
+        }
     }
     #[derive(Debug)]
     pub struct WgpuBindGroup0EntriesParams<'a> {
@@ -578,26 +683,33 @@ pub mod testbed {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(0, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(0, &self.0, offsets);
+        }
     }
     #[derive(Debug)]
     pub struct WgpuBindGroup1EntriesParams<'a> {
-        pub uniforms: wgpu::BufferBinding<'a>,
+        pub testbed_uniforms: wgpu::BufferBinding<'a>,
     }
     #[derive(Clone, Debug)]
     pub struct WgpuBindGroup1Entries<'a> {
-        pub uniforms: wgpu::BindGroupEntry<'a>,
+        pub testbed_uniforms: wgpu::BindGroupEntry<'a>,
     }
     impl<'a> WgpuBindGroup1Entries<'a> {
         pub fn new(params: WgpuBindGroup1EntriesParams<'a>) -> Self {
             Self {
-                uniforms: wgpu::BindGroupEntry {
+                testbed_uniforms: wgpu::BindGroupEntry {
                     binding: 0,
-                    resource: wgpu::BindingResource::Buffer(params.uniforms),
+                    resource: wgpu::BindingResource::Buffer(params.testbed_uniforms),
                 },
             }
         }
         pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 1] {
-            [self.uniforms]
+            [self.testbed_uniforms]
         }
         pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
             self.as_array().into_iter().collect()
@@ -609,7 +721,7 @@ pub mod testbed {
         pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = wgpu::BindGroupLayoutDescriptor {
             label: Some("Testbed::BindGroup1::LayoutDescriptor"),
             entries: &[
-                /// @binding(0): "uniforms"
+                /// @binding(0): "testbed_uniforms"
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
                     visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
@@ -617,7 +729,7 @@ pub mod testbed {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
                         min_binding_size: std::num::NonZeroU64::new(
-                            std::mem::size_of::<_root::testbed::Uniforms>() as _,
+                            std::mem::size_of::<_root::testbed::TestbedUniforms>() as _,
                         ),
                     },
                     count: None,
@@ -646,6 +758,13 @@ pub mod testbed {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(1, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(1, &self.0, offsets);
+        }
     }
     #[derive(Debug)]
     pub struct WgpuBindGroup2EntriesParams<'a> {
@@ -842,6 +961,13 @@ pub mod testbed {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(2, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(2, &self.0, offsets);
+        }
     }
     #[derive(Debug, Copy, Clone)]
     pub struct WgpuBindGroups<'a> {
@@ -855,6 +981,37 @@ pub mod testbed {
             self.bind_group1.set(pass);
             self.bind_group2.set(pass);
         }
+        /// Sets all the bind groups with the given dynamic offsets, in group order.
+        pub fn set_with_offsets(
+            &self,
+            pass: &mut wgpu::RenderPass<'a>,
+            offsets: [&[wgpu::DynamicOffset]; 3],
+        ) {
+            self.bind_group0.set_with_offsets(pass, offsets[0]);
+            self.bind_group1.set_with_offsets(pass, offsets[1]);
+            self.bind_group2.set_with_offsets(pass, offsets[2]);
+        }
+    }
+    /// The number of bind group slots in the pipeline layout.
+    pub const BIND_GROUP_COUNT: u32 = 3;
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(u32)]
+    pub enum BindGroupIndex {
+        Group0 = 0,
+        Group1 = 1,
+        Group2 = 2,
+    }
+    impl BindGroupIndex {
+        pub const ALL: [Self; 3] = [Self::Group0, Self::Group1, Self::Group2];
+        pub fn layout_descriptor(
+            self,
+        ) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+            match self {
+                Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+                Self::Group1 => &WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+                Self::Group2 => &WgpuBindGroup2::LAYOUT_DESCRIPTOR,
+            }
+        }
     }
     pub fn set_bind_groups<'a>(
         pass: &mut wgpu::RenderPass<'a>,
@@ -866,13 +1023,103 @@ pub mod testbed {
         bind_group1.set(pass);
         bind_group2.set(pass);
     }
+    pub fn set_bind_groups_with_offsets<'a>(
+        pass: &mut wgpu::RenderPass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+        bind_group0_offsets: &[wgpu::DynamicOffset],
+        bind_group1: &'a WgpuBindGroup1,
+        bind_group1_offsets: &[wgpu::DynamicOffset],
+        bind_group2: &'a WgpuBindGroup2,
+        bind_group2_offsets: &[wgpu::DynamicOffset],
+    ) {
+        bind_group0.set_with_offsets(pass, bind_group0_offsets);
+        bind_group1.set_with_offsets(pass, bind_group1_offsets);
+        bind_group2.set_with_offsets(pass, bind_group2_offsets);
+    }
+    /// Creates the layouts and all the bind groups from their entries, in group order.
+    pub fn create_bind_groups(
+        device: &wgpu::Device,
+        bind_group0: WgpuBindGroup0Entries,
+        bind_group1: WgpuBindGroup1Entries,
+        bind_group2: WgpuBindGroup2Entries,
+    ) -> (WgpuBindGroup0, WgpuBindGroup1, WgpuBindGroup2) {
+        (
+            WgpuBindGroup0::from_bindings(device, bind_group0),
+            WgpuBindGroup1::from_bindings(device, bind_group1),
+            WgpuBindGroup2::from_bindings(device, bind_group2),
+        )
+    }
+    pub const COLOR_TEXTURE_GROUP: u32 = 0;
+    pub const COLOR_TEXTURE_BINDING: u32 = 0;
+    pub const COLOR_SAMPLER_GROUP: u32 = 0;
+    pub const COLOR_SAMPLER_BINDING: u32 = 1;
+    pub const TESTBED_UNIFORMS_GROUP: u32 = 1;
+    pub const TESTBED_UNIFORMS_BINDING: u32 = 0;
+    pub const A_GROUP: u32 = 2;
+    pub const A_BINDING: u32 = 2;
+    pub const B_GROUP: u32 = 2;
+    pub const B_BINDING: u32 = 3;
+    pub const C_GROUP: u32 = 2;
+    pub const C_BINDING: u32 = 4;
+    pub const D_GROUP: u32 = 2;
+    pub const D_BINDING: u32 = 5;
+    pub const F_GROUP: u32 = 2;
+    pub const F_BINDING: u32 = 6;
+    pub const H_GROUP: u32 = 2;
+    pub const H_BINDING: u32 = 8;
+    pub const I_GROUP: u32 = 2;
+    pub const I_BINDING: u32 = 9;
+    /// Returns the group and binding index of the binding with the given name.
+    pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+        match name {
+            "color_texture" => Some((0, 0)),
+            "color_sampler" => Some((0, 1)),
+            "testbed_uniforms" => Some((1, 0)),
+            "a" => Some((2, 2)),
+            "b" => Some((2, 3)),
+            "c" => Some((2, 4)),
+            "d" => Some((2, 5)),
+            "f" => Some((2, 6)),
+            "h" => Some((2, 8)),
+            "i" => Some((2, 9)),
+            _ => None,
+        }
+    }
     pub const ENTRY_VERTEX_MAIN: &str = "vertex_main";
     pub const ENTRY_FRAGMENT_MAIN: &str = "fragment_main";
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum EntryPoint {
+        VertexMain,
+        FragmentMain,
+    }
+    impl EntryPoint {
+        pub const ALL: &'static [Self] = &[Self::VertexMain, Self::FragmentMain];
+        pub fn name(self) -> &'static str {
+            match self {
+                Self::VertexMain => ENTRY_VERTEX_MAIN,
+                Self::FragmentMain => ENTRY_FRAGMENT_MAIN,
+            }
+        }
+        pub fn stage(self) -> wgpu::ShaderStages {
+            match self {
+                Self::VertexMain => wgpu::ShaderStages::VERTEX,
+                Self::FragmentMain => wgpu::ShaderStages::FRAGMENT,
+            }
+        }
+        /// The workgroup size of compute entry points.
+        pub fn workgroup_size(self) -> Option<[u32; 3]> {
+            match self {
+                Self::VertexMain => None,
+                Self::FragmentMain => None,
+            }
+        }
+    }
     #[derive(Debug)]
     pub struct VertexEntry<const N: usize> {
         pub entry_point: &'static str,
         pub buffers: [wgpu::VertexBufferLayout<'static>; N],
         pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
     }
     pub fn vertex_state<'a, const N: usize>(
         module: &'a wgpu::ShaderModule,
@@ -884,6 +1131,7 @@ pub mod testbed {
             buffers: &entry.buffers,
             compilation_options: wgpu::PipelineCompilationOptions {
                 constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                 ..Default::default()
             },
         }
@@ -893,13 +1141,20 @@ pub mod testbed {
             entry_point: ENTRY_VERTEX_MAIN,
             buffers: [utils::types::VertexIn::vertex_buffer_layout(vertex_in)],
             constants: Default::default(),
+            zero_initialize_workgroup_memory: true,
         }
     }
+    /// Calls [`vertex_main_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:
+    /// - `VertexIn`: `Vertex`
+    pub fn vertex_main_entry_default() -> VertexEntry<1> {
+        vertex_main_entry(wgpu::VertexStepMode::Vertex)
+    }
     #[derive(Debug)]
     pub struct FragmentEntry<const N: usize> {
         pub entry_point: &'static str,
         pub targets: [Option<wgpu::ColorTargetState>; N],
         pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
     }
     pub fn fragment_state<'a, const N: usize>(
         module: &'a wgpu::ShaderModule,
@@ -911,6 +1166,7 @@ pub mod testbed {
             targets: &entry.targets,
             compilation_options: wgpu::PipelineCompilationOptions {
                 constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                 ..Default::default()
             },
         }
@@ -922,7 +1178,118 @@ pub mod testbed {
             entry_point: ENTRY_FRAGMENT_MAIN,
             targets,
             constants: Default::default(),
-        }
+            zero_initialize_workgroup_memory: true,
+        }
+    }
+    /// Calls [`fragment_main_entry`] with a single color target of `format`, without blending and writing all channels.
+    pub fn fragment_main_entry_single(format: wgpu::TextureFormat) -> FragmentEntry<1> {
+        fragment_main_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// Calls [`fragment_main_entry`] with a single color target of `format` blended with `blend`, writing all channels.
+    pub fn fragment_main_entry_single_blend(
+        format: wgpu::TextureFormat,
+        blend: wgpu::BlendState,
+    ) -> FragmentEntry<1> {
+        fragment_main_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// The output locations of the entry point in ascending order, with their scalar kinds:
+    /// - `0`: float
+    pub const FRAGMENT_MAIN_OUTPUT_LOCATIONS: [u32; 1] = [0];
+    /// Returns the color targets of the entry point with the same format for every output,
+    /// without blending and writing all channels.
+    pub fn fragment_main_targets(
+        format: wgpu::TextureFormat,
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        fragment_main_targets_with_formats([format; 1])
+    }
+    /// Returns the color targets of the entry point with one format per output, in the order
+    /// of the output locations, without blending and writing all channels.
+    pub fn fragment_main_targets_with_formats(
+        formats: [wgpu::TextureFormat; 1],
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        [
+            Some(wgpu::ColorTargetState {
+                format: formats[0],
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ]
+    }
+    /// Whether the entry point writes `@builtin(frag_depth)`.
+    pub const FRAGMENT_MAIN_WRITES_DEPTH: bool = false;
+    /// The entries and states of a render pipeline. The optional states default to a
+    /// triangle list without culling, no depth stencil and no multisampling.
+    #[derive(Debug)]
+    pub struct CreatePipelineArgs<const N: usize, const M: usize> {
+        pub vertex: VertexEntry<N>,
+        pub fragment: FragmentEntry<M>,
+        pub primitive: Option<wgpu::PrimitiveState>,
+        pub depth_stencil: Option<wgpu::DepthStencilState>,
+        pub multisample: Option<wgpu::MultisampleState>,
+    }
+    pub fn render_pipeline_descriptor<'a, const N: usize, const M: usize>(
+        layout: &'a wgpu::PipelineLayout,
+        module: &'a wgpu::ShaderModule,
+        args: &'a CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipelineDescriptor<'a> {
+        wgpu::RenderPipelineDescriptor {
+            label: Some("Testbed::RenderPipeline"),
+            layout: Some(layout),
+            vertex: vertex_state(module, &args.vertex),
+            primitive: args.primitive.unwrap_or_default(),
+            depth_stencil: args.depth_stencil.clone(),
+            multisample: args.multisample.unwrap_or_default(),
+            fragment: Some(fragment_state(module, &args.fragment)),
+            multiview: None,
+            cache: None,
+        }
+    }
+    pub fn create_pipeline_embed_source<const N: usize, const M: usize>(
+        device: &wgpu::Device,
+        args: CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipeline {
+        let module = create_shader_module_embed_source(device);
+        let layout = create_pipeline_layout(device);
+        device
+            .create_render_pipeline(&render_pipeline_descriptor(&layout, &module, &args))
+    }
+    pub fn create_pipeline_embedded<const N: usize, const M: usize>(
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<
+            String,
+            naga_oil::compose::ShaderDefValue,
+        >,
+        args: CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipeline {
+        let module = create_shader_module_embedded(device, shader_defs);
+        let layout = create_pipeline_layout(device);
+        device
+            .create_render_pipeline(&render_pipeline_descriptor(&layout, &module, &args))
+    }
+    pub fn create_pipeline_from_path<const N: usize, const M: usize>(
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<
+            String,
+            naga_oil::compose::ShaderDefValue,
+        >,
+        args: CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipeline {
+        let module = create_shader_module_from_path(device, shader_defs).unwrap();
+        let layout = create_pipeline_layout(device);
+        device
+            .create_render_pipeline(&render_pipeline_descriptor(&layout, &module, &args))
     }
     #[derive(Debug)]
     pub struct WgpuPipelineLayout;
@@ -933,20 +1300,41 @@ pub mod testbed {
             entries
         }
     }
+    /// The descriptor of the pipeline layout of the module with the given bind group layouts,
+    /// for callers that create or share the bind group layouts themselves.
+    pub fn pipeline_layout_descriptor<'a>(
+        bind_group_layouts: &'a [&'a wgpu::BindGroupLayout],
+    ) -> wgpu::PipelineLayoutDescriptor<'a> {
+        wgpu::PipelineLayoutDescriptor {
+            label: Some("Testbed::PipelineLayout"),
+            bind_group_layouts,
+            push_constant_ranges: &[],
+        }
+    }
     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
         device
             .create_pipeline_layout(
-                &wgpu::PipelineLayoutDescriptor {
-                    label: Some("Testbed::PipelineLayout"),
-                    bind_group_layouts: &[
+                &pipeline_layout_descriptor(
+                    &[
                         &WgpuBindGroup0::get_bind_group_layout(device),
                         &WgpuBindGroup1::get_bind_group_layout(device),
                         &WgpuBindGroup2::get_bind_group_layout(device),
                     ],
-                    push_constant_ranges: &[],
-                },
+                ),
             )
     }
+    /// Creates a pipeline layout with only the bind groups used by `vertex_main`.
+    pub fn create_pipeline_layout_for_vertex_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device.create_pipeline_layout(&pipeline_layout_descriptor(&[]))
+    }
+    /// Creates a pipeline layout with only the bind groups used by `fragment_main`.
+    pub fn create_pipeline_layout_for_fragment_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device.create_pipeline_layout(&pipeline_layout_descriptor(&[]))
+    }
     pub fn create_shader_module_embed_source(
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
@@ -1012,7 +1400,7 @@ struct VertexInX_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX {
     @location(0) position: vec4<f32>,
 }
 
-struct Uniforms {
+struct TestbedUniforms {
     color_rgb: vec4<f32>,
     scalars: ScalarsX_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX,
 }
@@ -1040,7 +1428,7 @@ var color_texture: texture_2d<f32>;
 @group(0) @binding(1) 
 var color_sampler: sampler;
 @group(1) @binding(0) 
-var<uniform> uniforms: Uniforms;
+var<uniform> testbed_uniforms: TestbedUniforms;
 
 @vertex 
 fn vertex_main(input: VertexInX_naga_oil_mod_XOV2GS3DTHI5HI6LQMVZQX) -> VertexOutput {
@@ -1181,6 +1569,18 @@ fn fragment_main(input_1: VertexOutput) -> @location(0) vec4<f32> {
                 }),
         )
     }
+    /// Creates the shader module from the shader files if they all exist, e.g. to hot reload
+    /// them during development, or else from the embedded source, which ignores `shader_defs`.
+    pub fn create_shader_module_from_path_or_embedded(
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::ShaderModule, naga_oil::compose::ComposerError> {
+        if SHADER_PATHS.iter().all(|path| std::path::Path::new(path).exists()) {
+            create_shader_module_from_path(device, shader_defs)
+        } else {
+            Ok(create_shader_module_embed_source(device))
+        }
+    }
 }
 pub mod triangle {
     use super::{_root, _root::*};
@@ -1267,7 +1667,7 @@ pub mod triangle {
                 /// @binding(0): "color_texture"
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Texture {
                         sample_type: wgpu::TextureSampleType::Float {
                             filterable: true,
@@ -1280,7 +1680,7 @@ pub mod triangle {
                 /// @binding(1): "color_sampler"
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
@@ -1308,6 +1708,13 @@ pub mod triangle {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(0, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(0, &self.0, offsets);
+        }
     }
     #[derive(Debug)]
     pub struct WgpuBindGroup1EntriesParams<'a> {
@@ -1342,7 +1749,7 @@ pub mod triangle {
                 /// @binding(0): "uniforms"
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -1376,6 +1783,13 @@ pub mod triangle {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(1, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(1, &self.0, offsets);
+        }
     }
     #[derive(Debug, Copy, Clone)]
     pub struct WgpuBindGroups<'a> {
@@ -1387,6 +1801,34 @@ pub mod triangle {
             self.bind_group0.set(pass);
             self.bind_group1.set(pass);
         }
+        /// Sets all the bind groups with the given dynamic offsets, in group order.
+        pub fn set_with_offsets(
+            &self,
+            pass: &mut wgpu::RenderPass<'a>,
+            offsets: [&[wgpu::DynamicOffset]; 2],
+        ) {
+            self.bind_group0.set_with_offsets(pass, offsets[0]);
+            self.bind_group1.set_with_offsets(pass, offsets[1]);
+        }
+    }
+    /// The number of bind group slots in the pipeline layout.
+    pub const BIND_GROUP_COUNT: u32 = 2;
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(u32)]
+    pub enum BindGroupIndex {
+        Group0 = 0,
+        Group1 = 1,
+    }
+    impl BindGroupIndex {
+        pub const ALL: [Self; 2] = [Self::Group0, Self::Group1];
+        pub fn layout_descriptor(
+            self,
+        ) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+            match self {
+                Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+                Self::Group1 => &WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+            }
+        }
     }
     pub fn set_bind_groups<'a>(
         pass: &mut wgpu::RenderPass<'a>,
@@ -1396,13 +1838,77 @@ pub mod triangle {
         bind_group0.set(pass);
         bind_group1.set(pass);
     }
+    pub fn set_bind_groups_with_offsets<'a>(
+        pass: &mut wgpu::RenderPass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+        bind_group0_offsets: &[wgpu::DynamicOffset],
+        bind_group1: &'a WgpuBindGroup1,
+        bind_group1_offsets: &[wgpu::DynamicOffset],
+    ) {
+        bind_group0.set_with_offsets(pass, bind_group0_offsets);
+        bind_group1.set_with_offsets(pass, bind_group1_offsets);
+    }
+    /// Creates the layouts and all the bind groups from their entries, in group order.
+    pub fn create_bind_groups(
+        device: &wgpu::Device,
+        bind_group0: WgpuBindGroup0Entries,
+        bind_group1: WgpuBindGroup1Entries,
+    ) -> (WgpuBindGroup0, WgpuBindGroup1) {
+        (
+            WgpuBindGroup0::from_bindings(device, bind_group0),
+            WgpuBindGroup1::from_bindings(device, bind_group1),
+        )
+    }
+    pub const COLOR_TEXTURE_GROUP: u32 = 0;
+    pub const COLOR_TEXTURE_BINDING: u32 = 0;
+    pub const COLOR_SAMPLER_GROUP: u32 = 0;
+    pub const COLOR_SAMPLER_BINDING: u32 = 1;
+    pub const UNIFORMS_GROUP: u32 = 1;
+    pub const UNIFORMS_BINDING: u32 = 0;
+    /// Returns the group and binding index of the binding with the given name.
+    pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+        match name {
+            "color_texture" => Some((0, 0)),
+            "color_sampler" => Some((0, 1)),
+            "uniforms" => Some((1, 0)),
+            _ => None,
+        }
+    }
     pub const ENTRY_VS_MAIN: &str = "vs_main";
     pub const ENTRY_FS_MAIN: &str = "fs_main";
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum EntryPoint {
+        VsMain,
+        FsMain,
+    }
+    impl EntryPoint {
+        pub const ALL: &'static [Self] = &[Self::VsMain, Self::FsMain];
+        pub fn name(self) -> &'static str {
+            match self {
+                Self::VsMain => ENTRY_VS_MAIN,
+                Self::FsMain => ENTRY_FS_MAIN,
+            }
+        }
+        pub fn stage(self) -> wgpu::ShaderStages {
+            match self {
+                Self::VsMain => wgpu::ShaderStages::VERTEX,
+                Self::FsMain => wgpu::ShaderStages::FRAGMENT,
+            }
+        }
+        /// The workgroup size of compute entry points.
+        pub fn workgroup_size(self) -> Option<[u32; 3]> {
+            match self {
+                Self::VsMain => None,
+                Self::FsMain => None,
+            }
+        }
+    }
     #[derive(Debug)]
     pub struct VertexEntry<const N: usize> {
         pub entry_point: &'static str,
         pub buffers: [wgpu::VertexBufferLayout<'static>; N],
         pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
     }
     pub fn vertex_state<'a, const N: usize>(
         module: &'a wgpu::ShaderModule,
@@ -1414,6 +1920,7 @@ pub mod triangle {
             buffers: &entry.buffers,
             compilation_options: wgpu::PipelineCompilationOptions {
                 constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                 ..Default::default()
             },
         }
@@ -1423,13 +1930,20 @@ pub mod triangle {
             entry_point: ENTRY_VS_MAIN,
             buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
             constants: Default::default(),
+            zero_initialize_workgroup_memory: true,
         }
     }
+    /// Calls [`vs_main_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:
+    /// - `VertexInput`: `Vertex`
+    pub fn vs_main_entry_default() -> VertexEntry<1> {
+        vs_main_entry(wgpu::VertexStepMode::Vertex)
+    }
     #[derive(Debug)]
     pub struct FragmentEntry<const N: usize> {
         pub entry_point: &'static str,
         pub targets: [Option<wgpu::ColorTargetState>; N],
         pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
     }
     pub fn fragment_state<'a, const N: usize>(
         module: &'a wgpu::ShaderModule,
@@ -1441,6 +1955,7 @@ pub mod triangle {
             targets: &entry.targets,
             compilation_options: wgpu::PipelineCompilationOptions {
                 constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                 ..Default::default()
             },
         }
@@ -1452,7 +1967,118 @@ pub mod triangle {
             entry_point: ENTRY_FS_MAIN,
             targets,
             constants: Default::default(),
-        }
+            zero_initialize_workgroup_memory: true,
+        }
+    }
+    /// Calls [`fs_main_entry`] with a single color target of `format`, without blending and writing all channels.
+    pub fn fs_main_entry_single(format: wgpu::TextureFormat) -> FragmentEntry<1> {
+        fs_main_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// Calls [`fs_main_entry`] with a single color target of `format` blended with `blend`, writing all channels.
+    pub fn fs_main_entry_single_blend(
+        format: wgpu::TextureFormat,
+        blend: wgpu::BlendState,
+    ) -> FragmentEntry<1> {
+        fs_main_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// The output locations of the entry point in ascending order, with their scalar kinds:
+    /// - `0`: float
+    pub const FS_MAIN_OUTPUT_LOCATIONS: [u32; 1] = [0];
+    /// Returns the color targets of the entry point with the same format for every output,
+    /// without blending and writing all channels.
+    pub fn fs_main_targets(
+        format: wgpu::TextureFormat,
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        fs_main_targets_with_formats([format; 1])
+    }
+    /// Returns the color targets of the entry point with one format per output, in the order
+    /// of the output locations, without blending and writing all channels.
+    pub fn fs_main_targets_with_formats(
+        formats: [wgpu::TextureFormat; 1],
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        [
+            Some(wgpu::ColorTargetState {
+                format: formats[0],
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ]
+    }
+    /// Whether the entry point writes `@builtin(frag_depth)`.
+    pub const FS_MAIN_WRITES_DEPTH: bool = false;
+    /// The entries and states of a render pipeline. The optional states default to a
+    /// triangle list without culling, no depth stencil and no multisampling.
+    #[derive(Debug)]
+    pub struct CreatePipelineArgs<const N: usize, const M: usize> {
+        pub vertex: VertexEntry<N>,
+        pub fragment: FragmentEntry<M>,
+        pub primitive: Option<wgpu::PrimitiveState>,
+        pub depth_stencil: Option<wgpu::DepthStencilState>,
+        pub multisample: Option<wgpu::MultisampleState>,
+    }
+    pub fn render_pipeline_descriptor<'a, const N: usize, const M: usize>(
+        layout: &'a wgpu::PipelineLayout,
+        module: &'a wgpu::ShaderModule,
+        args: &'a CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipelineDescriptor<'a> {
+        wgpu::RenderPipelineDescriptor {
+            label: Some("Triangle::RenderPipeline"),
+            layout: Some(layout),
+            vertex: vertex_state(module, &args.vertex),
+            primitive: args.primitive.unwrap_or_default(),
+            depth_stencil: args.depth_stencil.clone(),
+            multisample: args.multisample.unwrap_or_default(),
+            fragment: Some(fragment_state(module, &args.fragment)),
+            multiview: None,
+            cache: None,
+        }
+    }
+    pub fn create_pipeline_embed_source<const N: usize, const M: usize>(
+        device: &wgpu::Device,
+        args: CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipeline {
+        let module = create_shader_module_embed_source(device);
+        let layout = create_pipeline_layout(device);
+        device
+            .create_render_pipeline(&render_pipeline_descriptor(&layout, &module, &args))
+    }
+    pub fn create_pipeline_embedded<const N: usize, const M: usize>(
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<
+            String,
+            naga_oil::compose::ShaderDefValue,
+        >,
+        args: CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipeline {
+        let module = create_shader_module_embedded(device, shader_defs);
+        let layout = create_pipeline_layout(device);
+        device
+            .create_render_pipeline(&render_pipeline_descriptor(&layout, &module, &args))
+    }
+    pub fn create_pipeline_from_path<const N: usize, const M: usize>(
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<
+            String,
+            naga_oil::compose::ShaderDefValue,
+        >,
+        args: CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipeline {
+        let module = create_shader_module_from_path(device, shader_defs).unwrap();
+        let layout = create_pipeline_layout(device);
+        device
+            .create_render_pipeline(&render_pipeline_descriptor(&layout, &module, &args))
     }
     #[derive(Debug)]
     pub struct WgpuPipelineLayout;
@@ -1463,24 +2089,66 @@ pub mod triangle {
             entries
         }
     }
+    /// The descriptor of the pipeline layout of the module with the given bind group layouts,
+    /// for callers that create or share the bind group layouts themselves.
+    pub fn pipeline_layout_descriptor<'a>(
+        bind_group_layouts: &'a [&'a wgpu::BindGroupLayout],
+    ) -> wgpu::PipelineLayoutDescriptor<'a> {
+        wgpu::PipelineLayoutDescriptor {
+            label: Some("Triangle::PipelineLayout"),
+            bind_group_layouts,
+            push_constant_ranges: PUSH_CONSTANT_RANGES,
+        }
+    }
     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
         device
             .create_pipeline_layout(
-                &wgpu::PipelineLayoutDescriptor {
-                    label: Some("Triangle::PipelineLayout"),
-                    bind_group_layouts: &[
+                &pipeline_layout_descriptor(
+                    &[
                         &WgpuBindGroup0::get_bind_group_layout(device),
                         &WgpuBindGroup1::get_bind_group_layout(device),
                     ],
-                    push_constant_ranges: &[
-                        wgpu::PushConstantRange {
-                            stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                            range: 0..64,
-                        },
+                ),
+            )
+    }
+    /// Creates a pipeline layout with only the bind groups used by `vs_main`.
+    pub fn create_pipeline_layout_for_vs_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device.create_pipeline_layout(&pipeline_layout_descriptor(&[]))
+    }
+    /// Creates a pipeline layout with only the bind groups used by `fs_main`.
+    pub fn create_pipeline_layout_for_fs_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &pipeline_layout_descriptor(
+                    &[
+                        &WgpuBindGroup0::get_bind_group_layout(device),
+                        &WgpuBindGroup1::get_bind_group_layout(device),
                     ],
-                },
+                ),
             )
     }
+    pub const PUSH_CONSTANT_RANGES: &[wgpu::PushConstantRange] = &[
+        wgpu::PushConstantRange {
+            stages: wgpu::ShaderStages::FRAGMENT,
+            range: 0..64,
+        },
+    ];
+    /// Sets the push constants from the start of `data`, leaving out any padding the
+    /// Rust type has past the size of the shader type.
+    pub fn set_push_constants(
+        pass: &mut wgpu::RenderPass<'_>,
+        data: &_root::triangle::PushConstants,
+    ) {
+        pass.set_push_constants(
+            wgpu::ShaderStages::FRAGMENT,
+            0,
+            &bytemuck::bytes_of(data)[..64],
+        );
+    }
     pub fn create_shader_module_embed_source(
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
@@ -1639,4 +2307,16 @@ fn fs_main(in_1: VertexOutput) -> @location(0) vec4<f32> {
                 }),
         )
     }
+    /// Creates the shader module from the shader files if they all exist, e.g. to hot reload
+    /// them during development, or else from the embedded source, which ignores `shader_defs`.
+    pub fn create_shader_module_from_path_or_embedded(
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::ShaderModule, naga_oil::compose::ComposerError> {
+        if SHADER_PATHS.iter().all(|path| std::path::Path::new(path).exists()) {
+            create_shader_module_from_path(device, shader_defs)
+        } else {
+            Ok(create_shader_module_embed_source(device))
+        }
+    }
 }
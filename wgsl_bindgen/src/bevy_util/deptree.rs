@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use colored::*;
 use indexmap::map::Entry;
@@ -7,8 +8,9 @@ use smallvec::SmallVec;
 use thiserror::Error;
 use DependencyTreeError::*;
 
+use super::import_resolver::{FileSystemImportResolver, ImportResolver};
 use super::parse_imports::ImportStatement;
-use super::source_file::SourceFile;
+use super::source_file::{normalize_line_endings, SourceFile};
 use super::ModulePathResolver;
 use crate::{
   AdditionalScanDirectory, FxIndexMap, FxIndexSet, ImportPathPart, SourceFilePath,
@@ -20,17 +22,37 @@ pub enum DependencyTreeError {
   #[error("Source file not found: {path}")]
   SourceNotFound { path: SourceFilePath },
   #[error("Cannot find import `{path}` in this scope")]
-  #[diagnostic(help("Maybe a typo or a missing file."))]
   ImportPathNotFound {
     path: String,
-    stmt: ImportStatement,
+    stmt: Box<ImportStatement>,
+    /// The candidate file paths that were checked, in resolution order.
+    searched_paths: Vec<String>,
+
+    #[help]
+    help: String,
 
     #[source_code]
-    src: NamedSource<String>,
+    src: Arc<NamedSource<String>>,
 
     #[label("Import statement")]
     import_bit: SourceSpan,
   },
+  #[error("Failed to parse import statement: {msg}")]
+  ImportParseError {
+    msg: String,
+
+    #[source_code]
+    src: Arc<NamedSource<String>>,
+
+    #[label("Import statement")]
+    import_bit: SourceSpan,
+  },
+  #[error("Recursion limit exceeded while resolving imports")]
+  #[diagnostic(help("The files visited during the recursion were:{visited}"))]
+  RecursionLimitExceeded { visited: String },
+  #[error("Circular import detected: {}", chain.join(" -> "))]
+  #[diagnostic(help("Move the shared items into a module that both files can import."))]
+  CircularImport { chain: Vec<String> },
 }
 
 #[derive(Default)]
@@ -56,7 +78,24 @@ impl MaxRecursionLimiter {
     self
   }
 
-  fn check_depth(&self) {
+  /// Returns the chain of files leading back to `target` if it is already being crawled.
+  fn find_cycle(&self, target: &SourceFilePath) -> Option<Vec<String>> {
+    let target = target.to_string();
+    let start = self
+      .files_visited
+      .iter()
+      .position(|(path, _, _)| *path == target)?;
+
+    let chain = self.files_visited[start..]
+      .iter()
+      .map(|(path, _, _)| path.clone())
+      .chain(std::iter::once(target))
+      .collect();
+
+    Some(chain)
+  }
+
+  fn check_depth(&self) -> Result<(), DependencyTreeError> {
     if self.files_visited.len() > Self::MAX_RECURSION_DEPTH {
       let visited_files = self
         .files_visited
@@ -72,13 +111,12 @@ impl MaxRecursionLimiter {
         .rev()
         .collect::<String>();
 
-      panic!(
-        "{}\n{}\n{}\n",
-        "Recursion limit exceeded".red(),
-        "This error may be due to a circular dependency. The files visited during the recursion were:".red(),
-        visited_files
-       );
+      return Err(RecursionLimitExceeded {
+        visited: visited_files,
+      });
     }
+
+    Ok(())
   }
 }
 
@@ -91,6 +129,7 @@ pub struct SourceWithFullDependenciesResult<'a> {
 #[derive(Debug)]
 pub struct DependencyTree {
   resolver: ModulePathResolver,
+  import_resolver: Arc<dyn ImportResolver>,
   parsed_sources: FxIndexMap<SourceFilePath, SourceFile>,
   entry_points: FxIndexSet<SourceFilePath>,
 }
@@ -123,12 +162,31 @@ impl DependencyTree {
     entry_module_prefix: Option<String>,
     entry_points: Vec<SourceFilePath>, // path to entry points
     additional_scan_dirs: Vec<AdditionalScanDirectory>,
+  ) -> Result<Self, DependencyTreeError> {
+    Self::try_build_with_resolver(
+      workspace_root,
+      entry_module_prefix,
+      entry_points,
+      additional_scan_dirs,
+      Arc::new(FileSystemImportResolver),
+    )
+  }
+
+  /// Tries to build a dependency tree like [`Self::try_build`], reading the entry points and
+  /// their imports through the given `import_resolver` instead of the file system.
+  pub fn try_build_with_resolver(
+    workspace_root: PathBuf,
+    entry_module_prefix: Option<String>,
+    entry_points: Vec<SourceFilePath>,
+    additional_scan_dirs: Vec<AdditionalScanDirectory>,
+    import_resolver: Arc<dyn ImportResolver>,
   ) -> Result<Self, DependencyTreeError> {
     let resolver =
       ModulePathResolver::new(workspace_root, entry_module_prefix, additional_scan_dirs);
 
     let mut tree = Self {
       resolver,
+      import_resolver,
       parsed_sources: Default::default(),
       entry_points: Default::default(),
     };
@@ -149,38 +207,60 @@ impl DependencyTree {
     import_path_part: &ImportPathPart,
     limiter: &mut MaxRecursionLimiter,
   ) -> Result<(), DependencyTreeError> {
-    let possible_source_path = self
+    let possible_paths = self
       .resolver
-      .generate_best_possible_paths(&import_path_part, parent_source_path)
-      .into_iter()
-      .find(|(_, path)| path.is_file()); // make sure this is not reimporting itself
+      .generate_best_possible_paths(&import_path_part, parent_source_path);
+
+    let possible_source = possible_paths.iter().find_map(|(module_name, path)| {
+      if self.parsed_sources.contains_key(path) {
+        return Some((module_name.clone(), path.clone(), None));
+      }
+      let content = self.import_resolver.resolve(path)?;
+      Some((module_name.clone(), path.clone(), Some(content)))
+    });
 
     let Some(parent_source) = self.parsed_sources.get_mut(parent_source_path) else {
       unreachable!("{:?} source code as not parsed", parent_source_path)
     };
 
-    let Some((module_name, source_path)) = possible_source_path else {
+    let Some((module_name, source_path, content)) = possible_source else {
+      let searched_paths = possible_paths
+        .iter()
+        .map(|(_, path)| path.to_string())
+        .collect::<Vec<_>>();
+
+      let help = searched_paths.iter().fold(
+        "Maybe a typo or a missing file. Searched paths:".to_string(),
+        |help, path| format!("{help}\n  {path}"),
+      );
+
       return Err(ImportPathNotFound {
-        stmt: import_stmt.clone(),
+        stmt: Box::new(import_stmt.clone()),
         path: import_path_part.to_string(),
+        searched_paths,
+        help,
         import_bit: (&import_stmt.source_location).into(),
-        src: NamedSource::new(
+        src: Arc::new(NamedSource::new(
           parent_source_path.to_string(),
           parent_source.content.clone(),
-        ),
+        )),
       });
     };
 
     // add self as a dependency to the parent
     parent_source.add_direct_dependency(source_path.clone());
 
-    limiter.push(import_stmt, parent_source).check_depth();
+    limiter.push(import_stmt, parent_source);
+
+    if let Some(chain) = limiter.find_cycle(&source_path) {
+      return Err(CircularImport { chain });
+    }
+
+    limiter.check_depth()?;
 
     // if not crawled, crawl this import file
-    if !self.parsed_sources.contains_key(&source_path) {
-      self
-        .crawl_source(source_path, Some(module_name), limiter)
-        .expect("failed to crawl import path");
+    if let Some(content) = content {
+      self.crawl_source_content(source_path, Some(module_name), content, limiter)?;
     }
 
     limiter.pop();
@@ -194,16 +274,43 @@ impl DependencyTree {
     source_path: SourceFilePath,
     module_name: Option<SourceModuleName>,
     limiter: &mut MaxRecursionLimiter,
+  ) -> Result<(), DependencyTreeError> {
+    if self.parsed_sources.contains_key(&source_path) {
+      return Ok(());
+    }
+
+    let content =
+      self
+        .import_resolver
+        .resolve(&source_path)
+        .ok_or_else(|| SourceNotFound {
+          path: source_path.clone(),
+        })?;
+
+    self.crawl_source_content(source_path, module_name, content, limiter)
+  }
+
+  /// Parses the already resolved content of a source file and crawls its dependencies.
+  fn crawl_source_content(
+    &mut self,
+    source_path: SourceFilePath,
+    module_name: Option<SourceModuleName>,
+    content: String,
+    limiter: &mut MaxRecursionLimiter,
   ) -> Result<(), DependencyTreeError> {
     match self.parsed_sources.entry(source_path.clone()) {
       Entry::Occupied(_) => {} // do nothing
       Entry::Vacant(entry) => {
-        let content = entry.key().read_contents().or(Err(SourceNotFound {
-          path: entry.key().clone(),
-        }))?;
-
         let source_file =
-          SourceFile::create(entry.key().clone(), module_name.clone(), content);
+          SourceFile::create(entry.key().clone(), module_name.clone(), content.clone());
+        let source_file = source_file.map_err(|err| ImportParseError {
+          msg: err.msg,
+          import_bit: (&err.source_location).into(),
+          src: Arc::new(NamedSource::new(
+            entry.key().to_string(),
+            normalize_line_endings(&content),
+          )),
+        })?;
         entry.insert(source_file);
       }
     };
@@ -0,0 +1,97 @@
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+use crate::FxIndexMap;
+
+/// Provides the source of entry points and imported shader files.
+///
+/// The dependency tree asks the resolver for each candidate path generated for an import
+/// statement, in resolution order, and uses the first one that returns a source. The
+/// candidate path doubles as the canonical name of the file, so two imports that resolve
+/// to the same path are only crawled once.
+pub trait ImportResolver: Send + Sync {
+  /// Returns the source of the file at `path`, or `None` if the resolver doesn't have it.
+  fn resolve(&self, path: &Path) -> Option<String>;
+}
+
+impl Debug for dyn ImportResolver {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("ImportResolver")
+  }
+}
+
+impl<F> ImportResolver for F
+where
+  F: Fn(&Path) -> Option<String> + Send + Sync,
+{
+  fn resolve(&self, path: &Path) -> Option<String> {
+    self(path)
+  }
+}
+
+/// Reads the shader sources from the file system. This is the default resolver.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSystemImportResolver;
+
+impl ImportResolver for FileSystemImportResolver {
+  fn resolve(&self, path: &Path) -> Option<String> {
+    if !path.is_file() {
+      return None;
+    }
+    std::fs::read_to_string(path).ok()
+  }
+}
+
+/// Serves shader sources from memory, e.g. sources embedded with `include_str!`.
+///
+/// Files are looked up by the same paths the file system would be, so the workspace root
+/// and entry points should be set to paths the sources are registered under.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryImportResolver {
+  sources: FxIndexMap<PathBuf, String>,
+}
+
+impl InMemoryImportResolver {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Registers the source for the given path, replacing any previous source.
+  pub fn with_source(
+    mut self,
+    path: impl Into<PathBuf>,
+    source: impl Into<String>,
+  ) -> Self {
+    self.sources.insert(path.into(), source.into());
+    self
+  }
+}
+
+impl ImportResolver for InMemoryImportResolver {
+  fn resolve(&self, path: &Path) -> Option<String> {
+    self.sources.get(path).cloned()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use super::{ImportResolver, InMemoryImportResolver};
+
+  #[test]
+  fn should_resolve_registered_sources_only() {
+    let resolver = InMemoryImportResolver::new().with_source("shaders/a.wgsl", "// a");
+
+    assert_eq!(resolver.resolve(Path::new("shaders/a.wgsl")), Some("// a".to_string()));
+    assert_eq!(resolver.resolve(Path::new("shaders/b.wgsl")), None);
+  }
+
+  #[test]
+  fn should_resolve_with_closure() {
+    let resolver = |path: &Path| path.ends_with("a.wgsl").then(|| "// a".to_string());
+
+    assert!(resolver.resolve(Path::new("shaders/a.wgsl")).is_some());
+    assert!(resolver.resolve(Path::new("shaders/b.wgsl")).is_none());
+  }
+}
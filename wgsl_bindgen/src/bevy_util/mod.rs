@@ -1,9 +1,11 @@
 pub mod deptree;
+mod import_resolver;
 mod module_path_resolver;
 mod name_demangle;
 pub mod parse_imports;
 pub mod source_file;
 
 pub use deptree::*;
+pub use import_resolver::*;
 use module_path_resolver::*;
 pub use name_demangle::*;
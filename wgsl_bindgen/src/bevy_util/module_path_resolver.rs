@@ -87,7 +87,7 @@ impl ModulePathResolver {
       .collect();
 
     if import_parts.is_empty() {
-      panic!("import module is empty")
+      return FxIndexSet::default();
     }
 
     let source_dir = source_path.parent().unwrap_or(Path::new(""));
@@ -190,8 +190,7 @@ mod tests {
   }
 
   #[test]
-  #[should_panic]
-  fn should_panic_when_import_module_is_empty() {
+  fn should_return_empty_when_import_module_is_empty() {
     let module_prefix = None;
     let source_path = SourceFilePath::new("mydir/source.wgsl");
     let import_path_part = ImportPathPart::new("");
@@ -206,7 +205,6 @@ mod tests {
 
   // Should return an empty SmallVec when import_module has only the module prefix
   #[test]
-  #[should_panic]
   fn should_return_empty_smallvec_when_import_module_has_only_module_prefix() {
     let module_prefix = Some("prefix".to_string());
     let source_path = SourceFilePath::new("mydir/source.wgsl");
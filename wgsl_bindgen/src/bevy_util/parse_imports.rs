@@ -2,6 +2,7 @@ use std::ops::Range;
 use std::sync::OnceLock;
 
 use indexmap::IndexMap;
+use naga_oil::compose::comment_strip_iter::CommentReplaceExt;
 use regex::Regex;
 
 use crate::{FxIndexSet, ImportPathPart, SourceLocation};
@@ -29,16 +30,24 @@ impl ImportStatement {
   }
 }
 
+/// An `#import` statement that naga_oil failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportParseError {
+  pub source_location: SourceLocation,
+  pub msg: String,
+}
+
 fn import_prefix_regex() -> &'static Regex {
   static MEM: OnceLock<Regex> = OnceLock::new();
   MEM.get_or_init(|| Regex::new(r"(?m)^\s*(#import)").expect("Failed to compile regex"))
 }
 
-fn parse_import_stmt(input: &str) -> IndexMap<String, Vec<String>> {
+fn parse_import_stmt(input: &str) -> Result<IndexMap<String, Vec<String>>, String> {
   let mut declared_imports = IndexMap::default();
-  naga_oil::compose::parse_imports::parse_imports(input, &mut declared_imports)
-    .expect(format!("failed to parse imports: '{}'", input).as_str());
-  declared_imports
+  naga_oil::compose::parse_imports::parse_imports(input, &mut declared_imports).map_err(
+    |(token, offset)| format!("unexpected `{token}` at offset {offset} in `{input}`"),
+  )?;
+  Ok(declared_imports)
 }
 
 fn build_newline_offsets(content: &str) -> Vec<usize> {
@@ -63,7 +72,7 @@ fn get_line_and_column(offset: usize, newline_offsets: &[usize]) -> (usize, usiz
 
 pub(crate) fn parse_import_statements_iter(
   wgsl_content: &str,
-) -> impl Iterator<Item = ImportStatement> + '_ {
+) -> impl Iterator<Item = Result<ImportStatement, ImportParseError>> + '_ {
   let mut start = 0;
   let line_offsets = build_newline_offsets(wgsl_content);
 
@@ -104,11 +113,15 @@ pub(crate) fn parse_import_statements_iter(
         offset: range.start,
       };
 
-      let item_to_module_paths = parse_import_stmt(&wgsl_content[range.clone()]);
-
-      let import_stmt = ImportStatement {
-        source_location,
-        item_to_import_paths: item_to_module_paths,
+      let import_stmt = match parse_import_stmt(&wgsl_content[range.clone()]) {
+        Ok(item_to_module_paths) => Ok(ImportStatement {
+          source_location,
+          item_to_import_paths: item_to_module_paths,
+        }),
+        Err(msg) => Err(ImportParseError {
+          source_location,
+          msg,
+        }),
       };
 
       Some(import_stmt)
@@ -118,8 +131,18 @@ pub(crate) fn parse_import_statements_iter(
   })
 }
 
-pub fn get_import_statements<B: FromIterator<ImportStatement>>(content: &str) -> B {
-  parse_import_statements_iter(content).collect::<B>()
+/// Replaces comments with whitespace, keeping the byte offsets of everything else intact,
+/// so that `#import` statements inside comments are not picked up.
+fn replace_comments(content: &str) -> String {
+  let mut lines = content.lines();
+  lines.replace_comments().collect::<Vec<_>>().join("\n")
+}
+
+pub fn get_import_statements<B: FromIterator<ImportStatement>>(
+  content: &str,
+) -> Result<B, ImportParseError> {
+  let content = replace_comments(content);
+  parse_import_statements_iter(&content).collect::<Result<B, _>>()
 }
 
 #[cfg(test)]
@@ -155,8 +178,8 @@ mod tests {
   #[test]
   fn test_parsing_from_contents() {
     let test_imports = TEST_IMPORTS.replace("\r\n", "\n").replace("\r", "\n");
-    let actual = parse_import_statements_iter(&test_imports)
-      .collect::<SmallVec<[ImportStatement; 4]>>();
+    let actual = get_import_statements::<SmallVec<[ImportStatement; 4]>>(&test_imports)
+      .expect("failed to parse imports");
 
     let expected: SmallVec<[ImportStatement; 4]> = smallvec![
       ImportStatement {
@@ -234,9 +257,37 @@ mod tests {
     let contents =
       include_str!("../../tests/shaders/bevy_pbr_wgsl/mesh_view_bindings.wgsl");
     let actual = parse_import_statements_iter(contents)
-      .flat_map(|x| x.get_import_path_parts())
+      .flat_map(|x| x.unwrap().get_import_path_parts())
       .collect::<Vec<_>>();
 
     assert_eq!(actual, vec![ImportPathPart::new("bevy_pbr::mesh_view_types")]);
   }
+
+  #[test]
+  fn test_imports_inside_comments_are_ignored() {
+    let contents = indoc::indoc! {r#"
+      // #import a
+      /* #import b */
+      /*
+      #import c
+      */
+      #import d
+    "#};
+    let actual = get_import_statements::<Vec<_>>(contents)
+      .unwrap()
+      .into_iter()
+      .flat_map(|x| x.get_import_path_parts())
+      .collect::<Vec<_>>();
+
+    assert_eq!(actual, vec![ImportPathPart::new("d")]);
+  }
+
+  #[test]
+  fn test_parsing_invalid_import_reports_location() {
+    let contents = "fn a() {}\n#import a::{b\n";
+    let actual = get_import_statements::<Vec<_>>(contents).unwrap_err();
+
+    assert_eq!(actual.source_location.offset, 10);
+    assert!(actual.msg.contains("missing close brace"));
+  }
 }
@@ -1,7 +1,10 @@
+use std::sync::OnceLock;
+
+use regex::{Captures, Regex};
 use smallvec::SmallVec;
 
 use super::parse_imports;
-use super::parse_imports::ImportStatement;
+use super::parse_imports::{ImportParseError, ImportStatement};
 use crate::types::{FxIndexSet, SourceFilePath};
 use crate::{ImportPathPart, SourceModuleName};
 
@@ -14,24 +17,49 @@ pub struct SourceFile {
   pub direct_dependencies: FxIndexSet<SourceFilePath>,
 }
 
+/// Normalizes line endings so that source locations are stable across platforms.
+pub(crate) fn normalize_line_endings(content: &str) -> String {
+  content.replace("\r\n", "\n").replace("\r", "\n")
+}
+
+fn defined_directive_regex() -> &'static Regex {
+  static MEM: OnceLock<Regex> = OnceLock::new();
+  MEM.get_or_init(|| {
+    Regex::new(r"(?m)^([ \t]*#[ \t]*(?:else[ \t]+)?)if[ \t]*(!?)[ \t]*defined[ \t]*\([ \t]*(\w+)[ \t]*\)")
+      .expect("Failed to compile regex")
+  })
+}
+
+/// Rewrites `#if defined(NAME)` to `#ifdef NAME` and `#if !defined(NAME)` to `#ifndef NAME`,
+/// which are the spellings naga_oil understands. The `#else if` forms are rewritten too.
+/// Each directive is padded with spaces so that offsets in the rest of the source are kept.
+pub(crate) fn normalize_defined_directives(content: &str) -> String {
+  defined_directive_regex()
+    .replace_all(content, |caps: &Captures| {
+      let negate = if caps[2].is_empty() { "" } else { "n" };
+      let directive = format!("{}if{negate}def {}", &caps[1], &caps[3]);
+      format!("{directive:<width$}", width = caps[0].len())
+    })
+    .into_owned()
+}
+
 impl SourceFile {
   pub fn create(
     file_path: SourceFilePath,
     module_name: Option<SourceModuleName>,
     content: String,
-  ) -> Self {
-    let normalized_content = content.replace("\r\n", "\n").replace("\r", "\n");
+  ) -> Result<Self, ImportParseError> {
     let mut source = Self {
       file_path,
       module_name,
-      content: normalized_content,
+      content: normalize_defined_directives(&normalize_line_endings(&content)),
       imports: SmallVec::default(),
       direct_dependencies: FxIndexSet::default(),
     };
 
     source.imports =
-      parse_imports::get_import_statements::<SmallVec<_>>(&source.content.as_ref());
-    source
+      parse_imports::get_import_statements::<SmallVec<_>>(&source.content.as_ref())?;
+    Ok(source)
   }
 
   pub fn add_direct_dependency(&mut self, dependency: SourceFilePath) {
@@ -63,7 +91,8 @@ mod tests {
       module_name,
       include_str!("../../tests/shaders/bevy_pbr_wgsl/mesh_view_bindings.wgsl")
         .to_owned(),
-    );
+    )
+    .unwrap();
     let actual = source.get_import_path_parts();
 
     assert_eq!(
@@ -73,4 +102,30 @@ mod tests {
       }
     );
   }
+
+  #[test]
+  fn test_normalizing_defined_directives() {
+    let content = indoc::indoc! {"
+      #if defined(SHADOWS)
+      #else if !defined( MSAA )
+      #endif
+      #if  ! defined(A) // comment
+      #if QUALITY == 2
+    "};
+
+    let actual = normalize_defined_directives(content);
+    assert_eq!(actual.len(), content.len());
+
+    let actual = actual.lines().map(str::trim_end).collect::<Vec<_>>();
+    assert_eq!(
+      actual,
+      vec![
+        "#ifdef SHADOWS",
+        "#else ifndef MSAA",
+        "#endif",
+        "#ifndef A         // comment",
+        "#if QUALITY == 2",
+      ]
+    );
+  }
 }
@@ -1,15 +1,19 @@
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+use naga_oil::compose::preprocess::Preprocessor;
 use naga_oil::compose::{
   ComposableModuleDescriptor, Composer, ComposerError, NagaModuleDescriptor,
   ShaderLanguage,
 };
 
 use crate::bevy_util::source_file::SourceFile;
-use crate::bevy_util::DependencyTree;
+use crate::bevy_util::{DependencyTree, FileSystemImportResolver};
 use crate::{
-  create_rust_bindings, SourceFilePath, SourceWithFullDependenciesResult,
-  WgslBindgenError, WgslBindgenOption, WgslEntryResult, WgslShaderIrCapabilities,
+  create_rust_bindings, FastIndexMap, ShaderDefValue, ShaderDefsFromEnv, SourceFilePath,
+  SourceWithFullDependenciesResult, WgslBindgenError, WgslBindgenOption,
+  WgslBindgenWarning, WgslEntryResult, WgslShaderIrCapabilities,
 };
 
 const PKG_VER: &str = env!("CARGO_PKG_VERSION");
@@ -19,10 +23,18 @@ pub struct WGSLBindgen {
   dependency_tree: DependencyTree,
   options: WgslBindgenOption,
   content_hash: String,
+  env_shader_defs: Vec<(String, ShaderDefValue)>,
 }
 
 impl WGSLBindgen {
-  pub(crate) fn new(options: WgslBindgenOption) -> Result<Self, WgslBindgenError> {
+  pub(crate) fn new(mut options: WgslBindgenOption) -> Result<Self, WgslBindgenError> {
+    // defines set explicitly take precedence over the ones from the environment
+    let env_shader_defs = Self::read_env_shader_defs(&options.shader_defs_from_env)?
+      .into_iter()
+      .filter(|(name, _)| !options.shader_defs.contains_key(name))
+      .collect::<Vec<_>>();
+    options.shader_defs.extend(env_shader_defs.iter().cloned());
+
     let entry_points = options
       .entry_points
       .iter()
@@ -30,11 +42,17 @@ impl WGSLBindgen {
       .map(SourceFilePath::new)
       .collect();
 
-    let dependency_tree = DependencyTree::try_build(
+    let import_resolver = options
+      .import_resolver
+      .clone()
+      .unwrap_or_else(|| Arc::new(FileSystemImportResolver));
+
+    let dependency_tree = DependencyTree::try_build_with_resolver(
       options.workspace_root.clone(),
       options.module_import_root.clone(),
       entry_points,
       options.additional_scan_dirs.clone(),
+      import_resolver,
     )?;
 
     let content_hash = Self::get_contents_hash(&options, &dependency_tree);
@@ -43,15 +61,82 @@ impl WGSLBindgen {
       for file in Self::iter_files_to_watch(&dependency_tree) {
         println!("cargo:rerun-if-changed={}", file);
       }
+      for source in &options.shader_defs_from_env {
+        if let ShaderDefsFromEnv::Var { name, .. } = source {
+          println!("cargo:rerun-if-env-changed={}", name);
+        }
+      }
     }
 
     Ok(Self {
       dependency_tree,
       options,
       content_hash,
+      env_shader_defs,
     })
   }
 
+  fn read_env_shader_defs(
+    sources: &[ShaderDefsFromEnv],
+  ) -> Result<Vec<(String, ShaderDefValue)>, WgslBindgenError> {
+    let mut defs = Vec::new();
+
+    for source in sources {
+      match source {
+        ShaderDefsFromEnv::Var { name, required } => match std::env::var_os(name) {
+          Some(value) => {
+            defs.push((name.clone(), Self::parse_env_shader_def(name, value)?))
+          }
+          None if *required => {
+            return Err(WgslBindgenError::ShaderDefEnvVarNotFound { name: name.clone() })
+          }
+          None => {}
+        },
+        ShaderDefsFromEnv::Prefix(prefix) => {
+          let mut vars = std::env::vars_os()
+            .filter_map(|(key, value)| {
+              let name = key.to_str()?.strip_prefix(prefix.as_str())?.to_string();
+              (!name.is_empty()).then_some((key, name, value))
+            })
+            .collect::<Vec<_>>();
+          vars.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+
+          for (key, name, value) in vars {
+            let value = Self::parse_env_shader_def(key.to_str().unwrap(), value)?;
+            defs.push((name, value));
+          }
+        }
+      }
+    }
+
+    Ok(defs)
+  }
+
+  fn parse_env_shader_def(
+    name: &str,
+    value: std::ffi::OsString,
+  ) -> Result<ShaderDefValue, WgslBindgenError> {
+    let invalid = |value: &str| WgslBindgenError::InvalidShaderDefEnvValue {
+      name: name.to_string(),
+      value: value.to_string(),
+    };
+
+    let value = value
+      .into_string()
+      .map_err(|value| invalid(&value.to_string_lossy()))?;
+    let value = value.trim();
+
+    match value {
+      "" | "true" => Ok(ShaderDefValue::Bool(true)),
+      "false" => Ok(ShaderDefValue::Bool(false)),
+      _ => match value.strip_suffix('u') {
+        Some(uint) => uint.parse().map(ShaderDefValue::UInt),
+        None => value.parse().map(ShaderDefValue::Int),
+      }
+      .map_err(|_| invalid(value)),
+    }
+  }
+
   fn iter_files_to_watch(dep_tree: &DependencyTree) -> impl Iterator<Item = String> {
     dep_tree
       .all_files_including_dependencies()
@@ -72,15 +157,16 @@ impl WGSLBindgen {
     hasher.finalize().to_string()
   }
 
-  fn generate_naga_module_for_entry(
+  fn generate_naga_module_for_entry<'a>(
     ir_capabilities: Option<WgslShaderIrCapabilities>,
-    entry: SourceWithFullDependenciesResult<'_>,
-  ) -> Result<WgslEntryResult, WgslBindgenError> {
+    shader_defs: &FastIndexMap<String, ShaderDefValue>,
+    entry: SourceWithFullDependenciesResult<'a>,
+  ) -> Result<WgslEntryResult<'a>, WgslBindgenError> {
     let map_err = |composer: &Composer, err: ComposerError| {
       let msg = err.emit_to_string(composer);
       WgslBindgenError::NagaModuleComposeError {
         entry: entry.source_file.file_path.to_string(),
-        inner: err.inner,
+        inner: Box::new(err.inner),
         msg,
       }
     };
@@ -108,17 +194,93 @@ impl WGSLBindgen {
       .make_naga_module(NagaModuleDescriptor {
         source: &source.content,
         file_path: &source.file_path.to_string(),
+        shader_defs: shader_defs
+          .iter()
+          .map(|(name, value)| (name.clone(), *value))
+          .collect(),
         ..Default::default()
       })
       .map_err(|err| map_err(&composer, err))?;
 
+    let encountered_defines = Self::encountered_defines(&entry);
+    let shader_defs = Self::final_shader_defs(shader_defs, source);
+    let resolved_imports = entry
+      .full_dependencies
+      .iter()
+      .map(|dependency| dependency.file_path.to_path_buf())
+      .collect();
+
     Ok(WgslEntryResult {
       mod_name: source.file_path.file_prefix(),
       naga_module: module,
       source_including_deps: entry,
+      encountered_defines,
+      resolved_imports,
+      shader_defs,
     })
   }
 
+  /// The shader defines the entry is composed with, i.e. the configured shader defines
+  /// followed by the ones set with `#define` in the entry source, which take precedence.
+  fn final_shader_defs(
+    shader_defs: &FastIndexMap<String, ShaderDefValue>,
+    source: &SourceFile,
+  ) -> Vec<(String, ShaderDefValue)> {
+    let mut source_defines = Preprocessor::default()
+      .get_preprocessor_metadata(&source.content, true)
+      .map(|metadata| metadata.defines.into_iter().collect::<Vec<_>>())
+      .unwrap_or_default();
+    source_defines.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut shader_defs = shader_defs.clone();
+    shader_defs.extend(source_defines);
+    shader_defs.into_iter().collect()
+  }
+
+  /// Collects the shader defines referenced by conditional directives in the entry and its imports.
+  fn encountered_defines(entry: &SourceWithFullDependenciesResult<'_>) -> Vec<String> {
+    let preprocessor = Preprocessor::default();
+
+    let mut defines = std::iter::once(entry.source_file)
+      .chain(entry.full_dependencies.iter().copied())
+      .filter_map(|source| {
+        preprocessor
+          .get_preprocessor_metadata(&source.content, true)
+          .ok()
+      })
+      .flat_map(|metadata| metadata.effective_defs)
+      .collect::<Vec<_>>();
+
+    defines.sort();
+    defines.dedup();
+    defines
+  }
+
+  /// Returns the files the given source transitively imports, excluding the source itself.
+  /// Imports are collected regardless of shader defines, so this is the superset of the files
+  /// that may be read when composing it. Paths are canonicalized when they exist on disk.
+  pub fn dependencies(&self, entry: &Path) -> Result<Vec<PathBuf>, WgslBindgenError> {
+    let entry = SourceFilePath::new(entry);
+    if !self
+      .dependency_tree
+      .all_files_including_dependencies()
+      .contains(&entry)
+    {
+      return Err(WgslBindgenError::UnknownSourceFile {
+        path: entry.to_path_buf(),
+      });
+    }
+
+    let dependencies = self
+      .dependency_tree
+      .get_full_dependency_for(&entry)
+      .into_iter()
+      .map(|path| std::fs::canonicalize(&*path).unwrap_or_else(|_| path.to_path_buf()))
+      .collect();
+
+    Ok(dependencies)
+  }
+
   pub fn header_texts(&self) -> String {
     use std::fmt::Write;
     let mut text = String::new();
@@ -128,29 +290,121 @@ impl WGSLBindgen {
       writeln!(text, "// ^ {PKG_NAME} version {PKG_VER}",).unwrap();
       writeln!(text, "// Changes made to this file will not be saved.").unwrap();
       writeln!(text, "// SourceHash: {}", self.content_hash).unwrap();
+      if !self.env_shader_defs.is_empty() {
+        let defs = self
+          .env_shader_defs
+          .iter()
+          .map(|(name, value)| match value {
+            ShaderDefValue::Bool(value) => format!("{name}={value}"),
+            ShaderDefValue::Int(value) => format!("{name}={value}"),
+            ShaderDefValue::UInt(value) => format!("{name}={value}u"),
+          })
+          .collect::<Vec<_>>()
+          .join(", ");
+        writeln!(text, "// Shader defines from environment: {defs}").unwrap();
+      }
       writeln!(text).unwrap();
     }
     text
   }
 
-  fn generate_output(&self) -> Result<String, WgslBindgenError> {
+  fn generate_entry_results(&self) -> Result<Vec<WgslEntryResult<'_>>, WgslBindgenError> {
     let ir_capabilities = self.options.ir_capabilities;
-    let entry_results = self
+    let entries = self
       .dependency_tree
-      .get_source_files_with_full_dependencies()
-      .into_iter()
-      .map(|it| Self::generate_naga_module_for_entry(ir_capabilities, it))
-      .collect::<Result<Vec<_>, _>>()?;
+      .get_source_files_with_full_dependencies();
+
+    if self.options.shader_def_permutations.is_empty() {
+      return entries
+        .into_iter()
+        .map(|it| {
+          Self::generate_naga_module_for_entry(
+            ir_capabilities,
+            &self.options.shader_defs,
+            it,
+          )
+        })
+        .collect();
+    }
+
+    let mut results = Vec::new();
+    for entry in entries {
+      for permutation in &self.options.shader_def_permutations {
+        let mut shader_defs = self.options.shader_defs.clone();
+        for def in permutation {
+          shader_defs.insert(def.clone(), ShaderDefValue::Bool(true));
+        }
+
+        let mut result = Self::generate_naga_module_for_entry(
+          ir_capabilities,
+          &shader_defs,
+          entry.clone(),
+        )?;
+        result.mod_name =
+          format!("{}::{}", result.mod_name, Self::permutation_mod_name(permutation));
+        results.push(result);
+      }
+    }
 
-    Ok(create_rust_bindings(entry_results, &self.options)?)
+    Ok(results)
+  }
+
+  /// The submodule name used for a permutation, e.g. `shadows_msaa` for `["SHADOWS", "MSAA"]`.
+  fn permutation_mod_name(permutation: &[String]) -> String {
+    if permutation.is_empty() {
+      return "default".into();
+    }
+
+    permutation
+      .iter()
+      .map(|def| {
+        def
+          .chars()
+          .filter(|ch| ch.is_alphanumeric() || *ch == '_')
+          .collect::<String>()
+          .to_lowercase()
+      })
+      .collect::<Vec<_>>()
+      .join("_")
   }
 
   pub fn generate_string(&self) -> Result<String, WgslBindgenError> {
-    let mut text = self.header_texts();
-    text += &self.generate_output()?;
+    let (text, _) = self.generate_string_with_entry_results()?;
     Ok(text)
   }
 
+  /// Generates the bindings like [`Self::generate_string`], and also returns the result of
+  /// composing each entry point. This can be used to inspect the imported files and the
+  /// shader defines encountered for every entry, e.g. to emit `cargo:rerun-if-changed`.
+  pub fn generate_string_with_entry_results(
+    &self,
+  ) -> Result<(String, Vec<WgslEntryResult<'_>>), WgslBindgenError> {
+    let (text, entry_results, _) = self.generate_all()?;
+    Ok((text, entry_results))
+  }
+
+  /// Generates the bindings like [`Self::generate_string`], and also returns the
+  /// non-fatal problems found along the way, such as structs that were skipped because
+  /// they contain types that can't be represented in Rust.
+  pub fn generate_string_with_warnings(
+    &self,
+  ) -> Result<(String, Vec<WgslBindgenWarning>), WgslBindgenError> {
+    let (text, _, warnings) = self.generate_all()?;
+    Ok((text, warnings))
+  }
+
+  fn generate_all(
+    &self,
+  ) -> Result<(String, Vec<WgslEntryResult<'_>>, Vec<WgslBindgenWarning>), WgslBindgenError>
+  {
+    let entry_results = self.generate_entry_results()?;
+
+    let mut text = self.header_texts();
+    let (bindings, warnings) = create_rust_bindings(&entry_results, &self.options)?;
+    text += &bindings;
+    Ok((text, entry_results, warnings))
+  }
+
   pub fn generate(&self) -> Result<(), WgslBindgenError> {
     let out = self
       .options
@@ -169,7 +423,12 @@ impl WGSLBindgen {
       || old_hashstr_comment != format!("// SourceHash: {}", &self.content_hash);
 
     if self.options.skip_hash_check || is_hash_changed() {
-      let content = self.generate_string()?;
+      let (content, warnings) = self.generate_string_with_warnings()?;
+      if self.options.emit_rerun_if_change {
+        for warning in &warnings {
+          println!("cargo:warning={}", warning);
+        }
+      }
       std::fs::File::create(out)?.write_all(content.as_bytes())?
     }
 
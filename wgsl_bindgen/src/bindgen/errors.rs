@@ -22,7 +22,7 @@ pub enum WgslBindgenError {
   NagaModuleComposeError {
     entry: String,
     msg: String,
-    inner: naga_oil::compose::ComposerErrorInner,
+    inner: Box<naga_oil::compose::ComposerErrorInner>,
   },
 
   #[error(transparent)]
@@ -31,6 +31,18 @@ pub enum WgslBindgenError {
   #[error(transparent)]
   WriteOutputError(#[from] std::io::Error),
 
+  #[error("Environment variable `{name}` required for a shader define is not set")]
+  ShaderDefEnvVarNotFound { name: String },
+
+  #[error("Environment variable `{name}` has the value `{value}`, which is not a valid shader define value")]
+  #[diagnostic(help(
+    "Use `true`, `false`, an integer like `2` or an unsigned integer like `2u`."
+  ))]
+  InvalidShaderDefEnvValue { name: String, value: String },
+
+  #[error("`{}` is not an entry point or one of its imports", path.display())]
+  UnknownSourceFile { path: std::path::PathBuf },
+
   #[error("Output file is not specified. Maybe use `generate_string` instead")]
   OutputFileNotSpecified,
 }
@@ -9,6 +9,10 @@ pub enum BindResourceType {
   Buffer,
   Sampler,
   Texture,
+  /// A `binding_array` of samplers.
+  SamplerArray,
+  /// A `binding_array` of textures.
+  TextureArray,
 }
 
 #[derive(Clone)]
@@ -96,6 +100,8 @@ impl WgpuGetBindingsGeneratorConfig {
       (BindResourceType::Buffer, quote! { wgpu::BufferBinding<'a> }),
       (BindResourceType::Sampler, quote! { &'a wgpu::Sampler }),
       (BindResourceType::Texture, quote! { &'a wgpu::TextureView }),
+      (BindResourceType::SamplerArray, quote! { &'a [&'a wgpu::Sampler] }),
+      (BindResourceType::TextureArray, quote! { &'a [&'a wgpu::TextureView] }),
     ]
     .into_iter()
     .collect::<FastIndexMap<_, _>>();
@@ -115,6 +121,12 @@ impl WgpuGetBindingsGeneratorConfig {
         BindResourceType::Texture => {
           quote!(wgpu::BindingResource::TextureView(#binding_var))
         }
+        BindResourceType::SamplerArray => {
+          quote!(wgpu::BindingResource::SamplerArray(#binding_var))
+        }
+        BindResourceType::TextureArray => {
+          quote!(wgpu::BindingResource::TextureViewArray(#binding_var))
+        }
       };
 
       let binding = Index::from(binding);
@@ -2,20 +2,67 @@ mod bindings;
 mod types;
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 pub use bindings::*;
 use derive_builder::Builder;
 use derive_more::IsVariant;
 use enumflags2::{bitflags, BitFlags};
 pub use naga::valid::Capabilities as WgslShaderIrCapabilities;
+pub use naga_oil::compose::ShaderDefValue;
 use proc_macro2::TokenStream;
 use regex::Regex;
 pub use types::*;
 
+pub use crate::bevy_util::{
+  FileSystemImportResolver, ImportResolver, InMemoryImportResolver,
+};
 use crate::{
   FastIndexMap, WGSLBindgen, WgslBindgenError, WgslType, WgslTypeSerializeStrategy,
 };
 
+/// The default of [`WgslBindgenOption::instance_step_mode_regex`].
+pub const DEFAULT_INSTANCE_STEP_MODE_REGEX: &str = "(?i)instance";
+
+/// How the labels of the generated bind group layouts, bind groups, pipeline layouts and
+/// pipelines are made.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum WgslLabelFormat {
+  /// Labels like `Shader::BindGroup0::LayoutDescriptor`, `Shader::BindGroup0`,
+  /// `Shader::PipelineLayout`, `Shader::RenderPipeline` and `Shader::main::ComputePipeline`.
+  #[default]
+  Default,
+  /// A format string with the placeholders:
+  /// - `{module}`: the PascalCase name of the entry module, e.g. `Shader`.
+  /// - `{path}`: the path of the entry source file.
+  /// - `{group}`: the bind group index, empty for pipeline layouts and pipelines.
+  /// - `{entry}`: the entry point name, empty for everything but compute pipelines.
+  /// - `{kind}`: `BindGroupLayout`, `BindGroup`, `PipelineLayout`, `RenderPipeline` or
+  ///   `ComputePipeline`.
+  Custom(String),
+  /// Emits `label: None`, e.g. for builds that strip debug strings.
+  None,
+}
+
+/// The wgpu version the generated code is written against, for the APIs that changed
+/// between major versions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WgpuVersion {
+  /// wgpu 22, where the pipeline `entry_point` fields are `&str`.
+  #[default]
+  V22,
+  /// wgpu 23 and later, where the pipeline `entry_point` fields are `Option<&str>`.
+  V23,
+}
+
+/// The `wgpu::SamplerBindingType` a sampler binding is generated with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SamplerKind {
+  Filtering,
+  NonFiltering,
+  Comparison,
+}
+
 /// An enum representing the source type that will be generated for the output.
 #[bitflags(default = UseEmbed)]
 #[repr(u8)]
@@ -31,9 +78,27 @@ pub enum WgslShaderSourceType {
 
   /// Use Composer with absolute path to shaders, useful for hot-reloading
   /// This option allows shader defines and is useful for hot-reloading.
+  /// Combined with `UseEmbed`, `create_shader_module_from_path_or_embedded` is also generated,
+  /// which falls back to the embedded source when the shader files are missing.
   UseComposerWithPath = 0b0100,
 }
 
+/// The standard library traits derived for the generated structs, see
+/// [`WgslBindgenOption::standard_derives`].
+#[bitflags(default = Debug | PartialEq | Clone | Copy)]
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StdDerive {
+  Debug = 0b0000_0001,
+  PartialEq = 0b0000_0010,
+  Clone = 0b0000_0100,
+  Copy = 0b0000_1000,
+  Eq = 0b0001_0000,
+  Hash = 0b0010_0000,
+  /// The same as [`WgslBindgenOption::derive_default`].
+  Default = 0b0100_0000,
+}
+
 /// A struct representing a directory to scan for additional source files.
 ///
 /// This struct is used to represent a directory to scan for additional source files
@@ -56,6 +121,18 @@ impl From<(Option<&str>, &str)> for AdditionalScanDirectory {
   }
 }
 
+/// A source of shader defines read from the environment when the bindgen is built.
+#[derive(Debug, Clone)]
+pub enum ShaderDefsFromEnv {
+  /// Reads the variable `name` as a define of the same name. When `required` is `true`
+  /// and the variable is not set, building the bindgen fails, else it is skipped.
+  Var { name: String, required: bool },
+
+  /// Reads every variable starting with the prefix as a define named with the prefix stripped,
+  /// e.g. `WGSL_DEF_SHADOWS` becomes `SHADOWS` for the prefix `WGSL_DEF_`.
+  Prefix(String),
+}
+
 pub type WgslTypeMap = FastIndexMap<WgslType, TokenStream>;
 
 /// A trait for building `WgslType` to `TokenStream` map.
@@ -128,6 +205,23 @@ impl From<(&str, &str, TokenStream)> for OverrideStructFieldType {
   }
 }
 
+/// Struct for overriding the type of a single struct field by its exact path, e.g.
+/// `lighting::Light.color`. Takes precedence over [`OverrideStructFieldType`].
+#[derive(Clone, Debug)]
+pub struct OverrideStructFieldTypeExact {
+  /// fully qualified struct name and field name separated by a `.`, eg: `lighting::Light.color`
+  pub field_path: String,
+  pub override_type: TokenStream,
+}
+impl From<(&str, TokenStream)> for OverrideStructFieldTypeExact {
+  fn from((field_path, override_type): (&str, TokenStream)) -> Self {
+    Self {
+      field_path: field_path.to_owned(),
+      override_type,
+    }
+  }
+}
+
 /// Struct for overriding alignment of specific structs.
 #[derive(Clone, Debug)]
 pub struct OverrideStructAlignment {
@@ -192,6 +286,19 @@ pub struct WgslBindgenOption {
   #[builder(default = "false")]
   pub skip_header_comments: bool,
 
+  /// Whether to skip the compile-time assertions that the generated bytemuck structs and
+  /// the mapped vector and matrix types have the sizes and field offsets naga computes for
+  /// WGSL. These are only generated for `WgslTypeSerializeStrategy::Bytemuck`.
+  #[builder(default = "false")]
+  pub skip_layout_assertions: bool,
+
+  /// Whether to generate a `#[cfg(test)] mod generated_layout_tests` with a test per host
+  /// sharable struct, checking the size, alignment and field offsets against the WGSL
+  /// layout and round-tripping a value through bytes. Unlike the layout assertions, this
+  /// also covers structs serialized with encase, by checking their shader size.
+  #[builder(default = "false")]
+  pub emit_layout_tests: bool,
+
   /// A boolean flag indicating whether to skip the hash check. This will avoid reruns of bindings generation if
   /// entry shaders including their imports has not changed. Defaults to `false`.
   #[builder(default = "false")]
@@ -209,6 +316,75 @@ pub struct WgslBindgenOption {
   #[builder(default = "false")]
   pub derive_serde: bool,
 
+  /// The standard library traits derived for the generated structs and their Init
+  /// structs. Defaults to `Debug`, `PartialEq`, `Clone` and `Copy`. Combinations that
+  /// can't compile are generation errors, e.g. `Eq` for structs with float fields or no
+  /// `Copy` with bytemuck. `Copy` isn't derived for encase structs with runtime-sized
+  /// arrays, and requires the types of overridden fields to be `Copy`.
+  #[builder(default)]
+  pub standard_derives: BitFlags<StdDerive>,
+
+  /// Regular expressions matched against the fully qualified names of structs, e.g.
+  /// `main::Config`. Matching structs and their Init structs derive `Serialize` and
+  /// `Deserialize` like with [`Self::derive_serde`], which applies to all structs.
+  #[builder(default, setter(each(name = "add_serde_struct_regexp", into)))]
+  pub serde_struct_regexps: Vec<Regex>,
+
+  /// The name of a feature of the crate including the generated code that gates the
+  /// serde derives, emitting them as `#[cfg_attr(feature = "serde", derive(...))]`.
+  /// The derives are unconditional when `None`.
+  #[builder(default, setter(strip_option, into))]
+  pub serde_feature: Option<String>,
+
+  /// Implement `Default` for user defined WGSL structs when `true`, with every field
+  /// zeroed. `Default` is derived when all field types support it, otherwise a manual
+  /// impl is generated, e.g. for structs with padding or arrays longer than 32 elements.
+  #[builder(default = "false")]
+  pub derive_default: bool,
+
+  /// Whether to generate `atomic<T>` struct fields as the root level `WgslAtomic<T>`
+  /// newtype instead of the plain integer, making it visible that the GPU accesses them
+  /// atomically. The newtype has the layout of the integer. Only used with
+  /// [`WgslTypeSerializeStrategy::Bytemuck`], since encase can't derive `ShaderType`
+  /// for tuple structs.
+  #[builder(default = "false")]
+  pub wrap_atomics: bool,
+
+  /// Whether to generate `bool` struct fields as the root level `WgslBool`, a `u32` with
+  /// conversions from and to `bool`, since a Rust `bool` doesn't have the 4 byte size of
+  /// the WGSL one. The Init structs take a plain `bool`. Only used with
+  /// [`WgslTypeSerializeStrategy::Bytemuck`], and not for host sharable structs, whose
+  /// `bool`s keep the 1 byte layout naga gives them.
+  #[builder(default = "false")]
+  pub wrap_bools: bool,
+
+  /// Regular expressions matched against the fully qualified names of structs and the
+  /// names of their `u32` or `bool` fields, and generating matching fields as `WgslBool`
+  /// like [`Self::wrap_bools`]. Useful for `u32` flags with 0/1 semantics.
+  #[builder(default, setter(custom))]
+  pub wgsl_bool_fields: Vec<(Regex, Regex)>,
+
+  /// Whether to generate the `SIZE` and `ALIGNED_SIZE_256` constants and the
+  /// `aligned_size` and `offset_of_index` functions for host sharable structs, for placing
+  /// instances in a single buffer at offsets aligned to e.g.
+  /// `min_uniform_buffer_offset_alignment`. Structs ending in a runtime-sized array are
+  /// skipped, as their size isn't fixed.
+  #[builder(default = "false")]
+  pub struct_size_constants: bool,
+
+  /// Whether to generate `<FIELD>_LEN` constants with the lengths of fixed-size array
+  /// fields in the impls of structs, and `<GLOBAL>_LEN` constants for fixed-size array
+  /// global variables in the entry modules.
+  #[builder(default = "false")]
+  pub array_length_constants: bool,
+
+  /// Whether to generate the `FIELD_OFFSETS` table and `<FIELD>_OFFSET` constants with
+  /// the WGSL byte offsets of the fields of structs, and the `GPU_SIZE` constant with the
+  /// WGSL size, e.g. for partial `queue.write_buffer` updates. The values come from the
+  /// WGSL layout, so they stay correct with custom type maps.
+  #[builder(default = "false")]
+  pub field_offset_constants: bool,
+
   /// The shader source type generated bitflags. Defaults to `WgslShaderSourceType::UseSingleString`.
   #[builder(default)]
   pub shader_source_type: BitFlags<WgslShaderSourceType>,
@@ -221,6 +397,35 @@ pub struct WgslBindgenOption {
   #[builder(default, setter(into, each(name = "additional_scan_dir", into)))]
   pub additional_scan_dirs: Vec<AdditionalScanDirectory>,
 
+  /// Resolves the sources of the entry points and their imports. Defaults to `None`, which
+  /// reads them from the file system using [`FileSystemImportResolver`].
+  #[builder(default, setter(custom))]
+  pub import_resolver: Option<Arc<dyn ImportResolver>>,
+
+  /// Shader defines applied when composing every entry point, including all of its imports.
+  /// These control `#ifdef`/`#if` blocks and the bindings generated from them.
+  #[builder(default, setter(custom))]
+  pub shader_defs: FastIndexMap<String, ShaderDefValue>,
+
+  /// Environment variables read when the bindgen is built to seed `shader_defs`. Values are
+  /// parsed as `true`/`false`, an integer, or an unsigned integer with a `u` suffix, and an
+  /// empty value defines the name as `true`. Defines set in `shader_defs` take precedence.
+  #[builder(default, setter(custom))]
+  pub shader_defs_from_env: Vec<ShaderDefsFromEnv>,
+
+  /// Sets of shader defines to generate separate bindings for. When not empty, every entry
+  /// point is composed once per set and each permutation is emitted as a submodule of the
+  /// entry module, e.g. `my_shader::default` for an empty set and `my_shader::shadows` for
+  /// `["SHADOWS"]`. The defines in `shader_defs` are applied to every permutation.
+  #[builder(default, setter(custom))]
+  pub shader_def_permutations: Vec<Vec<String>>,
+
+  /// The number of elements for runtime-sized `binding_array` bindings, keyed by the binding
+  /// name, e.g. `textures` or `my_module::textures` for an imported binding. Binding arrays
+  /// with a constant size always use that size. Unlisted runtime-sized arrays get no count.
+  #[builder(default, setter(custom))]
+  pub binding_array_counts: FastIndexMap<String, u32>,
+
   /// The [wgpu::naga::valid::Capabilities](https://docs.rs/wgpu/latest/wgpu/naga/valid/struct.Capabilities.html) to support. Defaults to `None`.
   #[builder(default, setter(strip_option))]
   pub ir_capabilities: Option<WgslShaderIrCapabilities>,
@@ -243,21 +448,219 @@ pub struct WgslBindgenOption {
   #[builder(default, setter(each(name = "add_override_struct_mapping", into)))]
   pub override_struct: Vec<OverrideStruct>,
 
+  /// Regular expressions matched against the fully qualified names of structs, e.g.
+  /// `utils::Helper`. Matching structs aren't generated at all. Skipped structs used by
+  /// the fields of generated structs, by bindings or as vertex inputs are errors, since
+  /// the generated code refers to them; use [`Self::override_struct`] for those instead.
+  #[builder(default, setter(each(name = "add_skip_struct_regexp", into)))]
+  pub skip_struct_regexps: Vec<Regex>,
+
   /// A vector of `OverrideStructFieldType` to override the generated types for struct fields in matching structs.
   #[builder(default, setter(into))]
   pub override_struct_field_type: Vec<OverrideStructFieldType>,
 
+  /// A vector of `OverrideStructFieldTypeExact` to override the generated types of struct
+  /// fields by their exact paths, e.g. `lighting::Light.color`. These take precedence over
+  /// [`Self::override_struct_field_type`], and paths not matching any struct field are
+  /// errors, so typos don't silently do nothing.
+  #[builder(
+    default,
+    setter(each(name = "add_override_struct_field_type_exact", into))
+  )]
+  pub override_struct_field_type_exact: Vec<OverrideStructFieldTypeExact>,
+
   /// A vector of regular expressions and alignments that override the generated alignment for matching structs.
   /// This can be used in scenarios where a specific minimum alignment is required for a uniform buffer.
   /// Refer to the [WebGPU specs](https://www.w3.org/TR/webgpu/#dom-supported-limits-minuniformbufferoffsetalignment) for more information.
   #[builder(default, setter(into))]
   pub override_struct_alignment: Vec<OverrideStructAlignment>,
 
+  /// Regular expressions matched against the fully qualified names of structs, e.g.
+  /// `main::Vertex`, and attributes to emit on matching structs and their `Init` structs.
+  /// Derives are merged with the built-in ones, and duplicated derives or attributes
+  /// from multiple matching expressions are only emitted once.
+  #[builder(default, setter(custom))]
+  pub custom_derives: Vec<(Regex, TokenStream)>,
+
+  /// Regular expressions matched against the fully qualified names of structs and the
+  /// names of their fields, and attributes to emit on matching fields.
+  #[builder(default, setter(custom))]
+  pub custom_field_attributes: Vec<(Regex, Regex, TokenStream)>,
+
+  /// Regular expressions matched against the fully qualified names of vertex input
+  /// structs and the names of their fields, and the vertex format of matching attributes,
+  /// e.g. `Unorm8x4` for a `vec4<f32>` color stored as bytes. The field is generated with
+  /// the Rust type of the format, e.g. `[u8; 4]`. The first matching override is used, and
+  /// its component count must match the attribute's.
+  #[builder(default, setter(custom))]
+  pub vertex_format_overrides: Vec<(Regex, Regex, wgpu::VertexFormat)>,
+
   /// The regular expression of the padding fields used in the shader struct types.
   /// These fields will be omitted in the *Init structs generated, and will automatically be assigned the default values.
   #[builder(default, setter(each(name = "add_custom_padding_field_regexp", into)))]
   pub custom_padding_field_regexps: Vec<Regex>,
 
+  /// Whether shader struct members named `_pad`, optionally followed by a number like
+  /// `_pad0`, are padding fields, in addition to `custom_padding_field_regexps`. Like
+  /// those, they become zero-filled byte arrays, which encase can't serialize.
+  #[builder(default = "false")]
+  pub padding_fields_by_name: bool,
+
+  /// Regular expressions matched against the fully qualified names of buffer bindings,
+  /// e.g. `main::camera`. Matching bindings are generated with `has_dynamic_offset: true`,
+  /// and the `set` functions of their bind groups take one offset per such binding.
+  #[builder(default, setter(each(name = "add_dynamic_offset_binding", into)))]
+  pub dynamic_offset_bindings: Vec<Regex>,
+
+  /// Regular expressions matched against the fully qualified names of float texture
+  /// bindings, e.g. `main::hdr_texture`. Matching textures are generated with
+  /// `filterable: false`, and the samplers used to sample them become non-filtering.
+  #[builder(
+    default,
+    setter(each(name = "add_non_filterable_texture_binding", into))
+  )]
+  pub non_filterable_texture_bindings: Vec<Regex>,
+
+  /// Regular expressions matched against the fully qualified names of buffer bindings,
+  /// e.g. `main::lights`, and the `min_binding_size` to generate for matching buffers
+  /// instead of the size of their type. `None` removes the minimum size. The first
+  /// matching override is used.
+  #[builder(default, setter(custom))]
+  pub min_binding_size_overrides: Vec<(Regex, Option<u64>)>,
+
+  /// Regular expressions matched against the fully qualified names of sampler bindings,
+  /// e.g. `main::nearest_sampler`, and the sampler binding type to generate for matching
+  /// samplers. Overrides take precedence over the type inferred from the shader. It's an
+  /// error for a sampler to be matched by overrides of different kinds.
+  #[builder(default, setter(custom))]
+  pub sampler_binding_overrides: Vec<(Regex, SamplerKind)>,
+
+  /// How the labels of the generated bind group layouts, bind groups and pipeline layouts
+  /// are made.
+  #[builder(default, setter(into))]
+  pub label_format: WgslLabelFormat,
+
+  /// The regular expression matched against the names of vertex input structs, or of the
+  /// `<entry>_vertex_input` buffer of located parameters, to infer their step mode in the
+  /// generated `<entry>_entry_default` functions. Matching buffers use
+  /// `wgpu::VertexStepMode::Instance` and the others `Vertex`. Defaults to
+  /// [`DEFAULT_INSTANCE_STEP_MODE_REGEX`].
+  #[builder(default, setter(into, strip_option))]
+  pub instance_step_mode_regex: Option<Regex>,
+
+  /// The regular expression matched against the fully qualified names of structs to
+  /// convert to and from `wgpu::util::DispatchIndirectArgs`, using their first three `u32`
+  /// members as `x`, `y` and `z`. Structs used in storage bindings with exactly the `u32`
+  /// members `x`, `y` and `z` are always converted. The generated layout assertions make
+  /// the build fail if a matched struct doesn't have the 12-byte indirect layout.
+  #[builder(default, setter(into, strip_option))]
+  pub dispatch_indirect_struct_regex: Option<Regex>,
+
+  /// Whether bind groups that generate identical code in more than one entry, labels
+  /// aside, are generated once in `shared_bind_groups::layoutN` modules and re-exported
+  /// by the entries that use them. Besides the layout entries, the group index and the
+  /// binding names must match for groups to be shared.
+  #[builder(default = "false")]
+  pub share_identical_bind_groups: bool,
+
+  /// Whether structs defined by more than one entry, and generating identical code in
+  /// all of them, are generated once in the `common_types` module and re-exported by the
+  /// entries, so that they are the same Rust type. The shared structs are generated with
+  /// their `common_types::` path, which is also the path matched by the struct options.
+  /// Same-named structs that differ between entries are kept in their entries, with a
+  /// doc comment noting the conflict.
+  #[builder(default = "false")]
+  pub share_identical_structs: bool,
+
+  /// Whether structs with the same name but different layouts in different modules are
+  /// renamed after their layout, like `SettingsA` and `SettingsB`, with a warning instead
+  /// of an error. Imported structs are generated once and never conflict. The renamed
+  /// structs note their WGSL name and the other names in their doc comments.
+  #[builder(default = "false")]
+  pub disambiguate_conflicting_structs: bool,
+
+  /// Whether to generate accessors that create the bind group layouts and the pipeline
+  /// layout once and cache them in statics. The cached layouts belong to the device they
+  /// were first requested with.
+  #[builder(default = "false")]
+  pub cache_layouts: bool,
+
+  /// Whether to generate a `bind_groups_from_named` function for each module, which creates
+  /// all of its bind groups from a map of resources keyed by fully qualified binding names,
+  /// along with the `BIND_GROUP_ENTRY_NAMES` it expects.
+  #[builder(default = "false")]
+  pub named_bind_group_resources: bool,
+
+  /// Whether to generate a `WgpuBindGroupNEntriesBuilder` next to each entries struct,
+  /// with one setter per binding. Unset bindings fall back to the `BindGroupEntryDefaults`
+  /// passed to the builder, and `build` reports the names of the ones still missing.
+  #[builder(default = "false")]
+  pub bind_group_entries_builders: bool,
+
+  /// Whether the bind groups of a module may skip indices, e.g. when a group is reserved
+  /// for bindings defined elsewhere. The pipeline layout gets an empty bind group layout in
+  /// each skipped slot, and no bind group is generated for it. Otherwise gaps are an error.
+  #[builder(default = "false")]
+  pub allow_bind_group_gaps: bool,
+
+  /// Whether to generate a `BINDINGS` table in each module, describing every binding with
+  /// its module, name, indices and layout entry, along with the `ALL_BINDINGS` of all the
+  /// modules at the root.
+  #[builder(default = "false")]
+  pub binding_reflection_table: bool,
+
+  /// Whether bindings with the same name but different types in different modules are a
+  /// warning instead of an error. Visibility differences are ignored.
+  #[builder(default = "false")]
+  pub allow_conflicting_binding_types: bool,
+
+  /// Whether to leave out the bindings that no entry point of the module uses, so they
+  /// don't need resources. The indices of the remaining bindings are unchanged, and the
+  /// omitted ones are listed in the `OMITTED_BINDINGS` of the module.
+  #[builder(default = "false")]
+  pub omit_unused_bindings: bool,
+
+  /// Whether to generate a `NameBuffer` wrapper for each uniform and storage buffer
+  /// binding, which creates and writes the buffer from the binding's Rust type with the
+  /// usages it needs. Only generated with the bytemuck serialization strategy, and not for
+  /// structs ending in a runtime-sized array.
+  #[builder(default = "false")]
+  pub buffer_wrappers: bool,
+
+  /// Whether to generate `create_buffer`, `create_buffer_init` and `write` methods for
+  /// each struct used as a uniform or storage binding, creating buffers with the usages of
+  /// every binding of the struct across all entries and `COPY_DST`. The generated code
+  /// uses `wgpu::util::DeviceExt`. Only generated with the bytemuck serialization strategy,
+  /// and not for structs ending in a runtime-sized array.
+  #[builder(default = "false")]
+  pub struct_buffer_helpers: bool,
+
+  /// Whether to generate `VertexEntryDyn` and `FragmentEntryDyn` next to the const generic
+  /// `VertexEntry<N>` and `FragmentEntry<N>`, with `Vec` buffers and targets, `From` impls
+  /// and the `vertex_state_dyn` and `fragment_state_dyn` functions. Useful for storing the
+  /// entries of different shaders in the same container.
+  #[builder(default = "false")]
+  pub dyn_entry_structs: bool,
+
+  /// The wgpu major version to generate code for. Defaults to [`WgpuVersion::V22`], the
+  /// version this crate depends on.
+  #[builder(default)]
+  pub wgpu_version: WgpuVersion,
+
+  /// Whether the generated entries and pipelines skip zero initializing workgroup memory,
+  /// which wgpu does by default. The `zero_initialize_workgroup_memory` field of the
+  /// generated `VertexEntry` and `FragmentEntry` and the
+  /// `create_<entry>_pipeline_with_zero_init` functions of compute entries allow changing it
+  /// per pipeline.
+  #[builder(default = "false")]
+  pub skip_workgroup_memory_zero_init: bool,
+
+  /// Whether every binding is made visible to all the shader stages of the module's entry
+  /// points, instead of only to the stages whose entry points use it.
+  /// Useful for keeping bind group layouts compatible across shaders.
+  #[builder(default = "false")]
+  pub union_binding_visibility: bool,
+
   /// Whether to always have the init struct generated in the out. This is only applicable when using bytemuck mode.
   #[builder(default = "false")]
   pub always_generate_init_struct: bool,
@@ -310,6 +713,172 @@ impl WgslBindgenOptionBuilder {
     self.type_map(struct_mappings);
   }
 
+  /// Adds a shader define used when composing the entry points, e.g. `("SKINNING", ShaderDefValue::Bool(true))`.
+  pub fn add_shader_def(
+    &mut self,
+    name: impl Into<String>,
+    value: ShaderDefValue,
+  ) -> &mut Self {
+    self
+      .shader_defs
+      .get_or_insert_with(Default::default)
+      .insert(name.into(), value);
+    self
+  }
+
+  /// Adds a set of shader defines to generate a separate permutation of the bindings for.
+  /// See [`WgslBindgenOption::shader_def_permutations`].
+  pub fn add_shader_def_permutation(
+    &mut self,
+    defs: impl IntoIterator<Item = impl Into<String>>,
+  ) -> &mut Self {
+    self
+      .shader_def_permutations
+      .get_or_insert_with(Default::default)
+      .push(defs.into_iter().map(Into::into).collect());
+    self
+  }
+
+  /// Sets the resolver used to read the entry points and their imports, e.g. an
+  /// [`InMemoryImportResolver`] for shaders embedded with `include_str!`.
+  pub fn import_resolver(
+    &mut self,
+    resolver: impl ImportResolver + 'static,
+  ) -> &mut Self {
+    self.import_resolver = Some(Some(Arc::new(resolver)));
+    self
+  }
+
+  /// Adds a shader define read from the environment variable `name` when the bindgen is built.
+  /// See [`WgslBindgenOption::shader_defs_from_env`].
+  pub fn add_shader_def_from_env(
+    &mut self,
+    name: impl Into<String>,
+    required: bool,
+  ) -> &mut Self {
+    self
+      .shader_defs_from_env
+      .get_or_insert_with(Default::default)
+      .push(ShaderDefsFromEnv::Var {
+        name: name.into(),
+        required,
+      });
+    self
+  }
+
+  /// Adds every environment variable starting with `prefix` as a shader define, with the
+  /// prefix stripped from its name. See [`WgslBindgenOption::shader_defs_from_env`].
+  pub fn add_shader_defs_from_env_prefix(
+    &mut self,
+    prefix: impl Into<String>,
+  ) -> &mut Self {
+    self
+      .shader_defs_from_env
+      .get_or_insert_with(Default::default)
+      .push(ShaderDefsFromEnv::Prefix(prefix.into()));
+    self
+  }
+
+  /// Sets the number of elements for a runtime-sized `binding_array` binding.
+  /// See [`WgslBindgenOption::binding_array_counts`].
+  pub fn add_binding_array_count(
+    &mut self,
+    name: impl Into<String>,
+    count: u32,
+  ) -> &mut Self {
+    self
+      .binding_array_counts
+      .get_or_insert_with(Default::default)
+      .insert(name.into(), count);
+    self
+  }
+
+  /// Overrides the binding type of the samplers matching `regex`.
+  /// See [`WgslBindgenOption::sampler_binding_overrides`].
+  pub fn add_sampler_binding_override(
+    &mut self,
+    regex: impl Into<Regex>,
+    kind: SamplerKind,
+  ) -> &mut Self {
+    self
+      .sampler_binding_overrides
+      .get_or_insert_with(Default::default)
+      .push((regex.into(), kind));
+    self
+  }
+
+  /// Adds attributes, e.g. `#[derive(serde::Serialize)]`, to the structs matching `regex`.
+  /// See [`WgslBindgenOption::custom_derives`].
+  pub fn add_custom_derive(
+    &mut self,
+    regex: impl Into<Regex>,
+    attributes: TokenStream,
+  ) -> &mut Self {
+    self
+      .custom_derives
+      .get_or_insert_with(Default::default)
+      .push((regex.into(), attributes));
+    self
+  }
+
+  /// Adds attributes to the fields matching `field_regex` of the structs matching
+  /// `struct_regex`. See [`WgslBindgenOption::custom_field_attributes`].
+  pub fn add_custom_field_attribute(
+    &mut self,
+    struct_regex: impl Into<Regex>,
+    field_regex: impl Into<Regex>,
+    attributes: TokenStream,
+  ) -> &mut Self {
+    self
+      .custom_field_attributes
+      .get_or_insert_with(Default::default)
+      .push((struct_regex.into(), field_regex.into(), attributes));
+    self
+  }
+
+  /// Generates the `u32` or `bool` fields matching `field_regex` of the structs matching
+  /// `struct_regex` as `WgslBool`. See [`WgslBindgenOption::wgsl_bool_fields`].
+  pub fn add_wgsl_bool_field(
+    &mut self,
+    struct_regex: impl Into<Regex>,
+    field_regex: impl Into<Regex>,
+  ) -> &mut Self {
+    self
+      .wgsl_bool_fields
+      .get_or_insert_with(Default::default)
+      .push((struct_regex.into(), field_regex.into()));
+    self
+  }
+
+  /// Overrides the vertex format of the fields matching `field_regex` of the vertex input
+  /// structs matching `struct_regex`. See [`WgslBindgenOption::vertex_format_overrides`].
+  pub fn add_vertex_format_override(
+    &mut self,
+    struct_regex: impl Into<Regex>,
+    field_regex: impl Into<Regex>,
+    format: wgpu::VertexFormat,
+  ) -> &mut Self {
+    self
+      .vertex_format_overrides
+      .get_or_insert_with(Default::default)
+      .push((struct_regex.into(), field_regex.into(), format));
+    self
+  }
+
+  /// Overrides the `min_binding_size` of the buffers matching `regex`.
+  /// See [`WgslBindgenOption::min_binding_size_overrides`].
+  pub fn add_min_binding_size_override(
+    &mut self,
+    regex: impl Into<Regex>,
+    size: Option<u64>,
+  ) -> &mut Self {
+    self
+      .min_binding_size_overrides
+      .get_or_insert_with(Default::default)
+      .push((regex.into(), size));
+    self
+  }
+
   pub fn extra_binding_generator(
     &mut self,
     config: impl GetBindingsGeneratorConfig,
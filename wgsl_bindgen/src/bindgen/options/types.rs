@@ -85,3 +85,46 @@ impl WgslTypeMapBuild for NalgebraWgslTypeMap {
     .collect()
   }
 }
+
+/// `mint` types like `mint::Vector4<f32>` or `mint::ColumnMatrix4<f32>`, for
+/// interoperating with other math libraries.
+/// With bytemuck, `vec3` and matrices with 3 rows aren't mapped since the `mint` types
+/// lack the padding of the WGSL types, and use the output from [RustWgslTypeMap] instead.
+#[derive(Clone)]
+pub struct MintWgslTypeMap;
+
+impl WgslTypeMapBuild for MintWgslTypeMap {
+  fn build(&self, serialize_strategy: WgslTypeSerializeStrategy) -> WgslTypeMap {
+    use crate::WgslMatType::*;
+    use crate::WgslType::*;
+    use crate::WgslVecType::*;
+
+    // mint names matrices by rows x columns, and WGSL by columns x rows.
+    let mut types = vec![
+      (Vector(Vec2i), quote!(mint::Vector2<i32>)),
+      (Vector(Vec4i), quote!(mint::Vector4<i32>)),
+      (Vector(Vec2u), quote!(mint::Vector2<u32>)),
+      (Vector(Vec4u), quote!(mint::Vector4<u32>)),
+      (Vector(Vec2f), quote!(mint::Vector2<f32>)),
+      (Vector(Vec4f), quote!(mint::Vector4<f32>)),
+      (Matrix(Mat2x2f), quote!(mint::ColumnMatrix2<f32>)),
+      (Matrix(Mat2x4f), quote!(mint::ColumnMatrix4x2<f32>)),
+      (Matrix(Mat3x2f), quote!(mint::ColumnMatrix2x3<f32>)),
+      (Matrix(Mat3x4f), quote!(mint::ColumnMatrix4x3<f32>)),
+      (Matrix(Mat4x2f), quote!(mint::ColumnMatrix2x4<f32>)),
+      (Matrix(Mat4x4f), quote!(mint::ColumnMatrix4<f32>)),
+    ];
+    if serialize_strategy.is_encase() {
+      types.extend([
+        (Vector(Vec3i), quote!(mint::Vector3<i32>)),
+        (Vector(Vec3u), quote!(mint::Vector3<u32>)),
+        (Vector(Vec3f), quote!(mint::Vector3<f32>)),
+        (Matrix(Mat2x3f), quote!(mint::ColumnMatrix3x2<f32>)),
+        (Matrix(Mat3x3f), quote!(mint::ColumnMatrix3<f32>)),
+        (Matrix(Mat4x3f), quote!(mint::ColumnMatrix3x4<f32>)),
+      ]);
+    }
+
+    types.into_iter().collect()
+  }
+}
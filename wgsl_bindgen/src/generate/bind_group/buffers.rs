@@ -0,0 +1,123 @@
+use self::quote_gen::RustItemPath;
+use super::*;
+
+/// Generates a `NameBuffer` wrapper around a `wgpu::Buffer` for each buffer binding, with
+/// the usages of the binding and typed constructors and writes. Structs ending in a
+/// runtime-sized array get no wrapper, as their size isn't known from the type.
+pub(super) fn buffer_wrappers_items(
+  invoking_entry_module: &str,
+  naga_module: &naga::Module,
+  options: &WgslBindgenOption,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  let wrappers = bind_group_data
+    .values()
+    .flat_map(|group| &group.bindings)
+    .filter_map(|binding| {
+      buffer_wrapper(invoking_entry_module, naga_module, options, binding)
+    });
+
+  quote!(#(#wrappers)*)
+}
+
+fn buffer_wrapper(
+  invoking_entry_module: &str,
+  naga_module: &naga::Module,
+  options: &WgslBindgenOption,
+  binding: &GroupBinding,
+) -> Option<TokenStream> {
+  let usage = match binding.address_space {
+    naga::AddressSpace::Uniform => quote!(wgpu::BufferUsages::UNIFORM),
+    naga::AddressSpace::Storage { .. } => quote!(wgpu::BufferUsages::STORAGE),
+    _ => return None,
+  };
+
+  let path =
+    RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module);
+  let label = path.get_fully_qualified_name().to_string();
+  let wrapper_name =
+    format_ident!("{}Buffer", sanitize_and_pascal_case(path.name.as_str()));
+
+  let data_fns = match binding.binding_type.inner {
+    naga::TypeInner::Array {
+      base,
+      size: naga::ArraySize::Dynamic,
+      stride,
+    } => {
+      let element_type = rust_type(
+        Some(invoking_entry_module),
+        naga_module,
+        &naga_module.types[base],
+        options,
+      )
+      .ok()?;
+      let stride = proc_macro2::Literal::u64_unsuffixed(stride as u64);
+
+      quote! {
+        pub fn new(device: &wgpu::Device, data: &[#element_type]) -> Self {
+            Self(wgpu::util::DeviceExt::create_buffer_init(device, &wgpu::util::BufferInitDescriptor {
+                label: Some(#label),
+                contents: bytemuck::cast_slice(data),
+                usage: Self::USAGES,
+            }))
+        }
+
+        /// Creates an uninitialized buffer with room for `len` elements.
+        pub fn new_with_capacity(device: &wgpu::Device, len: u64) -> Self {
+            Self(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(#label),
+                size: len * #stride,
+                usage: Self::USAGES,
+                mapped_at_creation: false,
+            }))
+        }
+
+        pub fn write(&self, queue: &wgpu::Queue, data: &[#element_type]) {
+            queue.write_buffer(&self.0, 0, bytemuck::cast_slice(data));
+        }
+      }
+    }
+    _ => {
+      let rust_type = rust_type(
+        Some(invoking_entry_module),
+        naga_module,
+        binding.binding_type,
+        options,
+      )
+      .ok()?;
+      rust_type.size?;
+
+      quote! {
+        pub fn new(device: &wgpu::Device, data: &#rust_type) -> Self {
+            Self(wgpu::util::DeviceExt::create_buffer_init(device, &wgpu::util::BufferInitDescriptor {
+                label: Some(#label),
+                contents: bytemuck::bytes_of(data),
+                usage: Self::USAGES,
+            }))
+        }
+
+        pub fn write(&self, queue: &wgpu::Queue, data: &#rust_type) {
+            queue.write_buffer(&self.0, 0, bytemuck::bytes_of(data));
+        }
+      }
+    }
+  };
+
+  let doc = format!(" A buffer for the `{label}` binding.");
+
+  Some(quote! {
+    #[doc = #doc]
+    #[derive(Debug)]
+    pub struct #wrapper_name(pub wgpu::Buffer);
+
+    impl #wrapper_name {
+        pub const USAGES: wgpu::BufferUsages = #usage.union(wgpu::BufferUsages::COPY_DST);
+
+        #data_fns
+
+        pub fn as_entire_binding(&self) -> wgpu::BufferBinding<'_> {
+            self.0.as_entire_buffer_binding()
+        }
+    }
+  })
+}
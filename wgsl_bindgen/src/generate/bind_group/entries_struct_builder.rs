@@ -3,6 +3,50 @@ use derive_more::Constructor;
 use self::quote_gen::RustItemPath;
 use super::*;
 
+/// Returns the kind of resource bound to a binding.
+pub(super) fn binding_resource_type(
+  invoking_entry_module: &str,
+  binding: &GroupBinding,
+) -> Result<BindResourceType, CreateModuleError> {
+  // TODO: Support more types.
+  match (&binding.binding_type.inner, binding.binding_array_base) {
+    (
+      naga::TypeInner::Scalar(_)
+      | naga::TypeInner::Struct { .. }
+      | naga::TypeInner::Array { .. },
+      _,
+    ) => Ok(BindResourceType::Buffer),
+    (naga::TypeInner::Image { .. }, _) => Ok(BindResourceType::Texture),
+    (naga::TypeInner::Sampler { .. }, _) => Ok(BindResourceType::Sampler),
+    (naga::TypeInner::BindingArray { .. }, Some(base)) => match base.inner {
+      naga::TypeInner::Image { .. } => Ok(BindResourceType::TextureArray),
+      naga::TypeInner::Sampler { .. } => Ok(BindResourceType::SamplerArray),
+      _ => Err(unsupported_binding_type(invoking_entry_module, binding, base)),
+    },
+    _ => {
+      Err(unsupported_binding_type(invoking_entry_module, binding, binding.binding_type))
+    }
+  }
+}
+
+/// The root level types shared by the generated bind group entries builders.
+pub(crate) fn bind_group_entry_defaults() -> TokenStream {
+  quote! {
+    /// Fallback resources for the bindings left unset in the bind group entries builders.
+    /// Binding arrays have no fallback.
+    #[derive(Debug, Clone, Default)]
+    pub struct BindGroupEntryDefaults<'a> {
+      pub buffer: Option<wgpu::BufferBinding<'a>>,
+      pub sampler: Option<&'a wgpu::Sampler>,
+      pub texture_view: Option<&'a wgpu::TextureView>,
+    }
+
+    /// The names of the bindings that were neither set nor covered by a default.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct MissingBindGroupEntries(pub Vec<&'static str>);
+  }
+}
+
 #[derive(Constructor)]
 pub(super) struct BindGroupEntriesStructBuilder<'a> {
   invoking_entry_module: &'a str,
@@ -17,7 +61,7 @@ impl<'a> BindGroupEntriesStructBuilder<'a> {
     &self,
     binding_var_name: &Ident,
     binding: &GroupBinding,
-  ) -> TokenStream {
+  ) -> Result<TokenStream, CreateModuleError> {
     let entry_cons = self.generator.entry_constructor;
     let binding_index = binding.binding_index as usize;
     let demangled_name = RustItemPath::from_mangled(
@@ -27,25 +71,15 @@ impl<'a> BindGroupEntriesStructBuilder<'a> {
     let binding_name = Ident::new(&demangled_name.name, Span::call_site());
     let binding_var = quote!(#binding_var_name.#binding_name);
 
-    match binding.binding_type.inner {
-      naga::TypeInner::Scalar(_)
-      | naga::TypeInner::Struct { .. }
-      | naga::TypeInner::Array { .. } => {
-        entry_cons(binding_index, binding_var, BindResourceType::Buffer)
-      }
-      naga::TypeInner::Image { .. } => {
-        entry_cons(binding_index, binding_var, BindResourceType::Texture)
-      }
-      naga::TypeInner::Sampler { .. } => {
-        entry_cons(binding_index, binding_var, BindResourceType::Sampler)
-      }
-      // TODO: Better error handling.
-      _ => panic!("Failed to generate BindingType."),
-    }
+    let resource_type = binding_resource_type(self.invoking_entry_module, binding)?;
+    Ok(entry_cons(binding_index, binding_var, resource_type))
   }
 
   /// Assigns entries for the bind group from the provided parameters.
-  fn assign_entries_from_parameters(&self, param_var_name: Ident) -> Vec<TokenStream> {
+  fn assign_entries_from_parameters(
+    &self,
+    param_var_name: Ident,
+  ) -> Result<Vec<TokenStream>, CreateModuleError> {
     self
       .data
       .bindings
@@ -56,32 +90,27 @@ impl<'a> BindGroupEntriesStructBuilder<'a> {
           self.invoking_entry_module,
         );
         let binding_name = Ident::new(&demangled_name.name, Span::call_site());
-        let create_entry = self.create_entry_from_parameter(&param_var_name, binding);
+        let create_entry = self.create_entry_from_parameter(&param_var_name, binding)?;
 
-        quote! {
+        Ok(quote! {
           #binding_name: #create_entry
-        }
+        })
       })
       .collect()
   }
 
   /// Generates a tuple of parameter field and entry field for a binding.
-  fn binding_field_tuple(&self, binding: &GroupBinding) -> (TokenStream, TokenStream) {
+  fn binding_field_tuple(
+    &self,
+    binding: &GroupBinding,
+  ) -> Result<(TokenStream, TokenStream), CreateModuleError> {
     let rust_item_path = RustItemPath::from_mangled(
       binding.name.as_ref().unwrap(),
       self.invoking_entry_module,
     );
     let field_name = format_ident!("{}", &rust_item_path.name.as_str());
 
-    // TODO: Support more types.
-    let resource_type = match binding.binding_type.inner {
-      naga::TypeInner::Struct { .. } => BindResourceType::Buffer,
-      naga::TypeInner::Image { .. } => BindResourceType::Texture,
-      naga::TypeInner::Sampler { .. } => BindResourceType::Sampler,
-      naga::TypeInner::Array { .. } => BindResourceType::Buffer,
-      naga::TypeInner::Scalar(_) => BindResourceType::Buffer,
-      _ => panic!("Unsupported type for binding fields."),
-    };
+    let resource_type = binding_resource_type(self.invoking_entry_module, binding)?;
 
     let param_field_type = self.generator.binding_type_map[&resource_type].clone();
     let field_type = self.generator.entry_struct_type.clone();
@@ -89,7 +118,7 @@ impl<'a> BindGroupEntriesStructBuilder<'a> {
     let param_field = quote!(pub #field_name: #param_field_type);
     let entry_field = quote!(pub #field_name: #field_type);
 
-    (param_field, entry_field)
+    Ok((param_field, entry_field))
   }
 
   fn all_entries(&self, binding_var_name: Ident) -> Vec<TokenStream> {
@@ -108,13 +137,104 @@ impl<'a> BindGroupEntriesStructBuilder<'a> {
       .collect()
   }
 
-  pub(super) fn build(&self) -> TokenStream {
+  /// Generates the `EntriesBuilder` of the group, which has one setter per binding and
+  /// falls back to the [`bind_group_entry_defaults`] for the bindings left unset.
+  pub(super) fn build_entries_builder(&self) -> Result<TokenStream, CreateModuleError> {
+    let entry_collection_name = self
+      .generator
+      .bind_group_entries_struct_name_ident(self.group_no);
+    let entry_collection_param_name = format_ident!("{}Params", entry_collection_name);
+    let builder_name = format_ident!("{}Builder", entry_collection_name);
+
+    let mut fields = Vec::new();
+    let mut field_inits = Vec::new();
+    let mut setters = Vec::new();
+    let mut resolves = Vec::new();
+    let mut names = Vec::new();
+
+    for binding in &self.data.bindings {
+      let rust_item_path = RustItemPath::from_mangled(
+        binding.name.as_ref().unwrap(),
+        self.invoking_entry_module,
+      );
+      let name = rust_item_path.name.as_str();
+      let field_name = format_ident!("{}", name);
+
+      let resource_type = binding_resource_type(self.invoking_entry_module, binding)?;
+      let field_type = self.generator.binding_type_map[&resource_type].clone();
+
+      let fallback = match resource_type {
+        BindResourceType::Buffer => quote!(.or_else(|| self.defaults.buffer.clone())),
+        BindResourceType::Sampler => quote!(.or(self.defaults.sampler)),
+        BindResourceType::Texture => quote!(.or(self.defaults.texture_view)),
+        BindResourceType::SamplerArray | BindResourceType::TextureArray => quote!(),
+      };
+
+      fields.push(quote!(#field_name: Option<#field_type>));
+      field_inits.push(quote!(#field_name: None));
+      setters.push(quote! {
+        pub fn #field_name(mut self, #field_name: #field_type) -> Self {
+          self.#field_name = Some(#field_name);
+          self
+        }
+      });
+      resolves.push(quote! {
+        let #field_name = self.#field_name #fallback;
+        if #field_name.is_none() {
+          missing.push(#name);
+        }
+      });
+      names.push(field_name);
+    }
+
+    let doc = format!(
+      " Builds [`{entry_collection_name}`] one binding at a time, using the defaults for the bindings that aren't set."
+    );
+
+    Ok(quote! {
+      #[doc = #doc]
+      #[derive(Debug)]
+      pub struct #builder_name<'a> {
+        defaults: BindGroupEntryDefaults<'a>,
+        #(#fields),*
+      }
+
+      impl<'a> #builder_name<'a> {
+        pub fn new(defaults: BindGroupEntryDefaults<'a>) -> Self {
+          Self {
+            defaults,
+            #(#field_inits),*
+          }
+        }
+
+        #(#setters)*
+
+        pub fn build(self) -> Result<#entry_collection_name<'a>, MissingBindGroupEntries> {
+          let mut missing = Vec::new();
+
+          #(#resolves)*
+
+          let (#(Some(#names),)*) = (#(#names,)*) else {
+            return Err(MissingBindGroupEntries(missing));
+          };
+
+          Ok(#entry_collection_name::new(#entry_collection_param_name {
+            #(#names),*
+          }))
+        }
+      }
+    })
+  }
+
+  pub(super) fn build(&self) -> Result<TokenStream, CreateModuleError> {
     let (entries_param_fields, entries_fields): (Vec<_>, Vec<_>) = self
       .data
       .bindings
       .iter()
       .map(|binding| self.binding_field_tuple(binding))
-      .collect();
+      .collect::<Result<Vec<_>, _>>()?
+      .into_iter()
+      .unzip();
 
     let entry_collection_name = self
       .generator
@@ -134,11 +254,11 @@ impl<'a> BindGroupEntriesStructBuilder<'a> {
     };
 
     let entries_from_params =
-      self.assign_entries_from_parameters(format_ident!("params"));
+      self.assign_entries_from_parameters(format_ident!("params"))?;
     let entries_length = Index::from(entries_from_params.len() as usize);
     let all_entries = self.all_entries(format_ident!("self"));
 
-    quote! {
+    Ok(quote! {
         #[derive(Debug)]
         pub struct #entry_collection_param_name #lifetime {
             #(#entries_param_fields),*
@@ -164,6 +284,6 @@ impl<'a> BindGroupEntriesStructBuilder<'a> {
             self.as_array().into_iter().collect()
           }
         }
-    }
+    })
   }
 }
@@ -3,13 +3,20 @@ use std::collections::BTreeMap;
 use derive_more::Constructor;
 use generate::quote_shader_stages;
 use quote::{format_ident, quote};
-use quote_gen::{demangle_and_fully_qualify_str, rust_type};
+use quote_gen::{demangle_and_fully_qualify_str, rust_type, RustItemPath};
 
+use crate::bevy_util::demangle_str;
 use crate::wgsl::buffer_binding_type;
 use crate::*;
 
+mod buffers;
 mod entries_struct_builder;
+mod named_resources;
+mod reflection;
+pub(crate) use entries_struct_builder::bind_group_entry_defaults;
 use entries_struct_builder::*;
+pub(crate) use named_resources::named_resources_error;
+pub(crate) use reflection::{bindings_table, bindings_table_root};
 
 pub struct GroupData<'a> {
   pub bindings: Vec<GroupBinding<'a>>,
@@ -17,15 +24,21 @@ pub struct GroupData<'a> {
 
 pub struct GroupBinding<'a> {
   pub name: Option<String>,
+  pub global_handle: naga::Handle<naga::GlobalVariable>,
+  pub group_index: u32,
   pub binding_index: u32,
   pub binding_type: &'a naga::Type,
+  /// The element type when the binding is a `binding_array`.
+  pub binding_array_base: Option<&'a naga::Type>,
   pub address_space: naga::AddressSpace,
+  /// The stages of the entry points that use the binding, if the usage could be analyzed.
+  pub used_shader_stages: Option<wgpu::ShaderStages>,
 }
 
 #[derive(Constructor)]
 struct BindGroupBuilder<'a> {
   invoking_entry_name: &'a str,
-  sanitized_entry_name: &'a str,
+  labels: EntryLabels<'a>,
   group_no: u32,
   data: &'a GroupData<'a>,
   shader_stages: wgpu::ShaderStages,
@@ -34,8 +47,8 @@ struct BindGroupBuilder<'a> {
 }
 
 impl<'a> BindGroupBuilder<'a> {
-  fn bind_group_layout_descriptor(&self) -> TokenStream {
-    let entries: Vec<_> = self
+  fn bind_group_layout_descriptor(&self) -> Result<TokenStream, CreateModuleError> {
+    let entries = self
       .data
       .bindings
       .iter()
@@ -48,21 +61,18 @@ impl<'a> BindGroupBuilder<'a> {
           binding,
         )
       })
-      .collect();
+      .collect::<Result<Vec<_>, _>>()?;
 
-    let bind_group_label = format!(
-      "{}::BindGroup{}::LayoutDescriptor",
-      self.sanitized_entry_name, self.group_no
-    );
+    let bind_group_label = self.labels.bind_group_layout(self.options, self.group_no);
 
-    quote! {
+    Ok(quote! {
         wgpu::BindGroupLayoutDescriptor {
-            label: Some(#bind_group_label),
+            label: #bind_group_label,
             entries: &[
                 #(#entries),*
             ],
         }
-    }
+    })
   }
 
   fn struct_name(&self) -> syn::Ident {
@@ -73,7 +83,7 @@ impl<'a> BindGroupBuilder<'a> {
       .bind_group_name_ident(self.group_no)
   }
 
-  fn bind_group_struct_impl(&self) -> TokenStream {
+  fn bind_group_struct_impl(&self) -> Result<TokenStream, CreateModuleError> {
     // TODO: Support compute shader with vertex/fragment in the same module?
     let is_compute = self.shader_stages == wgpu::ShaderStages::COMPUTE;
 
@@ -90,13 +100,60 @@ impl<'a> BindGroupBuilder<'a> {
       .bind_group_layout
       .bind_group_entries_struct_name_ident(self.group_no);
 
-    let bind_group_layout_descriptor = self.bind_group_layout_descriptor();
+    let bind_group_layout_descriptor = self.bind_group_layout_descriptor()?;
 
     let group_no = Index::from(self.group_no as usize);
-    let bind_group_label =
-      format!("{}::BindGroup{}", self.sanitized_entry_name, self.group_no);
+    let bind_group_label = self.labels.bind_group(self.options, self.group_no);
+
+    let dynamic_bindings =
+      dynamic_offset_bindings(self.invoking_entry_name, self.options, self.data);
+    let set_fn = if dynamic_bindings.is_empty() {
+      quote! {
+        pub fn set<'a>(&'a self, render_pass: &mut #render_pass) {
+            render_pass.set_bind_group(#group_no, &self.0, &[]);
+        }
+      }
+    } else {
+      let offsets_len = Index::from(dynamic_bindings.len());
+      let slot_docs = dynamic_bindings.iter().enumerate().map(|(slot, binding)| {
+        let name = demangle_and_fully_qualify_str(binding.name.as_ref().unwrap(), None);
+        let doc =
+          format!(" - `offsets[{slot}]`: @binding({}) \"{name}\"", binding.binding_index);
+        quote!(#[doc = #doc])
+      });
+
+      quote! {
+        /// Sets the bind group with one dynamic offset per dynamic binding, in binding order:
+        #(#slot_docs)*
+        pub fn set<'a>(&'a self, render_pass: &mut #render_pass, offsets: [wgpu::DynamicOffset; #offsets_len]) {
+            render_pass.set_bind_group(#group_no, &self.0, &offsets);
+        }
+      }
+    };
 
-    quote! {
+    let cached_layout_fns = if self.options.cache_layouts {
+      quote! {
+        /// Returns the layout created on the first call, with the device of that call.
+        pub fn get_cached_bind_group_layout(device: &wgpu::Device) -> &'static wgpu::BindGroupLayout {
+            static LAYOUT: std::sync::OnceLock<wgpu::BindGroupLayout> = std::sync::OnceLock::new();
+            LAYOUT.get_or_init(|| Self::get_bind_group_layout(device))
+        }
+
+        pub fn from_bindings_with_cached_layout(device: &wgpu::Device, bindings: #bind_group_entries_struct_name) -> Self {
+            let entries = bindings.as_array();
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: #bind_group_label,
+                layout: Self::get_cached_bind_group_layout(device),
+                entries: &entries,
+            });
+            Self(bind_group)
+        }
+      }
+    } else {
+      quote!()
+    };
+
+    Ok(quote! {
         impl #bind_group_name {
             pub const LAYOUT_DESCRIPTOR: wgpu::BindGroupLayoutDescriptor<'static> = #bind_group_layout_descriptor;
 
@@ -108,21 +165,25 @@ impl<'a> BindGroupBuilder<'a> {
                 let bind_group_layout = Self::get_bind_group_layout(&device);
                 let entries = bindings.as_array();
                 let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                    label: Some(#bind_group_label),
+                    label: #bind_group_label,
                     layout: &bind_group_layout,
                     entries: &entries,
                 });
                 Self(bind_group)
             }
 
-            pub fn set<'a>(&'a self, render_pass: &mut #render_pass) {
-                render_pass.set_bind_group(#group_no, &self.0, &[]);
+            #cached_layout_fns
+
+            #set_fn
+
+            pub fn set_with_offsets<'a>(&'a self, render_pass: &mut #render_pass, offsets: &[wgpu::DynamicOffset]) {
+                render_pass.set_bind_group(#group_no, &self.0, offsets);
             }
         }
-    }
+    })
   }
 
-  fn build(self) -> TokenStream {
+  fn build(self) -> Result<TokenStream, CreateModuleError> {
     let bind_group_name = self.struct_name();
 
     let group_struct = quote! {
@@ -130,69 +191,333 @@ impl<'a> BindGroupBuilder<'a> {
         pub struct #bind_group_name(wgpu::BindGroup);
     };
 
-    let group_impl = self.bind_group_struct_impl();
+    let group_impl = self.bind_group_struct_impl()?;
 
-    quote! {
+    Ok(quote! {
         #group_struct
         #group_impl
-    }
+    })
   }
 }
 
-// TODO: Take an iterator instead?
-pub fn bind_groups_module(
+/// Generates the entries structs and the bind group struct of a single group.
+fn bind_group_items(
   invoking_entry_module: &str,
+  labels: EntryLabels,
   options: &WgslBindgenOption,
   naga_module: &naga::Module,
-  bind_group_data: &BTreeMap<u32, GroupData>,
+  group_no: u32,
+  group: &GroupData,
   shader_stages: wgpu::ShaderStages,
-) -> TokenStream {
-  let sanitized_entry_name = sanitize_and_pascal_case(invoking_entry_module);
-  let bind_groups: Vec<_> = bind_group_data
-    .iter()
-    .map(|(group_no, group)| {
-      let wgpu_generator = &options.wgpu_binding_generator;
+) -> Result<TokenStream, CreateModuleError> {
+  let wgpu_generator = &options.wgpu_binding_generator;
+
+  let bind_group_entries_struct = BindGroupEntriesStructBuilder::new(
+    invoking_entry_module,
+    group_no,
+    group,
+    &wgpu_generator.bind_group_layout,
+  )
+  .build()?;
+
+  let bind_group_entries_builder = if options.bind_group_entries_builders {
+    BindGroupEntriesStructBuilder::new(
+      invoking_entry_module,
+      group_no,
+      group,
+      &wgpu_generator.bind_group_layout,
+    )
+    .build_entries_builder()?
+  } else {
+    quote!()
+  };
 
-      let bind_group_entries_struct = BindGroupEntriesStructBuilder::new(
+  let additional_layout =
+    if let Some(additional_generator) = &options.extra_binding_generator {
+      BindGroupEntriesStructBuilder::new(
         invoking_entry_module,
-        *group_no,
+        group_no,
         group,
-        &wgpu_generator.bind_group_layout,
+        &additional_generator.bind_group_layout,
       )
-      .build();
-
-      let additional_layout =
-        if let Some(additional_generator) = &options.extra_binding_generator {
-          BindGroupEntriesStructBuilder::new(
-            invoking_entry_module,
-            *group_no,
-            group,
-            &additional_generator.bind_group_layout,
-          )
-          .build()
-        } else {
-          quote!()
-        };
+      .build()?
+    } else {
+      quote!()
+    };
 
-      let bindgroup = BindGroupBuilder::new(
-        &invoking_entry_module,
-        &sanitized_entry_name,
+  let bindgroup = BindGroupBuilder::new(
+    invoking_entry_module,
+    labels,
+    group_no,
+    group,
+    shader_stages,
+    options,
+    naga_module,
+  )
+  .build()?;
+
+  Ok(quote! {
+    #additional_layout
+    #bind_group_entries_struct
+    #bind_group_entries_builder
+    #bindgroup
+  })
+}
+
+/// The bind groups of an entry, as input to the checks and the sharing across entries.
+pub struct SharedBindGroupsInput<'a> {
+  pub invoking_entry_module: &'a str,
+  pub source_path: &'a str,
+  pub naga_module: &'a naga::Module,
+  pub bind_group_data: &'a BTreeMap<u32, GroupData<'a>>,
+  pub shader_stages: wgpu::ShaderStages,
+}
+
+/// The bind groups whose generated code is identical in more than one entry.
+#[derive(Default)]
+pub struct SharedBindGroups {
+  /// The modules holding the shared bind groups, by module path.
+  pub modules: Vec<(String, TokenStream)>,
+  /// For each entry, the path of the shared module used by each group index.
+  pub entry_groups: Vec<BTreeMap<u32, String>>,
+}
+
+pub const SHARED_BIND_GROUPS_MOD: &str = "shared_bind_groups";
+
+/// Finds the bind groups that generate identical code, labels aside, in more than one of
+/// the entries. The shared modules are numbered in order of first appearance, so the
+/// output is stable as long as the entries are.
+pub fn find_shared_bind_groups(
+  inputs: &[SharedBindGroupsInput],
+  options: &WgslBindgenOption,
+) -> Result<SharedBindGroups, CreateModuleError> {
+  let mut occurrences = FastIndexMap::<String, Vec<(usize, u32)>>::default();
+  for (entry_index, input) in inputs.iter().enumerate() {
+    for (group_no, group) in input.bind_group_data {
+      let labels = EntryLabels {
+        module: "Shared",
+        path: "",
+      };
+      let items = bind_group_items(
+        input.invoking_entry_module,
+        labels,
+        options,
+        input.naga_module,
         *group_no,
         group,
-        shader_stages,
+        input.shader_stages,
+      )?;
+      occurrences
+        .entry(items.to_string())
+        .or_default()
+        .push((entry_index, *group_no));
+    }
+  }
+
+  let mut shared = SharedBindGroups {
+    modules: Vec::new(),
+    entry_groups: vec![BTreeMap::new(); inputs.len()],
+  };
+
+  for groups in occurrences.values() {
+    let (first_entry, group_no) = groups[0];
+    if groups
+      .iter()
+      .all(|(entry_index, _)| *entry_index == first_entry)
+    {
+      continue;
+    }
+
+    let layout_name = format!("layout{}", shared.modules.len());
+    let input = &inputs[first_entry];
+    let module = format!("SharedBindGroups::{}", sanitize_and_pascal_case(&layout_name));
+    let labels = EntryLabels {
+      module: &module,
+      path: input.source_path,
+    };
+    let items = bind_group_items(
+      input.invoking_entry_module,
+      labels,
+      options,
+      input.naga_module,
+      group_no,
+      &input.bind_group_data[&group_no],
+      input.shader_stages,
+    )?;
+
+    let path = format!("{SHARED_BIND_GROUPS_MOD}::{layout_name}");
+    for (entry_index, group_no) in groups {
+      shared.entry_groups[*entry_index].insert(*group_no, path.clone());
+    }
+    shared.modules.push((path, items));
+  }
+
+  Ok(shared)
+}
+
+/// Checks that bindings with the same name have the same type in all the entries, as
+/// they'd be mistaken for one another when looked up by name. The visibility of the
+/// bindings isn't compared. Conflicts are errors, or warnings with
+/// [`WgslBindgenOption::allow_conflicting_binding_types`].
+pub fn check_binding_type_conflicts(
+  inputs: &[SharedBindGroupsInput],
+  options: &WgslBindgenOption,
+  warnings: &mut Vec<WgslBindgenWarning>,
+) -> Result<(), CreateModuleError> {
+  // The signature of the first binding seen with each name, and the entries using it.
+  let mut seen = FastIndexMap::<String, (String, Vec<String>)>::default();
+
+  for input in inputs {
+    for group in input.bind_group_data.values() {
+      for binding in &group.bindings {
+        let name = RustItemPath::from_mangled(
+          binding.name.as_ref().unwrap(),
+          input.invoking_entry_module,
+        )
+        .name
+        .to_string();
+        let signature = binding_type_signature(
+          input.invoking_entry_module,
+          input.naga_module,
+          options,
+          binding,
+        )?;
+
+        match seen.get_mut(&name) {
+          None => {
+            seen.insert(name, (signature, vec![input.invoking_entry_module.to_string()]));
+          }
+          Some((first, modules)) if *first == signature => {
+            modules.push(input.invoking_entry_module.to_string());
+          }
+          Some((_, modules)) => {
+            let mut modules = modules.clone();
+            modules.push(input.invoking_entry_module.to_string());
+            if !options.allow_conflicting_binding_types {
+              return Err(CreateModuleError::ConflictingBindingTypes { name, modules });
+            }
+            warnings.push(WgslBindgenWarning::ConflictingBindingTypes { name, modules });
+          }
+        }
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// The buffer usages of the structs used as uniform or storage bindings in any of the
+/// entries, by the fully qualified name of the struct. Structs bound in both address
+/// spaces get both usages.
+pub fn struct_buffer_usages(
+  inputs: &[SharedBindGroupsInput],
+) -> FastIndexMap<String, wgpu::BufferUsages> {
+  let mut usages = FastIndexMap::<String, wgpu::BufferUsages>::default();
+
+  for input in inputs {
+    for group in input.bind_group_data.values() {
+      for binding in &group.bindings {
+        let naga::TypeInner::Struct { .. } = binding.binding_type.inner else {
+          continue;
+        };
+        let usage = match binding.address_space {
+          naga::AddressSpace::Uniform => wgpu::BufferUsages::UNIFORM,
+          naga::AddressSpace::Storage { .. } => wgpu::BufferUsages::STORAGE,
+          _ => continue,
+        };
+        let Some(name) = binding.binding_type.name.as_ref() else {
+          continue;
+        };
+        let path = RustItemPath::from_mangled(name, input.invoking_entry_module);
+        *usages
+          .entry(path.get_fully_qualified_name().to_string())
+          .or_insert(wgpu::BufferUsages::empty()) |= usage;
+      }
+    }
+  }
+
+  usages
+}
+
+/// A description of the layout entry of a binding, without its visibility. Buffers are
+/// described by their size rather than their Rust type, since each entry has its own.
+fn binding_type_signature(
+  invoking_entry_module: &str,
+  naga_module: &naga::Module,
+  options: &WgslBindgenOption,
+  binding: &GroupBinding,
+) -> Result<String, CreateModuleError> {
+  match binding.binding_type.inner {
+    naga::TypeInner::Scalar(_)
+    | naga::TypeInner::Struct { .. }
+    | naga::TypeInner::Array { .. } => {
+      let min_binding_size =
+        min_binding_size_override(invoking_entry_module, options, binding)
+          .unwrap_or_else(|| {
+            Some(binding.binding_type.inner.size(naga_module.to_ctx()) as u64)
+          });
+      Ok(format!(
+        "{} has_dynamic_offset: {} min_binding_size: {min_binding_size:?}",
+        buffer_binding_type(binding.address_space),
+        has_dynamic_offset(invoking_entry_module, options, binding),
+      ))
+    }
+    _ => {
+      let (binding_type, count) =
+        binding_type_and_count(invoking_entry_module, naga_module, options, binding)?;
+      Ok(format!("{binding_type} count: {count}"))
+    }
+  }
+}
+
+/// Generates the bind groups of an entry, except for the groups found in `shared_groups`,
+/// which are re-exported from their shared module instead.
+// TODO: Take an iterator instead?
+pub fn bind_groups_module_with_shared(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  naga_module: &naga::Module,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+  shader_stages: wgpu::ShaderStages,
+  source_path: &str,
+  shared_groups: &BTreeMap<u32, String>,
+) -> Result<TokenStream, CreateModuleError> {
+  let sanitized_entry_name = sanitize_and_pascal_case(invoking_entry_module);
+  let labels = EntryLabels {
+    module: &sanitized_entry_name,
+    path: source_path,
+  };
+  let bind_groups = bind_group_data
+    .iter()
+    .map(|(group_no, group)| match shared_groups.get(group_no) {
+      Some(path) => {
+        let path = syn::parse_str::<syn::Path>(path).unwrap();
+        Ok(quote!(pub use _root::#path::*;))
+      }
+      None => bind_group_items(
+        invoking_entry_module,
+        labels,
         options,
         naga_module,
-      )
-      .build();
+        *group_no,
+        group,
+        shader_stages,
+      ),
+    })
+    .collect::<Result<Vec<_>, CreateModuleError>>()?;
 
-      quote! {
-        #additional_layout
-        #bind_group_entries_struct
-        #bindgroup
-      }
+  // The number of dynamic offsets taken by each bind group, if any.
+  let offsets_lens: BTreeMap<u32, usize> = bind_group_data
+    .iter()
+    .map(|(group_no, group)| {
+      let len = dynamic_offset_bindings(invoking_entry_module, options, group).len();
+      (*group_no, len)
     })
+    .filter(|(_, len)| *len > 0)
     .collect();
 
+  let offsets_ident = |group_no: u32| format_ident!("bind_group{}_offsets", group_no);
+
   let bind_group_fields: Vec<_> = bind_group_data
     .keys()
     .map(|group_no| {
@@ -201,7 +526,12 @@ pub fn bind_groups_module(
         .bind_group_layout
         .bind_group_name_ident(*group_no);
       let field = indexed_name_ident("bind_group", *group_no);
-      quote!(pub #field: &'a #group_name)
+      let offsets_field = offsets_lens.get(group_no).map(|len| {
+        let offsets = offsets_ident(*group_no);
+        let len = Index::from(*len);
+        quote!(, pub #offsets: [wgpu::DynamicOffset; #len])
+      });
+      quote!(pub #field: &'a #group_name #offsets_field)
     })
     .collect();
 
@@ -221,33 +551,145 @@ pub fn bind_groups_module(
         .wgpu_binding_generator
         .bind_group_layout
         .bind_group_name_ident(*group_no);
-      quote!(#group: &'a #group_name)
+      let offsets_param = offsets_lens.get(group_no).map(|len| {
+        let offsets = offsets_ident(*group_no);
+        let len = Index::from(*len);
+        quote!(, #offsets: [wgpu::DynamicOffset; #len])
+      });
+      quote!(#group: &'a #group_name #offsets_param)
     })
     .collect();
 
   // The set function for each bind group already sets the index.
-  let set_groups: Vec<_> = bind_group_data
+  let set_groups = |receiver: TokenStream| -> Vec<TokenStream> {
+    bind_group_data
+      .keys()
+      .map(|group_no| {
+        let group = indexed_name_ident("bind_group", *group_no);
+        if offsets_lens.contains_key(group_no) {
+          let offsets = offsets_ident(*group_no);
+          quote!(#receiver #group.set(pass, #receiver #offsets);)
+        } else {
+          quote!(#receiver #group.set(pass);)
+        }
+      })
+      .collect()
+  };
+  let set_groups_from_fields = set_groups(quote!(self.));
+  let set_groups_from_params = set_groups(quote!());
+
+  let num_groups = Index::from(bind_group_data.len());
+  let set_groups_with_offsets: Vec<_> = bind_group_data
+    .keys()
+    .enumerate()
+    .map(|(index, group_no)| {
+      let group = indexed_name_ident("bind_group", *group_no);
+      let index = Index::from(index);
+      quote!(self.#group.set_with_offsets(pass, offsets[#index]);)
+    })
+    .collect();
+
+  let group_parameters_with_offsets: Vec<_> = bind_group_data
+    .keys()
+    .map(|group_no| {
+      let group = indexed_name_ident("bind_group", *group_no);
+      let offsets = offsets_ident(*group_no);
+      let group_name = options
+        .wgpu_binding_generator
+        .bind_group_layout
+        .bind_group_name_ident(*group_no);
+      quote!(#group: &'a #group_name, #offsets: &[wgpu::DynamicOffset])
+    })
+    .collect();
+  let set_groups_from_params_with_offsets: Vec<_> = bind_group_data
     .keys()
     .map(|group_no| {
       let group = indexed_name_ident("bind_group", *group_no);
-      quote!(#group.set(pass);)
+      let offsets = offsets_ident(*group_no);
+      quote!(#group.set_with_offsets(pass, #offsets);)
     })
     .collect();
 
+  let (entries_parameters, created_groups): (Vec<_>, Vec<_>) = bind_group_data
+    .keys()
+    .map(|group_no| {
+      let group = indexed_name_ident("bind_group", *group_no);
+      let generator = &options.wgpu_binding_generator.bind_group_layout;
+      let group_name = generator.bind_group_name_ident(*group_no);
+      let entries_name = generator.bind_group_entries_struct_name_ident(*group_no);
+      (
+        quote!(#group: #entries_name),
+        quote!(#group_name::from_bindings(device, #group)),
+      )
+    })
+    .unzip();
+  let group_types: Vec<_> = bind_group_data
+    .keys()
+    .map(|group_no| {
+      let group_name = options
+        .wgpu_binding_generator
+        .bind_group_layout
+        .bind_group_name_ident(*group_no);
+      quote!(#group_name)
+    })
+    .collect();
+
+  let binding_index_items = binding_index_items(invoking_entry_module, bind_group_data)?;
+
+  let bind_groups_from_named = if options.named_bind_group_resources {
+    named_resources::bind_groups_from_named(
+      invoking_entry_module,
+      options,
+      bind_group_data,
+    )?
+  } else {
+    quote!()
+  };
+
+  let bind_group_index_items = bind_group_index_items(options, bind_group_data);
+
+  let buffer_wrappers = if options.buffer_wrappers
+    && options.serialization_strategy == WgslTypeSerializeStrategy::Bytemuck
+  {
+    buffers::buffer_wrappers_items(
+      invoking_entry_module,
+      naga_module,
+      options,
+      bind_group_data,
+    )
+  } else {
+    quote!()
+  };
+
   let set_bind_groups = quote! {
       pub fn set_bind_groups<'a>(
           pass: &mut #render_pass,
           #(#group_parameters),*
       ) {
-          #(#set_groups)*
+          #(#set_groups_from_params)*
+      }
+
+      pub fn set_bind_groups_with_offsets<'a>(
+          pass: &mut #render_pass,
+          #(#group_parameters_with_offsets),*
+      ) {
+          #(#set_groups_from_params_with_offsets)*
+      }
+
+      /// Creates the layouts and all the bind groups from their entries, in group order.
+      pub fn create_bind_groups(
+          device: &wgpu::Device,
+          #(#entries_parameters),*
+      ) -> (#(#group_types,)*) {
+          (#(#created_groups,)*)
       }
   };
 
   if bind_groups.is_empty() {
     // Don't include empty modules.
-    quote!()
+    Ok(quote!())
   } else {
-    quote! {
+    Ok(quote! {
       #(#bind_groups)*
 
       #[derive(Debug, Copy, Clone)]
@@ -257,13 +699,130 @@ pub fn bind_groups_module(
 
       impl<'a> WgpuBindGroups<'a> {
           pub fn set(&self, pass: &mut #render_pass) {
-              #(self.#set_groups)*
+              #(#set_groups_from_fields)*
+          }
+
+          /// Sets all the bind groups with the given dynamic offsets, in group order.
+          pub fn set_with_offsets(
+              &self,
+              pass: &mut #render_pass,
+              offsets: [&[wgpu::DynamicOffset]; #num_groups],
+          ) {
+              #(#set_groups_with_offsets)*
           }
       }
 
+      #bind_group_index_items
+
       #set_bind_groups
+
+      #binding_index_items
+
+      #bind_groups_from_named
+
+      #buffer_wrappers
+    })
+  }
+}
+
+/// Generates `BIND_GROUP_COUNT` and the `BindGroupIndex` enum of the generated groups.
+fn bind_group_index_items(
+  options: &WgslBindgenOption,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  // Gaps allowed by `allow_bind_group_gaps` still take a slot in the pipeline layout.
+  let count = bind_group_data
+    .last_key_value()
+    .map_or(0, |(group_no, _)| group_no + 1);
+  let count = proc_macro2::Literal::u32_unsuffixed(count);
+  let len = Index::from(bind_group_data.len());
+
+  let variants: Vec<_> = bind_group_data
+    .keys()
+    .map(|group_no| format_ident!("Group{}", group_no))
+    .collect();
+  let discriminants = bind_group_data
+    .keys()
+    .map(|group_no| proc_macro2::Literal::u32_unsuffixed(*group_no));
+  let descriptors = bind_group_data.keys().map(|group_no| {
+    options
+      .wgpu_binding_generator
+      .bind_group_layout
+      .bind_group_name_ident(*group_no)
+  });
+
+  quote! {
+    /// The number of bind group slots in the pipeline layout.
+    pub const BIND_GROUP_COUNT: u32 = #count;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(u32)]
+    pub enum BindGroupIndex {
+        #(#variants = #discriminants),*
+    }
+
+    impl BindGroupIndex {
+        pub const ALL: [Self; #len] = [#(Self::#variants),*];
+
+        pub fn layout_descriptor(self) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+            match self {
+                #(Self::#variants => &#descriptors::LAYOUT_DESCRIPTOR),*
+            }
+        }
+    }
+  }
+}
+
+/// Generates the `NAME_GROUP` and `NAME_BINDING` constants of each binding and the
+/// `binding_index_of` lookup, keyed by the demangled binding names.
+fn binding_index_items(
+  invoking_entry_module: &str,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> Result<TokenStream, CreateModuleError> {
+  // The binding that generated each constant name, to report collisions.
+  let mut const_names = BTreeMap::<String, String>::new();
+  let mut consts = Vec::new();
+  let mut lookups = Vec::new();
+
+  for (group_no, group) in bind_group_data {
+    for binding in &group.bindings {
+      let path =
+        RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module);
+      let name = path.name.as_str();
+      let group_index = proc_macro2::Literal::u32_unsuffixed(*group_no);
+      let binding_index = proc_macro2::Literal::u32_unsuffixed(binding.binding_index);
+      let upper_name = sanitized_upper_snake_case(name);
+
+      if let Some(first) = const_names.insert(upper_name.clone(), name.to_string()) {
+        return Err(CreateModuleError::DuplicateBindingConstName {
+          module: invoking_entry_module.to_string(),
+          name: format!("{upper_name}_GROUP"),
+          first,
+          second: name.to_string(),
+        });
+      }
+
+      let group_const = format_ident!("{upper_name}_GROUP");
+      let binding_const = format_ident!("{upper_name}_BINDING");
+      consts.push(quote! {
+        pub const #group_const: u32 = #group_index;
+        pub const #binding_const: u32 = #binding_index;
+      });
+      lookups.push(quote!(#name => Some((#group_index, #binding_index)),));
     }
   }
+
+  Ok(quote! {
+    #(#consts)*
+
+    /// Returns the group and binding index of the binding with the given name.
+    pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+        match name {
+            #(#lookups)*
+            _ => None,
+        }
+    }
+  })
 }
 
 fn bind_group_layout_entry(
@@ -272,41 +831,149 @@ fn bind_group_layout_entry(
   options: &WgslBindgenOption,
   shader_stages: wgpu::ShaderStages,
   binding: &GroupBinding,
-) -> TokenStream {
-  // TODO: Assume storage is only used for compute?
-  // TODO: Support just vertex or fragment?
-  // TODO: Visible from all stages?
-  let stages = quote_shader_stages(shader_stages);
+) -> Result<TokenStream, CreateModuleError> {
+  // Bindings the analysis found no use for stay visible to all stages.
+  let stages = match binding.used_shader_stages {
+    Some(used) if !options.union_binding_visibility && !used.is_empty() => used,
+    _ => shader_stages,
+  };
+  let stages = quote_shader_stages(stages);
 
   let binding_index = Index::from(binding.binding_index as usize);
+  let binding_name = binding.name.as_ref().unwrap();
+
+  let (binding_type, count) =
+    binding_type_and_count(invoking_entry_module, naga_module, options, binding)?;
+
+  let size_overridden =
+    min_binding_size_override(invoking_entry_module, options, binding).is_some();
+  let doc = format!(
+    " @binding({}): \"{}\"{}",
+    binding.binding_index,
+    demangle_and_fully_qualify_str(binding_name, None),
+    if size_overridden {
+      " (min_binding_size overridden)"
+    } else {
+      ""
+    },
+  );
+
+  Ok(quote! {
+      #[doc = #doc]
+      wgpu::BindGroupLayoutEntry {
+          binding: #binding_index,
+          visibility: #stages,
+          ty: #binding_type,
+          count: #count,
+      }
+  })
+}
+
+/// Generates the `wgpu::BindingType` and the `count` of a binding's layout entry.
+fn binding_type_and_count(
+  invoking_entry_module: &str,
+  naga_module: &naga::Module,
+  options: &WgslBindgenOption,
+  binding: &GroupBinding,
+) -> Result<(TokenStream, TokenStream), CreateModuleError> {
+  let binding_name = binding.name.as_ref().unwrap();
+
+  Ok(match (&binding.binding_type.inner, binding.binding_array_base) {
+    (naga::TypeInner::BindingArray { size, .. }, Some(base)) => {
+      let binding_type =
+        binding_ty(invoking_entry_module, naga_module, options, binding, base)?;
+      let count = match size {
+        naga::ArraySize::Constant(count) => Some(count.get()),
+        naga::ArraySize::Dynamic => options
+          .binding_array_counts
+          .get(demangle_str(binding_name).as_ref())
+          .copied(),
+      };
+      let count = match count {
+        Some(count) => quote!(std::num::NonZeroU32::new(#count)),
+        None => quote!(None),
+      };
+      (binding_type, count)
+    }
+    _ => {
+      let binding_type = binding_ty(
+        invoking_entry_module,
+        naga_module,
+        options,
+        binding,
+        binding.binding_type,
+      )?;
+      (binding_type, quote!(None))
+    }
+  })
+}
+
+/// Generates the `wgpu::BindingType` of a binding, or of its elements for a `binding_array`.
+fn binding_ty(
+  invoking_entry_module: &str,
+  naga_module: &naga::Module,
+  options: &WgslBindgenOption,
+  binding: &GroupBinding,
+  ty: &naga::Type,
+) -> Result<TokenStream, CreateModuleError> {
+  let unsupported = || unsupported_binding_type(invoking_entry_module, binding, ty);
+
   // TODO: Support more types.
-  let binding_type = match binding.binding_type.inner {
+  let binding_type = match ty.inner {
     naga::TypeInner::Scalar(_)
     | naga::TypeInner::Struct { .. }
     | naga::TypeInner::Array { .. } => {
       let buffer_binding_type = buffer_binding_type(binding.address_space);
 
-      let rust_type = rust_type(
-        Some(invoking_entry_module),
-        naga_module,
-        &binding.binding_type,
-        options,
-      );
+      let rust_type = rust_type(Some(invoking_entry_module), naga_module, ty, options)
+        .map_err(|_| unsupported())?;
 
-      let min_binding_size = rust_type.quote_min_binding_size();
+      let min_binding_size =
+        match min_binding_size_override(invoking_entry_module, options, binding) {
+          Some(Some(size)) => {
+            let size = proc_macro2::Literal::u64_unsuffixed(size);
+            quote!(std::num::NonZeroU64::new(#size))
+          }
+          Some(None) => quote!(None),
+          // A runtime-sized array at the end of a struct has at least one element.
+          None
+            if matches!(ty.inner, naga::TypeInner::Struct { .. })
+              && rust_type.is_dynamic_array() =>
+          {
+            let size =
+              proc_macro2::Literal::u32_unsuffixed(ty.inner.size(naga_module.to_ctx()));
+            quote!(std::num::NonZeroU64::new(#size))
+          }
+          None => rust_type.quote_min_binding_size(),
+        };
+
+      let has_dynamic_offset =
+        has_dynamic_offset(invoking_entry_module, options, binding);
 
       quote!(wgpu::BindingType::Buffer {
           ty: #buffer_binding_type,
-          has_dynamic_offset: false,
+          has_dynamic_offset: #has_dynamic_offset,
           min_binding_size: #min_binding_size,
       })
     }
-    naga::TypeInner::Image { dim, class, .. } => {
-      let view_dim = match dim {
-        naga::ImageDimension::D1 => quote!(wgpu::TextureViewDimension::D1),
-        naga::ImageDimension::D2 => quote!(wgpu::TextureViewDimension::D2),
-        naga::ImageDimension::D3 => quote!(wgpu::TextureViewDimension::D3),
-        naga::ImageDimension::Cube => quote!(wgpu::TextureViewDimension::Cube),
+    naga::TypeInner::Image {
+      dim,
+      arrayed,
+      class,
+    } => {
+      let view_dim = match (dim, arrayed) {
+        (naga::ImageDimension::D1, _) => quote!(wgpu::TextureViewDimension::D1),
+        (naga::ImageDimension::D2, false) => quote!(wgpu::TextureViewDimension::D2),
+        (naga::ImageDimension::D2, true) => {
+          quote!(wgpu::TextureViewDimension::D2Array)
+        }
+        (naga::ImageDimension::D3, _) => quote!(wgpu::TextureViewDimension::D3),
+        (naga::ImageDimension::Cube, false) => {
+          quote!(wgpu::TextureViewDimension::Cube)
+        }
+        (naga::ImageDimension::Cube, true) => {
+          quote!(wgpu::TextureViewDimension::CubeArray)
+        }
       };
 
       match class {
@@ -315,12 +982,19 @@ fn bind_group_layout_entry(
             naga::ScalarKind::Sint => quote!(wgpu::TextureSampleType::Sint),
             naga::ScalarKind::Uint => quote!(wgpu::TextureSampleType::Uint),
             naga::ScalarKind::Float => {
-              quote!(wgpu::TextureSampleType::Float { filterable: true })
+              // Multisampled textures can't be sampled with a filtering sampler.
+              let filterable = !multi
+                && !is_non_filterable_texture(
+                  invoking_entry_module,
+                  naga_module,
+                  options,
+                  binding.global_handle,
+                );
+              quote!(wgpu::TextureSampleType::Float { filterable: #filterable })
             }
-            _ => panic!("Unsupported sample type: {kind:#?}"),
+            _ => return Err(unsupported()),
           };
 
-          // TODO: Don't assume all textures are filterable.
           quote!(wgpu::BindingType::Texture {
               sample_type: #sample_type,
               view_dimension: #view_dim,
@@ -338,7 +1012,7 @@ fn bind_group_layout_entry(
           // TODO: Will the debug implementation always work with the macro?
           // Assume texture format variants are the same as storage formats.
           let format = syn::Ident::new(&format!("{format:?}"), Span::call_site());
-          let storage_access = storage_access(access);
+          let storage_access = storage_access(access).ok_or_else(unsupported)?;
 
           quote!(wgpu::BindingType::StorageTexture {
               access: #storage_access,
@@ -349,65 +1023,306 @@ fn bind_group_layout_entry(
       }
     }
     naga::TypeInner::Sampler { comparison } => {
-      let sampler_type = if comparison {
-        quote!(wgpu::SamplerBindingType::Comparison)
+      let samples_non_filterable = sampled_images(naga_module, binding.global_handle)
+        .into_iter()
+        .any(|image| {
+          is_non_filterable_texture(invoking_entry_module, naga_module, options, image)
+        });
+
+      let inferred = if comparison {
+        SamplerKind::Comparison
+      } else if samples_non_filterable {
+        SamplerKind::NonFiltering
       } else {
-        quote!(wgpu::SamplerBindingType::Filtering)
+        SamplerKind::Filtering
+      };
+
+      let sampler_type = match sampler_override(invoking_entry_module, options, binding)?
+        .unwrap_or(inferred)
+      {
+        SamplerKind::Filtering => quote!(wgpu::SamplerBindingType::Filtering),
+        SamplerKind::NonFiltering => quote!(wgpu::SamplerBindingType::NonFiltering),
+        SamplerKind::Comparison => quote!(wgpu::SamplerBindingType::Comparison),
       };
       quote!(wgpu::BindingType::Sampler(#sampler_type))
     }
-    // TODO: Better error handling.
-    _ => panic!("Failed to generate BindingType."),
+    _ => return Err(unsupported()),
   };
 
-  let doc = format!(
-    " @binding({}): \"{}\"",
-    binding.binding_index,
-    demangle_and_fully_qualify_str(binding.name.as_ref().unwrap(), None),
+  Ok(binding_type)
+}
+
+/// The error for a binding whose type, or element type for a `binding_array`, is `ty`.
+fn unsupported_binding_type(
+  invoking_entry_module: &str,
+  binding: &GroupBinding,
+  ty: &naga::Type,
+) -> CreateModuleError {
+  let binding_name = binding
+    .name
+    .as_deref()
+    .map(|name| {
+      RustItemPath::from_mangled(name, invoking_entry_module)
+        .get_fully_qualified_name()
+        .to_string()
+    })
+    .unwrap_or_default();
+
+  CreateModuleError::UnsupportedBindingType {
+    module: invoking_entry_module.to_string(),
+    binding_name,
+    group: binding.group_index,
+    binding: binding.binding_index,
+    type_desc: ty.name.clone().unwrap_or_else(|| format!("{:?}", ty.inner)),
+  }
+}
+
+/// Whether the binding is a buffer matched by [`WgslBindgenOption::dynamic_offset_bindings`].
+fn has_dynamic_offset(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  binding: &GroupBinding,
+) -> bool {
+  let is_buffer = matches!(
+    binding.binding_type.inner,
+    naga::TypeInner::Scalar(_)
+      | naga::TypeInner::Struct { .. }
+      | naga::TypeInner::Array { .. }
   );
+  if !is_buffer {
+    return false;
+  }
 
-  quote! {
-      #[doc = #doc]
-      wgpu::BindGroupLayoutEntry {
-          binding: #binding_index,
-          visibility: #stages,
-          ty: #binding_type,
-          count: None,
+  let path =
+    RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module);
+  let fully_qualified_name = path.get_fully_qualified_name();
+  options
+    .dynamic_offset_bindings
+    .iter()
+    .any(|regex| regex.is_match(&fully_qualified_name))
+}
+
+/// The kind given to the sampler by [`WgslBindgenOption::sampler_binding_overrides`], if
+/// any of them match it.
+fn sampler_override(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  binding: &GroupBinding,
+) -> Result<Option<SamplerKind>, CreateModuleError> {
+  let path =
+    RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module);
+  let fully_qualified_name = path.get_fully_qualified_name();
+
+  let kinds = options
+    .sampler_binding_overrides
+    .iter()
+    .filter(|(regex, _)| regex.is_match(&fully_qualified_name))
+    .map(|(_, kind)| *kind)
+    .collect::<Vec<_>>();
+
+  match kinds.split_first() {
+    None => Ok(None),
+    Some((first, rest)) if rest.iter().all(|kind| kind == first) => Ok(Some(*first)),
+    Some(_) => Err(CreateModuleError::ConflictingSamplerOverrides {
+      module: invoking_entry_module.to_string(),
+      binding_name: fully_qualified_name.to_string(),
+      kinds: format!("{kinds:?}"),
+    }),
+  }
+}
+
+/// The size given to the buffer by the first of [`WgslBindgenOption::min_binding_size_overrides`]
+/// that matches it, if any.
+fn min_binding_size_override(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  binding: &GroupBinding,
+) -> Option<Option<u64>> {
+  let is_buffer = matches!(
+    binding.binding_type.inner,
+    naga::TypeInner::Scalar(_)
+      | naga::TypeInner::Struct { .. }
+      | naga::TypeInner::Array { .. }
+  );
+  if !is_buffer {
+    return None;
+  }
+
+  let path =
+    RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module);
+  let fully_qualified_name = path.get_fully_qualified_name();
+  options
+    .min_binding_size_overrides
+    .iter()
+    .find(|(regex, _)| regex.is_match(&fully_qualified_name))
+    .map(|(_, size)| *size)
+}
+
+/// Whether the texture is matched by [`WgslBindgenOption::non_filterable_texture_bindings`].
+fn is_non_filterable_texture(
+  invoking_entry_module: &str,
+  naga_module: &naga::Module,
+  options: &WgslBindgenOption,
+  image: naga::Handle<naga::GlobalVariable>,
+) -> bool {
+  let Some(name) = naga_module.global_variables[image].name.as_ref() else {
+    return false;
+  };
+
+  let fully_qualified_name =
+    RustItemPath::from_mangled(name, invoking_entry_module).get_fully_qualified_name();
+  options
+    .non_filterable_texture_bindings
+    .iter()
+    .any(|regex| regex.is_match(&fully_qualified_name))
+}
+
+/// Returns the global textures that are sampled with the given global sampler anywhere in
+/// the module. Textures or samplers passed through function arguments aren't tracked.
+fn sampled_images(
+  naga_module: &naga::Module,
+  sampler: naga::Handle<naga::GlobalVariable>,
+) -> Vec<naga::Handle<naga::GlobalVariable>> {
+  // Peels binding array accesses off the expression to find the global it reads.
+  fn global_of(
+    function: &naga::Function,
+    mut expr: naga::Handle<naga::Expression>,
+  ) -> Option<naga::Handle<naga::GlobalVariable>> {
+    loop {
+      match function.expressions[expr] {
+        naga::Expression::GlobalVariable(global) => return Some(global),
+        naga::Expression::Access { base, .. }
+        | naga::Expression::AccessIndex { base, .. } => expr = base,
+        _ => return None,
+      }
+    }
+  }
+
+  let functions = naga_module
+    .functions
+    .iter()
+    .map(|(_, function)| function)
+    .chain(naga_module.entry_points.iter().map(|entry| &entry.function));
+
+  let mut images = Vec::new();
+  for function in functions {
+    for (_, expr) in function.expressions.iter() {
+      if let naga::Expression::ImageSample {
+        image, sampler: s, ..
+      } = *expr
+      {
+        if global_of(function, s) == Some(sampler) {
+          images.extend(global_of(function, image));
+        }
       }
+    }
   }
+  images
 }
 
-fn storage_access(access: naga::StorageAccess) -> TokenStream {
+/// Returns the bindings of the group that take a dynamic offset, in binding order, which is
+/// the order wgpu expects the offsets in.
+fn dynamic_offset_bindings<'a>(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  group: &'a GroupData<'a>,
+) -> Vec<&'a GroupBinding<'a>> {
+  let mut bindings = group
+    .bindings
+    .iter()
+    .filter(|binding| has_dynamic_offset(invoking_entry_module, options, binding))
+    .collect::<Vec<_>>();
+  bindings.sort_by_key(|binding| binding.binding_index);
+  bindings
+}
+
+fn storage_access(access: naga::StorageAccess) -> Option<TokenStream> {
   let is_read = access.contains(naga::StorageAccess::LOAD);
   let is_write = access.contains(naga::StorageAccess::STORE);
   match (is_read, is_write) {
-    (true, true) => quote!(wgpu::StorageTextureAccess::ReadWrite),
-    (true, false) => quote!(wgpu::StorageTextureAccess::ReadOnly),
-    (false, true) => quote!(wgpu::StorageTextureAccess::WriteOnly),
-    _ => todo!(), // shouldn't be possible
+    (true, true) => Some(quote!(wgpu::StorageTextureAccess::ReadWrite)),
+    (true, false) => Some(quote!(wgpu::StorageTextureAccess::ReadOnly)),
+    (false, true) => Some(quote!(wgpu::StorageTextureAccess::WriteOnly)),
+    _ => None, // shouldn't be possible
   }
 }
 
-pub fn get_bind_group_data(
-  module: &naga::Module,
-) -> Result<BTreeMap<u32, GroupData>, CreateModuleError> {
+/// The names of the bindings of the module that no entry point uses.
+pub fn unused_binding_names(module: &naga::Module) -> Vec<String> {
+  let Some(global_shader_stages) = crate::wgsl::global_shader_stages(module) else {
+    return Vec::new();
+  };
+
+  module
+    .global_variables
+    .iter()
+    .filter(|(handle, global)| {
+      global.binding.is_some() && global_shader_stages[handle.index()].is_empty()
+    })
+    .filter_map(|(_, global)| global.name.as_deref())
+    .map(|name| demangle_and_fully_qualify_str(name, None))
+    .collect()
+}
+
+/// Generates the `OMITTED_BINDINGS` of a module, whose doc comment lists the bindings left
+/// out by [`WgslBindgenOption::omit_unused_bindings`].
+pub fn omitted_bindings_items(module: &naga::Module) -> TokenStream {
+  let names = unused_binding_names(module);
+  let docs = names.iter().map(|name| {
+    let doc = format!(" - `{name}`");
+    quote!(#[doc = #doc])
+  });
+
+  quote! {
+    /// The bindings declared in the shader that no entry point uses, which have no layout
+    /// entries since `omit_unused_bindings` is set:
+    #(#docs)*
+    pub const OMITTED_BINDINGS: &[&str] = &[#(#names),*];
+  }
+}
+
+/// Collects the bindings of the module by group, leaving out the unused ones with
+/// [`WgslBindgenOption::omit_unused_bindings`]. Unless
+/// [`WgslBindgenOption::allow_bind_group_gaps`] is set, the groups must be consecutive
+/// starting from 0. Gaps are left out of the map.
+pub fn get_bind_group_data<'a>(
+  module: &'a naga::Module,
+  options: &WgslBindgenOption,
+) -> Result<BTreeMap<u32, GroupData<'a>>, CreateModuleError> {
   // Use a BTree to sort type and field names by group index.
   // This isn't strictly necessary but makes the generated code cleaner.
   let mut groups = BTreeMap::new();
+  let global_shader_stages = crate::wgsl::global_shader_stages(module);
 
   for global_handle in module.global_variables.iter() {
     let global = &module.global_variables[global_handle.0];
     if let Some(binding) = &global.binding {
+      let used_shader_stages = global_shader_stages
+        .as_ref()
+        .map(|stages| stages[global_handle.0.index()]);
+      if options.omit_unused_bindings && used_shader_stages.is_some_and(|s| s.is_empty())
+      {
+        continue;
+      }
+
       let group = groups.entry(binding.group).or_insert(GroupData {
         bindings: Vec::new(),
       });
       let binding_type = &module.types[module.global_variables[global_handle.0].ty];
+      let binding_array_base = match binding_type.inner {
+        naga::TypeInner::BindingArray { base, .. } => Some(&module.types[base]),
+        _ => None,
+      };
 
       let group_binding = GroupBinding {
         name: global.name.clone(),
+        global_handle: global_handle.0,
+        group_index: binding.group,
         binding_index: binding.binding,
         binding_type,
+        binding_array_base,
         address_space: global.space,
+        used_shader_stages,
       };
       // Repeated bindings will probably cause a compile error.
       // We'll still check for it here just in case.
@@ -424,8 +1339,15 @@ pub fn get_bind_group_data(
     }
   }
 
+  // Keep the generated entries in binding order, regardless of the declaration order.
+  for group in groups.values_mut() {
+    group.bindings.sort_by_key(|binding| binding.binding_index);
+  }
+
   // wgpu expects bind groups to be consecutive starting from 0.
-  if groups.keys().map(|i| *i as usize).eq(0..groups.len()) {
+  if options.allow_bind_group_gaps
+    || groups.keys().map(|i| *i as usize).eq(0..groups.len())
+  {
     Ok(groups)
   } else {
     Err(CreateModuleError::NonConsecutiveBindGroups)
@@ -439,6 +1361,24 @@ mod tests {
   use super::*;
   use crate::assert_tokens_eq;
 
+  fn bind_groups_module(
+    invoking_entry_module: &str,
+    options: &WgslBindgenOption,
+    naga_module: &naga::Module,
+    bind_group_data: &BTreeMap<u32, GroupData>,
+    shader_stages: wgpu::ShaderStages,
+  ) -> Result<TokenStream, CreateModuleError> {
+    bind_groups_module_with_shared(
+      invoking_entry_module,
+      options,
+      naga_module,
+      bind_group_data,
+      shader_stages,
+      "",
+      &BTreeMap::new(),
+    )
+  }
+
   #[test]
   fn bind_group_data_consecutive_bind_groups() {
     let source = indoc! {r#"
@@ -451,7 +1391,12 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    assert_eq!(3, get_bind_group_data(&module).unwrap().len());
+    assert_eq!(
+      3,
+      get_bind_group_data(&module, &WgslBindgenOption::default())
+        .unwrap()
+        .len()
+    );
   }
 
   #[test]
@@ -465,7 +1410,7 @@ mod tests {
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
     assert!(matches!(
-      get_bind_group_data(&module),
+      get_bind_group_data(&module, &WgslBindgenOption::default()),
       Err(CreateModuleError::NonConsecutiveBindGroups)
     ));
   }
@@ -483,11 +1428,45 @@ mod tests {
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
     assert!(matches!(
-      get_bind_group_data(&module),
+      get_bind_group_data(&module, &WgslBindgenOption::default()),
       Err(CreateModuleError::NonConsecutiveBindGroups)
     ));
   }
 
+  #[test]
+  fn bind_group_data_sorted_by_binding_index() {
+    let source = indoc! {r#"
+            @group(0) @binding(3) var<uniform> d: f32;
+            @group(0) @binding(1) var<uniform> b: f32;
+            @group(0) @binding(2) var<uniform> c: f32;
+            @group(0) @binding(0) var<uniform> a: f32;
+
+            @fragment
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data =
+      get_bind_group_data(&module, &WgslBindgenOption::default()).unwrap();
+    let indices: Vec<_> = bind_group_data[&0]
+      .bindings
+      .iter()
+      .map(|binding| binding.binding_index)
+      .collect();
+    assert_eq!(vec![0, 1, 2, 3], indices);
+
+    let actual = bind_groups_module(
+      "test",
+      &WgslBindgenOption::default(),
+      &module,
+      &bind_group_data,
+      wgpu::ShaderStages::FRAGMENT,
+    )
+    .unwrap()
+    .to_string();
+    assert!(actual.contains("[self . a , self . b , self . c , self . d]"));
+  }
+
   #[test]
   fn bind_groups_module_compute() {
     let source = indoc! {r#"
@@ -509,7 +1488,8 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let bind_group_data = get_bind_group_data(&module).unwrap();
+    let bind_group_data =
+      get_bind_group_data(&module, &WgslBindgenOption::default()).unwrap();
 
     let actual = bind_groups_module(
       "test",
@@ -517,7 +1497,8 @@ mod tests {
       &module,
       &bind_group_data,
       wgpu::ShaderStages::COMPUTE,
-    );
+    )
+    .unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -627,6 +1608,13 @@ mod tests {
               pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
                   render_pass.set_bind_group(0, &self.0, &[]);
               }
+              pub fn set_with_offsets<'a>(
+                  &'a self,
+                  render_pass: &mut wgpu::ComputePass<'a>,
+                  offsets: &[wgpu::DynamicOffset],
+              ) {
+                  render_pass.set_bind_group(0, &self.0, offsets);
+              }
           }
           #[derive(Debug)]
           pub struct WgpuBindGroup1EntriesParams<'a> {
@@ -693,6 +1681,13 @@ mod tests {
               pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
                   render_pass.set_bind_group(1, &self.0, &[]);
               }
+              pub fn set_with_offsets<'a>(
+                  &'a self,
+                  render_pass: &mut wgpu::ComputePass<'a>,
+                  offsets: &[wgpu::DynamicOffset],
+              ) {
+                  render_pass.set_bind_group(1, &self.0, offsets);
+              }
           }
           #[derive(Debug, Copy, Clone)]
           pub struct WgpuBindGroups<'a> {
@@ -704,6 +1699,26 @@ mod tests {
                   self.bind_group0.set(pass);
                   self.bind_group1.set(pass);
               }
+              /// Sets all the bind groups with the given dynamic offsets, in group order.
+              pub fn set_with_offsets(
+                  &self,
+                  pass: &mut wgpu::ComputePass<'a>,
+                  offsets: [&[wgpu::DynamicOffset]; 2],
+              ) {
+                  self.bind_group0.set_with_offsets(pass, offsets[0]);
+                  self.bind_group1.set_with_offsets(pass, offsets[1]);
+              }
+          }
+          /// The number of bind group slots in the pipeline layout.
+          pub const BIND_GROUP_COUNT: u32 = 2;
+          #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+          #[repr(u32)]
+          pub enum BindGroupIndex { Group0 = 0, Group1 = 1 }
+          impl BindGroupIndex {
+              pub const ALL: [Self; 2] = [Self::Group0, Self::Group1];
+              pub fn layout_descriptor(self) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+                  match self { Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR, Self::Group1 => &WgpuBindGroup1::LAYOUT_DESCRIPTOR }
+              }
           }
           pub fn set_bind_groups<'a>(
               pass: &mut wgpu::ComputePass<'a>,
@@ -713,6 +1728,45 @@ mod tests {
               bind_group0.set(pass);
               bind_group1.set(pass);
           }
+          pub fn set_bind_groups_with_offsets<'a>(
+              pass: &mut wgpu::ComputePass<'a>,
+              bind_group0: &'a WgpuBindGroup0,
+              bind_group0_offsets: &[wgpu::DynamicOffset],
+              bind_group1: &'a WgpuBindGroup1,
+              bind_group1_offsets: &[wgpu::DynamicOffset],
+          ) {
+              bind_group0.set_with_offsets(pass, bind_group0_offsets);
+              bind_group1.set_with_offsets(pass, bind_group1_offsets);
+          }
+          /// Creates the layouts and all the bind groups from their entries, in group order.
+          pub fn create_bind_groups(
+              device: &wgpu::Device,
+              bind_group0: WgpuBindGroup0Entries,
+              bind_group1: WgpuBindGroup1Entries,
+          ) -> (WgpuBindGroup0, WgpuBindGroup1) {
+              (
+                  WgpuBindGroup0::from_bindings(device, bind_group0),
+                  WgpuBindGroup1::from_bindings(device, bind_group1),
+              )
+          }
+          pub const SRC_GROUP: u32 = 0;
+          pub const SRC_BINDING: u32 = 0;
+          pub const VERTEX_WEIGHTS_GROUP: u32 = 0;
+          pub const VERTEX_WEIGHTS_BINDING: u32 = 1;
+          pub const DST_GROUP: u32 = 0;
+          pub const DST_BINDING: u32 = 2;
+          pub const TRANSFORMS_GROUP: u32 = 1;
+          pub const TRANSFORMS_BINDING: u32 = 0;
+          /// Returns the group and binding index of the binding with the given name.
+          pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+              match name {
+                  "src" => Some((0, 0)),
+                  "vertex_weights" => Some((0, 1)),
+                  "dst" => Some((0, 2)),
+                  "transforms" => Some((1, 0)),
+                  _ => None,
+              }
+          }
       },
       actual
     );
@@ -761,7 +1815,8 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let bind_group_data = get_bind_group_data(&module).unwrap();
+    let bind_group_data =
+      get_bind_group_data(&module, &WgslBindgenOption::default()).unwrap();
 
     let actual = bind_groups_module(
       "test",
@@ -769,7 +1824,8 @@ mod tests {
       &module,
       &bind_group_data,
       wgpu::ShaderStages::VERTEX_FRAGMENT,
-    );
+    )
+    .unwrap();
 
     // TODO: Are storage buffers valid for vertex/fragment?
     assert_tokens_eq!(
@@ -998,7 +2054,7 @@ mod tests {
                         visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
                         ty: wgpu::BindingType::Texture {
                             sample_type: wgpu::TextureSampleType::Float {
-                                filterable: true,
+                                filterable: false,
                             },
                             view_dimension: wgpu::TextureViewDimension::D2,
                             multisampled: true,
@@ -1037,6 +2093,13 @@ mod tests {
               pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
                   render_pass.set_bind_group(0, &self.0, &[]);
               }
+              pub fn set_with_offsets<'a>(
+                  &'a self,
+                  render_pass: &mut wgpu::RenderPass<'a>,
+                  offsets: &[wgpu::DynamicOffset],
+              ) {
+                  render_pass.set_bind_group(0, &self.0, offsets);
+              }
           }
           #[derive(Debug)]
           pub struct WgpuBindGroup1EntriesParams<'a> {
@@ -1121,6 +2184,13 @@ mod tests {
               pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
                   render_pass.set_bind_group(1, &self.0, &[]);
               }
+              pub fn set_with_offsets<'a>(
+                  &'a self,
+                  render_pass: &mut wgpu::RenderPass<'a>,
+                  offsets: &[wgpu::DynamicOffset],
+              ) {
+                  render_pass.set_bind_group(1, &self.0, offsets);
+              }
           }
           #[derive(Debug, Copy, Clone)]
           pub struct WgpuBindGroups<'a> {
@@ -1132,6 +2202,26 @@ mod tests {
                   self.bind_group0.set(pass);
                   self.bind_group1.set(pass);
               }
+              /// Sets all the bind groups with the given dynamic offsets, in group order.
+              pub fn set_with_offsets(
+                  &self,
+                  pass: &mut wgpu::RenderPass<'a>,
+                  offsets: [&[wgpu::DynamicOffset]; 2],
+              ) {
+                  self.bind_group0.set_with_offsets(pass, offsets[0]);
+                  self.bind_group1.set_with_offsets(pass, offsets[1]);
+              }
+          }
+          /// The number of bind group slots in the pipeline layout.
+          pub const BIND_GROUP_COUNT: u32 = 2;
+          #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+          #[repr(u32)]
+          pub enum BindGroupIndex { Group0 = 0, Group1 = 1 }
+          impl BindGroupIndex {
+              pub const ALL: [Self; 2] = [Self::Group0, Self::Group1];
+              pub fn layout_descriptor(self) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+                  match self { Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR, Self::Group1 => &WgpuBindGroup1::LAYOUT_DESCRIPTOR }
+              }
           }
           pub fn set_bind_groups<'a>(
               pass: &mut wgpu::RenderPass<'a>,
@@ -1142,6 +2232,72 @@ mod tests {
               bind_group0.set(pass);
               bind_group1.set(pass);
           }
+          pub fn set_bind_groups_with_offsets<'a>(
+              pass: &mut wgpu::RenderPass<'a>,
+              bind_group0: &'a WgpuBindGroup0,
+              bind_group0_offsets: &[wgpu::DynamicOffset],
+              bind_group1: &'a WgpuBindGroup1,
+              bind_group1_offsets: &[wgpu::DynamicOffset],
+          ) {
+              bind_group0.set_with_offsets(pass, bind_group0_offsets);
+              bind_group1.set_with_offsets(pass, bind_group1_offsets);
+          }
+          /// Creates the layouts and all the bind groups from their entries, in group order.
+          pub fn create_bind_groups(
+              device: &wgpu::Device,
+              bind_group0: WgpuBindGroup0Entries,
+              bind_group1: WgpuBindGroup1Entries,
+          ) -> (WgpuBindGroup0, WgpuBindGroup1) {
+              (
+                  WgpuBindGroup0::from_bindings(device, bind_group0),
+                  WgpuBindGroup1::from_bindings(device, bind_group1),
+              )
+          }
+          pub const COLOR_TEXTURE_GROUP: u32 = 0;
+          pub const COLOR_TEXTURE_BINDING: u32 = 0;
+          pub const COLOR_TEXTURE_I32_GROUP: u32 = 0;
+          pub const COLOR_TEXTURE_I32_BINDING: u32 = 1;
+          pub const COLOR_TEXTURE_U32_GROUP: u32 = 0;
+          pub const COLOR_TEXTURE_U32_BINDING: u32 = 2;
+          pub const COLOR_SAMPLER_GROUP: u32 = 0;
+          pub const COLOR_SAMPLER_BINDING: u32 = 3;
+          pub const DEPTH_TEXTURE_GROUP: u32 = 0;
+          pub const DEPTH_TEXTURE_BINDING: u32 = 4;
+          pub const COMPARISON_SAMPLER_GROUP: u32 = 0;
+          pub const COMPARISON_SAMPLER_BINDING: u32 = 5;
+          pub const STORAGE_TEX_READ_GROUP: u32 = 0;
+          pub const STORAGE_TEX_READ_BINDING: u32 = 6;
+          pub const STORAGE_TEX_WRITE_GROUP: u32 = 0;
+          pub const STORAGE_TEX_WRITE_BINDING: u32 = 7;
+          pub const STORAGE_TEX_READ_WRITE_GROUP: u32 = 0;
+          pub const STORAGE_TEX_READ_WRITE_BINDING: u32 = 8;
+          pub const COLOR_TEXTURE_MSAA_GROUP: u32 = 0;
+          pub const COLOR_TEXTURE_MSAA_BINDING: u32 = 9;
+          pub const DEPTH_TEXTURE_MSAA_GROUP: u32 = 0;
+          pub const DEPTH_TEXTURE_MSAA_BINDING: u32 = 10;
+          pub const TRANSFORMS_GROUP: u32 = 1;
+          pub const TRANSFORMS_BINDING: u32 = 0;
+          pub const ONE_GROUP: u32 = 1;
+          pub const ONE_BINDING: u32 = 1;
+          /// Returns the group and binding index of the binding with the given name.
+          pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+              match name {
+                  "color_texture" => Some((0, 0)),
+                  "color_texture_i32" => Some((0, 1)),
+                  "color_texture_u32" => Some((0, 2)),
+                  "color_sampler" => Some((0, 3)),
+                  "depth_texture" => Some((0, 4)),
+                  "comparison_sampler" => Some((0, 5)),
+                  "storage_tex_read" => Some((0, 6)),
+                  "storage_tex_write" => Some((0, 7)),
+                  "storage_tex_read_write" => Some((0, 8)),
+                  "color_texture_msaa" => Some((0, 9)),
+                  "depth_texture_msaa" => Some((0, 10)),
+                  "transforms" => Some((1, 0)),
+                  "one" => Some((1, 1)),
+                  _ => None,
+              }
+          }
       },
       actual
     );
@@ -1161,7 +2317,8 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let bind_group_data = get_bind_group_data(&module).unwrap();
+    let bind_group_data =
+      get_bind_group_data(&module, &WgslBindgenOption::default()).unwrap();
 
     let actual = bind_groups_module(
       "test",
@@ -1169,7 +2326,8 @@ mod tests {
       &module,
       &bind_group_data,
       wgpu::ShaderStages::VERTEX,
-    );
+    )
+    .unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -1239,6 +2397,13 @@ mod tests {
               pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
                   render_pass.set_bind_group(0, &self.0, &[]);
               }
+              pub fn set_with_offsets<'a>(
+                  &'a self,
+                  render_pass: &mut wgpu::RenderPass<'a>,
+                  offsets: &[wgpu::DynamicOffset],
+              ) {
+                  render_pass.set_bind_group(0, &self.0, offsets);
+              }
           }
           #[derive(Debug, Copy, Clone)]
           pub struct WgpuBindGroups<'a> {
@@ -1248,14 +2413,56 @@ mod tests {
               pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
                   self.bind_group0.set(pass);
               }
+              /// Sets all the bind groups with the given dynamic offsets, in group order.
+              pub fn set_with_offsets(
+                  &self,
+                  pass: &mut wgpu::RenderPass<'a>,
+                  offsets: [&[wgpu::DynamicOffset]; 1],
+              ) {
+                  self.bind_group0.set_with_offsets(pass, offsets[0]);
+              }
           }
 
+          /// The number of bind group slots in the pipeline layout.
+          pub const BIND_GROUP_COUNT: u32 = 1;
+          #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+          #[repr(u32)]
+          pub enum BindGroupIndex { Group0 = 0 }
+          impl BindGroupIndex {
+              pub const ALL: [Self; 1] = [Self::Group0];
+              pub fn layout_descriptor(self) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+                  match self { Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR }
+              }
+          }
           pub fn set_bind_groups<'a>(
               pass: &mut wgpu::RenderPass<'a>,
               bind_group0: &'a WgpuBindGroup0,
           ) {
               bind_group0.set(pass);
           }
+          pub fn set_bind_groups_with_offsets<'a>(
+              pass: &mut wgpu::RenderPass<'a>,
+              bind_group0: &'a WgpuBindGroup0,
+              bind_group0_offsets: &[wgpu::DynamicOffset],
+          ) {
+              bind_group0.set_with_offsets(pass, bind_group0_offsets);
+          }
+          /// Creates the layouts and all the bind groups from their entries, in group order.
+          pub fn create_bind_groups(
+              device: &wgpu::Device,
+              bind_group0: WgpuBindGroup0Entries,
+          ) -> (WgpuBindGroup0,) {
+              (WgpuBindGroup0::from_bindings(device, bind_group0),)
+          }
+          pub const TRANSFORMS_GROUP: u32 = 0;
+          pub const TRANSFORMS_BINDING: u32 = 0;
+          /// Returns the group and binding index of the binding with the given name.
+          pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+              match name {
+                  "transforms" => Some((0, 0)),
+                  _ => None,
+              }
+          }
       },
       actual
     );
@@ -1275,7 +2482,8 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let bind_group_data = get_bind_group_data(&module).unwrap();
+    let bind_group_data =
+      get_bind_group_data(&module, &WgslBindgenOption::default()).unwrap();
 
     let actual = bind_groups_module(
       "test",
@@ -1283,7 +2491,8 @@ mod tests {
       &module,
       &bind_group_data,
       wgpu::ShaderStages::FRAGMENT,
-    );
+    )
+    .unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -1352,6 +2561,13 @@ mod tests {
               pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
                   render_pass.set_bind_group(0, &self.0, &[]);
               }
+              pub fn set_with_offsets<'a>(
+                  &'a self,
+                  render_pass: &mut wgpu::RenderPass<'a>,
+                  offsets: &[wgpu::DynamicOffset],
+              ) {
+                  render_pass.set_bind_group(0, &self.0, offsets);
+              }
           }
           #[derive(Debug, Copy, Clone)]
           pub struct WgpuBindGroups<'a> {
@@ -1361,16 +2577,129 @@ mod tests {
               pub fn set(&self, pass: &mut wgpu::RenderPass<'a>) {
                   self.bind_group0.set(pass);
               }
+              /// Sets all the bind groups with the given dynamic offsets, in group order.
+              pub fn set_with_offsets(
+                  &self,
+                  pass: &mut wgpu::RenderPass<'a>,
+                  offsets: [&[wgpu::DynamicOffset]; 1],
+              ) {
+                  self.bind_group0.set_with_offsets(pass, offsets[0]);
+              }
           }
 
+          /// The number of bind group slots in the pipeline layout.
+          pub const BIND_GROUP_COUNT: u32 = 1;
+          #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+          #[repr(u32)]
+          pub enum BindGroupIndex { Group0 = 0 }
+          impl BindGroupIndex {
+              pub const ALL: [Self; 1] = [Self::Group0];
+              pub fn layout_descriptor(self) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+                  match self { Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR }
+              }
+          }
           pub fn set_bind_groups<'a>(
               pass: &mut wgpu::RenderPass<'a>,
               bind_group0: &'a WgpuBindGroup0,
           ) {
               bind_group0.set(pass);
           }
+          pub fn set_bind_groups_with_offsets<'a>(
+              pass: &mut wgpu::RenderPass<'a>,
+              bind_group0: &'a WgpuBindGroup0,
+              bind_group0_offsets: &[wgpu::DynamicOffset],
+          ) {
+              bind_group0.set_with_offsets(pass, bind_group0_offsets);
+          }
+          /// Creates the layouts and all the bind groups from their entries, in group order.
+          pub fn create_bind_groups(
+              device: &wgpu::Device,
+              bind_group0: WgpuBindGroup0Entries,
+          ) -> (WgpuBindGroup0,) {
+              (WgpuBindGroup0::from_bindings(device, bind_group0),)
+          }
+          pub const TRANSFORMS_GROUP: u32 = 0;
+          pub const TRANSFORMS_BINDING: u32 = 0;
+          /// Returns the group and binding index of the binding with the given name.
+          pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+              match name {
+                  "transforms" => Some((0, 0)),
+                  _ => None,
+              }
+          }
       },
       actual
     );
   }
+
+  #[test]
+  fn bind_groups_module_unsupported_binding_type() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> transform: mat4x4<f32>;
+            @group(0) @binding(1) var<storage, read_write> counter: atomic<u32>;
+
+            @compute
+            @workgroup_size(64)
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data =
+      get_bind_group_data(&module, &WgslBindgenOption::default()).unwrap();
+
+    let actual = bind_groups_module(
+      "test",
+      &WgslBindgenOption::default(),
+      &module,
+      &bind_group_data,
+      wgpu::ShaderStages::COMPUTE,
+    );
+
+    assert_eq!(
+      Err(CreateModuleError::UnsupportedBindingType {
+        module: "test".to_string(),
+        binding_name: "test::transform".to_string(),
+        group: 0,
+        binding: 0,
+        type_desc:
+          "Matrix { columns: Quad, rows: Quad, scalar: Scalar { kind: Float, width: 4 } }"
+            .to_string(),
+      }),
+      actual.map(|tokens| tokens.to_string())
+    );
+  }
+
+  #[test]
+  fn bind_groups_module_duplicate_binding_const_name() {
+    let source = indoc! {r#"
+            @group(0) @binding(0) var<uniform> light_count: u32;
+            @group(0) @binding(1) var<uniform> lightCount: u32;
+
+            @compute
+            @workgroup_size(64)
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let bind_group_data =
+      get_bind_group_data(&module, &WgslBindgenOption::default()).unwrap();
+
+    let actual = bind_groups_module(
+      "test",
+      &WgslBindgenOption::default(),
+      &module,
+      &bind_group_data,
+      wgpu::ShaderStages::COMPUTE,
+    );
+
+    assert_eq!(
+      Err(CreateModuleError::DuplicateBindingConstName {
+        module: "test".to_string(),
+        name: "LIGHT_COUNT_GROUP".to_string(),
+        first: "light_count".to_string(),
+        second: "lightCount".to_string(),
+      }),
+      actual.map(|tokens| tokens.to_string())
+    );
+  }
 }
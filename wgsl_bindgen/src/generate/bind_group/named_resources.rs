@@ -0,0 +1,108 @@
+use self::quote_gen::RustItemPath;
+use super::*;
+
+/// The root level error returned by the generated `bind_groups_from_named` functions.
+pub(crate) fn named_resources_error() -> TokenStream {
+  quote! {
+    /// The names that couldn't be used to create bind groups from named resources.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct NamedResourcesError {
+      /// The names without a resource.
+      pub missing: Vec<&'static str>,
+      /// The names whose resource is of the wrong kind for the binding.
+      pub wrong_kind: Vec<&'static str>,
+    }
+  }
+}
+
+/// Generates the `BIND_GROUP_ENTRY_NAMES` constant and the `bind_groups_from_named` function,
+/// which creates all the bind groups of a module from resources keyed by the fully qualified
+/// binding names.
+pub(super) fn bind_groups_from_named(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> Result<TokenStream, CreateModuleError> {
+  let generator = &options.wgpu_binding_generator.bind_group_layout;
+
+  let mut entry_names = Vec::new();
+  let mut lookups = Vec::new();
+  let mut locals = Vec::new();
+  let mut bind_groups = Vec::new();
+  let mut bind_group_types = Vec::new();
+
+  for (group_no, group) in bind_group_data {
+    let mut fields = Vec::new();
+
+    for binding in &group.bindings {
+      let path =
+        RustItemPath::from_mangled(binding.name.as_ref().unwrap(), invoking_entry_module);
+      let name = path.get_fully_qualified_name().to_string();
+      let field = format_ident!("{}", path.name.as_str());
+      let local = format_ident!("group{}_{}", group_no, path.name.as_str());
+
+      let (variant, value) = match binding_resource_type(invoking_entry_module, binding)?
+      {
+        BindResourceType::Buffer => (quote!(Buffer), quote!(resource.clone())),
+        BindResourceType::Sampler => (quote!(Sampler), quote!(*resource)),
+        BindResourceType::Texture => (quote!(TextureView), quote!(*resource)),
+        BindResourceType::SamplerArray => (quote!(SamplerArray), quote!(*resource)),
+        BindResourceType::TextureArray => (quote!(TextureViewArray), quote!(*resource)),
+      };
+
+      lookups.push(quote! {
+        let #local = match resources.get(#name) {
+          Some(wgpu::BindingResource::#variant(resource)) => Some(#value),
+          Some(_) => {
+            error.wrong_kind.push(#name);
+            None
+          }
+          None => {
+            error.missing.push(#name);
+            None
+          }
+        };
+      });
+      entry_names.push(name);
+      fields.push(quote!(#field: #local));
+      locals.push(local);
+    }
+
+    let bind_group_name = generator.bind_group_name_ident(*group_no);
+    let entries_name = generator.bind_group_entries_struct_name_ident(*group_no);
+    let entries_params_name = format_ident!("{}Params", entries_name);
+
+    bind_groups.push(quote! {
+      #bind_group_name::from_bindings(
+        device,
+        #entries_name::new(#entries_params_name { #(#fields),* }),
+      )
+    });
+    bind_group_types.push(quote!(#bind_group_name));
+  }
+
+  let entry_names_len = Index::from(entry_names.len());
+
+  Ok(quote! {
+    /// The fully qualified names of all the bindings, in group and binding order.
+    pub const BIND_GROUP_ENTRY_NAMES: [&str; #entry_names_len] = [#(#entry_names),*];
+
+    /// Creates all the bind groups from resources keyed by the names in
+    /// [`BIND_GROUP_ENTRY_NAMES`], reporting every name that is missing or has a resource
+    /// of the wrong kind.
+    pub fn bind_groups_from_named<'a>(
+      device: &wgpu::Device,
+      resources: &std::collections::HashMap<String, wgpu::BindingResource<'a>>,
+    ) -> Result<(#(#bind_group_types,)*), NamedResourcesError> {
+      let mut error = NamedResourcesError::default();
+
+      #(#lookups)*
+
+      let (#(Some(#locals),)*) = (#(#locals,)*) else {
+        return Err(error);
+      };
+
+      Ok((#(#bind_groups,)*))
+    }
+  })
+}
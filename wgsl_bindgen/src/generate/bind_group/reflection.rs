@@ -0,0 +1,76 @@
+use self::quote_gen::RustItemPath;
+use super::*;
+
+/// The root level binding description type and the table of the bindings of all modules.
+pub(crate) fn bindings_table_root(entry_modules: &[&str]) -> TokenStream {
+  let tables = entry_modules.iter().map(|mod_name| {
+    let path = syn::parse_str::<syn::Path>(mod_name).unwrap();
+    quote!(#path::BINDINGS)
+  });
+
+  quote! {
+    /// A binding of a module, with the layout entry it's generated with.
+    #[derive(Debug, Clone, Copy)]
+    pub struct BindingDesc {
+      /// The name of the module the binding belongs to.
+      pub module: &'static str,
+      /// The fully qualified name of the binding.
+      pub name: &'static str,
+      pub group: u32,
+      pub binding: u32,
+      pub entry: wgpu::BindGroupLayoutEntry,
+    }
+
+    /// The `BINDINGS` of every module. Bindings with the same name in several modules are
+    /// all kept.
+    pub const ALL_BINDINGS: &[&[BindingDesc]] = &[#(#tables),*];
+
+    /// Iterates over the bindings of all the modules.
+    pub fn all_bindings() -> impl Iterator<Item = &'static BindingDesc> {
+      ALL_BINDINGS.iter().flat_map(|bindings| bindings.iter())
+    }
+  }
+}
+
+/// Generates the `BINDINGS` table of a module, which refers to the layout entries of the
+/// generated bind groups.
+pub(crate) fn bindings_table(
+  invoking_entry_module: &str,
+  options: &WgslBindgenOption,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  let generator = &options.wgpu_binding_generator.bind_group_layout;
+
+  let descs = bind_group_data.iter().flat_map(|(group_no, group)| {
+    let bind_group_name = generator.bind_group_name_ident(*group_no);
+    group
+      .bindings
+      .iter()
+      .enumerate()
+      .map(move |(index, binding)| {
+        let path = RustItemPath::from_mangled(
+          binding.name.as_ref().unwrap(),
+          invoking_entry_module,
+        );
+        let name = path.get_fully_qualified_name().to_string();
+        let group_index = proc_macro2::Literal::u32_unsuffixed(*group_no);
+        let binding_index = proc_macro2::Literal::u32_unsuffixed(binding.binding_index);
+        let index = Index::from(index);
+
+        quote! {
+          BindingDesc {
+            module: #invoking_entry_module,
+            name: #name,
+            group: #group_index,
+            binding: #binding_index,
+            entry: #bind_group_name::LAYOUT_DESCRIPTOR.entries[#index],
+          }
+        }
+      })
+  });
+
+  quote! {
+    /// Every binding of the module, in group and binding order.
+    pub const BINDINGS: &[BindingDesc] = &[#(#descs),*];
+  }
+}
@@ -1,9 +1,10 @@
+use case::CaseExt;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::Ident;
+use syn::{Ident, Index};
 
 use crate::quote_gen::{rust_type, RustItem, RustItemPath, RustItemType};
-use crate::WgslBindgenOption;
+use crate::{wgsl, ShaderDefValue, WgslBindgenOption, WgslBindgenWarning};
 
 pub fn consts_items(invoking_entry_module: &str, module: &naga::Module) -> Vec<RustItem> {
   // Create matching Rust constants for WGSl constants.
@@ -17,10 +18,10 @@ pub fn consts_items(invoking_entry_module: &str, module: &naga::Module) -> Vec<R
       let rust_item_path = RustItemPath::from_mangled(name_str, invoking_entry_module);
       let name = Ident::new(&rust_item_path.name, Span::call_site());
 
-      // TODO: Add support for f64 and f16 once naga supports them.
+      // TODO: Add support for f16 once naga supports it.
       let type_and_value = match &module.global_expressions[t.init] {
         naga::Expression::Literal(literal) => match literal {
-          naga::Literal::F64(v) => Some(quote!(f32 = #v)),
+          naga::Literal::F64(v) => Some(quote!(f64 = #v)),
           naga::Literal::F32(v) => Some(quote!(f32 = #v)),
           naga::Literal::U32(v) => Some(quote!(u32 = #v)),
           naga::Literal::U64(v) => Some(quote!(u64 = #v)),
@@ -42,18 +43,120 @@ pub fn consts_items(invoking_entry_module: &str, module: &naga::Module) -> Vec<R
     .collect()
 }
 
+/// Creates `<GLOBAL>_LEN` constants with the lengths of fixed-size array globals, e.g.
+/// `var<uniform> lights: array<Light, 16>` becomes `pub const LIGHTS_LEN: usize = 16;`.
+pub fn global_array_length_consts_items(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Vec<RustItem> {
+  if !options.array_length_constants {
+    return Vec::new();
+  }
+
+  module
+    .global_variables
+    .iter()
+    .filter_map(|(_, global)| -> Option<RustItem> {
+      let name_str = global.name.as_ref()?;
+      let len = wgsl::fixed_array_length(&module.types[global.ty].inner)?;
+
+      let rust_item_path = RustItemPath::from_mangled(name_str, invoking_entry_module);
+      let name = wgsl::array_length_const_name(&rust_item_path.name);
+      let len = Index::from(len as usize);
+
+      Some(RustItem::new(
+        RustItemType::ConstVarDecls.into(),
+        rust_item_path,
+        quote! { pub const #name: usize = #len; },
+      ))
+    })
+    .collect()
+}
+
+/// Creates Rust constants for the integer shader defines of an entry, e.g. `#define MAX_LIGHTS 64`
+/// becomes `pub const MAX_LIGHTS: i32 = 64;`. WGSL constants of the same name take precedence.
+pub fn shader_def_consts_items(
+  invoking_entry_module: &str,
+  shader_defs: &[(String, ShaderDefValue)],
+  const_items: &[RustItem],
+  warnings: &mut Vec<WgslBindgenWarning>,
+) -> Vec<RustItem> {
+  shader_defs
+    .iter()
+    .filter_map(|(name_str, value)| -> Option<RustItem> {
+      let type_and_value = match value {
+        ShaderDefValue::Int(v) => quote!(i32 = #v),
+        ShaderDefValue::UInt(v) => quote!(u32 = #v),
+        ShaderDefValue::Bool(_) => return None,
+      };
+
+      let rust_item_path =
+        RustItemPath::new(invoking_entry_module.into(), name_str.into());
+      if const_items.iter().any(|item| item.path == rust_item_path) {
+        warnings.push(WgslBindgenWarning::ShadowedShaderDef {
+          name: name_str.clone(),
+          module: invoking_entry_module.to_string(),
+        });
+        return None;
+      }
+
+      let name = Ident::new(name_str, Span::call_site());
+      Some(RustItem::new(
+        RustItemType::ConstVarDecls.into(),
+        rust_item_path,
+        quote! { pub const #name: #type_and_value;},
+      ))
+    })
+    .collect()
+}
+
 pub fn pipeline_overridable_constants(
   module: &naga::Module,
   options: &WgslBindgenOption,
 ) -> TokenStream {
   let overrides: Vec<_> = module.overrides.iter().map(|(_, o)| o).collect();
+  let combined = override_constants_struct(
+    &Ident::new("OverrideConstants", Span::call_site()),
+    &overrides,
+    module,
+    options,
+  );
+
+  // Each entry point only needs the overrides it can reach.
+  let entries = module.entry_points.iter().map(|entry| {
+    let overrides = wgsl::entry_overrides(module, entry);
+    override_constants_struct(&entry_overrides_name(entry), &overrides, module, options)
+  });
+
+  quote! {
+      #combined
+      #(#entries)*
+  }
+}
+
+/// The name of the generated struct holding the overrides reachable from the entry point.
+pub fn entry_overrides_name(entry: &naga::EntryPoint) -> Ident {
+  Ident::new(&format!("{}Overrides", entry.name.to_camel()), Span::call_site())
+}
+
+fn override_constants_struct(
+  name: &Ident,
+  overrides: &[&naga::Override],
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  if overrides.is_empty() {
+    return quote!();
+  }
 
   let fields: Vec<_> = overrides
     .iter()
     .map(|o| {
-      let name = Ident::new(o.name.as_ref().unwrap(), Span::call_site());
+      let name = override_field_name(o);
       // TODO: Do we only need to handle scalar types here?
-      let ty = rust_type(None, module, &module.types[o.ty], options);
+      let ty = rust_type(None, module, &module.types[o.ty], options)
+        .expect("overrides are scalars");
 
       if o.init.is_some() {
         quote!(pub #name: Option<#ty>)
@@ -63,90 +166,83 @@ pub fn pipeline_overridable_constants(
     })
     .collect();
 
-  let required_entries: Vec<_> = overrides
-      .iter()
-      .filter_map(|o| {
-          if o.init.is_some() {
-              None
-          } else {
-              let key = override_key(o);
-
-              let name = Ident::new(o.name.as_ref().unwrap(), Span::call_site());
-
-              // TODO: Do we only need to handle scalar types here?
-              let ty = &module.types[o.ty];
-              let value = if matches!(ty.inner, naga::TypeInner::Scalar(s) if s.kind == naga::ScalarKind::Bool) {
-                  quote!(if self.#name { 1.0 } else { 0.0})
-              } else {
-                  quote!(self.#name as f64)
-              };
-
-              Some(quote!((#key.to_owned(), #value)))
-          }
-      })
-      .collect();
-
-  // Add code for optionally inserting the constants with defaults.
-  // Omitted constants will be initialized using the values defined in WGSL.
-  let insert_optional_entries: Vec<_> = overrides
-      .iter()
-      .filter_map(|o| {
-          if o.init.is_some() {
-              let key = override_key(o);
-
-              // TODO: Do we only need to handle scalar types here?
-              let ty = &module.types[o.ty];
-              let value = if matches!(ty.inner, naga::TypeInner::Scalar(s) if s.kind == naga::ScalarKind::Bool) {
-                  quote!(if value { 1.0 } else { 0.0})
-              } else {
-                  quote!(value as f64)
-              };
-
-              let name = Ident::new(o.name.as_ref().unwrap(), Span::call_site());
-
-              Some(quote! {
-                  if let Some(value) = self.#name {
-                      entries.insert(#key.to_owned(), #value);
-                  }
-              })
-          } else {
-              None
-          }
-      })
-      .collect();
-
-  let init_entries = if insert_optional_entries.is_empty() {
-    quote!(let entries = std::collections::HashMap::from([#(#required_entries),*]);)
-  } else {
-    quote!(let mut entries = std::collections::HashMap::from([#(#required_entries),*]);)
+  let to_f64 = |o: &naga::Override, value: TokenStream| {
+    // TODO: Do we only need to handle scalar types here?
+    let ty = &module.types[o.ty];
+    if matches!(ty.inner, naga::TypeInner::Scalar(s) if s.kind == naga::ScalarKind::Bool)
+    {
+      quote!(if #value { 1.0 } else { 0.0 })
+    } else {
+      quote!(#value as f64)
+    }
   };
 
-  if !fields.is_empty() {
-    // Create a Rust struct that can initialize the constants dictionary.
+  let insert_required_entries = overrides.iter().filter(|o| o.init.is_none()).map(|o| {
+    let key = override_key(o);
+    let name = override_field_name(o);
+    let value = to_f64(o, quote!(self.#name));
+    quote!(constants.insert(#key.to_owned(), #value);)
+  });
+
+  // Omitted optional constants will be initialized using the values defined in WGSL.
+  let insert_optional_entries = overrides.iter().filter(|o| o.init.is_some()).map(|o| {
+    let key = override_key(o);
+    let name = override_field_name(o);
+    let value = to_f64(o, quote!(value));
     quote! {
-        pub struct OverrideConstants {
-            #(#fields),*
-        }
-
-        // TODO: Only start with the required ones.
-        impl OverrideConstants {
-            pub fn constants(&self) -> std::collections::HashMap<String, f64> {
-                #init_entries
-                #(#insert_optional_entries);*
-                entries
-            }
+        if let Some(value) = self.#name {
+            constants.insert(#key.to_owned(), #value);
         }
     }
+  });
+
+  // Required constants have no sensible default.
+  let derives = if overrides.iter().all(|o| o.init.is_some()) {
+    quote!(#[derive(Debug, Clone, PartialEq, Default)])
   } else {
-    quote!()
+    quote!(#[derive(Debug, Clone, PartialEq)])
+  };
+
+  quote! {
+      #derives
+      pub struct #name {
+          #(#fields),*
+      }
+
+      impl #name {
+          pub fn constants(&self) -> std::collections::HashMap<String, f64> {
+              let mut entries = std::collections::HashMap::new();
+              self.merge_into(&mut entries);
+              entries
+          }
+
+          /// Inserts the set constants into `constants`, replacing any previous values for
+          /// the same overrides. This allows layering multiple sources of overrides.
+          pub fn merge_into(&self, constants: &mut std::collections::HashMap<String, f64>) {
+              #(#insert_required_entries)*
+              #(#insert_optional_entries)*
+          }
+      }
   }
 }
 
+/// The key of the override in `wgpu::PipelineCompilationOptions::constants`, which is the
+/// `@id` if present and the name otherwise.
 fn override_key(o: &naga::Override) -> String {
-  // The @id(id) should be the name if present.
-  o.id
-    .map(|i| i.to_string())
-    .unwrap_or(o.name.clone().unwrap())
+  match (o.id, &o.name) {
+    (Some(id), _) => id.to_string(),
+    (None, Some(name)) => name.clone(),
+    (None, None) => unreachable!("overrides without an id must be named"),
+  }
+}
+
+fn override_field_name(o: &naga::Override) -> Ident {
+  let name = match (&o.name, o.id) {
+    (Some(name), _) => name.clone(),
+    (None, Some(id)) => format!("override_{id}"),
+    (None, None) => unreachable!("overrides without an id must be named"),
+  };
+  Ident::new(&name, Span::call_site())
 }
 
 #[cfg(test)]
@@ -222,6 +318,7 @@ mod tests {
 
     assert_tokens_eq!(
       quote! {
+          #[derive(Debug, Clone, PartialEq)]
           pub struct OverrideConstants {
               pub b1: Option<bool>,
               pub b2: Option<bool>,
@@ -237,34 +334,189 @@ mod tests {
 
           impl OverrideConstants {
               pub fn constants(&self) -> std::collections::HashMap<String, f64> {
-                  let mut entries = std::collections::HashMap::from([
-                      ("b3".to_owned(), if self.b3 { 1.0 } else { 0.0 }),
-                      ("f2".to_owned(), self.f2 as f64),
-                      ("i2".to_owned(), self.i2 as f64)
-                  ]);
+                  let mut entries = std::collections::HashMap::new();
+                  self.merge_into(&mut entries);
+                  entries
+              }
+              /// Inserts the set constants into `constants`, replacing any previous values for
+              /// the same overrides. This allows layering multiple sources of overrides.
+              pub fn merge_into(&self, constants: &mut std::collections::HashMap<String, f64>) {
+                  constants.insert("b3".to_owned(), if self.b3 { 1.0 } else { 0.0 });
+                  constants.insert("f2".to_owned(), self.f2 as f64);
+                  constants.insert("i2".to_owned(), self.i2 as f64);
                   if let Some(value) = self.b1 {
-                      entries.insert("b1".to_owned(), if value { 1.0 } else { 0.0 });
+                      constants.insert("b1".to_owned(), if value { 1.0 } else { 0.0 });
                   }
                   if let Some(value) = self.b2 {
-                      entries.insert("b2".to_owned(), if value { 1.0 } else { 0.0 });
+                      constants.insert("b2".to_owned(), if value { 1.0 } else { 0.0 });
                   }
                   if let Some(value) = self.f1 {
-                      entries.insert("f1".to_owned(), value as f64);
+                      constants.insert("f1".to_owned(), value as f64);
                   }
                   if let Some(value) = self.i1 {
-                      entries.insert("i1".to_owned(), value as f64);
+                      constants.insert("i1".to_owned(), value as f64);
                   }
                   if let Some(value) = self.i3 {
-                      entries.insert("i3".to_owned(), value as f64);
+                      constants.insert("i3".to_owned(), value as f64);
                   }
                   if let Some(value) = self.a {
-                      entries.insert("0".to_owned(), value as f64);
+                      constants.insert("0".to_owned(), value as f64);
                   }
                   if let Some(value) = self.b {
-                      entries.insert("35".to_owned(), value as f64);
+                      constants.insert("35".to_owned(), value as f64);
                   }
+              }
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_entry_pipeline_overrideable_constants() {
+    let source = indoc! {r#"
+          override scale: f32;
+          override offset: f32 = 1.0;
+          override shifted: f32 = offset * 2.0;
+          override unused: u32 = 4u;
+          fn apply(x: f32) -> f32 { return x * scale; }
+          @vertex
+          fn vs_main() -> @builtin(position) vec4<f32> {
+              return vec4(apply(1.0));
+          }
+          @fragment
+          fn fs_main() -> @location(0) vec4<f32> {
+              return vec4(shifted);
+          }
+          @compute
+          @workgroup_size(1)
+          fn cs_main() {}
+      "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = pipeline_overridable_constants(&module, &WgslBindgenOption::default());
+
+    assert_tokens_eq!(
+      quote! {
+          #[derive(Debug, Clone, PartialEq)]
+          pub struct OverrideConstants {
+              pub scale: f32,
+              pub offset: Option<f32>,
+              pub shifted: Option<f32>,
+              pub unused: Option<u32>,
+          }
+
+          impl OverrideConstants {
+              pub fn constants(&self) -> std::collections::HashMap<String, f64> {
+                  let mut entries = std::collections::HashMap::new();
+                  self.merge_into(&mut entries);
                   entries
               }
+              /// Inserts the set constants into `constants`, replacing any previous values for
+              /// the same overrides. This allows layering multiple sources of overrides.
+              pub fn merge_into(&self, constants: &mut std::collections::HashMap<String, f64>) {
+                  constants.insert("scale".to_owned(), self.scale as f64);
+                  if let Some(value) = self.offset {
+                      constants.insert("offset".to_owned(), value as f64);
+                  }
+                  if let Some(value) = self.shifted {
+                      constants.insert("shifted".to_owned(), value as f64);
+                  }
+                  if let Some(value) = self.unused {
+                      constants.insert("unused".to_owned(), value as f64);
+                  }
+              }
+          }
+          #[derive(Debug, Clone, PartialEq)]
+          pub struct VsMainOverrides {
+              pub scale: f32
+          }
+
+          impl VsMainOverrides {
+              pub fn constants(&self) -> std::collections::HashMap<String, f64> {
+                  let mut entries = std::collections::HashMap::new();
+                  self.merge_into(&mut entries);
+                  entries
+              }
+              /// Inserts the set constants into `constants`, replacing any previous values for
+              /// the same overrides. This allows layering multiple sources of overrides.
+              pub fn merge_into(&self, constants: &mut std::collections::HashMap<String, f64>) {
+                  constants.insert("scale".to_owned(), self.scale as f64);
+              }
+          }
+          #[derive(Debug, Clone, PartialEq, Default)]
+          pub struct FsMainOverrides {
+              pub offset: Option<f32>,
+              pub shifted: Option<f32>
+          }
+
+          impl FsMainOverrides {
+              pub fn constants(&self) -> std::collections::HashMap<String, f64> {
+                  let mut entries = std::collections::HashMap::new();
+                  self.merge_into(&mut entries);
+                  entries
+              }
+              /// Inserts the set constants into `constants`, replacing any previous values for
+              /// the same overrides. This allows layering multiple sources of overrides.
+              pub fn merge_into(&self, constants: &mut std::collections::HashMap<String, f64>) {
+                  if let Some(value) = self.offset {
+                      constants.insert("offset".to_owned(), value as f64);
+                  }
+                  if let Some(value) = self.shifted {
+                      constants.insert("shifted".to_owned(), value as f64);
+                  }
+              }
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_pipeline_overrideable_constants_keys() {
+    let source = indoc! {r#"
+          @id(7) override gain: f32 = 1.0;
+          @id(8) override unnamed: u32;
+          override named: bool = true;
+          @fragment
+          fn main() {}
+      "#};
+
+    let mut module = naga::front::wgsl::parse_str(source).unwrap();
+    // Overrides from other frontends, like SPIR-V, may only have an id.
+    for (_, o) in module.overrides.iter_mut() {
+      if o.id == Some(8) {
+        o.name = None;
+      }
+    }
+
+    let actual = pipeline_overridable_constants(&module, &WgslBindgenOption::default());
+
+    assert_tokens_eq!(
+      quote! {
+          #[derive(Debug, Clone, PartialEq)]
+          pub struct OverrideConstants {
+              pub gain: Option<f32>,
+              pub override_8: u32,
+              pub named: Option<bool>,
+          }
+          impl OverrideConstants {
+              pub fn constants(&self) -> std::collections::HashMap<String, f64> {
+                  let mut entries = std::collections::HashMap::new();
+                  self.merge_into(&mut entries);
+                  entries
+              }
+              /// Inserts the set constants into `constants`, replacing any previous values for
+              /// the same overrides. This allows layering multiple sources of overrides.
+              pub fn merge_into(&self, constants: &mut std::collections::HashMap<String, f64>) {
+                  constants.insert("8".to_owned(), self.override_8 as f64);
+                  if let Some(value) = self.gain {
+                      constants.insert("7".to_owned(), value as f64);
+                  }
+                  if let Some(value) = self.named {
+                      constants.insert("named".to_owned(), if value { 1.0 } else { 0.0 });
+                  }
+              }
           }
       },
       actual
@@ -282,4 +534,38 @@ mod tests {
     let actual = pipeline_overridable_constants(&module, &WgslBindgenOption::default());
     assert_tokens_eq!(quote!(), actual);
   }
+
+  #[test]
+  fn shadowed_shader_def_consts_are_warnings() {
+    let source = indoc! {r#"
+          const MAX_LIGHTS: u32 = 16;
+          @fragment
+          fn main() {}
+      "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let const_items = consts_items("main", &module);
+    let shader_defs = [
+      ("MAX_LIGHTS".to_string(), ShaderDefValue::UInt(4)),
+      ("MAX_SHADOWS".to_string(), ShaderDefValue::UInt(2)),
+    ];
+    let mut warnings = Vec::new();
+    let items =
+      shader_def_consts_items("main", &shader_defs, &const_items, &mut warnings);
+
+    let actual = items.into_iter().map(|i| i.item).collect::<TokenStream>();
+    assert_tokens_eq!(
+      quote!(
+        pub const MAX_SHADOWS: u32 = 2u32;
+      ),
+      actual
+    );
+    assert_eq!(
+      vec![WgslBindgenWarning::ShadowedShaderDef {
+        name: "MAX_LIGHTS".to_string(),
+        module: "main".to_string(),
+      }],
+      warnings
+    );
+  }
 }
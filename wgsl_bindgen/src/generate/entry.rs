@@ -2,34 +2,269 @@ use case::CaseExt;
 use naga::ShaderStage;
 use proc_macro2::{Literal, Span, TokenStream};
 use quote::quote;
+use regex::Regex;
 use syn::{Ident, Index};
 
+use super::consts::entry_overrides_name;
+use super::EntryLabels;
 use crate::quote_gen::{RustItem, RustItemType};
-use crate::wgsl;
+use crate::{
+  wgsl, CreateModuleError, WgpuVersion, WgslBindgenOption,
+  DEFAULT_INSTANCE_STEP_MODE_REGEX,
+};
+
+/// A located output of a fragment entry point.
+struct FragmentOutput {
+  location: u32,
+  kind: Option<naga::ScalarKind>,
+}
+
+fn fragment_outputs(
+  module: &naga::Module,
+  entry_point: &naga::EntryPoint,
+) -> Result<Vec<FragmentOutput>, CreateModuleError> {
+  let output = |binding: Option<&naga::Binding>, ty: naga::Handle<naga::Type>| {
+    // Builtins don't have render targets.
+    match binding? {
+      naga::Binding::Location { location, .. } => Some(FragmentOutput {
+        location: *location,
+        kind: module.types[ty].inner.scalar_kind(),
+      }),
+      naga::Binding::BuiltIn(_) => None,
+    }
+  };
 
-fn fragment_target_count(module: &naga::Module, f: &naga::Function) -> usize {
-  match &f.result {
+  let outputs: Vec<_> = match &entry_point.function.result {
     Some(r) => match &r.binding {
-      Some(b) => {
-        // Builtins don't have render targets.
-        if matches!(b, naga::Binding::Location { .. }) {
-          1
-        } else {
-          0
-        }
-      }
+      Some(b) => output(Some(b), r.ty).into_iter().collect(),
       None => {
         // Fragment functions should return a single variable or a struct.
         match &module.types[r.ty].inner {
           naga::TypeInner::Struct { members, .. } => members
             .iter()
-            .filter(|m| matches!(m.binding, Some(naga::Binding::Location { .. })))
-            .count(),
-          _ => 0,
+            .filter_map(|m| output(m.binding.as_ref(), m.ty))
+            .collect(),
+          _ => Vec::new(),
         }
       }
     },
-    None => 0,
+    None => Vec::new(),
+  };
+
+  let mut locations = std::collections::BTreeSet::new();
+  for output in &outputs {
+    if !locations.insert(output.location) {
+      return Err(CreateModuleError::DuplicateFragmentOutputLocation {
+        entry: entry_point.name.clone(),
+        location: output.location,
+      });
+    }
+  }
+
+  Ok(outputs)
+}
+
+/// Generates `<entry>_entry_single` and `<entry>_entry_single_blend` for fragment entry
+/// points with a single color target, which call `<entry>_entry`.
+fn fragment_single_target_fns(
+  entry_point: &naga::EntryPoint,
+  entry_fn_name: &Ident,
+  overrides: Option<&TokenStream>,
+) -> TokenStream {
+  let single_fn =
+    Ident::new(&format!("{}_entry_single", entry_point.name), Span::call_site());
+  let single_blend_fn =
+    Ident::new(&format!("{}_entry_single_blend", entry_point.name), Span::call_site());
+  let override_arg = overrides.map(|_| quote!(overrides));
+
+  let single_doc = format!(
+    " Calls [`{entry_fn_name}`] with a single color target of `format`, without blending and \
+     writing all channels."
+  );
+  let single_blend_doc = format!(
+    " Calls [`{entry_fn_name}`] with a single color target of `format` blended with `blend`, \
+     writing all channels."
+  );
+
+  quote! {
+      #[doc = #single_doc]
+      pub fn #single_fn(format: wgpu::TextureFormat, #overrides) -> FragmentEntry<1> {
+          #entry_fn_name(
+              [Some(wgpu::ColorTargetState {
+                  format,
+                  blend: None,
+                  write_mask: wgpu::ColorWrites::ALL,
+              })],
+              #override_arg
+          )
+      }
+
+      #[doc = #single_blend_doc]
+      pub fn #single_blend_fn(
+          format: wgpu::TextureFormat,
+          blend: wgpu::BlendState,
+          #overrides
+      ) -> FragmentEntry<1> {
+          #entry_fn_name(
+              [Some(wgpu::ColorTargetState {
+                  format,
+                  blend: Some(blend),
+                  write_mask: wgpu::ColorWrites::ALL,
+              })],
+              #override_arg
+          )
+      }
+  }
+}
+
+/// Whether the fragment entry point writes `@builtin(frag_depth)`.
+fn fragment_writes_depth(module: &naga::Module, entry_point: &naga::EntryPoint) -> bool {
+  let is_depth = |binding: Option<&naga::Binding>| {
+    matches!(binding, Some(naga::Binding::BuiltIn(naga::BuiltIn::FragDepth)))
+  };
+
+  entry_point
+    .function
+    .result
+    .as_ref()
+    .is_some_and(|r| match &r.binding {
+      Some(b) => is_depth(Some(b)),
+      None => match &module.types[r.ty].inner {
+        naga::TypeInner::Struct { members, .. } => {
+          members.iter().any(|m| is_depth(m.binding.as_ref()))
+        }
+        _ => false,
+      },
+    })
+}
+
+/// Generates `<ENTRY>_WRITES_DEPTH` and, for entry points that write the depth, the
+/// `<entry>_depth_stencil_state` function.
+fn fragment_depth_items(
+  entry_point: &naga::EntryPoint,
+  writes_depth: bool,
+) -> TokenStream {
+  let const_name = Ident::new(
+    &format!("{}_WRITES_DEPTH", entry_point.name.to_uppercase()),
+    Span::call_site(),
+  );
+
+  // The depth written by the shader is discarded without depth writes.
+  let depth_stencil_state = writes_depth.then(|| {
+    let fn_name =
+      Ident::new(&format!("{}_depth_stencil_state", entry_point.name), Span::call_site());
+    quote! {
+        /// A depth stencil state with depth writes enabled, which the entry point needs for
+        /// its `frag_depth` output to have an effect.
+        pub fn #fn_name(
+            format: wgpu::TextureFormat,
+            compare: wgpu::CompareFunction,
+        ) -> wgpu::DepthStencilState {
+            wgpu::DepthStencilState {
+                format,
+                depth_write_enabled: true,
+                depth_compare: compare,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }
+        }
+    }
+  });
+
+  quote! {
+      /// Whether the entry point writes `@builtin(frag_depth)`.
+      pub const #const_name: bool = #writes_depth;
+      #depth_stencil_state
+  }
+}
+
+/// The number of color targets of the fragment outputs, which are indexed by location.
+fn fragment_target_count(outputs: &[FragmentOutput]) -> usize {
+  outputs
+    .iter()
+    .map(|output| output.location as usize + 1)
+    .max()
+    .unwrap_or(0)
+}
+
+/// Generates `<ENTRY>_OUTPUT_LOCATIONS` and the `<entry>_targets` functions of a fragment
+/// entry point with outputs.
+fn fragment_targets_items(
+  entry_point: &naga::EntryPoint,
+  outputs: &[FragmentOutput],
+) -> TokenStream {
+  if outputs.is_empty() {
+    return quote!();
+  }
+
+  let target_count = fragment_target_count(outputs);
+  let mut outputs: Vec<_> = outputs.iter().collect();
+  outputs.sort_by_key(|output| output.location);
+
+  let locations_name = Ident::new(
+    &format!("{}_OUTPUT_LOCATIONS", entry_point.name.to_uppercase()),
+    Span::call_site(),
+  );
+  let targets_fn =
+    Ident::new(&format!("{}_targets", entry_point.name), Span::call_site());
+  let targets_with_formats_fn =
+    Ident::new(&format!("{}_targets_with_formats", entry_point.name), Span::call_site());
+
+  let output_count = Index::from(outputs.len());
+
+  let location_docs = outputs.iter().map(|output| {
+    let kind = match output.kind {
+      Some(naga::ScalarKind::Float) => "float",
+      Some(naga::ScalarKind::Sint) => "sint",
+      Some(naga::ScalarKind::Uint) => "uint",
+      _ => "unknown",
+    };
+    let doc = format!(" - `{}`: {kind}", output.location);
+    quote!(#[doc = #doc])
+  });
+  let locations = outputs
+    .iter()
+    .map(|output| Index::from(output.location as usize));
+
+  let targets = (0..target_count as u32).map(|location| {
+    match outputs
+      .iter()
+      .position(|output| output.location == location)
+    {
+      Some(index) => {
+        let index = Index::from(index);
+        quote! {
+            Some(wgpu::ColorTargetState {
+                format: formats[#index],
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })
+        }
+      }
+      None => quote!(None),
+    }
+  });
+
+  let target_count = Index::from(target_count);
+
+  quote! {
+      /// The output locations of the entry point in ascending order, with their scalar kinds:
+      #(#location_docs)*
+      pub const #locations_name: [u32; #output_count] = [#(#locations),*];
+
+      /// Returns the color targets of the entry point with the same format for every output,
+      /// without blending and writing all channels.
+      pub fn #targets_fn(format: wgpu::TextureFormat) -> [Option<wgpu::ColorTargetState>; #target_count] {
+          #targets_with_formats_fn([format; #output_count])
+      }
+
+      /// Returns the color targets of the entry point with one format per output, in the order
+      /// of the output locations, without blending and writing all channels.
+      pub fn #targets_with_formats_fn(
+          formats: [wgpu::TextureFormat; #output_count],
+      ) -> [Option<wgpu::ColorTargetState>; #target_count] {
+          [#(#targets),*]
+      }
   }
 }
 
@@ -49,145 +284,380 @@ pub fn entry_point_constants(module: &naga::Module) -> TokenStream {
     })
     .collect();
 
+  let entry_point_enum = entry_point_enum(module);
+
   quote! {
       #(#entry_points)*
+
+      #entry_point_enum
   }
 }
 
-pub fn vertex_states(invoking_entry_module: &str, module: &naga::Module) -> TokenStream {
-  let vertex_input_structs =
-    wgsl::get_vertex_input_structs(invoking_entry_module, module);
+/// Generates the `EntryPoint` enum of the module, if it has entry points.
+fn entry_point_enum(module: &naga::Module) -> TokenStream {
+  if module.entry_points.is_empty() {
+    return quote!();
+  }
 
-  let mut step_mode_params = vec![];
-  let layout_expressions: Vec<TokenStream> = vertex_input_structs
+  let variants: Vec<_> = module
+    .entry_points
     .iter()
-    .map(|input| {
-      let struct_ref = input.item_path.short_token_stream(invoking_entry_module);
-      let step_mode = Ident::new(&input.item_path.name.to_snake(), Span::call_site());
-      step_mode_params.push(quote!(#step_mode: wgpu::VertexStepMode));
-      quote!(#struct_ref::vertex_buffer_layout(#step_mode))
-    })
+    .map(|entry_point| Ident::new(&entry_point.name.to_camel(), Span::call_site()))
     .collect();
+  let const_names = module.entry_points.iter().map(|entry_point| {
+    Ident::new(&format!("ENTRY_{}", &entry_point.name.to_uppercase()), Span::call_site())
+  });
+  let stages = module
+    .entry_points
+    .iter()
+    .map(|entry_point| match entry_point.stage {
+      ShaderStage::Vertex => quote!(wgpu::ShaderStages::VERTEX),
+      ShaderStage::Fragment => quote!(wgpu::ShaderStages::FRAGMENT),
+      ShaderStage::Compute => quote!(wgpu::ShaderStages::COMPUTE),
+    });
+  let workgroup_sizes = module.entry_points.iter().map(|entry_point| {
+    if entry_point.stage == ShaderStage::Compute {
+      let size = Ident::new(
+        &format!("{}_WORKGROUP_SIZE", entry_point.name.to_uppercase()),
+        Span::call_site(),
+      );
+      quote!(Some(compute::#size))
+    } else {
+      quote!(None)
+    }
+  });
+
+  quote! {
+      #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+      pub enum EntryPoint {
+          #(#variants),*
+      }
+
+      impl EntryPoint {
+          pub const ALL: &'static [Self] = &[#(Self::#variants),*];
+
+          pub fn name(self) -> &'static str {
+              match self {
+                  #(Self::#variants => #const_names),*
+              }
+          }
+
+          pub fn stage(self) -> wgpu::ShaderStages {
+              match self {
+                  #(Self::#variants => #stages),*
+              }
+          }
+
+          /// The workgroup size of compute entry points.
+          pub fn workgroup_size(self) -> Option<[u32; 3]> {
+              match self {
+                  #(Self::#variants => #workgroup_sizes),*
+              }
+          }
+      }
+  }
+}
+
+pub fn vertex_states(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
+  let default_instance_regex;
+  let instance_regex = match &options.instance_step_mode_regex {
+    Some(regex) => regex,
+    None => {
+      default_instance_regex = Regex::new(DEFAULT_INSTANCE_STEP_MODE_REGEX).unwrap();
+      &default_instance_regex
+    }
+  };
 
   let vertex_entries: Vec<TokenStream> = module
     .entry_points
     .iter()
-    .filter_map(|entry_point| match &entry_point.stage {
-      ShaderStage::Vertex => {
-        let fn_name =
-          Ident::new(&format!("{}_entry", &entry_point.name), Span::call_site());
+    .filter(|entry_point| entry_point.stage == ShaderStage::Vertex)
+    .map(|entry_point| {
+      // Each entry point takes the buffers of its own vertex inputs.
+      let vertex_input_structs = wgsl::get_entry_vertex_input_structs(
+        invoking_entry_module,
+        module,
+        entry_point,
+      );
 
-        let const_name = Ident::new(
-          &format!("ENTRY_{}", &entry_point.name.to_uppercase()),
-          Span::call_site(),
-        );
+      let mut step_mode_params = vec![];
+      let mut buffer_names = vec![];
+      let mut layout_expressions: Vec<TokenStream> = vertex_input_structs
+        .iter()
+        .map(|input| {
+          let struct_ref = input.item_path.short_token_stream(invoking_entry_module);
+          let step_mode =
+            Ident::new(&input.item_path.name.to_snake(), Span::call_site());
+          step_mode_params.push(quote!(#step_mode: wgpu::VertexStepMode));
+          buffer_names.push(input.item_path.name.to_string());
+          quote!(#struct_ref::vertex_buffer_layout(#step_mode))
+        })
+        .collect();
+
+      let located_inputs = located_vertex_inputs(module, entry_point)?;
+      if let Some(located) = &located_inputs {
+        step_mode_params.insert(located.index, located.step_mode_param.clone());
+        buffer_names.insert(located.index, located.name.clone());
+        layout_expressions.insert(located.index, located.layout_expression.clone());
+      }
+      let located_items = located_inputs.map(|located| located.items);
 
-        let n = vertex_input_structs.len();
-        let n = Literal::usize_unsuffixed(n);
+      let fn_name =
+        Ident::new(&format!("{}_entry", &entry_point.name), Span::call_site());
 
-        let overrides = if !module.overrides.is_empty() {
-          Some(quote!(overrides: &OverrideConstants))
-        } else {
-          None
-        };
+      let const_name = Ident::new(
+        &format!("ENTRY_{}", &entry_point.name.to_uppercase()),
+        Span::call_site(),
+      );
 
-        let constants = if !module.overrides.is_empty() {
-          quote!(overrides.constants())
-        } else {
-          quote!(Default::default())
-        };
+      let n = Literal::usize_unsuffixed(layout_expressions.len());
 
-        let params = if step_mode_params.is_empty() {
-          quote!(#overrides)
-        } else {
-          quote!(#(#step_mode_params),*, #overrides)
-        };
+      let (overrides, constants) = entry_overrides_param(module, entry_point);
+      let zero_init = !options.skip_workgroup_memory_zero_init;
+
+      let params = if step_mode_params.is_empty() {
+        quote!(#overrides)
+      } else {
+        quote!(#(#step_mode_params),*, #overrides)
+      };
+
+      // Infer the step mode of each buffer from its name.
+      let default_fn = if buffer_names.is_empty() {
+        None
+      } else {
+        let default_fn_name = Ident::new(
+          &format!("{}_entry_default", &entry_point.name),
+          Span::call_site(),
+        );
+        let doc = format!(
+          " Calls [`{fn_name}`] with `Instance` step mode for the buffers whose names match \
+           `{}`, and `Vertex` for the others:",
+          instance_regex.as_str()
+        );
+        let (buffer_docs, step_modes): (Vec<_>, Vec<_>) = buffer_names
+          .iter()
+          .map(|name| {
+            let step_mode = if instance_regex.is_match(name) {
+              quote!(Instance)
+            } else {
+              quote!(Vertex)
+            };
+            let doc = format!(" - `{name}`: `{step_mode}`");
+            (quote!(#[doc = #doc]), quote!(wgpu::VertexStepMode::#step_mode))
+          })
+          .unzip();
+        let override_arg = overrides.as_ref().map(|_| quote!(overrides));
 
         Some(quote! {
-            pub fn #fn_name(#params) -> VertexEntry<#n> {
-                VertexEntry {
-                    entry_point: #const_name,
-                    buffers: [
-                        #(#layout_expressions),*
-                    ],
-                    constants: #constants
-                }
+            #[doc = #doc]
+            #(#buffer_docs)*
+            pub fn #default_fn_name(#overrides) -> VertexEntry<#n> {
+                #fn_name(#(#step_modes),*, #override_arg)
             }
         })
-      }
-      _ => None,
+      };
+
+      Ok(quote! {
+          #located_items
+
+          pub fn #fn_name(#params) -> VertexEntry<#n> {
+              VertexEntry {
+                  entry_point: #const_name,
+                  buffers: [
+                      #(#layout_expressions),*
+                  ],
+                  constants: #constants,
+                  zero_initialize_workgroup_memory: #zero_init,
+              }
+          }
+
+          #default_fn
+      })
     })
-    .collect();
+    .collect::<Result<_, _>>()?;
+
+  let entry_point = quote_entry_point(options, quote!(entry.entry_point));
 
   // Don't generate unused code.
   if vertex_entries.is_empty() {
-    quote!()
-  } else {
-    quote! {
-        #[derive(Debug)]
-        pub struct VertexEntry<const N: usize> {
-            pub entry_point: &'static str,
-            pub buffers: [wgpu::VertexBufferLayout<'static>; N],
-            pub constants: std::collections::HashMap<String, f64>,
-        }
+    return Ok(quote!());
+  }
 
-        pub fn vertex_state<'a, const N: usize>(
-            module: &'a wgpu::ShaderModule,
-            entry: &'a VertexEntry<N>,
-        ) -> wgpu::VertexState<'a> {
-            wgpu::VertexState {
-                module,
-                entry_point: entry.entry_point,
-                buffers: &entry.buffers,
-                compilation_options: wgpu::PipelineCompilationOptions {
-                  constants: &entry.constants,
-                  ..Default::default()
-                },
-            }
-        }
+  Ok(quote! {
+    #[derive(Debug)]
+    pub struct VertexEntry<const N: usize> {
+        pub entry_point: &'static str,
+        pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+        pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
+    }
 
-        #(#vertex_entries)*
+    pub fn vertex_state<'a, const N: usize>(
+        module: &'a wgpu::ShaderModule,
+        entry: &'a VertexEntry<N>,
+    ) -> wgpu::VertexState<'a> {
+        wgpu::VertexState {
+            module,
+            entry_point: #entry_point,
+            buffers: &entry.buffers,
+            compilation_options: wgpu::PipelineCompilationOptions {
+              constants: &entry.constants,
+              zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+              ..Default::default()
+            },
+        }
     }
+
+    #(#vertex_entries)*
+  })
+}
+
+/// The vertex buffer of the located parameters of a vertex entry point.
+struct LocatedVertexInputs {
+  /// The position of the buffer among the buffers of the entry point.
+  index: usize,
+  /// The name used to infer the step mode of the buffer.
+  name: String,
+  step_mode_param: TokenStream,
+  layout_expression: TokenStream,
+  /// The attributes and layout function of the buffer.
+  items: TokenStream,
+}
+
+/// Returns a tightly packed vertex buffer for the located parameters of the entry point
+/// that aren't in a struct, if any.
+fn located_vertex_inputs(
+  module: &naga::Module,
+  entry_point: &naga::EntryPoint,
+) -> Result<Option<LocatedVertexInputs>, CreateModuleError> {
+  let inputs = wgsl::get_entry_located_vertex_inputs(module, entry_point)?;
+  if inputs.is_empty() {
+    return Ok(None);
   }
+
+  // Place the buffer after the struct arguments that come before the first parameter.
+  let index = entry_point
+    .function
+    .arguments
+    .iter()
+    .take_while(|a| !matches!(a.binding, Some(naga::Binding::Location { .. })))
+    .filter(|a| a.binding.is_none())
+    .count();
+
+  let mut offset = 0;
+  let attributes: Vec<_> = inputs
+    .iter()
+    .map(|(location, format)| {
+      let location = Index::from(*location as usize);
+      let attribute_offset = Index::from(offset as usize);
+      offset += format.size();
+      let format = Ident::new(&format!("{format:?}"), Span::call_site());
+      quote! {
+          wgpu::VertexAttribute {
+              format: wgpu::VertexFormat::#format,
+              offset: #attribute_offset,
+              shader_location: #location,
+          }
+      }
+    })
+    .collect();
+
+  let count = Index::from(attributes.len());
+  let stride = Index::from(offset as usize);
+  let attributes_name = Ident::new(
+    &format!("{}_VERTEX_ATTRIBUTES", entry_point.name.to_uppercase()),
+    Span::call_site(),
+  );
+  let layout_fn =
+    Ident::new(&format!("{}_vertex_buffer_layout", entry_point.name), Span::call_site());
+  let step_mode =
+    Ident::new(&format!("{}_vertex_input", entry_point.name), Span::call_site());
+
+  Ok(Some(LocatedVertexInputs {
+    index,
+    name: step_mode.to_string(),
+    step_mode_param: quote!(#step_mode: wgpu::VertexStepMode),
+    layout_expression: quote!(#layout_fn(#step_mode)),
+    items: quote! {
+        pub const #attributes_name: [wgpu::VertexAttribute; #count] = [#(#attributes),*];
+
+        /// The tightly packed vertex buffer of the parameters that aren't in a struct.
+        pub const fn #layout_fn(step_mode: wgpu::VertexStepMode) -> wgpu::VertexBufferLayout<'static> {
+            wgpu::VertexBufferLayout {
+                array_stride: #stride,
+                step_mode,
+                attributes: &#attributes_name
+            }
+        }
+    },
+  }))
 }
 
 pub fn vertex_struct_impls(
   invoking_entry_module: &str,
   module: &naga::Module,
-) -> Vec<RustItem> {
-  let structs = vertex_input_structs_impls(invoking_entry_module, module);
+  options: &WgslBindgenOption,
+) -> Result<Vec<RustItem>, CreateModuleError> {
+  let structs = vertex_input_structs_impls(invoking_entry_module, module, options);
   structs
 }
 
 fn vertex_input_structs_impls(
   invoking_entry_module: &str,
   module: &naga::Module,
-) -> Vec<RustItem> {
+  options: &WgslBindgenOption,
+) -> Result<Vec<RustItem>, CreateModuleError> {
   let vertex_inputs = wgsl::get_vertex_input_structs(invoking_entry_module, module);
   vertex_inputs.iter().map(|input|  {
     let name = Ident::new(&input.item_path.name, Span::call_site());
+    let fully_qualified_name = input.item_path.get_fully_qualified_name();
 
     // Use index to avoid adding prefix to literals.
     let count = Index::from(input.fields.len());
-    let attributes: Vec<_> = input
+    let attributes = input
         .fields
         .iter()
         .map(|(location, m)| {
-            let field_name: TokenStream = m.name.as_ref().unwrap().parse().unwrap();
+            let member_name = m.name.as_ref().unwrap();
+            let field_name: TokenStream = member_name.parse().unwrap();
             let location = Index::from(*location as usize);
-            let format = wgsl::vertex_format(&module.types[m.ty]);
+            let ty = &module.types[m.ty];
+            let format = match wgsl::vertex_format_override(options, &fully_qualified_name, member_name) {
+                Some(format) => {
+                    let (_, format_components) = wgsl::vertex_format_rust_type(format);
+                    let attribute_components = wgsl::vertex_attribute_components(ty);
+                    if format_components != attribute_components {
+                        return Err(CreateModuleError::VertexFormatOverrideMismatch {
+                            field: format!("{fully_qualified_name}::{member_name}"),
+                            format: format!("{format:?}"),
+                            format_components,
+                            attribute_components,
+                        });
+                    }
+                    format
+                }
+                None => wgsl::attribute_vertex_format(
+                    module,
+                    ty,
+                    format!("{fully_qualified_name}::{member_name}"),
+                )?,
+            };
             // TODO: Will the debug implementation always work with the macro?
             let format = Ident::new(&format!("{format:?}"), Span::call_site());
 
-            quote! {
+            Ok(quote! {
                 wgpu::VertexAttribute {
                     format: wgpu::VertexFormat::#format,
                     offset: std::mem::offset_of!(Self, #field_name) as u64,
                     shader_location: #location,
                 }
-            }
+            })
         })
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
 
 
     // The vertex_attr_array! macro doesn't account for field alignment.
@@ -198,7 +668,6 @@ fn vertex_input_structs_impls(
     // TODO: Should this enforce WebGPU alignment requirements for compatibility?
     // https://gpuweb.github.io/gpuweb/#abstract-opdef-validating-gpuvertexbufferlayout
 
-    // TODO: Support vertex inputs that aren't in a struct.
     let ts = quote! {
         impl #name {
             pub const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; #count] = [#(#attributes),*];
@@ -213,67 +682,74 @@ fn vertex_input_structs_impls(
         }
     };
 
-    RustItem { types: RustItemType::TypeImpls.into(), path: input.item_path.clone(), item: ts }
+    Ok(RustItem { types: RustItemType::TypeImpls.into(), path: input.item_path.clone(), item: ts })
     }).collect()
 }
 
-pub fn fragment_states(module: &naga::Module) -> TokenStream {
+pub fn fragment_states(
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Result<TokenStream, CreateModuleError> {
   let entries: Vec<TokenStream> = module
     .entry_points
     .iter()
-    .filter_map(|entry_point| match &entry_point.stage {
-      ShaderStage::Fragment => {
-        let fn_name =
-          Ident::new(&format!("{}_entry", &entry_point.name), Span::call_site());
+    .filter(|entry_point| entry_point.stage == ShaderStage::Fragment)
+    .map(|entry_point| {
+      let fn_name =
+        Ident::new(&format!("{}_entry", &entry_point.name), Span::call_site());
 
-        let const_name = Ident::new(
-          &format!("ENTRY_{}", &entry_point.name.to_uppercase()),
-          Span::call_site(),
-        );
+      let const_name = Ident::new(
+        &format!("ENTRY_{}", &entry_point.name.to_uppercase()),
+        Span::call_site(),
+      );
 
-        // Use index to avoid adding prefix to literals.
-        let target_count =
-          Index::from(fragment_target_count(module, &entry_point.function));
+      let outputs = fragment_outputs(module, entry_point)?;
+      let targets_items = fragment_targets_items(entry_point, &outputs);
+      let depth_items =
+        fragment_depth_items(entry_point, fragment_writes_depth(module, entry_point));
 
-        let overrides = if !module.overrides.is_empty() {
-          Some(quote!(overrides: &OverrideConstants))
-        } else {
-          None
-        };
+      // Use index to avoid adding prefix to literals.
+      let target_count = Index::from(fragment_target_count(&outputs));
 
-        let constants = if !module.overrides.is_empty() {
-          quote!(overrides.constants())
-        } else {
-          quote!(Default::default())
-        };
+      let (overrides, constants) = entry_overrides_param(module, entry_point);
+      let zero_init = !options.skip_workgroup_memory_zero_init;
 
-        Some(quote! {
-            pub fn #fn_name(
-                targets: [Option<wgpu::ColorTargetState>; #target_count],
-                #overrides
-            ) -> FragmentEntry<#target_count> {
-                FragmentEntry {
-                    entry_point: #const_name,
-                    targets,
-                    constants: #constants
-                }
-            }
-        })
-      }
-      _ => None,
+      let single_target_fns = (fragment_target_count(&outputs) == 1)
+        .then(|| fragment_single_target_fns(entry_point, &fn_name, overrides.as_ref()));
+
+      Ok(quote! {
+          pub fn #fn_name(
+              targets: [Option<wgpu::ColorTargetState>; #target_count],
+              #overrides
+          ) -> FragmentEntry<#target_count> {
+              FragmentEntry {
+                  entry_point: #const_name,
+                  targets,
+                  constants: #constants,
+                  zero_initialize_workgroup_memory: #zero_init,
+              }
+          }
+
+          #single_target_fns
+          #targets_items
+          #depth_items
+      })
     })
-    .collect();
+    .collect::<Result<_, _>>()?;
+
+  let entry_point = quote_entry_point(options, quote!(entry.entry_point));
 
   // Don't generate unused code.
   if entries.is_empty() {
-    quote!()
+    Ok(quote!())
   } else {
-    quote! {
+    Ok(quote! {
         #[derive(Debug)]
         pub struct FragmentEntry<const N: usize> {
             pub entry_point: &'static str,
             pub targets: [Option<wgpu::ColorTargetState>; N],
             pub constants: std::collections::HashMap<String, f64>,
+            pub zero_initialize_workgroup_memory: bool,
         }
 
         pub fn fragment_state<'a, const N: usize>(
@@ -282,17 +758,280 @@ pub fn fragment_states(module: &naga::Module) -> TokenStream {
         ) -> wgpu::FragmentState<'a> {
             wgpu::FragmentState {
                 module,
-                entry_point: entry.entry_point,
+                entry_point: #entry_point,
                 targets: &entry.targets,
                 compilation_options: wgpu::PipelineCompilationOptions {
                     constants: &entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                     ..Default::default()
                 },
             }
         }
 
         #(#entries)*
+    })
+  }
+}
+
+/// The value of a wgpu `entry_point` field, which is an `Option` since wgpu 23.
+pub(super) fn quote_entry_point(
+  options: &WgslBindgenOption,
+  entry_point: TokenStream,
+) -> TokenStream {
+  match options.wgpu_version {
+    WgpuVersion::V22 => entry_point,
+    WgpuVersion::V23 => quote!(Some(#entry_point)),
+  }
+}
+
+/// Generates the non-generic `VertexEntryDyn` and `FragmentEntryDyn` for the entry structs
+/// the module has.
+pub(crate) fn dyn_entry_items(
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> TokenStream {
+  let has_stage =
+    |stage: ShaderStage| module.entry_points.iter().any(|e| e.stage == stage);
+  let entry_point = quote_entry_point(options, quote!(entry.entry_point));
+
+  let vertex = has_stage(ShaderStage::Vertex).then(|| {
+    quote! {
+        #[derive(Debug)]
+        pub struct VertexEntryDyn {
+            pub entry_point: &'static str,
+            pub buffers: Vec<wgpu::VertexBufferLayout<'static>>,
+            pub constants: std::collections::HashMap<String, f64>,
+            pub zero_initialize_workgroup_memory: bool,
+        }
+
+        impl<const N: usize> From<VertexEntry<N>> for VertexEntryDyn {
+            fn from(entry: VertexEntry<N>) -> Self {
+                Self {
+                    entry_point: entry.entry_point,
+                    buffers: entry.buffers.into(),
+                    constants: entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                }
+            }
+        }
+
+        pub fn vertex_state_dyn<'a>(
+            module: &'a wgpu::ShaderModule,
+            entry: &'a VertexEntryDyn,
+        ) -> wgpu::VertexState<'a> {
+            wgpu::VertexState {
+                module,
+                entry_point: #entry_point,
+                buffers: &entry.buffers,
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                    ..Default::default()
+                },
+            }
+        }
+    }
+  });
+
+  let fragment = has_stage(ShaderStage::Fragment).then(|| {
+    quote! {
+        #[derive(Debug)]
+        pub struct FragmentEntryDyn {
+            pub entry_point: &'static str,
+            pub targets: Vec<Option<wgpu::ColorTargetState>>,
+            pub constants: std::collections::HashMap<String, f64>,
+            pub zero_initialize_workgroup_memory: bool,
+        }
+
+        impl<const N: usize> From<FragmentEntry<N>> for FragmentEntryDyn {
+            fn from(entry: FragmentEntry<N>) -> Self {
+                Self {
+                    entry_point: entry.entry_point,
+                    targets: entry.targets.into(),
+                    constants: entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                }
+            }
+        }
+
+        pub fn fragment_state_dyn<'a>(
+            module: &'a wgpu::ShaderModule,
+            entry: &'a FragmentEntryDyn,
+        ) -> wgpu::FragmentState<'a> {
+            wgpu::FragmentState {
+                module,
+                entry_point: #entry_point,
+                targets: &entry.targets,
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                    ..Default::default()
+                },
+            }
+        }
     }
+  });
+
+  quote! {
+      #vertex
+      #fragment
+  }
+}
+
+/// Returns the `overrides` parameter for the functions of the entry point, typed as the
+/// entry's own overrides struct, and the expression for its pipeline constants.
+fn entry_overrides_param(
+  module: &naga::Module,
+  entry_point: &naga::EntryPoint,
+) -> (Option<TokenStream>, TokenStream) {
+  if wgsl::entry_overrides(module, entry_point).is_empty() {
+    (None, quote!(Default::default()))
+  } else {
+    let name = entry_overrides_name(entry_point);
+    (Some(quote!(overrides: &#name)), quote!(overrides.constants()))
+  }
+}
+
+pub(crate) fn compute_states(
+  labels: &EntryLabels,
+  options: &WgslBindgenOption,
+  module: &naga::Module,
+) -> TokenStream {
+  let entries: Vec<TokenStream> = module
+    .entry_points
+    .iter()
+    .filter(|entry_point| entry_point.stage == ShaderStage::Compute)
+    .map(|entry_point| {
+      let fn_name =
+        Ident::new(&format!("create_{}_pipeline", &entry_point.name), Span::call_site());
+      let with_zero_init_fn_name = Ident::new(
+        &format!("create_{}_pipeline_with_zero_init", &entry_point.name),
+        Span::call_site(),
+      );
+
+      let const_name = Ident::new(
+        &format!("ENTRY_{}", &entry_point.name.to_uppercase()),
+        Span::call_site(),
+      );
+
+      let (overrides, constants) = entry_overrides_param(module, entry_point);
+      let overrides = overrides.map(|param| quote!(, #param));
+
+      let label = labels.compute_pipeline(options, &entry_point.name);
+      let entry_point = quote_entry_point(options, quote!(#const_name));
+
+      let zero_init = !options.skip_workgroup_memory_zero_init;
+      let override_arg = overrides.as_ref().map(|_| quote!(, overrides));
+      let doc = format!(
+        " Calls [`{with_zero_init_fn_name}`] with `zero_initialize_workgroup_memory` set to \
+         `{zero_init}`."
+      );
+
+      quote! {
+          #[doc = #doc]
+          pub fn #fn_name(
+              device: &wgpu::Device,
+              module: &wgpu::ShaderModule
+              #overrides
+          ) -> wgpu::ComputePipeline {
+              #with_zero_init_fn_name(device, module #override_arg, #zero_init)
+          }
+
+          pub fn #with_zero_init_fn_name(
+              device: &wgpu::Device,
+              module: &wgpu::ShaderModule
+              #overrides,
+              zero_initialize_workgroup_memory: bool,
+          ) -> wgpu::ComputePipeline {
+              let layout = create_pipeline_layout(device);
+              let constants: std::collections::HashMap<String, f64> = #constants;
+              device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                  label: #label,
+                  layout: Some(&layout),
+                  module,
+                  entry_point: #entry_point,
+                  compilation_options: wgpu::PipelineCompilationOptions {
+                      constants: &constants,
+                      zero_initialize_workgroup_memory,
+                      ..Default::default()
+                  },
+                  cache: None,
+              })
+          }
+      }
+    })
+    .collect();
+
+  quote! {
+      #(#entries)*
+  }
+}
+
+/// Generates `CreatePipelineArgs`, `render_pipeline_descriptor` and a `create_pipeline`
+/// function per shader source type for modules with both vertex and fragment entries.
+pub(crate) fn render_pipeline_items(
+  labels: &EntryLabels,
+  options: &WgslBindgenOption,
+  module: &naga::Module,
+) -> TokenStream {
+  let has_stage =
+    |stage: ShaderStage| module.entry_points.iter().any(|e| e.stage == stage);
+  if !has_stage(ShaderStage::Vertex) || !has_stage(ShaderStage::Fragment) {
+    return quote!();
+  }
+
+  let label = labels.render_pipeline(options);
+
+  let create_pipeline_fns = options.shader_source_type.iter().map(|source_type| {
+    let fn_name = Ident::new(source_type.create_render_pipeline_fn_name(), Span::call_site());
+    let create_shader_module =
+      Ident::new(source_type.create_shader_module_fn_name(), Span::call_site());
+    let unwrap_result = source_type.unwrap_result();
+    let (param_defs, params) = source_type.shader_module_params_defs_and_params();
+
+    quote! {
+        pub fn #fn_name<const N: usize, const M: usize>(
+            #param_defs,
+            args: CreatePipelineArgs<N, M>,
+        ) -> wgpu::RenderPipeline {
+            let module = #create_shader_module(#params) #unwrap_result;
+            let layout = create_pipeline_layout(device);
+            device.create_render_pipeline(&render_pipeline_descriptor(&layout, &module, &args))
+        }
+    }
+  });
+
+  quote! {
+      /// The entries and states of a render pipeline. The optional states default to a
+      /// triangle list without culling, no depth stencil and no multisampling.
+      #[derive(Debug)]
+      pub struct CreatePipelineArgs<const N: usize, const M: usize> {
+          pub vertex: VertexEntry<N>,
+          pub fragment: FragmentEntry<M>,
+          pub primitive: Option<wgpu::PrimitiveState>,
+          pub depth_stencil: Option<wgpu::DepthStencilState>,
+          pub multisample: Option<wgpu::MultisampleState>,
+      }
+
+      pub fn render_pipeline_descriptor<'a, const N: usize, const M: usize>(
+          layout: &'a wgpu::PipelineLayout,
+          module: &'a wgpu::ShaderModule,
+          args: &'a CreatePipelineArgs<N, M>,
+      ) -> wgpu::RenderPipelineDescriptor<'a> {
+          wgpu::RenderPipelineDescriptor {
+              label: #label,
+              layout: Some(layout),
+              vertex: vertex_state(module, &args.vertex),
+              primitive: args.primitive.unwrap_or_default(),
+              depth_stencil: args.depth_stencil.clone(),
+              multisample: args.multisample.unwrap_or_default(),
+              fragment: Some(fragment_state(module, &args.fragment)),
+              multiview: None,
+              cache: None,
+          }
+      }
+
+      #(#create_pipeline_fns)*
   }
 }
 
@@ -311,7 +1050,8 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
+      .unwrap()
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -334,7 +1074,8 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
+      .unwrap()
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -394,7 +1135,8 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
+      .unwrap()
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -455,7 +1197,8 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
+      .unwrap()
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -515,7 +1258,8 @@ mod test {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_struct_impls("test", &module)
+    let actual = vertex_struct_impls("test", &module, &WgslBindgenOption::default())
+      .unwrap()
       .into_iter()
       .map(|it| it.item)
       .collect::<TokenStream>();
@@ -586,6 +1330,46 @@ mod test {
           pub const ENTRY_ANOTHER_VS: &str = "another_vs";
           pub const ENTRY_FS_MAIN: &str = "fs_main";
           pub const ENTRY_ANOTHER_FS: &str = "another_fs";
+          #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+          pub enum EntryPoint {
+              VsMain,
+              AnotherVs,
+              FsMain,
+              AnotherFs
+          }
+          impl EntryPoint {
+              pub const ALL: &'static [Self] = &[
+                  Self::VsMain,
+                  Self::AnotherVs,
+                  Self::FsMain,
+                  Self::AnotherFs
+              ];
+              pub fn name(self) -> &'static str {
+                  match self {
+                      Self::VsMain => ENTRY_VS_MAIN,
+                      Self::AnotherVs => ENTRY_ANOTHER_VS,
+                      Self::FsMain => ENTRY_FS_MAIN,
+                      Self::AnotherFs => ENTRY_ANOTHER_FS
+                  }
+              }
+              pub fn stage(self) -> wgpu::ShaderStages {
+                  match self {
+                      Self::VsMain => wgpu::ShaderStages::VERTEX,
+                      Self::AnotherVs => wgpu::ShaderStages::VERTEX,
+                      Self::FsMain => wgpu::ShaderStages::FRAGMENT,
+                      Self::AnotherFs => wgpu::ShaderStages::FRAGMENT
+                  }
+              }
+              /// The workgroup size of compute entry points.
+              pub fn workgroup_size(self) -> Option<[u32; 3]> {
+                  match self {
+                      Self::VsMain => None,
+                      Self::AnotherVs => None,
+                      Self::FsMain => None,
+                      Self::AnotherFs => None
+                  }
+              }
+          }
       },
       actual
     )
@@ -600,7 +1384,7 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_states("test", &module);
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default()).unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -609,6 +1393,7 @@ mod test {
               pub entry_point: &'static str,
               pub buffers: [wgpu::VertexBufferLayout<'static>; N],
               pub constants: std::collections::HashMap<String, f64>,
+              pub zero_initialize_workgroup_memory: bool,
           }
           pub fn vertex_state<'a, const N: usize>(
               module: &'a wgpu::ShaderModule,
@@ -620,6 +1405,7 @@ mod test {
                   buffers: &entry.buffers,
                   compilation_options: wgpu::PipelineCompilationOptions {
                     constants: &entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                     ..Default::default()
                   },
               }
@@ -629,6 +1415,7 @@ mod test {
                   entry_point: ENTRY_VS_MAIN,
                   buffers: [],
                   constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
               }
           }
       },
@@ -651,7 +1438,7 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_states("test", &module);
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default()).unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -660,6 +1447,7 @@ mod test {
               pub entry_point: &'static str,
               pub buffers: [wgpu::VertexBufferLayout<'static>; N],
               pub constants: std::collections::HashMap<String, f64>,
+              pub zero_initialize_workgroup_memory: bool,
           }
           pub fn vertex_state<'a, const N: usize>(
               module: &'a wgpu::ShaderModule,
@@ -671,6 +1459,7 @@ mod test {
                   buffers: &entry.buffers,
                   compilation_options: wgpu::PipelineCompilationOptions {
                     constants: &entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                     ..Default::default()
                   },
               }
@@ -679,21 +1468,211 @@ mod test {
               VertexEntry {
                   entry_point: ENTRY_VS_MAIN_1,
                   buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
-                  constants: Default::default()
+                  constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
               }
           }
+          #[doc = " Calls [`vs_main_1_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:"]
+          #[doc = " - `VertexInput`: `Vertex`"]
+          pub fn vs_main_1_entry_default() -> VertexEntry<1> {
+              vs_main_1_entry(wgpu::VertexStepMode::Vertex, )
+          }
           pub fn vs_main_2_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
               VertexEntry {
                   entry_point: ENTRY_VS_MAIN_2,
                   buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
-                  constants: Default::default()
+                  constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
               }
           }
+          #[doc = " Calls [`vs_main_2_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:"]
+          #[doc = " - `VertexInput`: `Vertex`"]
+          pub fn vs_main_2_entry_default() -> VertexEntry<1> {
+              vs_main_2_entry(wgpu::VertexStepMode::Vertex, )
+          }
       },
       actual
     )
   }
 
+  #[test]
+  fn write_vertex_shader_entries_with_different_inputs() {
+    let source = indoc! {r#"
+            struct VertexInput {
+                @location(0) position: vec4<f32>,
+            };
+            struct InstanceInput {
+                @location(1) offset: vec4<f32>,
+            };
+            @vertex
+            fn vs_main(in: VertexInput, instance: InstanceInput) {}
+
+            @vertex
+            fn vs_shadow(in: VertexInput) {}
+        "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default()).unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          #[derive(Debug)]
+          pub struct VertexEntry<const N: usize> {
+              pub entry_point: &'static str,
+              pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+              pub constants: std::collections::HashMap<String, f64>,
+              pub zero_initialize_workgroup_memory: bool,
+          }
+          pub fn vertex_state<'a, const N: usize>(
+              module: &'a wgpu::ShaderModule,
+              entry: &'a VertexEntry<N>,
+          ) -> wgpu::VertexState<'a> {
+              wgpu::VertexState {
+                  module,
+                  entry_point: entry.entry_point,
+                  buffers: &entry.buffers,
+                  compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                    ..Default::default()
+                  },
+              }
+          }
+          pub fn vs_main_entry(
+              vertex_input: wgpu::VertexStepMode,
+              instance_input: wgpu::VertexStepMode
+          ) -> VertexEntry<2> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      VertexInput::vertex_buffer_layout(vertex_input),
+                      InstanceInput::vertex_buffer_layout(instance_input)
+                  ],
+                  constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
+              }
+          }
+          #[doc = " Calls [`vs_main_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:"]
+          #[doc = " - `VertexInput`: `Vertex`"]
+          #[doc = " - `InstanceInput`: `Instance`"]
+          pub fn vs_main_entry_default() -> VertexEntry<2> {
+              vs_main_entry(wgpu::VertexStepMode::Vertex, wgpu::VertexStepMode::Instance, )
+          }
+          pub fn vs_shadow_entry(vertex_input: wgpu::VertexStepMode) -> VertexEntry<1> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_SHADOW,
+                  buffers: [VertexInput::vertex_buffer_layout(vertex_input)],
+                  constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
+              }
+          }
+          #[doc = " Calls [`vs_shadow_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:"]
+          #[doc = " - `VertexInput`: `Vertex`"]
+          pub fn vs_shadow_entry_default() -> VertexEntry<1> {
+              vs_shadow_entry(wgpu::VertexStepMode::Vertex, )
+          }
+      },
+      actual
+    );
+
+    // The shared struct gets a single impl.
+    let impls =
+      vertex_struct_impls("test", &module, &WgslBindgenOption::default()).unwrap();
+    let names: Vec<_> = impls.iter().map(|item| item.path.name.as_str()).collect();
+    assert_eq!(names, ["VertexInput", "InstanceInput"]);
+  }
+
+  #[test]
+  fn write_vertex_shader_entry_located_parameters() {
+    let source = indoc! {r#"
+            struct InstanceInput {
+                @location(2) offset: vec4<f32>,
+            };
+            @vertex
+            fn vs_main(
+                @builtin(vertex_index) index: u32,
+                @location(0) position: vec3<f32>,
+                @location(1) uv: vec2<f32>,
+                instance: InstanceInput,
+            ) {}
+        "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default()).unwrap();
+
+    assert_tokens_eq!(
+      quote! {
+          #[derive(Debug)]
+          pub struct VertexEntry<const N: usize> {
+              pub entry_point: &'static str,
+              pub buffers: [wgpu::VertexBufferLayout<'static>; N],
+              pub constants: std::collections::HashMap<String, f64>,
+              pub zero_initialize_workgroup_memory: bool,
+          }
+          pub fn vertex_state<'a, const N: usize>(
+              module: &'a wgpu::ShaderModule,
+              entry: &'a VertexEntry<N>,
+          ) -> wgpu::VertexState<'a> {
+              wgpu::VertexState {
+                  module,
+                  entry_point: entry.entry_point,
+                  buffers: &entry.buffers,
+                  compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
+                    ..Default::default()
+                  },
+              }
+          }
+          pub const VS_MAIN_VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 2] = [
+              wgpu::VertexAttribute {
+                  format: wgpu::VertexFormat::Float32x3,
+                  offset: 0,
+                  shader_location: 0,
+              },
+              wgpu::VertexAttribute {
+                  format: wgpu::VertexFormat::Float32x2,
+                  offset: 12,
+                  shader_location: 1,
+              }
+          ];
+          /// The tightly packed vertex buffer of the parameters that aren't in a struct.
+          pub const fn vs_main_vertex_buffer_layout(
+              step_mode: wgpu::VertexStepMode
+          ) -> wgpu::VertexBufferLayout<'static> {
+              wgpu::VertexBufferLayout {
+                  array_stride: 20,
+                  step_mode,
+                  attributes: &VS_MAIN_VERTEX_ATTRIBUTES
+              }
+          }
+          pub fn vs_main_entry(
+              vs_main_vertex_input: wgpu::VertexStepMode,
+              instance_input: wgpu::VertexStepMode
+          ) -> VertexEntry<2> {
+              VertexEntry {
+                  entry_point: ENTRY_VS_MAIN,
+                  buffers: [
+                      vs_main_vertex_buffer_layout(vs_main_vertex_input),
+                      InstanceInput::vertex_buffer_layout(instance_input)
+                  ],
+                  constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
+              }
+          }
+          #[doc = " Calls [`vs_main_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:"]
+          #[doc = " - `vs_main_vertex_input`: `Vertex`"]
+          #[doc = " - `InstanceInput`: `Instance`"]
+          pub fn vs_main_entry_default() -> VertexEntry<2> {
+              vs_main_entry(wgpu::VertexStepMode::Vertex, wgpu::VertexStepMode::Instance, )
+          }
+      },
+      actual
+    );
+  }
+
   #[test]
   fn write_vertex_shader_entry_multiple_buffers() {
     let source = indoc! {r#"
@@ -705,12 +1684,12 @@ mod test {
                 @location(1) some_data: vec2<f32>
             }
             @vertex
-            fn vs_main(in0: Input0, in1: Input1) {}
+            fn vs_main(in0: Input0, in1: Input1) { let x = tests; }
         "#
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_states("test", &module);
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default()).unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -718,7 +1697,8 @@ mod test {
           pub struct VertexEntry<const N: usize> {
               pub entry_point: &'static str,
               pub buffers: [wgpu::VertexBufferLayout<'static>; N],
-              pub constants: std::collections::HashMap<String, f64>
+              pub constants: std::collections::HashMap<String, f64>,
+              pub zero_initialize_workgroup_memory: bool,
           }
           pub fn vertex_state<'a, const N: usize>(
               module: &'a wgpu::ShaderModule,
@@ -730,6 +1710,7 @@ mod test {
                   buffers: &entry.buffers,
                   compilation_options: wgpu::PipelineCompilationOptions {
                     constants: &entry.constants,
+                    zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                     ..Default::default()
                   },
               }
@@ -737,7 +1718,7 @@ mod test {
           pub fn vs_main_entry(
             input0: wgpu::VertexStepMode,
             input1: wgpu::VertexStepMode,
-            overrides: &OverrideConstants
+            overrides: &VsMainOverrides
           ) -> VertexEntry<2> {
               VertexEntry {
                   entry_point: ENTRY_VS_MAIN,
@@ -746,8 +1727,15 @@ mod test {
                       Input1::vertex_buffer_layout(input1),
                   ],
                   constants: overrides.constants(),
+                  zero_initialize_workgroup_memory: true,
               }
           }
+          #[doc = " Calls [`vs_main_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:"]
+          #[doc = " - `Input0`: `Vertex`"]
+          #[doc = " - `Input1`: `Vertex`"]
+          pub fn vs_main_entry_default(overrides: &VsMainOverrides) -> VertexEntry<2> {
+              vs_main_entry(wgpu::VertexStepMode::Vertex, wgpu::VertexStepMode::Vertex, overrides)
+          }
       },
       actual
     )
@@ -765,7 +1753,7 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = vertex_states("test", &module);
+    let actual = vertex_states("test", &module, &WgslBindgenOption::default()).unwrap();
 
     assert_tokens_eq!(quote!(), actual)
   }
@@ -790,7 +1778,7 @@ mod test {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = fragment_states(&module);
+    let actual = fragment_states(&module, &WgslBindgenOption::default()).unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -799,6 +1787,7 @@ mod test {
               pub entry_point: &'static str,
               pub targets: [Option<wgpu::ColorTargetState>; N],
               pub constants: std::collections::HashMap<String, f64>,
+              pub zero_initialize_workgroup_memory: bool,
           }
           pub fn fragment_state<'a, const N: usize>(
               module: &'a wgpu::ShaderModule,
@@ -810,6 +1799,7 @@ mod test {
                   targets: &entry.targets,
                   compilation_options: wgpu::PipelineCompilationOptions {
                       constants: &entry.constants,
+                      zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                       ..Default::default()
                   },
               }
@@ -821,6 +1811,47 @@ mod test {
                   entry_point: ENTRY_FS_MULTIPLE,
                   targets,
                   constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
+              }
+          }
+          /// The output locations of the entry point in ascending order, with their scalar kinds:
+          #[doc = " - `0`: float"]
+          #[doc = " - `1`: float"]
+          pub const FS_MULTIPLE_OUTPUT_LOCATIONS: [u32; 2] = [0, 1];
+          /// Returns the color targets of the entry point with the same format for every output,
+          /// without blending and writing all channels.
+          pub fn fs_multiple_targets(format: wgpu::TextureFormat) -> [Option<wgpu::ColorTargetState>; 2] {
+              fs_multiple_targets_with_formats([format; 2])
+          }
+          /// Returns the color targets of the entry point with one format per output, in the order
+          /// of the output locations, without blending and writing all channels.
+          pub fn fs_multiple_targets_with_formats(
+              formats: [wgpu::TextureFormat; 2],
+          ) -> [Option<wgpu::ColorTargetState>; 2] {
+              [Some(wgpu::ColorTargetState {
+                  format: formats[0],
+                  blend: None,
+                  write_mask: wgpu::ColorWrites::ALL,
+              }), Some(wgpu::ColorTargetState {
+                  format: formats[1],
+                  blend: None,
+                  write_mask: wgpu::ColorWrites::ALL,
+              })]
+          }
+          /// Whether the entry point writes `@builtin(frag_depth)`.
+          pub const FS_MULTIPLE_WRITES_DEPTH: bool = true;
+          /// A depth stencil state with depth writes enabled, which the entry point needs for
+          /// its `frag_depth` output to have an effect.
+          pub fn fs_multiple_depth_stencil_state(
+              format: wgpu::TextureFormat,
+              compare: wgpu::CompareFunction,
+          ) -> wgpu::DepthStencilState {
+              wgpu::DepthStencilState {
+                  format,
+                  depth_write_enabled: true,
+                  depth_compare: compare,
+                  stencil: wgpu::StencilState::default(),
+                  bias: wgpu::DepthBiasState::default(),
               }
           }
           pub fn fs_single_entry(
@@ -830,8 +1861,55 @@ mod test {
                   entry_point: ENTRY_FS_SINGLE,
                   targets,
                   constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
               }
           }
+          #[doc = " Calls [`fs_single_entry`] with a single color target of `format`, without blending and writing all channels."]
+          pub fn fs_single_entry_single(format: wgpu::TextureFormat) -> FragmentEntry<1> {
+              fs_single_entry(
+                  [Some(wgpu::ColorTargetState {
+                      format,
+                      blend: None,
+                      write_mask: wgpu::ColorWrites::ALL,
+                  })],
+                  
+              )
+          }
+          #[doc = " Calls [`fs_single_entry`] with a single color target of `format` blended with `blend`, writing all channels."]
+          pub fn fs_single_entry_single_blend(
+              format: wgpu::TextureFormat,
+              blend: wgpu::BlendState,
+          ) -> FragmentEntry<1> {
+              fs_single_entry(
+                  [Some(wgpu::ColorTargetState {
+                      format,
+                      blend: Some(blend),
+                      write_mask: wgpu::ColorWrites::ALL,
+                  })],
+                  
+              )
+          }
+          /// The output locations of the entry point in ascending order, with their scalar kinds:
+          #[doc = " - `0`: float"]
+          pub const FS_SINGLE_OUTPUT_LOCATIONS: [u32; 1] = [0];
+          /// Returns the color targets of the entry point with the same format for every output,
+          /// without blending and writing all channels.
+          pub fn fs_single_targets(format: wgpu::TextureFormat) -> [Option<wgpu::ColorTargetState>; 1] {
+              fs_single_targets_with_formats([format; 1])
+          }
+          /// Returns the color targets of the entry point with one format per output, in the order
+          /// of the output locations, without blending and writing all channels.
+          pub fn fs_single_targets_with_formats(
+              formats: [wgpu::TextureFormat; 1],
+          ) -> [Option<wgpu::ColorTargetState>; 1] {
+              [Some(wgpu::ColorTargetState {
+                  format: formats[0],
+                  blend: None,
+                  write_mask: wgpu::ColorWrites::ALL,
+              })]
+          }
+          /// Whether the entry point writes `@builtin(frag_depth)`.
+          pub const FS_SINGLE_WRITES_DEPTH: bool = false;
           pub fn fs_single_builtin_entry(
               targets: [Option<wgpu::ColorTargetState>; 0]
           ) -> FragmentEntry<0> {
@@ -839,6 +1917,23 @@ mod test {
                   entry_point: ENTRY_FS_SINGLE_BUILTIN,
                   targets,
                   constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
+              }
+          }
+          /// Whether the entry point writes `@builtin(frag_depth)`.
+          pub const FS_SINGLE_BUILTIN_WRITES_DEPTH: bool = true;
+          /// A depth stencil state with depth writes enabled, which the entry point needs for
+          /// its `frag_depth` output to have an effect.
+          pub fn fs_single_builtin_depth_stencil_state(
+              format: wgpu::TextureFormat,
+              compare: wgpu::CompareFunction,
+          ) -> wgpu::DepthStencilState {
+              wgpu::DepthStencilState {
+                  format,
+                  depth_write_enabled: true,
+                  depth_compare: compare,
+                  stencil: wgpu::StencilState::default(),
+                  bias: wgpu::DepthBiasState::default(),
               }
           }
           pub fn fs_empty_entry(
@@ -848,24 +1943,100 @@ mod test {
                   entry_point: ENTRY_FS_EMPTY,
                   targets,
                   constants: Default::default(),
+                  zero_initialize_workgroup_memory: true,
               }
           }
+          /// Whether the entry point writes `@builtin(frag_depth)`.
+          pub const FS_EMPTY_WRITES_DEPTH: bool = false;
       },
       actual
     )
   }
 
+  #[test]
+  fn fragment_states_duplicate_output_location() {
+    let source = indoc! {r#"
+          struct Output {
+              @location(0) col0: vec4<f32>,
+              @location(0) col1: vec4<f32>,
+          };
+          @fragment
+          fn fs_main() -> Output {}
+      "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    assert!(matches!(
+      fragment_states(&module, &WgslBindgenOption::default()),
+      Err(CreateModuleError::DuplicateFragmentOutputLocation { location: 0, .. })
+    ));
+  }
+
+  #[test]
+  fn write_fragment_states_output_gap() {
+    let source = indoc! {r#"
+          struct Output {
+              @location(2) id: vec4<u32>,
+              @location(0) color: vec4<f32>,
+          };
+          @fragment
+          fn fs_main() -> Output {}
+      "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let actual = fragment_states(&module, &WgslBindgenOption::default())
+      .unwrap()
+      .to_string();
+
+    assert!(actual.contains("[Option < wgpu :: ColorTargetState > ; 3]"));
+    assert!(actual.contains("FS_MAIN_OUTPUT_LOCATIONS : [u32 ; 2] = [0 , 2]"));
+    assert!(actual.contains("formats [0]"));
+    assert!(actual.contains("None , Some"));
+    assert!(actual.contains("\" - `2`: uint\""));
+  }
+
+  #[test]
+  fn write_vertex_states_instance_step_mode_regex() {
+    let source = indoc! {r#"
+          struct VertexInput { @location(0) position: vec4<f32> }
+          struct PerSprite { @location(1) offset: vec4<f32> }
+
+          @vertex
+          fn vs_main(in: VertexInput, sprite: PerSprite) -> @builtin(position) vec4<f32> {
+            return in.position + sprite.offset;
+          }
+      "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let options = WgslBindgenOption {
+      instance_step_mode_regex: Some(Regex::new("^Per").unwrap()),
+      ..Default::default()
+    };
+    let actual = vertex_states("test", &module, &options).unwrap().to_string();
+
+    assert!(actual.contains("pub fn vs_main_entry_default () -> VertexEntry < 2 >"));
+    assert!(actual.contains("\" - `VertexInput`: `Vertex`\""));
+    assert!(actual.contains("\" - `PerSprite`: `Instance`\""));
+    assert!(actual.contains("match `^Per`"));
+    assert!(actual.contains(
+      "vs_main_entry (wgpu :: VertexStepMode :: Vertex , \
+       wgpu :: VertexStepMode :: Instance ,)"
+    ));
+  }
+
   #[test]
   fn write_fragment_states_single_entry() {
     let source = indoc! {r#"
           override test: bool = true;
           @fragment
-          fn fs_single() -> @location(0) vec4<f32> {}
+          fn fs_single() -> @location(0) vec4<f32> { let x = test; }
       "#
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = fragment_states(&module);
+    let actual = fragment_states(&module, &WgslBindgenOption::default()).unwrap();
 
     assert_tokens_eq!(
       quote! {
@@ -874,6 +2045,7 @@ mod test {
               pub entry_point: &'static str,
               pub targets: [Option<wgpu::ColorTargetState>; N],
               pub constants: std::collections::HashMap<String, f64>,
+              pub zero_initialize_workgroup_memory: bool,
           }
           pub fn fragment_state<'a, const N: usize>(
               module: &'a wgpu::ShaderModule,
@@ -885,22 +2057,210 @@ mod test {
                   targets: &entry.targets,
                   compilation_options: wgpu::PipelineCompilationOptions {
                       constants: &entry.constants,
+                      zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                       ..Default::default()
                   },
               }
           }
           pub fn fs_single_entry(
               targets: [Option<wgpu::ColorTargetState>; 1],
-              overrides: &OverrideConstants
+              overrides: &FsSingleOverrides
           ) -> FragmentEntry<1> {
               FragmentEntry {
                   entry_point: ENTRY_FS_SINGLE,
                   targets,
                   constants: overrides.constants(),
+                  zero_initialize_workgroup_memory: true,
               }
           }
+          #[doc = " Calls [`fs_single_entry`] with a single color target of `format`, without blending and writing all channels."]
+          pub fn fs_single_entry_single(format: wgpu::TextureFormat, overrides: &FsSingleOverrides) -> FragmentEntry<1> {
+              fs_single_entry(
+                  [Some(wgpu::ColorTargetState {
+                      format,
+                      blend: None,
+                      write_mask: wgpu::ColorWrites::ALL,
+                  })],
+                  overrides
+              )
+          }
+          #[doc = " Calls [`fs_single_entry`] with a single color target of `format` blended with `blend`, writing all channels."]
+          pub fn fs_single_entry_single_blend(
+              format: wgpu::TextureFormat,
+              blend: wgpu::BlendState,
+              overrides: &FsSingleOverrides
+          ) -> FragmentEntry<1> {
+              fs_single_entry(
+                  [Some(wgpu::ColorTargetState {
+                      format,
+                      blend: Some(blend),
+                      write_mask: wgpu::ColorWrites::ALL,
+                  })],
+                  overrides
+              )
+          }
+          /// The output locations of the entry point in ascending order, with their scalar kinds:
+          #[doc = " - `0`: float"]
+          pub const FS_SINGLE_OUTPUT_LOCATIONS: [u32; 1] = [0];
+          /// Returns the color targets of the entry point with the same format for every output,
+          /// without blending and writing all channels.
+          pub fn fs_single_targets(format: wgpu::TextureFormat) -> [Option<wgpu::ColorTargetState>; 1] {
+              fs_single_targets_with_formats([format; 1])
+          }
+          /// Returns the color targets of the entry point with one format per output, in the order
+          /// of the output locations, without blending and writing all channels.
+          pub fn fs_single_targets_with_formats(
+              formats: [wgpu::TextureFormat; 1],
+          ) -> [Option<wgpu::ColorTargetState>; 1] {
+              [Some(wgpu::ColorTargetState {
+                  format: formats[0],
+                  blend: None,
+                  write_mask: wgpu::ColorWrites::ALL,
+              })]
+          }
+          /// Whether the entry point writes `@builtin(frag_depth)`.
+          pub const FS_SINGLE_WRITES_DEPTH: bool = false;
       },
       actual
     )
   }
+
+  #[test]
+  fn write_compute_states_multiple_entries() {
+    let source = indoc! {r#"
+          override scale: f32;
+          @compute
+          @workgroup_size(64)
+          fn cs_first() { let x = scale; }
+          @compute
+          @workgroup_size(64)
+          fn cs_second() {}
+          @fragment
+          fn fs_main() {}
+      "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let labels = EntryLabels {
+      module: "Test",
+      path: "test.wgsl",
+    };
+    let actual = compute_states(&labels, &WgslBindgenOption::default(), &module);
+
+    assert_tokens_eq!(
+      quote! {
+          #[doc = " Calls [`create_cs_first_pipeline_with_zero_init`] with `zero_initialize_workgroup_memory` set to `true`."]
+          pub fn create_cs_first_pipeline(
+              device: &wgpu::Device,
+              module: &wgpu::ShaderModule,
+              overrides: &CsFirstOverrides
+          ) -> wgpu::ComputePipeline {
+              create_cs_first_pipeline_with_zero_init(device, module, overrides, true)
+          }
+          pub fn create_cs_first_pipeline_with_zero_init(
+              device: &wgpu::Device,
+              module: &wgpu::ShaderModule,
+              overrides: &CsFirstOverrides,
+              zero_initialize_workgroup_memory: bool,
+          ) -> wgpu::ComputePipeline {
+              let layout = create_pipeline_layout(device);
+              let constants: std::collections::HashMap<String, f64> = overrides.constants();
+              device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                  label: Some("Test::cs_first::ComputePipeline"),
+                  layout: Some(&layout),
+                  module,
+                  entry_point: ENTRY_CS_FIRST,
+                  compilation_options: wgpu::PipelineCompilationOptions {
+                      constants: &constants,
+                      zero_initialize_workgroup_memory,
+                      ..Default::default()
+                  },
+                  cache: None,
+              })
+          }
+          #[doc = " Calls [`create_cs_second_pipeline_with_zero_init`] with `zero_initialize_workgroup_memory` set to `true`."]
+          pub fn create_cs_second_pipeline(
+              device: &wgpu::Device,
+              module: &wgpu::ShaderModule
+          ) -> wgpu::ComputePipeline {
+              create_cs_second_pipeline_with_zero_init(device, module, true)
+          }
+          pub fn create_cs_second_pipeline_with_zero_init(
+              device: &wgpu::Device,
+              module: &wgpu::ShaderModule,
+              zero_initialize_workgroup_memory: bool,
+          ) -> wgpu::ComputePipeline {
+              let layout = create_pipeline_layout(device);
+              let constants: std::collections::HashMap<String, f64> = Default::default();
+              device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                  label: Some("Test::cs_second::ComputePipeline"),
+                  layout: Some(&layout),
+                  module,
+                  entry_point: ENTRY_CS_SECOND,
+                  compilation_options: wgpu::PipelineCompilationOptions {
+                      constants: &constants,
+                      zero_initialize_workgroup_memory,
+                      ..Default::default()
+                  },
+                  cache: None,
+              })
+          }
+      },
+      actual
+    )
+  }
+
+  #[test]
+  fn write_render_pipeline_items() {
+    let source = indoc! {r#"
+          struct VertexInput { @location(0) position: vec4<f32> }
+
+          @vertex
+          fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+            return in.position;
+          }
+
+          @fragment
+          fn fs_main() -> @location(0) vec4<f32> {
+            return vec4<f32>(1.0);
+          }
+      "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let labels = EntryLabels {
+      module: "Test",
+      path: "test.wgsl",
+    };
+    let actual =
+      render_pipeline_items(&labels, &WgslBindgenOption::default(), &module).to_string();
+
+    assert!(actual
+      .contains("pub struct CreatePipelineArgs < const N : usize , const M : usize >"));
+    assert!(actual.contains("pub fn render_pipeline_descriptor < 'a , const N : usize"));
+    assert!(actual.contains("pub fn create_pipeline_embed_source < const N : usize"));
+    assert!(actual.contains("let module = create_shader_module_embed_source (device) ;"));
+    assert!(actual.contains("label : Some (\"Test::RenderPipeline\")"));
+    assert!(actual.contains("primitive : args . primitive . unwrap_or_default ()"));
+  }
+
+  #[test]
+  fn write_render_pipeline_items_without_fragment_entry() {
+    let source = indoc! {r#"
+          @vertex
+          fn vs_main() -> @builtin(position) vec4<f32> {
+            return vec4<f32>(0.0);
+          }
+      "#
+    };
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let labels = EntryLabels {
+      module: "Test",
+      path: "test.wgsl",
+    };
+    let actual = render_pipeline_items(&labels, &WgslBindgenOption::default(), &module);
+
+    assert_tokens_eq!(quote!(), actual);
+  }
 }
@@ -1,6 +1,8 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
+use crate::{WgslBindgenOption, WgslLabelFormat};
+
 pub(crate) mod bind_group;
 pub(crate) mod consts;
 pub(crate) mod entry;
@@ -8,6 +10,81 @@ pub(crate) mod pipeline;
 pub(crate) mod shader_module;
 pub(crate) mod shader_registry;
 
+/// The names the labels of the wgpu objects generated for an entry are made from.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct EntryLabels<'a> {
+  /// The PascalCase name of the entry module.
+  pub module: &'a str,
+  /// The path of the entry source file.
+  pub path: &'a str,
+}
+
+impl<'a> EntryLabels<'a> {
+  pub fn bind_group_layout(
+    &self,
+    options: &WgslBindgenOption,
+    group: u32,
+  ) -> TokenStream {
+    let default = || format!("{}::BindGroup{}::LayoutDescriptor", self.module, group);
+    self.quote(options, "BindGroupLayout", Some(group), default)
+  }
+
+  pub fn bind_group(&self, options: &WgslBindgenOption, group: u32) -> TokenStream {
+    let default = || format!("{}::BindGroup{}", self.module, group);
+    self.quote(options, "BindGroup", Some(group), default)
+  }
+
+  pub fn pipeline_layout(&self, options: &WgslBindgenOption) -> TokenStream {
+    let default = || format!("{}::PipelineLayout", self.module);
+    self.quote(options, "PipelineLayout", None, default)
+  }
+
+  pub fn render_pipeline(&self, options: &WgslBindgenOption) -> TokenStream {
+    let default = || format!("{}::RenderPipeline", self.module);
+    self.quote(options, "RenderPipeline", None, default)
+  }
+
+  pub fn compute_pipeline(
+    &self,
+    options: &WgslBindgenOption,
+    entry: &str,
+  ) -> TokenStream {
+    let default = || format!("{}::{}::ComputePipeline", self.module, entry);
+    self.quote_with_entry(options, "ComputePipeline", None, entry, default)
+  }
+
+  fn quote(
+    &self,
+    options: &WgslBindgenOption,
+    kind: &str,
+    group: Option<u32>,
+    default: impl FnOnce() -> String,
+  ) -> TokenStream {
+    self.quote_with_entry(options, kind, group, "", default)
+  }
+
+  fn quote_with_entry(
+    &self,
+    options: &WgslBindgenOption,
+    kind: &str,
+    group: Option<u32>,
+    entry: &str,
+    default: impl FnOnce() -> String,
+  ) -> TokenStream {
+    let label = match &options.label_format {
+      WgslLabelFormat::Default => default(),
+      WgslLabelFormat::Custom(format) => format
+        .replace("{module}", self.module)
+        .replace("{path}", self.path)
+        .replace("{group}", &group.map(|g| g.to_string()).unwrap_or_default())
+        .replace("{entry}", entry)
+        .replace("{kind}", kind),
+      WgslLabelFormat::None => return quote!(None),
+    };
+    quote!(Some(#label))
+  }
+}
+
 pub(crate) fn quote_shader_stages(shader_stages: wgpu::ShaderStages) -> TokenStream {
   match shader_stages {
     wgpu::ShaderStages::VERTEX_FRAGMENT => quote!(wgpu::ShaderStages::VERTEX_FRAGMENT),
@@ -25,8 +102,11 @@ pub(crate) fn quote_shader_stages(shader_stages: wgpu::ShaderStages) -> TokenStr
       if shader_stages.contains(wgpu::ShaderStages::COMPUTE) {
         stage_tokens.push(quote!(wgpu::ShaderStages::COMPUTE));
       }
-      // quote!(#(#stage_tokens)|*)
-      unimplemented!()
+      // `BitOr` isn't const, so combine the stages with `union`.
+      match stage_tokens.split_first() {
+        Some((first, rest)) => quote!(#first #(.union(#rest))*),
+        None => quote!(wgpu::ShaderStages::NONE),
+      }
     }
   }
 }
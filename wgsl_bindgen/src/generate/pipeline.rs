@@ -2,8 +2,10 @@ use std::collections::BTreeMap;
 
 use derive_more::Constructor;
 use generate::quote_shader_stages;
+use quote_gen::rust_type;
 
 use super::bind_group::GroupData;
+use super::EntryLabels;
 use crate::*;
 
 #[derive(Constructor)]
@@ -15,7 +17,7 @@ pub struct PipelineLayoutDataEntriesBuilder<'a> {
 impl<'a> PipelineLayoutDataEntriesBuilder<'a> {
   fn bind_group_layout_entries_fn(&self) -> TokenStream {
     let entry_type = self.generator.bind_group_layout_type.clone();
-    let len = Index::from(self.bind_group_data.len());
+    let len = Index::from(bind_group_slots(self.bind_group_data));
 
     quote! {
       pub fn bind_group_layout_entries(entries: [#entry_type; #len]) -> [#entry_type; #len] {
@@ -39,49 +41,113 @@ impl<'a> PipelineLayoutDataEntriesBuilder<'a> {
   }
 }
 
-fn push_constant_range(
+/// Generates `PUSH_CONSTANT_RANGES` and `set_push_constants` for the push constant variable
+/// of the module, if any.
+fn push_constants_items(
+  invoking_entry_module: &str,
   module: &naga::Module,
   shader_stages: wgpu::ShaderStages,
+  options: &WgslBindgenOption,
 ) -> Option<TokenStream> {
   // Assume only one variable is used with var<push_constant> in WGSL.
-  let push_constant_size = module.global_variables.iter().find_map(|g| {
-    if g.1.space == naga::AddressSpace::PushConstant {
-      Some(module.types[g.1.ty].inner.size(module.to_ctx()))
-    } else {
-      None
-    }
-  });
+  let (handle, global) = module
+    .global_variables
+    .iter()
+    .find(|(_, g)| g.space == naga::AddressSpace::PushConstant)?;
+  let ty = &module.types[global.ty];
+  let size = Index::from(ty.inner.size(module.to_ctx()) as usize);
 
-  let stages = quote_shader_stages(shader_stages);
+  // Like bindings, narrow the range to the stages that use the push constants.
+  let used_stages = crate::wgsl::global_shader_stages(module)
+    .map(|stages| stages[handle.index()])
+    .filter(|stages| !options.union_binding_visibility && !stages.is_empty());
+  let stages = quote_shader_stages(used_stages.unwrap_or(shader_stages));
 
   // Use a single push constant range for all shader stages.
   // This allows easily setting push constants in a single call with offset 0.
-  let push_constant_range = push_constant_size.map(|size| {
-    let size = Index::from(size as usize);
-    quote! {
+  let ranges = quote! {
+    pub const PUSH_CONSTANT_RANGES: &[wgpu::PushConstantRange] = &[
         wgpu::PushConstantRange {
             stages: #stages,
             range: 0..#size
         }
-    }
-  });
-  push_constant_range
+    ];
+  };
+
+  let set_push_constants =
+    if options.serialization_strategy == WgslTypeSerializeStrategy::Bytemuck {
+      let rust_type = rust_type(Some(invoking_entry_module), module, ty, options)
+        .expect("push constants with unrepresentable types are errors");
+
+      // TODO: Support compute shader with vertex/fragment in the same module?
+      let set = if shader_stages == wgpu::ShaderStages::COMPUTE {
+        quote! {
+          pub fn set_push_constants(pass: &mut wgpu::ComputePass<'_>, data: &#rust_type) {
+              pass.set_push_constants(0, &bytemuck::bytes_of(data)[..#size]);
+          }
+        }
+      } else {
+        quote! {
+          pub fn set_push_constants(pass: &mut wgpu::RenderPass<'_>, data: &#rust_type) {
+              pass.set_push_constants(#stages, 0, &bytemuck::bytes_of(data)[..#size]);
+          }
+        }
+      };
+      quote! {
+        /// Sets the push constants from the start of `data`, leaving out any padding the
+        /// Rust type has past the size of the shader type.
+        #set
+      }
+    } else {
+      quote!()
+    };
+
+  Some(quote! {
+    #ranges
+    #set_push_constants
+  })
+}
+
+/// The number of bind group slots of the pipeline layout, including the gaps allowed by
+/// [`WgslBindgenOption::allow_bind_group_gaps`].
+fn bind_group_slots(bind_group_data: &BTreeMap<u32, GroupData>) -> usize {
+  bind_group_data
+    .last_key_value()
+    .map_or(0, |(group_no, _)| *group_no as usize + 1)
+}
+
+/// Creates an empty layout for a bind group slot without bindings.
+fn empty_bind_group_layout(
+  labels: &EntryLabels,
+  options: &WgslBindgenOption,
+  group_no: u32,
+) -> TokenStream {
+  let label = labels.bind_group_layout(options, group_no);
+  quote! {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: #label,
+        entries: &[],
+    })
+  }
 }
 
 pub fn create_pipeline_layout_fn(
-  entry_name: &str,
+  invoking_entry_module: &str,
+  labels: EntryLabels,
   naga_module: &naga::Module,
   shader_stages: wgpu::ShaderStages,
   options: &WgslBindgenOption,
   bind_group_data: &BTreeMap<u32, GroupData>,
 ) -> TokenStream {
-  let bind_group_layouts: Vec<_> = bind_group_data
-    .keys()
+  let bind_group_layouts: Vec<_> = (0..bind_group_slots(bind_group_data) as u32)
     .map(|group_no| {
+      if !bind_group_data.contains_key(&group_no) {
+        return empty_bind_group_layout(&labels, options, group_no);
+      }
       let group = options
         .wgpu_binding_generator
         .bind_group_layout
-        .bind_group_name_ident(*group_no);
+        .bind_group_name_ident(group_no);
       quote!(#group::get_bind_group_layout(device))
     })
     .collect();
@@ -97,21 +163,215 @@ pub fn create_pipeline_layout_fn(
       quote!()
     };
 
-  let push_constant_range = push_constant_range(&naga_module, shader_stages);
+  let push_constants_items =
+    push_constants_items(invoking_entry_module, naga_module, shader_stages, options);
+  let push_constant_ranges = match push_constants_items {
+    Some(_) => quote!(PUSH_CONSTANT_RANGES),
+    None => quote!(&[]),
+  };
 
-  let pipeline_layout_name = format!("{}::PipelineLayout", entry_name);
+  let pipeline_layout_label = labels.pipeline_layout(options);
+
+  let cached_layouts = if options.cache_layouts {
+    cached_layouts_struct(&labels, options, bind_group_data)
+  } else {
+    quote!()
+  };
+
+  let entry_pipeline_layout_fns =
+    entry_pipeline_layout_fns(&labels, naga_module, options, bind_group_data);
 
   quote! {
     #additional_pipeline_entries_struct
     #wgpu_pipeline_entries_struct
+      /// The descriptor of the pipeline layout of the module with the given bind group layouts,
+      /// for callers that create or share the bind group layouts themselves.
+      pub fn pipeline_layout_descriptor<'a>(
+          bind_group_layouts: &'a [&'a wgpu::BindGroupLayout],
+      ) -> wgpu::PipelineLayoutDescriptor<'a> {
+          wgpu::PipelineLayoutDescriptor {
+              label: #pipeline_layout_label,
+              bind_group_layouts,
+              push_constant_ranges: #push_constant_ranges,
+          }
+      }
       pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
-          device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-              label: Some(#pipeline_layout_name),
-              bind_group_layouts: &[
+          device.create_pipeline_layout(&pipeline_layout_descriptor(&[
+              #(&#bind_group_layouts),*
+          ]))
+      }
+    #(#entry_pipeline_layout_fns)*
+    #push_constants_items
+    #cached_layouts
+  }
+}
+
+/// Generates a `create_pipeline_layout_for_<entry>` function for each entry point, with only
+/// the bind groups the entry point uses. Slots of groups below the highest used group that
+/// the entry point doesn't use get an empty layout.
+fn entry_pipeline_layout_fns(
+  labels: &EntryLabels,
+  naga_module: &naga::Module,
+  options: &WgslBindgenOption,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> Vec<TokenStream> {
+  let entry_groups = crate::wgsl::entry_point_bind_groups(naga_module);
+
+  naga_module
+    .entry_points
+    .iter()
+    .enumerate()
+    .map(|(index, entry)| {
+      let fn_name = format_ident!("create_pipeline_layout_for_{}", entry.name);
+      let doc = format!(
+        " Creates a pipeline layout with only the bind groups used by `{}`.",
+        entry.name
+      );
+
+      // Fall back to the layout of the whole module if the usage is unknown.
+      let Some(used) = entry_groups.as_ref().map(|groups| &groups[index]) else {
+        return quote! {
+            #[doc = #doc]
+            pub fn #fn_name(device: &wgpu::Device) -> wgpu::PipelineLayout {
+                create_pipeline_layout(device)
+            }
+        };
+      };
+
+      let slots = used.last().map_or(0, |group_no| group_no + 1);
+      let bind_group_layouts = (0..slots).map(|group_no| {
+        if !used.contains(&group_no) || !bind_group_data.contains_key(&group_no) {
+          return empty_bind_group_layout(labels, options, group_no);
+        }
+        let group = options
+          .wgpu_binding_generator
+          .bind_group_layout
+          .bind_group_name_ident(group_no);
+        quote!(#group::get_bind_group_layout(device))
+      });
+
+      quote! {
+          #[doc = #doc]
+          pub fn #fn_name(device: &wgpu::Device) -> wgpu::PipelineLayout {
+              device.create_pipeline_layout(&pipeline_layout_descriptor(&[
                   #(&#bind_group_layouts),*
-              ],
-              push_constant_ranges: &[#push_constant_range],
-          })
+              ]))
+          }
+      }
+    })
+    .collect()
+}
+
+/// Generates `CachedLayouts`, which holds all the cached bind group layouts of the module
+/// together with a pipeline layout created from them.
+fn cached_layouts_struct(
+  labels: &EntryLabels,
+  options: &WgslBindgenOption,
+  bind_group_data: &BTreeMap<u32, GroupData>,
+) -> TokenStream {
+  let slots = bind_group_slots(bind_group_data);
+  let cached_bind_group_layouts: Vec<_> = (0..slots as u32)
+    .map(|group_no| {
+      if !bind_group_data.contains_key(&group_no) {
+        let empty_layout = empty_bind_group_layout(labels, options, group_no);
+        return quote!({
+            static LAYOUT: std::sync::OnceLock<wgpu::BindGroupLayout> = std::sync::OnceLock::new();
+            LAYOUT.get_or_init(|| #empty_layout)
+        });
       }
+      let group = options
+        .wgpu_binding_generator
+        .bind_group_layout
+        .bind_group_name_ident(group_no);
+      quote!(#group::get_cached_bind_group_layout(device))
+    })
+    .collect();
+  let len = Index::from(slots);
+
+  quote! {
+    /// The bind group layouts and the pipeline layout of the module, created on first use
+    /// with the device of that call.
+    #[derive(Debug, Clone, Copy)]
+    pub struct CachedLayouts {
+        pub bind_group_layouts: [&'static wgpu::BindGroupLayout; #len],
+        pub pipeline_layout: &'static wgpu::PipelineLayout,
+    }
+
+    impl CachedLayouts {
+        pub fn get(device: &wgpu::Device) -> Self {
+            static PIPELINE_LAYOUT: std::sync::OnceLock<wgpu::PipelineLayout> = std::sync::OnceLock::new();
+            let bind_group_layouts = [#(#cached_bind_group_layouts),*];
+            let pipeline_layout = PIPELINE_LAYOUT.get_or_init(|| {
+                device.create_pipeline_layout(&pipeline_layout_descriptor(&bind_group_layouts))
+            });
+            Self {
+                bind_group_layouts,
+                pipeline_layout,
+            }
+        }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use indoc::indoc;
+
+  use super::*;
+  use crate::generate::bind_group::get_bind_group_data;
+
+  #[test]
+  fn entry_pipeline_layouts_pad_unused_groups() {
+    let source = indoc! {r#"
+      struct Camera { view_proj: mat4x4<f32> }
+      @group(0) @binding(0) var<uniform> camera: Camera;
+      @group(1) @binding(0) var<uniform> material: Camera;
+      @group(2) @binding(0) var<uniform> light: Camera;
+
+      @vertex
+      fn vs_shadow(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+        return camera.view_proj * position;
+      }
+
+      @vertex
+      fn vs_light(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+        return camera.view_proj * light.view_proj * position;
+      }
+
+      @fragment
+      fn fs_main() -> @location(0) vec4<f32> {
+        return material.view_proj[0];
+      }
+    "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let options = WgslBindgenOption::default();
+    let bind_group_data = get_bind_group_data(&module, &options).unwrap();
+    let labels = EntryLabels {
+      module: "Test",
+      path: "test.wgsl",
+    };
+    let layouts = entry_pipeline_layout_fns(&labels, &module, &options, &bind_group_data)
+      .into_iter()
+      .map(|layout| layout.to_string())
+      .collect::<Vec<_>>();
+
+    let [shadow, light, fragment] = &layouts[..] else {
+      panic!("expected one layout per entry point, got {}", layouts.len());
+    };
+    assert!(shadow.contains("create_pipeline_layout_for_vs_shadow"));
+    assert!(shadow.contains("WgpuBindGroup0 :: get_bind_group_layout (device)"));
+    assert!(!shadow.contains("WgpuBindGroup1"));
+    assert!(!shadow.contains("WgpuBindGroup2"));
+
+    // The unused group 1 is padded with an empty layout.
+    assert!(light.contains("WgpuBindGroup0 :: get_bind_group_layout (device)"));
+    assert!(!light.contains("WgpuBindGroup1"));
+    assert!(light.contains("entries : & []"));
+    assert!(light.contains("WgpuBindGroup2 :: get_bind_group_layout (device)"));
+
+    assert!(fragment.contains("entries : & []"));
+    assert!(fragment.contains("WgpuBindGroup1 :: get_bind_group_layout (device)"));
+    assert!(!fragment.contains("WgpuBindGroup2"));
   }
 }
@@ -4,11 +4,11 @@
 use std::path::Path;
 
 use derive_more::Constructor;
-use enumflags2::BitFlags;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, TokenStreamExt};
 use syn::{Ident, Index};
 
+use super::entry::quote_entry_point;
 use crate::naga_util::module_to_source;
 use crate::quote_gen::create_shader_raw_string_literal;
 use crate::{WgslBindgenOption, WgslEntryResult, WgslShaderSourceType};
@@ -52,6 +52,15 @@ impl WgslShaderSourceType {
     }
   }
 
+  pub(crate) fn create_render_pipeline_fn_name(&self) -> &'static str {
+    use WgslShaderSourceType::*;
+    match self {
+      UseEmbed => "create_pipeline_embed_source",
+      UseComposerEmbed => "create_pipeline_embedded",
+      UseComposerWithPath => "create_pipeline_from_path",
+    }
+  }
+
   pub(crate) fn get_return_type(&self, type_to_return: TokenStream) -> TokenStream {
     use WgslShaderSourceType::*;
     match self {
@@ -169,11 +178,12 @@ impl WgslShaderSourceType {
 #[derive(Constructor)]
 struct ComputeModuleBuilder<'a> {
   module: &'a naga::Module,
-  source_type_flags: BitFlags<WgslShaderSourceType>,
+  options: &'a WgslBindgenOption,
 }
 
 impl<'a> ComputeModuleBuilder<'a> {
   fn build_compute_pipeline_fn(
+    &self,
     e: &naga::EntryPoint,
     source_type: WgslShaderSourceType,
   ) -> TokenStream {
@@ -183,6 +193,8 @@ impl<'a> ComputeModuleBuilder<'a> {
       format_ident!("{}", source_type.create_compute_pipeline_fn_name(&e.name));
 
     let entry_point = &e.name;
+    let entry_point = quote_entry_point(self.options, quote!(#entry_point));
+    let zero_init = !self.options.skip_workgroup_memory_zero_init;
     // TODO: Include a user supplied module name in the label?
     let label = format!("Compute Pipeline {}", e.name);
 
@@ -202,7 +214,10 @@ impl<'a> ComputeModuleBuilder<'a> {
                 layout: Some(&layout),
                 module: &module,
                 entry_point: #entry_point,
-                compilation_options: Default::default(),
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    zero_initialize_workgroup_memory: #zero_init,
+                    ..Default::default()
+                },
                 cache: None,
             })
         }
@@ -216,6 +231,40 @@ impl<'a> ComputeModuleBuilder<'a> {
     quote!(pub const #name: [u32; 3] = [#x, #y, #z];)
   }
 
+  fn dispatch_fns(e: &naga::EntryPoint) -> TokenStream {
+    let size = format_ident!("{}_WORKGROUP_SIZE", e.name.to_uppercase());
+    let count_fn = format_ident!("{}_dispatch_count", e.name);
+    let dispatch_fn = format_ident!("dispatch_{}", e.name);
+    let dispatch_indirect_fn = format_ident!("dispatch_indirect_{}", e.name);
+
+    quote! {
+        /// Returns the number of workgroups needed to cover `work_items`, rounding up.
+        pub const fn #count_fn(work_items: [u32; 3]) -> [u32; 3] {
+            [
+                work_items[0].div_ceil(#size[0]),
+                work_items[1].div_ceil(#size[1]),
+                work_items[2].div_ceil(#size[2]),
+            ]
+        }
+
+        /// Dispatches enough workgroups to cover `work_items` with the pipeline set on `pass`.
+        pub fn #dispatch_fn(pass: &mut wgpu::ComputePass<'_>, work_items: [u32; 3]) {
+            let [x, y, z] = #count_fn(work_items);
+            pass.dispatch_workgroups(x, y, z);
+        }
+
+        /// Dispatches the workgroup counts stored in `buffer` at `offset`, laid out like
+        /// `wgpu::util::DispatchIndirectArgs`, with the pipeline set on `pass`.
+        pub fn #dispatch_indirect_fn(
+            pass: &mut wgpu::ComputePass<'_>,
+            buffer: &wgpu::Buffer,
+            offset: wgpu::BufferAddress,
+        ) {
+            pass.dispatch_workgroups_indirect(buffer, offset);
+        }
+    }
+  }
+
   pub(crate) fn entry_points_iter(&self) -> impl Iterator<Item = &naga::EntryPoint> {
     self
       .module
@@ -229,15 +278,18 @@ impl<'a> ComputeModuleBuilder<'a> {
       .entry_points_iter()
       .map(|e| {
         let workgroup_size_constant = Self::workgroup_size(e);
+        let dispatch_fns = Self::dispatch_fns(e);
 
         let create_pipeline_fns = self
-          .source_type_flags
+          .options
+          .shader_source_type
           .iter()
-          .map(|source_type| Self::build_compute_pipeline_fn(e, source_type))
+          .map(|source_type| self.build_compute_pipeline_fn(e, source_type))
           .collect::<Vec<_>>();
 
         quote! {
             #workgroup_size_constant
+            #dispatch_fns
             #(#create_pipeline_fns)*
         }
       })
@@ -257,9 +309,9 @@ impl<'a> ComputeModuleBuilder<'a> {
 }
 pub(crate) fn compute_module(
   module: &naga::Module,
-  source_type_flags: BitFlags<WgslShaderSourceType>,
+  options: &WgslBindgenOption,
 ) -> TokenStream {
-  ComputeModuleBuilder::new(module, source_type_flags).build()
+  ComputeModuleBuilder::new(module, options).build()
 }
 
 fn generate_shader_module_embedded(entry: &WgslEntryResult) -> TokenStream {
@@ -571,9 +623,34 @@ pub(crate) fn shader_module(
     token_stream.append_all(builder.build());
   }
 
+  if source_type.contains(UseEmbed) && source_type.contains(UseComposerWithPath) {
+    token_stream.append_all(create_shader_module_with_fallback_fn());
+  }
+
   token_stream
 }
 
+fn create_shader_module_with_fallback_fn() -> TokenStream {
+  use WgslShaderSourceType::*;
+  let from_path = format_ident!("{}", UseComposerWithPath.create_shader_module_fn_name());
+  let embedded = format_ident!("{}", UseEmbed.create_shader_module_fn_name());
+
+  quote! {
+    /// Creates the shader module from the shader files if they all exist, e.g. to hot reload
+    /// them during development, or else from the embedded source, which ignores `shader_defs`.
+    pub fn create_shader_module_from_path_or_embedded(
+      device: &wgpu::Device,
+      shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>
+    ) -> Result<wgpu::ShaderModule, naga_oil::compose::ComposerError> {
+      if SHADER_PATHS.iter().all(|path| std::path::Path::new(path).exists()) {
+        #from_path(device, shader_defs)
+      } else {
+        Ok(#embedded(device))
+      }
+    }
+  }
+}
+
 fn get_path_relative_to(relative_to: &std::path::Path, file: &std::path::Path) -> String {
   pathdiff::diff_paths(file, relative_to)
     .expect("failed to get relative path")
@@ -622,7 +699,7 @@ mod tests {
         "#};
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = compute_module(&module, WgslShaderSourceType::UseEmbed.into());
+    let actual = compute_module(&module, &WgslBindgenOption::default());
 
     assert_tokens_eq!(quote!(), actual);
   }
@@ -641,12 +718,34 @@ mod tests {
     };
 
     let module = naga::front::wgsl::parse_str(source).unwrap();
-    let actual = compute_module(&module, WgslShaderSourceType::UseEmbed.into());
+    let actual = compute_module(&module, &WgslBindgenOption::default());
 
     assert_tokens_eq!(
       quote! {
           pub mod compute {
               pub const MAIN1_WORKGROUP_SIZE: [u32; 3] = [1, 2, 3];
+              /// Returns the number of workgroups needed to cover `work_items`, rounding up.
+              pub const fn main1_dispatch_count(work_items: [u32; 3]) -> [u32; 3] {
+                  [
+                      work_items[0].div_ceil(MAIN1_WORKGROUP_SIZE[0]),
+                      work_items[1].div_ceil(MAIN1_WORKGROUP_SIZE[1]),
+                      work_items[2].div_ceil(MAIN1_WORKGROUP_SIZE[2]),
+                  ]
+              }
+              /// Dispatches enough workgroups to cover `work_items` with the pipeline set on `pass`.
+              pub fn dispatch_main1(pass: &mut wgpu::ComputePass<'_>, work_items: [u32; 3]) {
+                  let [x, y, z] = main1_dispatch_count(work_items);
+                  pass.dispatch_workgroups(x, y, z);
+              }
+              /// Dispatches the workgroup counts stored in `buffer` at `offset`, laid out like
+              /// `wgpu::util::DispatchIndirectArgs`, with the pipeline set on `pass`.
+              pub fn dispatch_indirect_main1(
+                  pass: &mut wgpu::ComputePass<'_>,
+                  buffer: &wgpu::Buffer,
+                  offset: wgpu::BufferAddress,
+              ) {
+                  pass.dispatch_workgroups_indirect(buffer, offset);
+              }
               pub fn create_main1_pipeline_embed_source(device: &wgpu::Device) -> wgpu::ComputePipeline {
                   let module = super::create_shader_module_embed_source(device);
                   let layout = super::create_pipeline_layout(device);
@@ -657,12 +756,37 @@ mod tests {
                               layout: Some(&layout),
                               module: &module,
                               entry_point: "main1",
-                              compilation_options: Default::default(),
+                              compilation_options: wgpu::PipelineCompilationOptions {
+                                  zero_initialize_workgroup_memory: true,
+                                  ..Default::default()
+                              },
                               cache: None,
                           },
                       )
               }
               pub const MAIN2_WORKGROUP_SIZE: [u32; 3] = [256, 1, 1];
+              /// Returns the number of workgroups needed to cover `work_items`, rounding up.
+              pub const fn main2_dispatch_count(work_items: [u32; 3]) -> [u32; 3] {
+                  [
+                      work_items[0].div_ceil(MAIN2_WORKGROUP_SIZE[0]),
+                      work_items[1].div_ceil(MAIN2_WORKGROUP_SIZE[1]),
+                      work_items[2].div_ceil(MAIN2_WORKGROUP_SIZE[2]),
+                  ]
+              }
+              /// Dispatches enough workgroups to cover `work_items` with the pipeline set on `pass`.
+              pub fn dispatch_main2(pass: &mut wgpu::ComputePass<'_>, work_items: [u32; 3]) {
+                  let [x, y, z] = main2_dispatch_count(work_items);
+                  pass.dispatch_workgroups(x, y, z);
+              }
+              /// Dispatches the workgroup counts stored in `buffer` at `offset`, laid out like
+              /// `wgpu::util::DispatchIndirectArgs`, with the pipeline set on `pass`.
+              pub fn dispatch_indirect_main2(
+                  pass: &mut wgpu::ComputePass<'_>,
+                  buffer: &wgpu::Buffer,
+                  offset: wgpu::BufferAddress,
+              ) {
+                  pass.dispatch_workgroups_indirect(buffer, offset);
+              }
               pub fn create_main2_pipeline_embed_source(device: &wgpu::Device) -> wgpu::ComputePipeline {
                   let module = super::create_shader_module_embed_source(device);
                   let layout = super::create_pipeline_layout(device);
@@ -673,7 +797,10 @@ mod tests {
                               layout: Some(&layout),
                               module: &module,
                               entry_point: "main2",
-                              compilation_options: Default::default(),
+                              compilation_options: wgpu::PipelineCompilationOptions {
+                                  zero_initialize_workgroup_memory: true,
+                                  ..Default::default()
+                              },
                               cache: None,
                           },
                       )
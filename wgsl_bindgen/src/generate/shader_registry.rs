@@ -9,6 +9,15 @@ use quote::{format_ident, quote};
 
 use crate::{sanitize_and_pascal_case, WgslEntryResult, WgslShaderSourceType};
 
+/// Returns the path to the module of the entry, which may be nested, e.g. `shader::shadows`.
+fn entry_mod_path(entry: &WgslEntryResult<'_>) -> TokenStream {
+  let parts = entry
+    .mod_name
+    .split("::")
+    .map(|part| format_ident!("{part}"));
+  quote!(#(#parts)::*)
+}
+
 #[derive(Constructor)]
 struct ShaderEntryBuilder<'a, 'b> {
   entries: &'a [WgslEntryResult<'b>],
@@ -32,7 +41,7 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
 
   fn build_create_pipeline_layout_fn(&self) -> TokenStream {
     let match_arms = self.entries.iter().map(|entry| {
-      let mod_path = format_ident!("{}", entry.mod_name);
+      let mod_path = entry_mod_path(entry);
       let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
 
       quote! {
@@ -54,7 +63,7 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
     let (param_defs, params) = source_type.shader_module_params_defs_and_params();
 
     let match_arms = self.entries.iter().map(|entry| {
-      let mod_path = format_ident!("{}", entry.mod_name);
+      let mod_path = entry_mod_path(entry);
       let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
 
       quote! {
@@ -117,7 +126,7 @@ impl<'a, 'b> ShaderEntryBuilder<'a, 'b> {
     }
 
     let match_arms = self.entries.iter().map(|entry| {
-      let mod_path = format_ident!("{}", entry.mod_name);
+      let mod_path = entry_mod_path(entry);
       let enum_variant = format_ident!("{}", sanitize_and_pascal_case(&entry.mod_name));
 
       quote! {
@@ -39,15 +39,22 @@
 #[allow(dead_code, unused)]
 extern crate wgpu_types as wgpu;
 
+use std::collections::HashSet;
+use std::path::PathBuf;
+
 use bevy_util::SourceWithFullDependenciesResult;
 use case::CaseExt;
 use derive_more::IsVariant;
 use generate::entry::{self, entry_point_constants, vertex_struct_impls};
-use generate::{bind_group, consts, pipeline, shader_module, shader_registry};
+use generate::{
+  bind_group, consts, pipeline, shader_module, shader_registry, EntryLabels,
+};
 use heck::ToPascalCase;
 use proc_macro2::{Span, TokenStream};
 use qs::{format_ident, quote, Ident, Index};
-use quote_gen::{custom_vector_matrix_assertions, RustModBuilder, MOD_STRUCT_ASSERTIONS};
+use quote_gen::{
+  custom_vector_matrix_assertions, RustItemPath, RustModBuilder, MOD_STRUCT_ASSERTIONS,
+};
 use thiserror::Error;
 
 pub mod bevy_util;
@@ -95,95 +102,407 @@ pub enum CreateModuleError {
   /// Each binding resource must be associated with exactly one binding index.
   #[error("duplicate binding found with index `{binding}`")]
   DuplicateBinding { binding: u32 },
+
+  /// Two bindings of a module map to the same index constant names.
+  #[error(
+    "bindings `{first}` and `{second}` in module `{module}` both generate the constant `{name}`"
+  )]
+  DuplicateBindingConstName {
+    module: String,
+    name: String,
+    first: String,
+    second: String,
+  },
+
+  /// The type of a binding can't be mapped to a `wgpu::BindingType`.
+  #[error(
+    "unsupported type `{type_desc}` for binding `{binding_name}` at @group({group}) @binding({binding}) in module `{module}`"
+  )]
+  UnsupportedBindingType {
+    module: String,
+    binding_name: String,
+    group: u32,
+    binding: u32,
+    type_desc: String,
+  },
+
+  /// Bindings with the same name have different types in different modules.
+  #[error(
+    "binding `{name}` has conflicting types across modules: {}",
+    modules.join(", ")
+  )]
+  ConflictingBindingTypes { name: String, modules: Vec<String> },
+
+  /// Structs with the same name have different layouts in different modules.
+  #[error(
+    "struct `{name}` has conflicting layouts across modules: {}",
+    modules.join(", ")
+  )]
+  ConflictingStructDefinitions { name: String, modules: Vec<String> },
+
+  /// Two outputs of a fragment entry point use the same location.
+  #[error(
+    "fragment entry point `{entry}` has more than one output at @location({location})"
+  )]
+  DuplicateFragmentOutputLocation { entry: String, location: u32 },
+
+  /// The standard derives can't be derived for a struct.
+  #[error("cannot derive `{derive}` for struct `{name}`: {reason}")]
+  IncompatibleStandardDerive {
+    name: String,
+    derive: String,
+    reason: String,
+  },
+
+  /// A struct skipped by `skip_struct_regexps` is used by the generated code.
+  #[error(
+    "struct `{name}` is skipped by `skip_struct_regexps` but used by {used_by}, override it with `override_struct` instead"
+  )]
+  SkippedStructInUse { name: String, used_by: String },
+
+  /// A vertex format override doesn't have the component count of the attribute.
+  #[error(
+    "vertex format `{format}` of `{field}` has {format_components} components, but the attribute has {attribute_components}"
+  )]
+  VertexFormatOverrideMismatch {
+    field: String,
+    format: String,
+    format_components: u32,
+    attribute_components: u32,
+  },
+
+  /// The type of a vertex attribute has no `wgpu::VertexFormat`, like `f16` or `vec3<f16>`.
+  #[error("vertex attribute `{attribute}` has type `{ty}`, which has no vertex format")]
+  UnsupportedVertexFormat { attribute: String, ty: String },
+
+  /// An exact struct field type override doesn't match any struct field.
+  #[error("struct field type override `{field_path}` doesn't match any struct field{suggestion}")]
+  UnknownStructFieldPath {
+    field_path: String,
+    suggestion: String,
+  },
+
+  /// A type without a Rust representation, like a pointer, is used by a buffer binding,
+  /// push constants or a vertex input, which need the Rust type.
+  #[error("{ty} can't be represented in Rust, but is used by {used_by}")]
+  UnrepresentableTypeInUse { ty: String, used_by: String },
+
+  /// A sampler binding is matched by sampler binding overrides of different kinds.
+  #[error(
+    "sampler binding `{binding_name}` in module `{module}` matches conflicting overrides: {kinds}"
+  )]
+  ConflictingSamplerOverrides {
+    module: String,
+    binding_name: String,
+    kinds: String,
+  },
+}
+
+/// A problem found while generating the bindings that doesn't stop the generation.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WgslBindgenWarning {
+  /// A struct isn't generated, since a field contains a type without a Rust
+  /// representation, like a pointer.
+  #[error(
+    "struct `{name}` is skipped, since field `{field}` contains a {ty}, which can't be represented in Rust"
+  )]
+  UnrepresentableStructSkipped {
+    name: String,
+    field: String,
+    ty: String,
+  },
+
+  /// An integer shader define isn't generated as a constant, since a WGSL constant of the
+  /// same name takes precedence.
+  #[error(
+    "shader define `{name}` in `{module}` is shadowed by a WGSL constant of the same name"
+  )]
+  ShadowedShaderDef { name: String, module: String },
+
+  /// Bindings with the same name have different types in different modules, see
+  /// [`CreateModuleError::ConflictingBindingTypes`].
+  #[error(
+    "binding `{name}` has conflicting types across modules: {}",
+    modules.join(", ")
+  )]
+  ConflictingBindingTypes { name: String, modules: Vec<String> },
+
+  /// No indirect dispatch conversions are generated for a struct matching
+  /// `dispatch_indirect_struct_regex`, since it doesn't start with three `u32` members.
+  #[error(
+    "`{name}` matches the dispatch indirect struct regex but doesn't start with three `u32` members"
+  )]
+  InvalidDispatchIndirectStruct { name: String },
+
+  /// Structs with the same name have different layouts in different modules, see
+  /// [`CreateModuleError::ConflictingStructDefinitions`]. They are renamed after their
+  /// layout, since `disambiguate_conflicting_structs` is set.
+  #[error(
+    "struct `{name}` has conflicting layouts across modules: {}",
+    modules.join(", ")
+  )]
+  ConflictingStructDefinitions { name: String, modules: Vec<String> },
 }
 
+/// The result of composing a single entry point, used to generate its bindings.
 #[derive(Debug)]
-pub(crate) struct WgslEntryResult<'a> {
-  mod_name: String,
-  naga_module: naga::Module,
-  source_including_deps: SourceWithFullDependenciesResult<'a>,
+pub struct WgslEntryResult<'a> {
+  /// The name of the generated Rust module for this entry.
+  pub mod_name: String,
+  /// The composed naga module, with all imports resolved.
+  pub naga_module: naga::Module,
+  /// The entry source file together with all of its dependencies.
+  pub source_including_deps: SourceWithFullDependenciesResult<'a>,
+  /// The shader defines referenced by `#ifdef`/`#ifndef`/`#if` directives in the entry
+  /// and all of its imports, sorted by name.
+  pub encountered_defines: Vec<String>,
+  /// The paths of all the files imported by the entry, directly or transitively.
+  pub resolved_imports: Vec<PathBuf>,
+  /// The shader defines the entry was composed with, including the ones set with `#define`
+  /// in the entry source.
+  pub shader_defs: Vec<(String, ShaderDefValue)>,
 }
 
 fn create_rust_bindings(
-  entries: Vec<WgslEntryResult<'_>>,
+  entries: &[WgslEntryResult<'_>],
   options: &WgslBindgenOption,
-) -> Result<String, CreateModuleError> {
+) -> Result<(String, Vec<WgslBindgenWarning>), CreateModuleError> {
   let mut mod_builder = RustModBuilder::new(true, true);
+  let mut warnings = Vec::new();
 
   if let Some(custom_wgsl_type_asserts) = custom_vector_matrix_assertions(options) {
     mod_builder.add(MOD_STRUCT_ASSERTIONS, custom_wgsl_type_asserts);
   }
 
-  for entry in entries.iter() {
-    let WgslEntryResult {
-      mod_name,
-      naga_module,
-      ..
-    } = entry;
+  let disambiguated_structs =
+    structs::disambiguate_conflicting_structs(entries, options, &mut warnings)?;
+  let naga_modules = &disambiguated_structs.modules;
+
+  let entries_bind_group_data = naga_modules
+    .iter()
+    .map(|naga_module| bind_group::get_bind_group_data(naga_module, options))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  let source_paths = entries
+    .iter()
+    .map(|entry| {
+      entry
+        .source_including_deps
+        .source_file
+        .file_path
+        .to_string()
+    })
+    .collect::<Vec<_>>();
+
+  let entries_bind_groups = entries
+    .iter()
+    .zip(naga_modules)
+    .zip(&entries_bind_group_data)
+    .zip(&source_paths)
+    .map(|(((entry, naga_module), bind_group_data), source_path)| {
+      bind_group::SharedBindGroupsInput {
+        invoking_entry_module: &entry.mod_name,
+        source_path,
+        naga_module,
+        bind_group_data,
+        shader_stages: wgsl::shader_stages(naga_module),
+      }
+    })
+    .collect::<Vec<_>>();
+
+  bind_group::check_binding_type_conflicts(&entries_bind_groups, options, &mut warnings)?;
+  let struct_buffer_usages = bind_group::struct_buffer_usages(&entries_bind_groups);
+
+  let shared_bind_groups = if options.share_identical_bind_groups {
+    bind_group::find_shared_bind_groups(&entries_bind_groups, options)?
+  } else {
+    Default::default()
+  };
+
+  for (path, items) in &shared_bind_groups.modules {
+    mod_builder.add(path, items.clone());
+  }
+
+  let shared_structs = if options.share_identical_structs {
+    structs::find_shared_structs(&entries_bind_groups, options, &struct_buffer_usages)?
+  } else {
+    Default::default()
+  };
+  mod_builder.add_items(shared_structs.items).unwrap();
+
+  let mut field_paths = entries
+    .iter()
+    .zip(naga_modules)
+    .flat_map(|(entry, naga_module)| {
+      structs::struct_field_paths(&entry.mod_name, naga_module)
+    })
+    .collect::<HashSet<_>>();
+  if options.share_identical_structs {
+    field_paths.extend(naga_modules.iter().flat_map(|naga_module| {
+      structs::struct_field_paths(structs::SHARED_STRUCTS_MOD, naga_module)
+    }));
+  }
+  structs::check_exact_field_type_overrides(&field_paths, options)?;
+
+  for (entry_index, entry) in entries.iter().enumerate() {
+    let mod_name = &entry.mod_name;
+    let naga_module = naga_modules[entry_index].as_ref();
     let entry_name = sanitize_and_pascal_case(&mod_name);
-    let bind_group_data = bind_group::get_bind_group_data(naga_module)?;
+    let bind_group_data = &entries_bind_group_data[entry_index];
     let shader_stages = wgsl::shader_stages(naga_module);
+    let shared_groups = shared_bind_groups
+      .entry_groups
+      .get(entry_index)
+      .cloned()
+      .unwrap_or_default();
+
+    structs::check_skipped_structs(mod_name, naga_module, options)?;
+    structs::check_unrepresentable_types(mod_name, naga_module)?;
+
+    // Write all the structs, including uniforms and entry function inputs. The shared
+    // structs are re-exported instead.
+    let shared_struct_names = shared_structs
+      .entry_structs
+      .get(entry_index)
+      .cloned()
+      .unwrap_or_default();
+    let is_shared_struct = |path: &RustItemPath| {
+      path.module == mod_name.as_str() && shared_struct_names.contains(path.name.as_str())
+    };
+    let mut struct_items = structs::structs_items_where(
+      &mod_name,
+      naga_module,
+      options,
+      &struct_buffer_usages,
+      &mut warnings,
+      |path| !is_shared_struct(path),
+    )?;
+    if let Some(notes) = shared_structs.entry_conflicts.get(entry_index) {
+      structs::add_struct_notes(&mut struct_items, mod_name, notes);
+    }
+    let renamed_notes = &disambiguated_structs.entry_notes[entry_index];
+    structs::add_struct_notes(&mut struct_items, mod_name, renamed_notes);
+    mod_builder.add_items(struct_items).unwrap();
+    if !shared_struct_names.is_empty() {
+      let shared_mod = format_ident!("{}", structs::SHARED_STRUCTS_MOD);
+      let names = shared_struct_names
+        .iter()
+        .map(|name| Ident::new(name, Span::call_site()));
+      mod_builder.add(mod_name, quote!(pub use _root::#shared_mod::{#(#names),*};));
+    }
+
+    let const_items = consts::consts_items(&mod_name, naga_module);
+    let shader_def_items = consts::shader_def_consts_items(
+      mod_name,
+      &entry.shader_defs,
+      &const_items,
+      &mut warnings,
+    );
 
-    // Write all the structs, including uniforms and entry function inputs.
-    mod_builder
-      .add_items(structs::structs_items(&mod_name, naga_module, options))
-      .unwrap();
-
+    mod_builder.add_items(const_items).unwrap();
+    mod_builder.add_items(shader_def_items).unwrap();
     mod_builder
-      .add_items(consts::consts_items(&mod_name, naga_module))
+      .add_items(consts::global_array_length_consts_items(mod_name, naga_module, options))
       .unwrap();
 
     mod_builder
       .add(mod_name, consts::pipeline_overridable_constants(naga_module, options));
 
-    mod_builder
-      .add_items(vertex_struct_impls(mod_name, naga_module))
-      .unwrap();
+    let mut vertex_items = vertex_struct_impls(mod_name, naga_module, options)?;
+    vertex_items.retain(|item| !is_shared_struct(&item.path));
+    mod_builder.add_items(vertex_items).unwrap();
 
     mod_builder.add(
       mod_name,
-      bind_group::bind_groups_module(
+      bind_group::bind_groups_module_with_shared(
         &mod_name,
         &options,
         naga_module,
-        &bind_group_data,
+        bind_group_data,
         shader_stages,
-      ),
+        &source_paths[entry_index],
+        &shared_groups,
+      )?,
     );
 
-    mod_builder.add(
-      mod_name,
-      shader_module::compute_module(naga_module, options.shader_source_type),
-    );
+    mod_builder.add(mod_name, shader_module::compute_module(naga_module, options));
     mod_builder.add(mod_name, entry_point_constants(naga_module));
 
-    mod_builder.add(mod_name, entry::vertex_states(mod_name, naga_module));
-    mod_builder.add(mod_name, entry::fragment_states(naga_module));
+    mod_builder.add(mod_name, entry::vertex_states(mod_name, naga_module, options)?);
+    mod_builder.add(mod_name, entry::fragment_states(naga_module, options)?);
+    if options.dyn_entry_structs {
+      mod_builder.add(mod_name, entry::dyn_entry_items(naga_module, options));
+    }
 
+    let labels = EntryLabels {
+      module: &entry_name,
+      path: &source_paths[entry_index],
+    };
+    mod_builder.add(mod_name, entry::compute_states(&labels, options, naga_module));
+    mod_builder
+      .add(mod_name, entry::render_pipeline_items(&labels, options, naga_module));
     let create_pipeline_layout = pipeline::create_pipeline_layout_fn(
-      &entry_name,
+      mod_name,
+      labels,
       naga_module,
       shader_stages,
       &options,
-      &bind_group_data,
+      bind_group_data,
     );
 
     mod_builder.add(mod_name, create_pipeline_layout);
+    if options.omit_unused_bindings {
+      mod_builder.add(mod_name, bind_group::omitted_bindings_items(naga_module));
+    }
+    if options.binding_reflection_table {
+      mod_builder
+        .add(mod_name, bind_group::bindings_table(mod_name, options, bind_group_data));
+    }
     mod_builder.add(mod_name, shader_module::shader_module(entry, options));
   }
 
   let mod_token_stream = mod_builder.generate();
   let shader_registry =
-    shader_registry::build_shader_registry(&entries, options.shader_source_type);
+    shader_registry::build_shader_registry(entries, options.shader_source_type);
+
+  let named_resources_error = if options.named_bind_group_resources {
+    bind_group::named_resources_error()
+  } else {
+    quote!()
+  };
+
+  let bind_group_entry_defaults = if options.bind_group_entries_builders {
+    bind_group::bind_group_entry_defaults()
+  } else {
+    quote!()
+  };
+
+  let bindings_table = if options.binding_reflection_table {
+    let entry_modules = entries
+      .iter()
+      .map(|entry| entry.mod_name.as_str())
+      .collect::<Vec<_>>();
+    bind_group::bindings_table_root(&entry_modules)
+  } else {
+    quote!()
+  };
+
+  let wgsl_atomic = structs::wgsl_atomic_type(options);
+  let wgsl_bool = structs::wgsl_bool_type(options);
 
   let output = quote! {
     #![allow(unused, non_snake_case, non_camel_case_types, non_upper_case_globals)]
 
     #shader_registry
+    #wgsl_atomic
+    #wgsl_bool
+    #named_resources_error
+    #bind_group_entry_defaults
+    #bindings_table
     #mod_token_stream
   };
 
-  Ok(pretty_print(&output))
+  Ok((pretty_print(&output), warnings))
 }
 
 fn pretty_print(tokens: &TokenStream) -> String {
@@ -196,7 +515,8 @@ fn indexed_name_ident(name: &str, index: u32) -> Ident {
 }
 
 fn sanitize_and_pascal_case(v: &str) -> String {
-  v.chars()
+  v.replace("::", "_")
+    .chars()
     .filter(|ch| ch.is_alphanumeric() || *ch == '_')
     .collect::<String>()
     .to_pascal_case()
@@ -233,7 +553,8 @@ mod test {
     options: WgslBindgenOption,
   ) -> Result<String, CreateModuleError> {
     let naga_module = naga::front::wgsl::parse_str(source).unwrap();
-    let dummy_source = SourceFile::create(SourceFilePath::new(""), None, "".into());
+    let dummy_source =
+      SourceFile::create(SourceFilePath::new(""), None, "".into()).unwrap();
     let entry = WgslEntryResult {
       mod_name: "test".into(),
       naga_module,
@@ -241,9 +562,13 @@ mod test {
         full_dependencies: Default::default(),
         source_file: &dummy_source,
       },
+      encountered_defines: Default::default(),
+      resolved_imports: Default::default(),
+      shader_defs: Default::default(),
     };
 
-    Ok(create_rust_bindings(vec![entry], &options)?)
+    let (output, _) = create_rust_bindings(&[entry], &options)?;
+    Ok(output)
   }
 
   #[test]
@@ -285,11 +610,35 @@ mod test {
                 pub mod test {
                     use super::{_root, _root::*};
                     pub const ENTRY_FS_MAIN: &str = "fs_main";
+                    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                    pub enum EntryPoint {
+                        FsMain,
+                    }
+                    impl EntryPoint {
+                        pub const ALL: &'static [Self] = &[Self::FsMain];
+                        pub fn name(self) -> &'static str {
+                            match self {
+                                Self::FsMain => ENTRY_FS_MAIN,
+                            }
+                        }
+                        pub fn stage(self) -> wgpu::ShaderStages {
+                            match self {
+                                Self::FsMain => wgpu::ShaderStages::FRAGMENT,
+                            }
+                        }
+                        /// The workgroup size of compute entry points.
+                        pub fn workgroup_size(self) -> Option<[u32; 3]> {
+                            match self {
+                                Self::FsMain => None,
+                            }
+                        }
+                    }
                     #[derive(Debug)]
                     pub struct FragmentEntry<const N: usize> {
                         pub entry_point: &'static str,
                         pub targets: [Option<wgpu::ColorTargetState>; N],
                         pub constants: std::collections::HashMap<String, f64>,
+                        pub zero_initialize_workgroup_memory: bool,
                     }
                     pub fn fragment_state<'a, const N: usize>(
                         module: &'a wgpu::ShaderModule,
@@ -301,6 +650,7 @@ mod test {
                             targets: &entry.targets,
                             compilation_options: wgpu::PipelineCompilationOptions {
                                 constants: &entry.constants,
+                                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                                 ..Default::default()
                             },
                         }
@@ -312,8 +662,11 @@ mod test {
                             entry_point: ENTRY_FS_MAIN,
                             targets,
                             constants: Default::default(),
+                            zero_initialize_workgroup_memory: true,
                         }
                     }
+                    /// Whether the entry point writes `@builtin(frag_depth)`.
+                    pub const FS_MAIN_WRITES_DEPTH: bool = false;
                     #[derive(Debug)]
                     pub struct WgpuPipelineLayout;
                     impl WgpuPipelineLayout {
@@ -323,21 +676,32 @@ mod test {
                             entries
                         }
                     }
+                    /// The descriptor of the pipeline layout of the module with the given bind group layouts,
+                    /// for callers that create or share the bind group layouts themselves.
+                    pub fn pipeline_layout_descriptor<'a>(
+                        bind_group_layouts: &'a [&'a wgpu::BindGroupLayout],
+                    ) -> wgpu::PipelineLayoutDescriptor<'a> {
+                        wgpu::PipelineLayoutDescriptor {
+                            label: Some("Test::PipelineLayout"),
+                            bind_group_layouts,
+                            push_constant_ranges: PUSH_CONSTANT_RANGES,
+                        }
+                    }
                     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
-                        device
-                            .create_pipeline_layout(
-                                &wgpu::PipelineLayoutDescriptor {
-                                    label: Some("Test::PipelineLayout"),
-                                    bind_group_layouts: &[],
-                                    push_constant_ranges: &[
-                                        wgpu::PushConstantRange {
-                                            stages: wgpu::ShaderStages::FRAGMENT,
-                                            range: 0..16,
-                                        },
-                                    ],
-                                },
-                            )
+                        device.create_pipeline_layout(&pipeline_layout_descriptor(&[]))
+                    }
+                    /// Creates a pipeline layout with only the bind groups used by `fs_main`.
+                    pub fn create_pipeline_layout_for_fs_main(
+                        device: &wgpu::Device,
+                    ) -> wgpu::PipelineLayout {
+                        device.create_pipeline_layout(&pipeline_layout_descriptor(&[]))
                     }
+                    pub const PUSH_CONSTANT_RANGES: &[wgpu::PushConstantRange] = &[
+                        wgpu::PushConstantRange {
+                            stages: wgpu::ShaderStages::FRAGMENT,
+                            range: 0..16,
+                        },
+                    ];
                     pub fn create_shader_module_embed_source(
                         device: &wgpu::Device,
                     ) -> wgpu::ShaderModule {
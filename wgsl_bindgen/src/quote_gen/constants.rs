@@ -4,6 +4,7 @@ use proc_macro2::Ident;
 pub(crate) const MOD_REFERENCE_ROOT: &str = "_root";
 pub(crate) const MOD_STRUCT_ASSERTIONS: &str = "layout_asserts";
 pub(crate) const MOD_BYTEMUCK_IMPLS: &str = "bytemuck_impls";
+pub(crate) const MOD_LAYOUT_TESTS: &str = "generated_layout_tests";
 
 pub(crate) fn mod_reference_root() -> Ident {
   unsafe { syn::parse_str(MOD_REFERENCE_ROOT).unwrap_unchecked() }
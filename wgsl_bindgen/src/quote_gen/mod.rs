@@ -25,7 +25,17 @@ use crate::bevy_util::demangle_str;
 ///
 /// The token stream representing the raw string literal.
 pub(crate) fn create_shader_raw_string_literal(shader_content: &str) -> TokenStream {
-  syn::parse_str::<TokenStream>(&format!("r#\"\n{}\"#", &shader_content)).unwrap()
+  // Use one more `#` than the longest run following a quote, e.g. in a comment, so the
+  // source can't end the literal early.
+  let hashes = shader_content
+    .split('"')
+    .skip(1)
+    .map(|after_quote| after_quote.chars().take_while(|c| *c == '#').count())
+    .max()
+    .map_or(1, |longest| longest + 1);
+  let hashes = "#".repeat(hashes);
+  syn::parse_str::<TokenStream>(&format!("r{hashes}\"\n{shader_content}\"{hashes}"))
+    .unwrap()
 }
 
 /// Demangles the given string and qualifies it with the qualification root.
@@ -72,7 +82,7 @@ pub(crate) fn demangle_and_fully_qualify(
 mod tests {
   use pretty_assertions::assert_eq;
 
-  use super::demangle_and_fully_qualify;
+  use super::{create_shader_raw_string_literal, demangle_and_fully_qualify};
 
   #[test]
   fn should_fully_qualify_mangled_string() {
@@ -87,4 +97,14 @@ mod tests {
     let actual = demangle_and_fully_qualify(string, None);
     assert_eq!(actual.to_string(), "MatricesF64");
   }
+
+  #[test]
+  fn should_pick_raw_string_hashes_for_shader_source() {
+    let actual = create_shader_raw_string_literal("fn main() {}");
+    assert_eq!(actual.to_string(), "r#\"\nfn main() {}\"#");
+
+    let source = "// a \"#quoted\"## comment\nfn main() {}";
+    let actual = create_shader_raw_string_literal(source);
+    assert_eq!(actual.to_string(), format!("r###\"\n{source}\"###"));
+  }
 }
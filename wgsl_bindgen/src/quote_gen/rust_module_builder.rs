@@ -8,7 +8,7 @@ use smallvec::SmallVec;
 use syn::Ident;
 use thiserror::Error;
 
-use super::constants::MOD_REFERENCE_ROOT;
+use super::constants::{MOD_LAYOUT_TESTS, MOD_REFERENCE_ROOT};
 use super::{RustItem, RustItemType};
 use crate::quote_gen::constants::mod_reference_root;
 use crate::FastIndexMap;
@@ -25,7 +25,8 @@ pub enum RustModuleBuilderError {
 
 struct UniqueItemInfo {
   index: usize,
-  types: BitFlags<RustItemType>,
+  /// The item types and contents added for the id, in the order they were added.
+  parts: Vec<(BitFlags<RustItemType>, String)>,
 }
 
 #[derive(Default)]
@@ -59,10 +60,11 @@ impl RustModule {
   /// Adds unique content to the `RustModule`.
   ///
   /// This function checks if the provided `id` already exists in the `unique_content_info` map.
-  /// If it does, it compares the existing content with the new content.
+  /// If content with the same `RustItemKind`s was already added for it, it compares the
+  /// existing content with the new content.
   /// If the content is the same, it does nothing.
-  /// If the content is different but the `RustItemKind` is the same, it appends the new content to the existing content.
-  /// If the `RustItemKind` is different, it returns an error.
+  /// If the content is different, it returns an error.
+  /// Otherwise, it appends the new content to the existing content.
   fn add_unique(
     &mut self,
     id: &str,
@@ -75,26 +77,27 @@ impl RustModule {
         Some((info, content))
       })
     {
-      if previous_info.types == types {
-        let existing = existing_content.to_string();
-        let received = content.to_string();
-        if existing != received {
+      let received = content.to_string();
+      match previous_info.parts.iter().find(|(t, _)| *t == types) {
+        Some((_, existing)) if *existing != received => {
           return Err(RustModuleBuilderError::DuplicateContentError {
             id: id.to_string(),
-            existing,
+            existing: existing.clone(),
             received,
           });
         }
-      } else {
-        existing_content.extend(content);
-        previous_info.types |= types;
+        Some(_) => {}
+        None => {
+          existing_content.extend(content);
+          previous_info.parts.push((types, received));
+        }
       }
     } else {
       self.unique_content_info.insert(
         id.to_string(),
         UniqueItemInfo {
           index: self.content.len(),
-          types,
+          parts: vec![(types, content.to_string())],
         },
       );
       self.content.push(content);
@@ -158,6 +161,19 @@ pub struct RustModBuilderConfig {
 
 impl RustModBuilderConfig {
   fn build_module(&self, mod_name: &str) -> RustModule {
+    let module = self.build_module_with_root(mod_name);
+    if mod_name == MOD_LAYOUT_TESTS {
+      RustModule {
+        is_public: false,
+        module_attributes: quote!(#[cfg(test)]),
+        ..module
+      }
+    } else {
+      module
+    }
+  }
+
+  fn build_module_with_root(&self, mod_name: &str) -> RustModule {
     if self.use_relative_root {
       // this helps import relative items for nested mods under this root
       // https://discord.com/channels/442252698964721669/448238009733742612/1207323647203868712
@@ -283,11 +299,14 @@ impl RustModBuilder {
 
 #[cfg(test)]
 mod tests {
+  use enumflags2::BitFlags;
   use pretty_assertions::assert_eq;
+  use proc_macro2::TokenStream;
   use quote::quote;
 
   use super::{RustModBuilder, RustModuleBuilderError};
   use crate::assert_tokens_eq;
+  use crate::quote_gen::RustItemType;
 
   #[test]
   fn test_module_generation_works() {
@@ -414,6 +433,32 @@ mod tests {
     assert_eq!(error.is_err(), true);
   }
 
+  #[test]
+  fn test_module_add_duplicates_with_extensions() -> Result<(), RustModuleBuilderError> {
+    let mut mod_builder = RustModBuilder::new(false, false);
+    let mut add = |types: BitFlags<RustItemType>, content: TokenStream| {
+      mod_builder
+        .get_or_create_module("a")
+        .add_unique("A", types, content)
+    };
+    add(RustItemType::TypeDefs.into(), quote! {struct A;})?;
+    add(RustItemType::TraitImpls.into(), quote! {impl Clone for A {}})?;
+    add(RustItemType::TypeDefs.into(), quote! {struct A;})?;
+    add(RustItemType::TraitImpls.into(), quote! {impl Clone for A {}})?;
+    assert!(add(RustItemType::TypeDefs.into(), quote! {struct B;}).is_err());
+
+    assert_tokens_eq!(
+      mod_builder.generate(),
+      quote! {
+        pub mod a {
+          struct A;
+          impl Clone for A {}
+        }
+      }
+    );
+    Ok(())
+  }
+
   #[test]
   fn test_merge() {
     let mut builder1 = RustModBuilder::new(false, false);
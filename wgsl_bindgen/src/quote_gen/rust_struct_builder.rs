@@ -3,16 +3,18 @@ use std::usize;
 use derive_more::IsVariant;
 use naga::StructMember;
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 use smol_str::SmolStr;
 use syn::{Ident, Index};
 
 use super::{rust_type, RustItem, RustItemPath, RustTypeInfo};
 use crate::bevy_util::demangle_str;
-use crate::quote_gen::{RustItemType, MOD_BYTEMUCK_IMPLS, MOD_STRUCT_ASSERTIONS};
+use crate::quote_gen::{
+  RustItemType, MOD_BYTEMUCK_IMPLS, MOD_LAYOUT_TESTS, MOD_STRUCT_ASSERTIONS,
+};
 use crate::{
-  sanitized_upper_snake_case, WgslBindgenOption, WgslTypeSerializeStrategy,
-  WgslTypeVisibility,
+  sanitized_upper_snake_case, wgsl, CreateModuleError, StdDerive, WgslBindgenOption,
+  WgslTypeSerializeStrategy, WgslTypeVisibility,
 };
 
 impl WgslTypeVisibility {
@@ -43,6 +45,140 @@ impl Padding {
     let pad_size = &self.pad_size_tokens;
     quote!(pub #pad_name: [u8; #pad_size])
   }
+
+  fn supports_derive_default(&self) -> bool {
+    syn::parse2::<syn::Expr>(self.pad_size_tokens.clone())
+      .is_ok_and(|len| array_len_supports_derive_default(&len))
+  }
+}
+
+/// Whether the member is explicit padding in the shader, named `_pad` followed by an
+/// optional number, e.g. `_pad0`.
+fn is_padding_field_name(member_name: &str) -> bool {
+  member_name
+    .strip_prefix("_pad")
+    .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// The alignment of the first of [`WgslBindgenOption::override_struct_alignment`] matching
+/// the struct.
+fn custom_struct_alignment(
+  options: &WgslBindgenOption,
+  fully_qualified_name: &str,
+) -> Option<naga::proc::Alignment> {
+  options
+    .override_struct_alignment
+    .iter()
+    .find_map(|struct_align| {
+      struct_align
+        .struct_regex
+        .is_match(fully_qualified_name)
+        .then_some(struct_align.alignment as u32)
+    })
+    .and_then(naga::proc::Alignment::new)
+}
+
+/// Removes duplicated token streams, keeping the first occurrence.
+/// The serde derives to add to the derive list of a struct and the attribute to emit
+/// instead when they are gated by [`WgslBindgenOption::serde_feature`].
+pub(crate) fn serde_derives(
+  options: &WgslBindgenOption,
+  derive_serde: bool,
+) -> (Vec<TokenStream>, TokenStream) {
+  if !derive_serde {
+    return (Vec::new(), quote!());
+  }
+  let derives = vec![quote!(serde::Serialize), quote!(serde::Deserialize)];
+  match &options.serde_feature {
+    None => (derives, quote!()),
+    Some(feature) => {
+      (Vec::new(), quote!(#[cfg_attr(feature = #feature, derive(#(#derives),*))]))
+    }
+  }
+}
+
+fn type_contains_float(naga_module: &naga::Module, ty: &naga::TypeInner) -> bool {
+  match ty {
+    naga::TypeInner::Scalar(scalar)
+    | naga::TypeInner::Vector { scalar, .. }
+    | naga::TypeInner::Matrix { scalar, .. }
+    | naga::TypeInner::Atomic(scalar) => scalar.kind == naga::ScalarKind::Float,
+    naga::TypeInner::Array { base, .. } => {
+      type_contains_float(naga_module, &naga_module.types[*base].inner)
+    }
+    naga::TypeInner::Struct { members, .. } => members
+      .iter()
+      .any(|m| type_contains_float(naga_module, &naga_module.types[m.ty].inner)),
+    _ => false,
+  }
+}
+
+fn dedup_tokens(tokens: Vec<TokenStream>) -> Vec<TokenStream> {
+  let mut seen = std::collections::HashSet::new();
+  tokens
+    .into_iter()
+    .filter(|t| seen.insert(t.to_string()))
+    .collect()
+}
+
+/// The name of a derive, the last segment of its path, e.g. `Default` for
+/// `std::default::Default`.
+fn derive_name(derive: &TokenStream) -> String {
+  match syn::parse2::<syn::Path>(derive.clone()) {
+    Ok(path) => path
+      .segments
+      .last()
+      .map_or_else(String::new, |segment| segment.ident.to_string()),
+    Err(_) => derive.to_string(),
+  }
+}
+
+/// Appends the `derives` whose names are not already in `base`.
+fn merge_derives(
+  mut base: Vec<TokenStream>,
+  derives: &[TokenStream],
+) -> Vec<TokenStream> {
+  for derive in derives {
+    let name = derive_name(derive);
+    if !base.iter().any(|existing| derive_name(existing) == name) {
+      base.push(derive.clone());
+    }
+  }
+  base
+}
+
+/// `Default` is only implemented by std for arrays of up to 32 elements.
+fn array_len_supports_derive_default(len: &syn::Expr) -> bool {
+  match len {
+    syn::Expr::Lit(syn::ExprLit {
+      lit: syn::Lit::Int(int),
+      ..
+    }) => int.base10_parse::<usize>().is_ok_and(|len| len <= 32),
+    _ => false,
+  }
+}
+
+fn type_supports_derive_default(ty: &syn::Type) -> bool {
+  match ty {
+    syn::Type::Array(array) => {
+      array_len_supports_derive_default(&array.len)
+        && type_supports_derive_default(&array.elem)
+    }
+    _ => true,
+  }
+}
+
+/// Builds the zero/default value for `ty`, using repeat expressions for arrays
+/// so that arrays of any length can be initialized.
+fn default_value_for_type(ty: &syn::Type) -> TokenStream {
+  match ty {
+    syn::Type::Array(array) => {
+      let elem = default_value_for_type(&array.elem);
+      let len = &array.len;
+      quote!([#elem; #len])
+    }
+    _ => quote!(Default::default()),
+  }
 }
 
 #[derive(Default)]
@@ -52,7 +188,8 @@ struct NagaToRustStructState<'a> {
 }
 
 impl<'a> NagaToRustStructState<'a> {
-  /// This replaces the `rust_type` with a custom field map if necessary
+  /// This replaces the `rust_type` with a custom field map, preferring exact paths over
+  /// regular expressions, or the type of an overridden vertex format if necessary
   fn get_rust_type(
     options: &WgslBindgenOption,
     fully_qualified_name: &SmolStr,
@@ -60,17 +197,74 @@ impl<'a> NagaToRustStructState<'a> {
     member_name: &str,
   ) -> proc_macro2::TokenStream {
     let fully_qualified_name = fully_qualified_name.as_str();
+    let field_path = format!("{fully_qualified_name}.{member_name}");
     options
-      .override_struct_field_type
+      .override_struct_field_type_exact
       .iter()
-      .find_map(|o| {
-        let struct_matches = o.struct_regex.is_match(fully_qualified_name);
-        let field_matches = o.field_regex.is_match(member_name);
-        (struct_matches && field_matches).then_some(o.override_type.clone())
+      .find(|o| o.field_path == field_path)
+      .map(|o| o.override_type.clone())
+      .or_else(|| {
+        options.override_struct_field_type.iter().find_map(|o| {
+          let struct_matches = o.struct_regex.is_match(fully_qualified_name);
+          let field_matches = o.field_regex.is_match(member_name);
+          (struct_matches && field_matches).then_some(o.override_type.clone())
+        })
+      })
+      .or_else(|| {
+        wgsl::vertex_format_override(options, fully_qualified_name, member_name)
+          .map(|format| wgsl::vertex_format_rust_type(format).0)
       })
       .unwrap_or(rust_type.tokens)
   }
 
+  /// Whether the field is generated as `WgslBool`, see `wrap_bools` and `wgsl_bool_fields`.
+  /// WGSL `bool`s only have a 4 byte size in structs that aren't host sharable, and keep
+  /// their 1 byte naga layout otherwise.
+  fn is_wgsl_bool_field(
+    options: &WgslBindgenOption,
+    fully_qualified_name: &SmolStr,
+    member_name: &str,
+    naga_type: &naga::Type,
+    is_directly_sharable: bool,
+  ) -> bool {
+    if !options.serialization_strategy.is_bytemuck() {
+      return false;
+    }
+    let matches_regex = || {
+      options
+        .wgsl_bool_fields
+        .iter()
+        .any(|(struct_regex, field_regex)| {
+          struct_regex.is_match(fully_qualified_name) && field_regex.is_match(member_name)
+        })
+    };
+    match naga_type.inner {
+      naga::TypeInner::Scalar(naga::Scalar::BOOL) => {
+        !is_directly_sharable && (options.wrap_bools || matches_regex())
+      }
+      naga::TypeInner::Scalar(naga::Scalar::U32) => matches_regex(),
+      _ => false,
+    }
+  }
+
+  /// Collects the attributes of all `custom_field_attributes` matching the field
+  fn get_field_attributes(
+    options: &WgslBindgenOption,
+    fully_qualified_name: &SmolStr,
+    member_name: &str,
+  ) -> Vec<TokenStream> {
+    let fully_qualified_name = fully_qualified_name.as_str();
+    let attributes = options
+      .custom_field_attributes
+      .iter()
+      .filter(|(struct_regex, field_regex, _)| {
+        struct_regex.is_match(fully_qualified_name) && field_regex.is_match(member_name)
+      })
+      .map(|(_, _, attributes)| attributes.clone())
+      .collect();
+    dedup_tokens(attributes)
+  }
+
   fn create_fold(
     options: &'a WgslBindgenOption,
     fully_qualified_name: SmolStr,
@@ -88,7 +282,8 @@ impl<'a> NagaToRustStructState<'a> {
       let name_ident = Ident::new(member_name, Span::call_site());
       let naga_type = &naga_module.types[naga_member.ty];
 
-      let rust_type = rust_type(None, naga_module, naga_type, &options);
+      let rust_type = rust_type(None, naga_module, naga_type, &options)
+        .expect("structs with unrepresentable fields are skipped");
       let is_rsa = rust_type.size.is_none();
 
       if is_rsa && state.index != naga_members.len() - 1 {
@@ -131,10 +326,12 @@ impl<'a> NagaToRustStructState<'a> {
         }
       };
 
-      let is_current_field_padding = options
-        .custom_padding_field_regexps
-        .iter()
-        .any(|pad_expr| pad_expr.is_match(&member_name));
+      let is_current_field_padding = (options.padding_fields_by_name
+        && is_padding_field_name(member_name))
+        || options
+          .custom_padding_field_regexps
+          .iter()
+          .any(|pad_expr| pad_expr.is_match(&member_name));
 
       // both padding field and built-in fields are handled in the same way
       // skip builtins like @builtin(vertex_index)
@@ -150,8 +347,33 @@ impl<'a> NagaToRustStructState<'a> {
           pad_size_tokens,
         })
       } else {
-        let rust_type =
-          Self::get_rust_type(options, &fully_qualified_name, rust_type, member_name);
+        let is_wgsl_bool = Self::is_wgsl_bool_field(
+          options,
+          &fully_qualified_name,
+          member_name,
+          naga_type,
+          is_directly_sharable,
+        );
+        let default_rust_type = rust_type.tokens.to_string();
+        let rust_type = if is_wgsl_bool {
+          quote!(_root::WgslBool)
+        } else {
+          Self::get_rust_type(options, &fully_qualified_name, rust_type, member_name)
+        };
+        // All directly sharable structs have Init structs with `always_generate_init_struct`,
+        // unless they are mapped to other types.
+        let has_nested_init = is_directly_sharable
+          && options.always_generate_init_struct
+          && matches!(naga_type.inner, naga::TypeInner::Struct { .. })
+          && rust_type.to_string() == default_rust_type
+          && !options.type_map.contains_key(&crate::WgslType::Struct {
+            fully_qualified_name: demangle_str(
+              naga_type.name.as_deref().unwrap_or_default(),
+            )
+            .into(),
+          });
+        let attributes =
+          Self::get_field_attributes(options, &fully_qualified_name, member_name);
 
         RustStructMemberEntry::Field(Field {
           name_ident: name_ident.clone(),
@@ -159,6 +381,9 @@ impl<'a> NagaToRustStructState<'a> {
           naga_type,
           rust_type: syn::Type::Verbatim(rust_type),
           is_rsa,
+          is_wgsl_bool,
+          has_nested_init,
+          attributes,
         })
       };
 
@@ -181,6 +406,12 @@ pub struct Field<'a> {
   pub naga_type: &'a naga::Type,
   pub rust_type: syn::Type,
   pub is_rsa: bool,
+  /// Whether the field is a `WgslBool` that the Init struct takes as a `bool`.
+  pub is_wgsl_bool: bool,
+  /// Whether the field is a generated struct with its own Init struct, which
+  /// `update_from` updates recursively to keep its padding.
+  pub has_nested_init: bool,
+  pub attributes: Vec<TokenStream>,
 }
 
 impl<'a> Field<'a> {
@@ -192,7 +423,50 @@ impl<'a> Field<'a> {
   fn generate_member_definition(&self) -> TokenStream {
     let name = &self.name_ident;
     let ty = &self.rust_type;
-    quote!(pub #name: #ty)
+    let attributes = &self.attributes;
+    quote!(#(#attributes)* pub #name: #ty)
+  }
+
+  fn generate_init_member_instantiate(
+    &self,
+    init_struct_var_name: &Ident,
+  ) -> TokenStream {
+    let name = &self.name_ident;
+    if self.is_wgsl_bool {
+      quote!(#name: _root::WgslBool::new(#init_struct_var_name.#name))
+    } else {
+      self.generate_member_instantiate(init_struct_var_name)
+    }
+  }
+
+  fn generate_init_member_from(&self, struct_var_name: &Ident) -> TokenStream {
+    let name = &self.name_ident;
+    if self.is_wgsl_bool {
+      quote!(#name: #struct_var_name.#name.get())
+    } else {
+      self.generate_member_instantiate(struct_var_name)
+    }
+  }
+
+  fn generate_init_member_update(&self, init_struct_var_name: &Ident) -> TokenStream {
+    let name = &self.name_ident;
+    if self.is_wgsl_bool {
+      quote!(self.#name = _root::WgslBool::new(#init_struct_var_name.#name);)
+    } else if self.has_nested_init {
+      quote!(self.#name.update_from(&#init_struct_var_name.#name.into());)
+    } else {
+      quote!(self.#name = #init_struct_var_name.#name;)
+    }
+  }
+
+  fn generate_init_member_definition(&self) -> TokenStream {
+    if self.is_wgsl_bool {
+      let name = &self.name_ident;
+      let attributes = &self.attributes;
+      quote!(#(#attributes)* pub #name: bool)
+    } else {
+      self.generate_member_definition()
+    }
   }
 
   fn generate_fn_new_param(&self) -> TokenStream {
@@ -200,6 +474,25 @@ impl<'a> Field<'a> {
     let ty = &self.rust_type;
     quote!(#name: #ty)
   }
+
+  fn parsed_rust_type(&self) -> Option<syn::Type> {
+    syn::parse2::<syn::Type>(self.rust_type.to_token_stream()).ok()
+  }
+
+  fn supports_derive_default(&self) -> bool {
+    self
+      .parsed_rust_type()
+      .is_some_and(|ty| type_supports_derive_default(&ty))
+  }
+
+  fn generate_default_instantiate(&self) -> TokenStream {
+    let name = &self.name_ident;
+    let value = self
+      .parsed_rust_type()
+      .map(|ty| default_value_for_type(&ty))
+      .unwrap_or_else(|| quote!(Default::default()));
+    quote!(#name: #value)
+  }
 }
 
 #[derive(IsVariant)]
@@ -243,6 +536,7 @@ pub struct RustStructBuilder<'a> {
   has_rts_array: bool,
   naga_module: &'a naga::Module,
   layout: naga::proc::TypeLayout,
+  custom_alignment: Option<naga::proc::Alignment>,
   options: &'a WgslBindgenOption,
 }
 
@@ -336,14 +630,57 @@ impl<'a> RustStructBuilder<'a> {
 
     let mut init_struct_members = vec![];
     let mut mem_assignments = vec![];
+    let mut init_mem_assignments = vec![];
+    let mut mem_updates = vec![];
 
     let init_var_name = Ident::new("self", Span::call_site());
+    let data_var_name = Ident::new("data", Span::call_site());
+    let update_var_name = Ident::new("init", Span::call_site());
+
+    let (custom_derives, custom_attributes) = self.custom_attributes();
+    let (serde_derives, serde_attribute) =
+      serde_derives(self.options, self.derives_serde());
+    let mut derives = {
+      use StdDerive::*;
+      self.standard_derives(&[Debug, PartialEq, Clone, Copy, Eq, Hash])
+    };
+    derives.extend(serde_derives);
+    let mut derives = merge_derives(derives, &custom_derives);
+
+    // The init struct has no padding, but a custom `Default` derive still fails for
+    // long arrays, so it is implemented manually like for the struct itself.
+    let init_fields = || {
+      self.members.iter().filter_map(|m| match m {
+        RustStructMemberEntry::Field(field) => Some(field),
+        RustStructMemberEntry::Padding(_) => None,
+      })
+    };
+    let init_default_impl = if derives.iter().any(|d| derive_name(d) == "Default")
+      && !init_fields().all(|field| field.supports_derive_default())
+    {
+      derives.retain(|d| derive_name(d) != "Default");
+      let member_defaults =
+        init_fields().map(|field| field.generate_default_instantiate());
+      quote! {
+        #impl_fragment Default for #init_struct_name_in_usage {
+          fn default() -> Self {
+            Self {
+              #(#member_defaults),*
+            }
+          }
+        }
+      }
+    } else {
+      quote!()
+    };
 
     for entry in self.members.iter() {
       match entry {
         RustStructMemberEntry::Field(field) => {
-          init_struct_members.push(field.generate_member_definition());
-          mem_assignments.push(field.generate_member_instantiate(&init_var_name));
+          init_struct_members.push(field.generate_init_member_definition());
+          mem_assignments.push(field.generate_init_member_instantiate(&init_var_name));
+          init_mem_assignments.push(field.generate_init_member_from(&data_var_name));
+          mem_updates.push(field.generate_init_member_update(&update_var_name));
         }
         RustStructMemberEntry::Padding(padding) => {
           mem_assignments.push(padding.generate_member_instantiate())
@@ -353,11 +690,15 @@ impl<'a> RustStructBuilder<'a> {
 
     quote! {
       #[repr(C)]
-      #[derive(Debug, PartialEq, Clone, Copy)]
+      #[derive(#(#derives),*)]
+      #serde_attribute
+      #(#custom_attributes)*
       #visibility struct #init_struct_name_def {
         #(#init_struct_members),*
       }
 
+      #init_default_impl
+
       #impl_fragment #init_struct_name_in_usage {
         pub const fn build(&self) -> #struct_name_in_usage {
           #struct_name {
@@ -371,6 +712,21 @@ impl<'a> RustStructBuilder<'a> {
           data.build()
         }
       }
+
+      #impl_fragment From<#struct_name_in_usage> for #init_struct_name_in_usage {
+        fn from(data: #struct_name_in_usage) -> Self {
+          Self {
+            #(#init_mem_assignments),*
+          }
+        }
+      }
+
+      #impl_fragment #struct_name_in_usage {
+        /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+        pub fn update_from(&mut self, init: &#init_struct_name_in_usage) {
+          #(#mem_updates)*
+        }
+      }
     }
   }
 
@@ -433,6 +789,8 @@ impl<'a> RustStructBuilder<'a> {
             is_rsa: is_rts,
             naga_member: member,
             naga_type,
+            attributes,
+            ..
           } = field;
 
           let doc_comment = if self.is_directly_shareable() {
@@ -460,6 +818,7 @@ impl<'a> RustStructBuilder<'a> {
           quote! {
             #doc_comment
             #runtime_size_attribute
+            #(#attributes)*
             pub #name: #rust_type
           }
         }
@@ -470,28 +829,181 @@ impl<'a> RustStructBuilder<'a> {
     members
   }
 
-  fn build_derives(&self) -> Vec<TokenStream> {
+  /// Splits the attributes of all `custom_derives` matching this struct into the
+  /// paths of the `#[derive(..)]` attributes and the remaining attributes, so the
+  /// derives can be merged with the built-in ones.
+  fn custom_attributes(&self) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    let fully_qualified_name = self.item_path.get_fully_qualified_name();
     let mut derives = Vec::new();
-    derives.push(quote!(Debug));
-    derives.push(quote!(PartialEq));
-    derives.push(quote!(Clone));
+    let mut attributes = Vec::new();
+
+    for (_, tokens) in self
+      .options
+      .custom_derives
+      .iter()
+      .filter(|(regex, _)| regex.is_match(&fully_qualified_name))
+    {
+      let Ok(attrs) =
+        syn::parse::Parser::parse2(syn::Attribute::parse_outer, tokens.clone())
+      else {
+        attributes.push(tokens.clone());
+        continue;
+      };
+
+      for attr in attrs {
+        let paths = attr.path().is_ident("derive").then(|| {
+          attr.parse_args_with(
+            syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+          )
+        });
+        match paths {
+          Some(Ok(paths)) => derives.extend(paths.iter().map(|p| p.to_token_stream())),
+          _ => attributes.push(attr.to_token_stream()),
+        }
+      }
+    }
+
+    (dedup_tokens(derives), dedup_tokens(attributes))
+  }
+
+  fn supports_derive_default(&self) -> bool {
+    self.members.iter().all(|m| match m {
+      RustStructMemberEntry::Field(field) => field.supports_derive_default(),
+      RustStructMemberEntry::Padding(padding) => padding.supports_derive_default(),
+    })
+  }
+
+  /// Builds a manual `impl Default` for structs that can not derive it, e.g.
+  /// because they contain padding or arrays longer than 32 elements.
+  fn build_default_impl(&self) -> TokenStream {
+    if !self.implements_default_manually() {
+      return quote!();
+    }
+
+    let struct_name_in_usage = self.struct_name_in_usage_fragment();
+    let impl_fragment = self.impl_trait_for_fragment();
+
+    let member_defaults = self.members.iter().map(|m| match m {
+      RustStructMemberEntry::Field(field) => field.generate_default_instantiate(),
+      RustStructMemberEntry::Padding(padding) => padding.generate_member_instantiate(),
+    });
+
+    quote! {
+      #impl_fragment Default for #struct_name_in_usage {
+        fn default() -> Self {
+          Self {
+            #(#member_defaults),*
+          }
+        }
+      }
+    }
+  }
+
+  /// The standard derives of [`WgslBindgenOption::standard_derives`] in `order`.
+  fn standard_derives(&self, order: &[StdDerive]) -> Vec<TokenStream> {
+    order
+      .iter()
+      .filter(|derive| self.options.standard_derives.contains(**derive))
+      .map(|derive| {
+        let derive = Ident::new(&format!("{derive:?}"), Span::call_site());
+        quote!(#derive)
+      })
+      .collect()
+  }
+
+  fn derives_default(&self) -> bool {
+    self.options.derive_default
+      || self.options.standard_derives.contains(StdDerive::Default)
+  }
+
+  /// Whether `Default` is implemented by [`Self::build_default_impl`] instead of derived.
+  fn implements_default_manually(&self) -> bool {
+    self.derives_default() && !self.supports_derive_default()
+  }
+
+  /// Checks that the generated code can derive the standard derives for the struct.
+  pub fn check_standard_derives(&self) -> Result<(), CreateModuleError> {
+    let derives = self.options.standard_derives;
+    let error = |derive: StdDerive, reason: String| {
+      Err(CreateModuleError::IncompatibleStandardDerive {
+        name: self.item_path.get_fully_qualified_name().to_string(),
+        derive: format!("{derive:?}"),
+        reason,
+      })
+    };
+
+    if derives.contains(StdDerive::Copy) && !derives.contains(StdDerive::Clone) {
+      return error(StdDerive::Copy, "`Clone` is not derived".to_string());
+    }
+    if derives.contains(StdDerive::Eq) && !derives.contains(StdDerive::PartialEq) {
+      return error(StdDerive::Eq, "`PartialEq` is not derived".to_string());
+    }
+    if self.options.serialization_strategy.is_bytemuck()
+      && !derives.contains(StdDerive::Copy)
+    {
+      return error(
+        StdDerive::Copy,
+        "`Copy` is required by `bytemuck::Pod`, but not derived".to_string(),
+      );
+    }
+
+    for derive in [StdDerive::Eq, StdDerive::Hash] {
+      if !derives.contains(derive) {
+        continue;
+      }
+      let float_field = self.members.iter().find_map(|m| match m {
+        RustStructMemberEntry::Field(field)
+          if type_contains_float(self.naga_module, &field.naga_type.inner) =>
+        {
+          Some(&field.name_ident)
+        }
+        _ => None,
+      });
+      if let Some(field) = float_field {
+        return error(derive, format!("field `{field}` contains floats"));
+      }
+    }
+
+    Ok(())
+  }
+
+  fn derives_serde(&self) -> bool {
+    let fully_qualified_name = self.item_path.get_fully_qualified_name();
+    self.options.derive_serde
+      || self
+        .options
+        .serde_struct_regexps
+        .iter()
+        .any(|regex| regex.is_match(&fully_qualified_name))
+  }
+
+  fn build_derives(&self) -> Vec<TokenStream> {
+    use StdDerive::*;
+    let mut derives = self.standard_derives(&[Debug, PartialEq, Clone]);
 
     match self.options.serialization_strategy {
       WgslTypeSerializeStrategy::Bytemuck => {
-        derives.push(quote!(Copy));
+        derives.extend(self.standard_derives(&[Copy]));
       }
       WgslTypeSerializeStrategy::Encase => {
         if !self.has_rts_array {
-          derives.push(quote!(Copy));
+          derives.extend(self.standard_derives(&[Copy]));
         }
-        derives.push(quote!(encase::ShaderType));
       }
     }
-    if self.options.derive_serde {
-      derives.push(quote!(serde::Serialize));
-      derives.push(quote!(serde::Deserialize));
+    derives.extend(self.standard_derives(&[Eq, Hash]));
+    if self.options.serialization_strategy.is_encase() {
+      derives.push(quote!(encase::ShaderType));
+    }
+    if self.derives_default() && self.supports_derive_default() {
+      derives.push(quote!(Default));
+    }
+    derives.extend(serde_derives(self.options, self.derives_serde()).0);
+    let (mut custom_derives, _) = self.custom_attributes();
+    if self.implements_default_manually() {
+      custom_derives.retain(|derive| derive_name(derive) != "Default");
     }
-    derives
+    merge_derives(derives, &custom_derives)
   }
 
   fn build_layout_assertion(
@@ -524,7 +1036,7 @@ impl<'a> RustStructBuilder<'a> {
       })
       .collect();
 
-    if self.is_directly_shareable() {
+    if self.is_directly_shareable() && !self.options.skip_layout_assertions {
       // Assert that the Rust layout matches the WGSL layout.
       // Enable for bytemuck since it uses the Rust struct's memory layout.
       let struct_size = custom_alignment
@@ -549,6 +1061,198 @@ impl<'a> RustStructBuilder<'a> {
     }
   }
 
+  fn build_layout_test(&self) -> TokenStream {
+    if !self.options.emit_layout_tests || !self.is_host_sharable || self.has_rts_array {
+      return quote!();
+    }
+
+    let fully_qualified_name_str = self.item_path.get_fully_qualified_name();
+    let struct_name = syn::parse_str::<TokenStream>(&fully_qualified_name_str).unwrap();
+    let test_name = format_ident!(
+      "{}_layout",
+      sanitized_upper_snake_case(&fully_qualified_name_str).to_lowercase()
+    );
+
+    match self.options.serialization_strategy {
+      WgslTypeSerializeStrategy::Bytemuck => {
+        let size = self
+          .custom_alignment
+          .map(|alignment| alignment.round_up(self.layout.size))
+          .unwrap_or(self.layout.size);
+        let size = Index::from(size as usize);
+        let alignment = self.custom_alignment.unwrap_or(self.layout.alignment) * 1u32;
+        let alignment = Index::from(alignment as usize);
+
+        let assert_member_offsets = self
+          .members
+          .iter()
+          .filter_map(|m| match m {
+            RustStructMemberEntry::Field(field) => Some(field.naga_member),
+            RustStructMemberEntry::Padding(_) => None,
+          })
+          .map(|m| {
+            let name = Ident::new(m.name.as_ref().unwrap(), Span::call_site());
+            let wgsl_offset = Index::from(m.offset as usize);
+            quote!(assert_eq!(std::mem::offset_of!(#struct_name, #name), #wgsl_offset);)
+          });
+
+        quote! {
+          #[test]
+          fn #test_name() {
+            assert_eq!(std::mem::size_of::<#struct_name>(), #size);
+            assert_eq!(std::mem::align_of::<#struct_name>(), #alignment);
+            #(#assert_member_offsets)*
+
+            let value: #struct_name = bytemuck::Zeroable::zeroed();
+            let bytes = bytemuck::bytes_of(&value);
+            assert_eq!(bytes.len(), #size);
+            let read = bytemuck::pod_read_unaligned::<#struct_name>(bytes);
+            assert_eq!(bytemuck::bytes_of(&read), bytes);
+          }
+        }
+      }
+      WgslTypeSerializeStrategy::Encase => {
+        let size = Index::from(self.layout.size as usize);
+
+        quote! {
+          #[test]
+          fn #test_name() {
+            assert_eq!(<#struct_name as encase::ShaderSize>::SHADER_SIZE.get(), #size);
+          }
+        }
+      }
+    }
+  }
+
+  fn build_size_constants(&self) -> TokenStream {
+    if !self.options.struct_size_constants || !self.is_host_sharable || self.has_rts_array
+    {
+      return quote!();
+    }
+
+    let struct_name = self.name_ident();
+    let size = self
+      .custom_alignment
+      .map(|alignment| alignment.round_up(self.layout.size))
+      .unwrap_or(self.layout.size);
+    let size = Index::from(size as usize);
+
+    quote! {
+      impl #struct_name {
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const SIZE: u64 = #size;
+        /// The size of the struct rounded up to 256 bytes, the default
+        /// `min_uniform_buffer_offset_alignment` and `min_storage_buffer_offset_alignment`.
+        pub const ALIGNED_SIZE_256: u64 = Self::aligned_size(256);
+
+        /// The size of the struct rounded up to a multiple of `alignment`, i.e. the stride
+        /// of instances placed at aligned offsets in a buffer.
+        pub const fn aligned_size(alignment: u64) -> u64 {
+          Self::SIZE.next_multiple_of(alignment)
+        }
+
+        /// The offset of the instance at `index` in a buffer of instances placed at
+        /// offsets aligned to `alignment`.
+        pub const fn offset_of_index(index: u64, alignment: u64) -> u64 {
+          index * Self::aligned_size(alignment)
+        }
+      }
+    }
+  }
+
+  fn build_array_length_constants(&self) -> TokenStream {
+    if !self.options.array_length_constants {
+      return quote!();
+    }
+
+    let constants = self
+      .members
+      .iter()
+      .filter_map(|entry| match entry {
+        RustStructMemberEntry::Field(field) => Some(field),
+        RustStructMemberEntry::Padding(_) => None,
+      })
+      .filter_map(|field| {
+        let len = wgsl::fixed_array_length(&field.naga_type.inner)?;
+        let member_name = field.naga_member.name.as_ref()?;
+        let const_name = wgsl::array_length_const_name(member_name);
+        let len = Index::from(len as usize);
+        let doc = format!(" The length of the `{member_name}` array.");
+        Some(quote! {
+          #[doc = #doc]
+          pub const #const_name: usize = #len;
+        })
+      })
+      .collect::<Vec<_>>();
+
+    if constants.is_empty() {
+      return quote!();
+    }
+
+    let impl_fragment = self.impl_trait_for_fragment();
+    let struct_name_in_usage = self.struct_name_in_usage_fragment();
+
+    quote! {
+      #impl_fragment #struct_name_in_usage {
+        #(#constants)*
+      }
+    }
+  }
+
+  fn build_field_offset_constants(&self) -> TokenStream {
+    if !self.options.field_offset_constants {
+      return quote!();
+    }
+
+    let fields = self
+      .members
+      .iter()
+      .filter_map(|entry| match entry {
+        RustStructMemberEntry::Field(field) => {
+          let name = field.naga_member.name.as_ref()?;
+          Some((name, Index::from(field.naga_member.offset as usize)))
+        }
+        RustStructMemberEntry::Padding(_) => None,
+      })
+      .collect::<Vec<_>>();
+
+    let offsets = fields.iter().map(|(name, offset)| quote!((#name, #offset)));
+    let offset_constants = fields.iter().map(|(name, offset)| {
+      let const_name = Ident::new(
+        &format!("{}_OFFSET", sanitized_upper_snake_case(name)),
+        Span::call_site(),
+      );
+      let doc = format!(" The byte offset of `{name}` with the WGSL layout.");
+      quote! {
+        #[doc = #doc]
+        pub const #const_name: u64 = #offset;
+      }
+    });
+
+    // The size of a struct ending in a runtime-sized array isn't fixed.
+    let gpu_size = if self.has_rts_array {
+      quote!()
+    } else {
+      let size = Index::from(self.layout.size as usize);
+      quote! {
+        /// The size of the struct in bytes with the WGSL layout.
+        pub const GPU_SIZE: u64 = #size;
+      }
+    };
+
+    let impl_fragment = self.impl_trait_for_fragment();
+    let struct_name_in_usage = self.struct_name_in_usage_fragment();
+
+    quote! {
+      #impl_fragment #struct_name_in_usage {
+        /// The names and byte offsets of the fields with the WGSL layout.
+        pub const FIELD_OFFSETS: &[(&str, u64)] = &[#(#offsets),*];
+        #(#offset_constants)*
+        #gpu_size
+      }
+    }
+  }
+
   pub fn build_bytemuck_impls(&self) -> TokenStream {
     let struct_name_in_usage = self.fully_qualified_struct_name_in_usage_fragment();
     let impl_fragment = self.impl_trait_for_fragment();
@@ -579,22 +1283,10 @@ impl<'a> RustStructBuilder<'a> {
       && self.options.serialization_strategy == WgslTypeSerializeStrategy::Bytemuck;
 
     let derives = self.build_derives();
+    let (_, serde_attribute) = serde_derives(self.options, self.derives_serde());
+    let (_, custom_attributes) = self.custom_attributes();
 
-    let fully_qualified_name = self.item_path.get_fully_qualified_name();
-    let fully_qualified_name = fully_qualified_name.as_str();
-    let custom_alignment = self
-      .options
-      .override_struct_alignment
-      .iter()
-      .find_map(|struct_align| {
-        struct_align
-          .struct_regex
-          .is_match(fully_qualified_name)
-          .then_some(struct_align.alignment as u32)
-      })
-      .map(|align| naga::proc::Alignment::new(align))
-      .flatten();
-
+    let custom_alignment = self.custom_alignment;
     let alignment = custom_alignment.unwrap_or(self.layout.alignment) * 1u32;
     let alignment = Index::from(alignment as usize);
     let repr_c = if !has_rts_array {
@@ -610,24 +1302,35 @@ impl<'a> RustStructBuilder<'a> {
     let fields = self.build_fields();
     let struct_new_fn = self.build_fn_new();
     let init_struct = self.build_init_struct();
+    let default_impl = self.build_default_impl();
+    let size_constants = self.build_size_constants();
+    let array_length_constants = self.build_array_length_constants();
+    let field_offset_constants = self.build_field_offset_constants();
     let assert_layout = self.build_layout_assertion(custom_alignment);
+    let layout_test = self.build_layout_test();
     let unsafe_bytemuck_pod_impl = self.build_bytemuck_impls();
     let fully_qualified_name = self.item_path.get_fully_qualified_name();
     let visibility = self.options.type_visibility.generate_quote();
 
-    vec![
+    let mut items = vec![
       RustItem::new(
         RustItemType::TypeDefs | RustItemType::TypeImpls,
         self.item_path.clone(),
         quote! {
           #repr_c
           #[derive(#(#derives),*)]
+          #serde_attribute
+          #(#custom_attributes)*
           #visibility struct #struct_name_def {
               #(#fields),*
           }
 
           #struct_new_fn
           #init_struct
+          #default_impl
+          #size_constants
+          #array_length_constants
+          #field_offset_constants
         },
       ),
       RustItem::new(
@@ -640,7 +1343,18 @@ impl<'a> RustStructBuilder<'a> {
         RustItemPath::new(MOD_BYTEMUCK_IMPLS.into(), fully_qualified_name.clone()),
         unsafe_bytemuck_pod_impl,
       ),
-    ]
+    ];
+
+    // Only add the test module to the output if it has tests.
+    if !layout_test.is_empty() {
+      items.push(RustItem::new(
+        RustItemType::ConstVarDecls.into(),
+        RustItemPath::new(MOD_LAYOUT_TESTS.into(), fully_qualified_name),
+        layout_test,
+      ));
+    }
+
+    items
   }
 
   pub fn from_naga(
@@ -653,12 +1367,21 @@ impl<'a> RustStructBuilder<'a> {
     is_host_sharable: bool,
     has_rts_array: bool,
   ) -> Self {
+    let custom_alignment =
+      custom_struct_alignment(options, &item_path.get_fully_qualified_name());
+
+    // The last member is padded up to the overridden alignment, so that the Rust struct
+    // has no implicit trailing padding and its size is the stride of arrays of it.
+    let layout_size = custom_alignment
+      .map(|alignment| alignment.round_up(layout.size))
+      .unwrap_or(layout.size);
+
     let members = RustStructMemberEntry::from_naga(
       options,
       item_path,
       naga_members,
       naga_module,
-      layout.size as usize,
+      layout_size as usize,
       is_directly_sharable,
     );
 
@@ -670,6 +1393,7 @@ impl<'a> RustStructBuilder<'a> {
       options: &options,
       has_rts_array,
       layout,
+      custom_alignment,
     }
   }
 }
@@ -1,15 +1,49 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
 
 use naga::{Handle, Type};
-
-use crate::quote_gen::{RustItem, RustItemPath, RustStructBuilder};
-use crate::{WgslBindgenOption, WgslTypeSerializeStrategy};
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::Ident;
+
+use crate::bevy_util::demangle_str;
+use crate::generate::bind_group::SharedBindGroupsInput;
+use crate::generate::entry::vertex_struct_impls;
+use crate::quote_gen::{
+  find_unrepresentable_type, rust_type, serde_derives, RustItem, RustItemPath,
+  RustItemType, RustStructBuilder, UnrepresentableType, MOD_STRUCT_ASSERTIONS,
+};
+use crate::{
+  sanitized_upper_snake_case, wgsl, CreateModuleError, FastIndexMap, WgslBindgenOption,
+  WgslBindgenWarning, WgslEntryResult, WgslTypeSerializeStrategy,
+};
 
 pub fn structs_items(
   invoking_entry_module: &str,
   module: &naga::Module,
   options: &WgslBindgenOption,
-) -> Vec<RustItem> {
+  struct_buffer_usages: &FastIndexMap<String, wgpu::BufferUsages>,
+  warnings: &mut Vec<WgslBindgenWarning>,
+) -> Result<Vec<RustItem>, CreateModuleError> {
+  structs_items_where(
+    invoking_entry_module,
+    module,
+    options,
+    struct_buffer_usages,
+    warnings,
+    |_| true,
+  )
+}
+
+/// Like [`structs_items`], but only for the structs whose paths match `include`.
+pub fn structs_items_where(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+  struct_buffer_usages: &FastIndexMap<String, wgpu::BufferUsages>,
+  warnings: &mut Vec<WgslBindgenWarning>,
+  include: impl Fn(&RustItemPath) -> bool,
+) -> Result<Vec<RustItem>, CreateModuleError> {
   // Initialize the layout calculator provided by naga.
   let mut layouter = naga::proc::Layouter::default();
   layouter.update(module.to_ctx()).unwrap();
@@ -19,6 +53,13 @@ pub fn structs_items(
     add_types_recursive(&mut global_variable_types, module, g.1.ty);
   }
 
+  let mut storage_types = HashSet::new();
+  for (_, g) in module.global_variables.iter() {
+    if matches!(g.space, naga::AddressSpace::Storage { .. }) {
+      add_types_recursive(&mut storage_types, module, g.ty);
+    }
+  }
+
   // Create matching Rust structs for WGSL structs.
   // This is a UniqueArena, so each struct will only be generated once.
   module
@@ -39,18 +80,34 @@ pub fn structs_items(
           .any(|e| e.function.arguments.iter().any(|a| a.ty == *h))
         || global_variable_types.contains(h)
     })
-    .flat_map(|(t_handle, ty)| {
+    .map(|(t_handle, ty)| {
       if let naga::TypeInner::Struct { members, .. } = &ty.inner {
         let rust_item_path =
           RustItemPath::from_mangled(ty.name.as_ref().unwrap(), invoking_entry_module);
 
-        // skip if using custom struct mapping
-        if options.type_map.contains_key(&crate::WgslType::Struct {
+        // skip if using custom struct mapping or skipped entirely
+        let is_mapped = options.type_map.contains_key(&crate::WgslType::Struct {
           fully_qualified_name: rust_item_path.get_fully_qualified_name().into(),
-        }) {
-          Vec::new()
+        });
+        if !include(&rust_item_path)
+          || is_mapped
+          || is_skipped_struct(options, &rust_item_path)
+        {
+          Ok(Vec::new())
+        } else if let Some((field, ty)) = unrepresentable_field(module, members) {
+          // Bindings and vertex inputs using the struct are errors, see
+          // `check_unrepresentable_types`, so the struct isn't needed.
+          let warning = WgslBindgenWarning::UnrepresentableStructSkipped {
+            name: rust_item_path.get_fully_qualified_name().to_string(),
+            field: field.to_string(),
+            ty: ty.to_string(),
+          };
+          if !warnings.contains(&warning) {
+            warnings.push(warning);
+          }
+          Ok(Vec::new())
         } else {
-          rust_struct(
+          let mut items = rust_struct(
             &rust_item_path,
             members,
             &layouter,
@@ -58,13 +115,38 @@ pub fn structs_items(
             module,
             options,
             &global_variable_types,
-          )
+          )?;
+          items.extend(dispatch_indirect_items(
+            &rust_item_path,
+            members,
+            module,
+            options,
+            storage_types.contains(&t_handle),
+            warnings,
+          ));
+          items.extend(runtime_sized_array_items(
+            invoking_entry_module,
+            &rust_item_path,
+            members,
+            module,
+            options,
+            global_variable_types.contains(&t_handle),
+          ));
+          items.extend(buffer_helpers_items(
+            &rust_item_path,
+            members,
+            module,
+            options,
+            struct_buffer_usages,
+          ));
+          Ok(items)
         }
       } else {
-        Vec::new()
+        Ok(Vec::new())
       }
     })
-    .collect()
+    .collect::<Result<Vec<_>, _>>()
+    .map(|items| items.into_iter().flatten().collect())
 }
 
 fn rust_struct(
@@ -75,7 +157,7 @@ fn rust_struct(
   naga_module: &naga::Module,
   options: &WgslBindgenOption,
   global_variable_types: &HashSet<Handle<Type>>,
-) -> Vec<RustItem> {
+) -> Result<Vec<RustItem>, CreateModuleError> {
   let layout = layouter[t_handle];
 
   // Assume types used in global variables are host shareable and require validation.
@@ -101,7 +183,1000 @@ fn rust_struct(
     is_host_sharable,
     has_rts_array,
   );
-  builder.build()
+  builder.check_standard_derives()?;
+  Ok(builder.build())
+}
+
+/// Generates the conversions between the struct and `wgpu::util::DispatchIndirectArgs` if
+/// the struct is used for indirect dispatches, together with assertions that the struct has
+/// the 12-byte layout of the indirect arguments.
+fn dispatch_indirect_items(
+  rust_item_path: &RustItemPath,
+  members: &[naga::StructMember],
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+  is_storage: bool,
+  warnings: &mut Vec<WgslBindgenWarning>,
+) -> Vec<RustItem> {
+  let fully_qualified_name = rust_item_path.get_fully_qualified_name();
+  let is_u32 = |m: &naga::StructMember| {
+    matches!(
+      module.types[m.ty].inner,
+      naga::TypeInner::Scalar(naga::Scalar {
+        kind: naga::ScalarKind::Uint,
+        width: 4
+      })
+    )
+  };
+
+  let is_xyz = members.len() == 3
+    && members
+      .iter()
+      .zip(["x", "y", "z"])
+      .all(|(m, name)| m.name.as_deref() == Some(name) && is_u32(m));
+  let is_matched = options
+    .dispatch_indirect_struct_regex
+    .as_ref()
+    .is_some_and(|r| r.is_match(&fully_qualified_name));
+  if !(is_matched || is_storage && is_xyz) {
+    return Vec::new();
+  }
+
+  let Some(xyz) = members.get(..3).filter(|xyz| xyz.iter().all(is_u32)) else {
+    let warning = WgslBindgenWarning::InvalidDispatchIndirectStruct {
+      name: fully_qualified_name.to_string(),
+    };
+    if !warnings.contains(&warning) {
+      warnings.push(warning);
+    }
+    return Vec::new();
+  };
+  let [x, y, z] =
+    [0, 1, 2].map(|i| Ident::new(xyz[i].name.as_ref().unwrap(), Span::call_site()));
+
+  let name = Ident::new(&rust_item_path.name, Span::call_site());
+
+  // Extra members can't be set from the indirect arguments.
+  let from_args = (members.len() == 3).then(|| {
+    quote! {
+        impl From<wgpu::util::DispatchIndirectArgs> for #name {
+            fn from(args: wgpu::util::DispatchIndirectArgs) -> Self {
+                Self {
+                    #x: args.x,
+                    #y: args.y,
+                    #z: args.z,
+                }
+            }
+        }
+    }
+  });
+
+  let struct_path =
+    syn::parse_str::<proc_macro2::TokenStream>(&fully_qualified_name).unwrap();
+  let assertion_name = format_ident!(
+    "{}_DISPATCH_INDIRECT_ASSERTS",
+    sanitized_upper_snake_case(&fully_qualified_name)
+  );
+  let message = format!(
+    "`{fully_qualified_name}` doesn't have the 12-byte layout of `wgpu::util::DispatchIndirectArgs`"
+  );
+
+  vec![
+    RustItem::new(
+      RustItemType::TraitImpls.into(),
+      rust_item_path.clone(),
+      quote! {
+          impl From<#name> for wgpu::util::DispatchIndirectArgs {
+              fn from(args: #name) -> Self {
+                  Self {
+                      x: args.#x,
+                      y: args.#y,
+                      z: args.#z,
+                  }
+              }
+          }
+
+          #from_args
+      },
+    ),
+    RustItem::new(
+      RustItemType::ConstVarDecls.into(),
+      RustItemPath::new(
+        MOD_STRUCT_ASSERTIONS.into(),
+        format!("{fully_qualified_name}::DispatchIndirectArgs").into(),
+      ),
+      quote! {
+          const #assertion_name: () = {
+              assert!(std::mem::offset_of!(#struct_path, #x) == 0, #message);
+              assert!(std::mem::offset_of!(#struct_path, #y) == 4, #message);
+              assert!(std::mem::offset_of!(#struct_path, #z) == 8, #message);
+              assert!(std::mem::size_of::<#struct_path>() == 12, #message);
+          };
+      },
+    ),
+  ]
+}
+
+/// Generates the sizes of the struct and a function writing it into a byte buffer if the
+/// struct ends in a runtime-sized array, since the generated Rust struct can only hold a
+/// fixed number of elements.
+fn runtime_sized_array_items(
+  invoking_entry_module: &str,
+  rust_item_path: &RustItemPath,
+  members: &[naga::StructMember],
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+  is_host_sharable: bool,
+) -> Vec<RustItem> {
+  if !is_host_sharable || !options.serialization_strategy.is_bytemuck() {
+    return Vec::new();
+  }
+  let Some((last, header_members)) = members.split_last() else {
+    return Vec::new();
+  };
+  let naga::TypeInner::Array {
+    base,
+    size: naga::ArraySize::Dynamic,
+    stride,
+  } = module.types[last.ty].inner
+  else {
+    return Vec::new();
+  };
+
+  let gctx = module.to_ctx();
+  let name = Ident::new(&rust_item_path.name, Span::call_site());
+  let fixed_size = Literal::u64_unsuffixed(last.offset as u64);
+  let element_stride = Literal::u64_unsuffixed(stride as u64);
+  let element_size =
+    Literal::usize_unsuffixed(module.types[base].inner.size(gctx) as usize);
+  let element_type =
+    rust_type(Some(invoking_entry_module), module, &module.types[base], options)
+      .expect("structs with unrepresentable fields are skipped");
+  let array_name = last.name.as_deref().unwrap_or_default();
+
+  let write_header_members = header_members.iter().map(|m| {
+    let member_name = Ident::new(m.name.as_ref().unwrap(), Span::call_site());
+    // The Rust type can be larger than the WGSL type, e.g. `glam::Vec3A` for `vec3<f32>`.
+    let size = module.types[m.ty].inner.size(gctx) as usize;
+    let start = Literal::usize_unsuffixed(m.offset as usize);
+    let end = Literal::usize_unsuffixed(m.offset as usize + size);
+    let size = Literal::usize_unsuffixed(size);
+    quote! {
+      let offset = std::mem::offset_of!(Self, #member_name);
+      out[#start..#end].copy_from_slice(&header_bytes[offset..offset + #size]);
+    }
+  });
+
+  // Mapped 3 component vectors and matrices like `glam::Vec3A` have padding and can't be
+  // viewed as bytes with bytemuck, so `write_into` is only generated for other elements.
+  let is_padded_mapped_type = match module.types[base].inner {
+    naga::TypeInner::Vector {
+      size: naga::VectorSize::Tri,
+      ..
+    }
+    | naga::TypeInner::Matrix {
+      rows: naga::VectorSize::Tri,
+      ..
+    } => !element_type.tokens.to_string().starts_with('['),
+    _ => false,
+  };
+
+  let write_into = if is_padded_mapped_type {
+    quote!()
+  } else {
+    quote! {
+      /// Writes the fields of `header` followed by `elements` into `out` with the WGSL
+      /// layout, zeroing any padding.
+      ///
+      /// Panics if `out` is smaller than [`Self::buffer_size`] for the elements.
+      pub fn write_into(header: &Self, elements: &[#element_type], out: &mut [u8]) {
+        let size = Self::buffer_size(elements.len() as u64) as usize;
+        let out = &mut out[..size];
+        out.fill(0);
+
+        let header_bytes = bytemuck::bytes_of(header);
+        #(#write_header_members)*
+
+        for (i, element) in elements.iter().enumerate() {
+          let offset = (Self::FIXED_SIZE + Self::ELEMENT_STRIDE * i as u64) as usize;
+          out[offset..offset + #element_size]
+            .copy_from_slice(&bytemuck::bytes_of(element)[..#element_size]);
+        }
+      }
+    }
+  };
+
+  let fixed_size_doc = format!(
+    " The size in bytes of the fields before the runtime-sized `{array_name}` array."
+  );
+  let element_stride_doc = format!(
+    " The stride in bytes of the elements of the runtime-sized `{array_name}` array."
+  );
+
+  vec![RustItem::new(
+    RustItemType::TypeImpls.into(),
+    rust_item_path.clone(),
+    quote! {
+      impl #name<0> {
+        #[doc = #fixed_size_doc]
+        pub const FIXED_SIZE: u64 = #fixed_size;
+        #[doc = #element_stride_doc]
+        pub const ELEMENT_STRIDE: u64 = #element_stride;
+
+        /// The size in bytes of a buffer holding the fields and `element_count` elements.
+        pub const fn buffer_size(element_count: u64) -> u64 {
+          Self::FIXED_SIZE + Self::ELEMENT_STRIDE * element_count
+        }
+
+        #write_into
+      }
+    },
+  )]
+}
+
+/// Generates methods creating and writing buffers for structs used as uniform or storage
+/// bindings, with the union of the usages of all their bindings.
+fn buffer_helpers_items(
+  rust_item_path: &RustItemPath,
+  members: &[naga::StructMember],
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+  struct_buffer_usages: &FastIndexMap<String, wgpu::BufferUsages>,
+) -> Vec<RustItem> {
+  if !options.struct_buffer_helpers || !options.serialization_strategy.is_bytemuck() {
+    return Vec::new();
+  }
+  let Some(usage) =
+    struct_buffer_usages.get(rust_item_path.get_fully_qualified_name().as_str())
+  else {
+    return Vec::new();
+  };
+  let is_runtime_sized = members.last().is_some_and(|last| {
+    matches!(
+      module.types[last.ty].inner,
+      naga::TypeInner::Array {
+        size: naga::ArraySize::Dynamic,
+        ..
+      }
+    )
+  });
+  if is_runtime_sized {
+    return Vec::new();
+  }
+
+  let name = Ident::new(&rust_item_path.name, Span::call_site());
+  let usages = (*usage | wgpu::BufferUsages::COPY_DST)
+    .iter_names()
+    .map(|(flag, _)| {
+      let flag = Ident::new(flag, Span::call_site());
+      quote!(wgpu::BufferUsages::#flag)
+    });
+  let usages = usages.reduce(|acc, flag| quote!(#acc.union(#flag)));
+
+  vec![RustItem::new(
+    RustItemType::TypeImpls.into(),
+    rust_item_path.clone(),
+    quote! {
+      impl #name {
+        /// The usages of the buffers created for the struct by its bindings.
+        pub const BUFFER_USAGES: wgpu::BufferUsages = #usages;
+
+        /// Creates an uninitialized buffer the size of the struct.
+        pub fn create_buffer(device: &wgpu::Device, label: Option<&str>) -> wgpu::Buffer {
+          device.create_buffer(&wgpu::BufferDescriptor {
+            label,
+            size: std::mem::size_of::<Self>() as u64,
+            usage: Self::BUFFER_USAGES,
+            mapped_at_creation: false,
+          })
+        }
+
+        /// Creates a buffer initialized with the struct.
+        pub fn create_buffer_init(
+          &self,
+          device: &wgpu::Device,
+          label: Option<&str>,
+        ) -> wgpu::Buffer {
+          wgpu::util::DeviceExt::create_buffer_init(device, &wgpu::util::BufferInitDescriptor {
+            label,
+            contents: bytemuck::bytes_of(self),
+            usage: Self::BUFFER_USAGES,
+          })
+        }
+
+        /// Writes the struct into `buffer` at `offset`.
+        pub fn write(&self, queue: &wgpu::Queue, buffer: &wgpu::Buffer, offset: u64) {
+          queue.write_buffer(buffer, offset, bytemuck::bytes_of(self));
+        }
+      }
+    },
+  )]
+}
+
+/// The root level newtype used for `atomic<T>` fields when `wrap_atomics` is enabled.
+pub(crate) fn wgsl_atomic_type(options: &WgslBindgenOption) -> TokenStream {
+  if !options.wrap_atomics || !options.serialization_strategy.is_bytemuck() {
+    return quote!();
+  }
+
+  // Serializable structs may contain atomics.
+  let derive_serde = options.derive_serde || !options.serde_struct_regexps.is_empty();
+  let (serde_derives, serde_attribute) = serde_derives(options, derive_serde);
+
+  quote! {
+    /// An integer that the GPU accesses atomically, e.g. a WGSL `atomic<u32>`.
+    /// Has the same layout as the integer.
+    #[repr(transparent)]
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, #(#serde_derives),*)]
+    #serde_attribute
+    pub struct WgslAtomic<T>(pub T);
+
+    impl<T> From<T> for WgslAtomic<T> {
+      fn from(value: T) -> Self {
+        Self(value)
+      }
+    }
+
+    unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for WgslAtomic<T> {}
+    unsafe impl<T: bytemuck::Pod> bytemuck::Pod for WgslAtomic<T> {}
+  }
+}
+
+/// The root level newtype used for the fields selected by `wrap_bools` and
+/// `wgsl_bool_fields`.
+pub(crate) fn wgsl_bool_type(options: &WgslBindgenOption) -> TokenStream {
+  let wraps_bools = options.wrap_bools || !options.wgsl_bool_fields.is_empty();
+  if !wraps_bools || !options.serialization_strategy.is_bytemuck() {
+    return quote!();
+  }
+
+  let derive_serde = options.derive_serde || !options.serde_struct_regexps.is_empty();
+  let (serde_derives, serde_attribute) = serde_derives(options, derive_serde);
+  let assert_size = if options.skip_layout_assertions {
+    quote!()
+  } else {
+    quote!(
+      const WGSL_BOOL_ASSERTS: () = assert!(std::mem::size_of::<WgslBool>() == 4);
+    )
+  };
+
+  quote! {
+    /// A boolean with the 4 byte size of a WGSL `bool`, stored as a `u32` that is 1 for
+    /// `true` and 0 for `false`.
+    #[repr(transparent)]
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, #(#serde_derives),*)]
+    #serde_attribute
+    pub struct WgslBool(pub u32);
+
+    impl WgslBool {
+      pub const FALSE: Self = Self(0);
+      pub const TRUE: Self = Self(1);
+
+      pub const fn new(value: bool) -> Self {
+        Self(value as u32)
+      }
+
+      /// Whether the value is non-zero.
+      pub const fn get(self) -> bool {
+        self.0 != 0
+      }
+    }
+
+    impl From<bool> for WgslBool {
+      fn from(value: bool) -> Self {
+        Self::new(value)
+      }
+    }
+
+    impl From<WgslBool> for bool {
+      fn from(value: WgslBool) -> Self {
+        value.get()
+      }
+    }
+
+    unsafe impl bytemuck::Zeroable for WgslBool {}
+    unsafe impl bytemuck::Pod for WgslBool {}
+
+    #assert_size
+  }
+}
+
+fn is_skipped_struct(options: &WgslBindgenOption, rust_item_path: &RustItemPath) -> bool {
+  let fully_qualified_name = rust_item_path.get_fully_qualified_name();
+  options
+    .skip_struct_regexps
+    .iter()
+    .any(|regex| regex.is_match(&fully_qualified_name))
+}
+
+/// The path of the skipped struct `ty` refers to, looking through arrays.
+fn skipped_struct_path(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+  ty: Handle<Type>,
+) -> Option<RustItemPath> {
+  let ty = &module.types[ty];
+  match &ty.inner {
+    naga::TypeInner::Array { base, .. } | naga::TypeInner::BindingArray { base, .. } => {
+      skipped_struct_path(invoking_entry_module, module, options, *base)
+    }
+    naga::TypeInner::Struct { .. } => {
+      let path = RustItemPath::from_mangled(ty.name.as_ref()?, invoking_entry_module);
+      is_skipped_struct(options, &path).then_some(path)
+    }
+    _ => None,
+  }
+}
+
+/// The `<struct>.<field>` paths of all struct fields in the module, as matched by
+/// `override_struct_field_type_exact`, e.g. `lighting::Light.color`.
+pub fn struct_field_paths<'a>(
+  invoking_entry_module: &'a str,
+  module: &'a naga::Module,
+) -> impl Iterator<Item = String> + 'a {
+  module.types.iter().flat_map(move |(_, ty)| {
+    let members = match &ty.inner {
+      naga::TypeInner::Struct { members, .. } => members.as_slice(),
+      _ => &[],
+    };
+    let struct_path = ty.name.as_ref().map(|name| {
+      RustItemPath::from_mangled(name, invoking_entry_module).get_fully_qualified_name()
+    });
+    members.iter().filter_map(move |member| {
+      Some(format!("{}.{}", struct_path.as_ref()?, member.name.as_ref()?))
+    })
+  })
+}
+
+/// Checks that all the paths of `override_struct_field_type_exact` match struct fields,
+/// suggesting similar paths for typos.
+pub fn check_exact_field_type_overrides(
+  field_paths: &HashSet<String>,
+  options: &WgslBindgenOption,
+) -> Result<(), CreateModuleError> {
+  for o in &options.override_struct_field_type_exact {
+    if field_paths.contains(&o.field_path) {
+      continue;
+    }
+
+    let mut near_matches = field_paths
+      .iter()
+      .map(|path| (edit_distance(path, &o.field_path), path))
+      .filter(|(distance, path)| {
+        *distance <= 3 || path.ends_with(&format!("::{}", o.field_path))
+      })
+      .collect::<Vec<_>>();
+    near_matches.sort();
+
+    let suggestion = if near_matches.is_empty() {
+      String::new()
+    } else {
+      let paths = near_matches
+        .iter()
+        .take(5)
+        .map(|(_, path)| format!("`{path}`"))
+        .collect::<Vec<_>>();
+      format!(", did you mean {}?", paths.join(", "))
+    };
+
+    return Err(CreateModuleError::UnknownStructFieldPath {
+      field_path: o.field_path.clone(),
+      suggestion,
+    });
+  }
+  Ok(())
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b = b.chars().collect::<Vec<_>>();
+  let mut row = (0..=b.len()).collect::<Vec<_>>();
+  for (i, ca) in a.chars().enumerate() {
+    let mut previous = row[0];
+    row[0] = i + 1;
+    for (j, cb) in b.iter().enumerate() {
+      let substitution = previous + usize::from(ca != *cb);
+      previous = row[j + 1];
+      row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+    }
+  }
+  row[b.len()]
+}
+
+pub const SHARED_STRUCTS_MOD: &str = "common_types";
+
+/// The structs defined with the same name by more than one entry.
+#[derive(Default)]
+pub struct SharedStructs {
+  /// The items of the structs that generate identical code in all the entries defining
+  /// them, generated in [`SHARED_STRUCTS_MOD`].
+  pub items: Vec<RustItem>,
+  /// For each entry, the names of the shared structs it defines.
+  pub entry_structs: Vec<BTreeSet<String>>,
+  /// For each entry, the doc comments noting the structs it defines differently from
+  /// other entries, by struct name.
+  pub entry_conflicts: Vec<FastIndexMap<String, String>>,
+}
+
+/// Finds the structs defined with the same name by more than one of the entries, not
+/// counting imported structs, which already have a single module. The structs that
+/// generate identical code in all of these entries, vertex attributes included, are
+/// shared unless they contain structs of the entries that aren't.
+pub fn find_shared_structs(
+  inputs: &[SharedBindGroupsInput],
+  options: &WgslBindgenOption,
+  struct_buffer_usages: &FastIndexMap<String, wgpu::BufferUsages>,
+) -> Result<SharedStructs, CreateModuleError> {
+  // The entries defining each struct, by name.
+  let mut definitions = FastIndexMap::<String, Vec<usize>>::default();
+  for (entry_index, input) in inputs.iter().enumerate() {
+    let names =
+      local_struct_names(input.invoking_entry_module, input.naga_module, options)?;
+    for name in names {
+      definitions.entry(name).or_default().push(entry_index);
+    }
+  }
+  definitions.retain(|_, entries| entries.len() > 1);
+
+  // The shared structs are used as buffers wherever one of the entries uses them.
+  let mut shared_usages = struct_buffer_usages.clone();
+  for (name, entries) in &definitions {
+    let usages = entries
+      .iter()
+      .filter_map(|entry_index| {
+        let module = inputs[*entry_index].invoking_entry_module;
+        struct_buffer_usages.get(&format!("{module}::{name}"))
+      })
+      .fold(wgpu::BufferUsages::empty(), |all, usages| all | *usages);
+    if !usages.is_empty() {
+      shared_usages.insert(format!("{SHARED_STRUCTS_MOD}::{name}"), usages);
+    }
+  }
+
+  let mut shared = SharedStructs {
+    items: Vec::new(),
+    entry_structs: vec![BTreeSet::new(); inputs.len()],
+    entry_conflicts: vec![FastIndexMap::default(); inputs.len()],
+  };
+
+  let is_shared_path = |path: &RustItemPath, name: &str| {
+    path.module == SHARED_STRUCTS_MOD && path.name == name
+  };
+  let mut candidates = FastIndexMap::<String, Vec<RustItem>>::default();
+  for (name, entries) in &definitions {
+    let entry_items = entries
+      .iter()
+      .map(|entry_index| {
+        let input = &inputs[*entry_index];
+        let mut items = structs_items_where(
+          SHARED_STRUCTS_MOD,
+          input.naga_module,
+          options,
+          &shared_usages,
+          &mut Vec::new(),
+          |path| is_shared_path(path, name),
+        )?;
+        let vertex_impls =
+          vertex_struct_impls(SHARED_STRUCTS_MOD, input.naga_module, options)?;
+        items.extend(
+          vertex_impls
+            .into_iter()
+            .filter(|item| is_shared_path(&item.path, name)),
+        );
+        Ok(items)
+      })
+      .collect::<Result<Vec<_>, CreateModuleError>>()?;
+    let signatures = entry_items
+      .iter()
+      .map(|items| {
+        items
+          .iter()
+          .map(|item| item.item.to_string())
+          .collect::<String>()
+      })
+      .collect::<Vec<_>>();
+
+    if signatures
+      .iter()
+      .all(|signature| *signature == signatures[0])
+    {
+      candidates.insert(name.clone(), entry_items.into_iter().next().unwrap());
+      continue;
+    }
+
+    for (i, entry_index) in entries.iter().enumerate() {
+      let others = entries
+        .iter()
+        .zip(&signatures)
+        .filter(|(_, signature)| **signature != signatures[i])
+        .map(|(other, _)| format!("`{}`", inputs[*other].invoking_entry_module))
+        .collect::<Vec<_>>();
+      let note = format!(
+        " `{name}` is defined differently by {}, so it isn't shared in `{SHARED_STRUCTS_MOD}`.",
+        others.join(", ")
+      );
+      shared.entry_conflicts[*entry_index].insert(name.clone(), note);
+    }
+  }
+
+  // Fields of shared structs refer to the other structs of the entry by their shared
+  // path, so these have to be shared too.
+  loop {
+    let unshared = candidates
+      .keys()
+      .filter(|name| {
+        let input = &inputs[definitions[*name][0]];
+        local_struct_dependencies(input.naga_module, name)
+          .iter()
+          .any(|dependency| !candidates.contains_key(dependency))
+      })
+      .cloned()
+      .collect::<Vec<_>>();
+    if unshared.is_empty() {
+      break;
+    }
+    for name in unshared {
+      candidates.shift_remove(&name);
+    }
+  }
+
+  for (name, items) in candidates {
+    for entry_index in &definitions[&name] {
+      shared.entry_structs[*entry_index].insert(name.clone());
+    }
+    shared.items.extend(items);
+  }
+
+  Ok(shared)
+}
+
+/// The names of the structs generated for the module of the entry itself, rather than for
+/// the modules they're imported from.
+fn local_struct_names(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Result<Vec<String>, CreateModuleError> {
+  let items = structs_items(
+    invoking_entry_module,
+    module,
+    options,
+    &Default::default(),
+    &mut Vec::new(),
+  )?;
+  Ok(
+    items
+      .into_iter()
+      .filter(|item| {
+        item.types.contains(RustItemType::TypeDefs)
+          && item.path.module == invoking_entry_module
+      })
+      .map(|item| item.path.name.to_string())
+      .collect(),
+  )
+}
+
+/// The handle and members of the struct `name` of the module.
+fn find_struct<'a>(
+  module: &'a naga::Module,
+  name: &str,
+) -> Option<(Handle<Type>, &'a [naga::StructMember], u32)> {
+  module
+    .types
+    .iter()
+    .find_map(|(handle, ty)| match &ty.inner {
+      naga::TypeInner::Struct { members, span } if ty.name.as_deref() == Some(name) => {
+        Some((handle, members.as_slice(), *span))
+      }
+      _ => None,
+    })
+}
+
+/// The names of the structs defined by the module itself, rather than imported, which are
+/// contained by the struct `name`.
+fn local_struct_dependencies(module: &naga::Module, name: &str) -> Vec<String> {
+  let Some((_, members, _)) = find_struct(module, name) else {
+    return Vec::new();
+  };
+
+  let mut types = HashSet::new();
+  for member in members {
+    add_types_recursive(&mut types, module, member.ty);
+  }
+  types
+    .into_iter()
+    .filter_map(|handle| {
+      let ty = &module.types[handle];
+      let path = RustItemPath::from_mangled(ty.name.as_deref()?, SHARED_STRUCTS_MOD);
+      let is_local = matches!(ty.inner, naga::TypeInner::Struct { .. })
+        && path.module == SHARED_STRUCTS_MOD;
+      is_local.then(|| path.name.to_string())
+    })
+    .collect()
+}
+
+/// The naga modules of the entries, with the conflicting structs renamed, see
+/// [`disambiguate_conflicting_structs`].
+pub struct DisambiguatedStructs<'a> {
+  pub modules: Vec<Cow<'a, naga::Module>>,
+  /// For each entry, the doc comments noting the renamed structs, by their new name.
+  pub entry_notes: Vec<FastIndexMap<String, String>>,
+}
+
+/// Checks that the structs defined with the same name by more than one entry, not
+/// counting imported structs, have the same layout in all of them, as they'd be mistaken
+/// for one another when looked up by name. Conflicts are errors, unless
+/// [`WgslBindgenOption::disambiguate_conflicting_structs`] is set. Then they are warnings,
+/// and each layout of the struct gets its own name, `SettingsA`, `SettingsB` and so on in
+/// order of first appearance.
+pub fn disambiguate_conflicting_structs<'a>(
+  entries: &'a [WgslEntryResult<'_>],
+  options: &WgslBindgenOption,
+  warnings: &mut Vec<WgslBindgenWarning>,
+) -> Result<DisambiguatedStructs<'a>, CreateModuleError> {
+  // The entries using each layout of each struct, by name.
+  let mut layouts = FastIndexMap::<String, FastIndexMap<String, Vec<usize>>>::default();
+  for (entry_index, entry) in entries.iter().enumerate() {
+    let module = &entry.naga_module;
+    let mut layouter = naga::proc::Layouter::default();
+    layouter.update(module.to_ctx()).unwrap();
+    for name in local_struct_names(&entry.mod_name, module, options)? {
+      let layout = struct_layout(module, &layouter, &name);
+      let entries = layouts.entry(name).or_default().entry(layout).or_default();
+      entries.push(entry_index);
+    }
+  }
+  layouts.retain(|_, layouts| layouts.len() > 1);
+
+  let mut disambiguated = DisambiguatedStructs {
+    modules: entries
+      .iter()
+      .map(|entry| Cow::Borrowed(&entry.naga_module))
+      .collect(),
+    entry_notes: vec![FastIndexMap::default(); entries.len()],
+  };
+
+  for (name, layouts) in layouts {
+    let mut entry_indices = layouts.values().flatten().collect::<Vec<_>>();
+    entry_indices.sort();
+    let modules = entry_indices
+      .into_iter()
+      .map(|entry_index| entries[*entry_index].mod_name.clone())
+      .collect();
+    if !options.disambiguate_conflicting_structs {
+      return Err(CreateModuleError::ConflictingStructDefinitions { name, modules });
+    }
+    warnings.push(WgslBindgenWarning::ConflictingStructDefinitions {
+      name: name.clone(),
+      modules,
+    });
+
+    let renames = layouts
+      .values()
+      .enumerate()
+      .flat_map(|(layout_index, entry_indices)| {
+        let new_name = format!("{name}{}", layout_suffix(layout_index));
+        entry_indices
+          .iter()
+          .map(move |entry_index| (*entry_index, new_name.clone()))
+      })
+      .collect::<Vec<_>>();
+    for (entry_index, new_name) in &renames {
+      let others = renames
+        .iter()
+        .filter(|(_, other_name)| other_name != new_name)
+        .map(|(other, other_name)| {
+          format!("`{}` as `{other_name}`", entries[*other].mod_name)
+        })
+        .collect::<Vec<_>>();
+      let note = format!(
+        " Renamed from the WGSL struct `{name}`, which is defined with a different layout by {}.",
+        others.join(", ")
+      );
+      disambiguated.entry_notes[*entry_index].insert(new_name.clone(), note);
+
+      let module = disambiguated.modules[*entry_index].to_mut();
+      let (handle, _, _) = find_struct(module, &name).unwrap();
+      let ty = module.types[handle].clone();
+      let renamed = naga::Type {
+        name: Some(new_name.clone()),
+        ..ty
+      };
+      module.types.replace(handle, renamed);
+    }
+  }
+
+  Ok(disambiguated)
+}
+
+/// The layout of the struct `name`, as the names, WGSL types and offsets of its members,
+/// its size and its alignment.
+fn struct_layout(
+  module: &naga::Module,
+  layouter: &naga::proc::Layouter,
+  name: &str,
+) -> String {
+  let Some((handle, members, span)) = find_struct(module, name) else {
+    return String::new();
+  };
+  let gctx = module.to_ctx();
+  let members = members
+    .iter()
+    .map(|member| {
+      let ty = &module.types[member.ty];
+      let ty_name = match &ty.name {
+        Some(name) => demangle_str(name).to_string(),
+        None => demangle_str(&ty.inner.to_wgsl(&gctx)).to_string(),
+      };
+      let member_name = member.name.as_deref().unwrap_or_default();
+      format!("{member_name}: {ty_name} @ {}", member.offset)
+    })
+    .collect::<Vec<_>>();
+  let alignment = layouter[handle].alignment * 1u32;
+  format!("{{ {} }} size {span} align {alignment}", members.join(", "))
+}
+
+/// The suffix of the name of the struct with the `index`th layout of a conflicting
+/// struct, `A` to `Z`, then `AA` and so on.
+fn layout_suffix(index: usize) -> String {
+  let letter = (b'A' + (index % 26) as u8) as char;
+  if index < 26 {
+    letter.to_string()
+  } else {
+    format!("{}{letter}", layout_suffix(index / 26 - 1))
+  }
+}
+
+/// Adds the doc comments of `notes` to the definitions of the structs of
+/// `invoking_entry_module` in `items`, by struct name.
+pub fn add_struct_notes(
+  items: &mut [RustItem],
+  invoking_entry_module: &str,
+  notes: &FastIndexMap<String, String>,
+) {
+  for item in items {
+    if !item.types.contains(RustItemType::TypeDefs)
+      || item.path.module != invoking_entry_module
+    {
+      continue;
+    }
+    if let Some(note) = notes.get(item.path.name.as_str()) {
+      let definition = &item.item;
+      item.item = quote! {
+        #[doc = #note]
+        #definition
+      };
+    }
+  }
+}
+
+/// The first field of a struct containing a type without a Rust representation, and that
+/// type.
+fn unrepresentable_field<'a>(
+  module: &naga::Module,
+  members: &'a [naga::StructMember],
+) -> Option<(&'a str, UnrepresentableType)> {
+  members.iter().find_map(|m| {
+    let ty = find_unrepresentable_type(module, &module.types[m.ty])?;
+    Some((m.name.as_deref().unwrap_or_default(), ty))
+  })
+}
+
+/// Checks that types without a Rust representation, which also skip the structs containing
+/// them, aren't used by buffer bindings, push constants or vertex inputs.
+pub fn check_unrepresentable_types(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+) -> Result<(), CreateModuleError> {
+  let unrepresentable_error = |ty: UnrepresentableType, used_by: String| {
+    Err(CreateModuleError::UnrepresentableTypeInUse {
+      ty: ty.to_string(),
+      used_by,
+    })
+  };
+
+  for (_, g) in module.global_variables.iter() {
+    let used_by = match g.space {
+      naga::AddressSpace::Uniform | naga::AddressSpace::Storage { .. } => "binding",
+      naga::AddressSpace::PushConstant => "push constants",
+      _ => continue,
+    };
+    if let Some(ty) = find_unrepresentable_type(module, &module.types[g.ty]) {
+      let path = RustItemPath::from_mangled(
+        g.name.as_deref().unwrap_or_default(),
+        invoking_entry_module,
+      );
+      let used_by = format!("{used_by} `{}`", path.get_fully_qualified_name());
+      return unrepresentable_error(ty, used_by);
+    }
+  }
+
+  for entry in &module.entry_points {
+    if entry.stage != naga::ShaderStage::Vertex {
+      continue;
+    }
+    for argument in &entry.function.arguments {
+      if let Some(ty) = find_unrepresentable_type(module, &module.types[argument.ty]) {
+        let used_by = format!("the vertex input of `{}`", entry.name);
+        return unrepresentable_error(ty, used_by);
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Checks that the structs skipped by `skip_struct_regexps` aren't used by the fields of
+/// generated structs, by bindings or as vertex inputs.
+pub fn check_skipped_structs(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  options: &WgslBindgenOption,
+) -> Result<(), CreateModuleError> {
+  if options.skip_struct_regexps.is_empty() {
+    return Ok(());
+  }
+  let skipped_struct_error = |path: RustItemPath, used_by: String| {
+    Err(CreateModuleError::SkippedStructInUse {
+      name: path.get_fully_qualified_name().to_string(),
+      used_by,
+    })
+  };
+
+  let mut global_variable_types = HashSet::new();
+  for (_, g) in module.global_variables.iter() {
+    add_types_recursive(&mut global_variable_types, module, g.ty);
+  }
+
+  for (_, g) in module.global_variables.iter() {
+    if g.binding.is_none() {
+      continue;
+    }
+    if let Some(path) = skipped_struct_path(invoking_entry_module, module, options, g.ty)
+    {
+      let binding = RustItemPath::from_mangled(
+        g.name.as_deref().unwrap_or_default(),
+        invoking_entry_module,
+      );
+      let used_by = format!("binding `{}`", binding.get_fully_qualified_name());
+      return skipped_struct_error(path, used_by);
+    }
+  }
+
+  for (handle, ty) in module.types.iter() {
+    let naga::TypeInner::Struct { members, .. } = &ty.inner else {
+      continue;
+    };
+    if !global_variable_types.contains(&handle) {
+      continue;
+    }
+    let struct_path =
+      RustItemPath::from_mangled(ty.name.as_ref().unwrap(), invoking_entry_module);
+    if is_skipped_struct(options, &struct_path) {
+      continue;
+    }
+    for member in members {
+      if let Some(path) =
+        skipped_struct_path(invoking_entry_module, module, options, member.ty)
+      {
+        let used_by = format!(
+          "field `{}::{}`",
+          struct_path.get_fully_qualified_name(),
+          member.name.as_deref().unwrap_or_default()
+        );
+        return skipped_struct_error(path, used_by);
+      }
+    }
+  }
+
+  for input in wgsl::get_vertex_input_structs(invoking_entry_module, module) {
+    if is_skipped_struct(options, &input.item_path) {
+      return skipped_struct_error(input.item_path, "a vertex entry point".to_string());
+    }
+  }
+
+  Ok(())
 }
 
 fn add_types_recursive(
@@ -150,7 +1225,8 @@ mod tests {
   use crate::*;
 
   pub fn structs(module: &naga::Module, options: &WgslBindgenOption) -> Vec<TokenStream> {
-    structs_items("", module, options)
+    structs_items("", module, options, &Default::default(), &mut Vec::new())
+      .unwrap()
       .into_iter()
       .map(|s| s.item)
       .collect()
@@ -735,49 +1811,263 @@ mod tests {
               pub b: [f32; 3],
               pub c: [nalgebra::SMatrix<f32, 4, 4>; 512],
           }
-          impl StaticArrays {
-            pub const fn new(
-              a: [u32; 5],
-              b: [f32; 3],
-              c: [nalgebra::SMatrix<f32, 4, 4>; 512],
-            ) -> Self {
-                Self { a, b, c }
+          impl StaticArrays {
+            pub const fn new(
+              a: [u32; 5],
+              b: [f32; 3],
+              c: [nalgebra::SMatrix<f32, 4, 4>; 512],
+            ) -> Self {
+                Self { a, b, c }
+            }
+          }
+          #[repr(C)]
+          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
+          pub struct Nested {
+              pub a: MatricesF32,
+              pub b: VectorsF32,
+          }
+          impl Nested {
+            pub const fn new(a: MatricesF32, b: VectorsF32) -> Self {
+                Self { a, b }
+            }
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_all_structs_encase() {
+    let source = indoc! {r#"
+            struct Input0 {
+                a: u32,
+                b: i32,
+                c: f32,
+            };
+
+            struct Nested {
+                a: Input0,
+                b: f32
+            }
+
+            var<uniform> a: Input0;
+            var<storage, read> b: Nested;
+
+            @fragment
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+
+    let structs = structs(
+      &module,
+      &WgslBindgenOption {
+        serialization_strategy: WgslTypeSerializeStrategy::Encase,
+        derive_serde: false,
+        type_map: RustWgslTypeMap.build(WgslTypeSerializeStrategy::Encase),
+        ..Default::default()
+      },
+    );
+    let actual = quote!(#(#structs)*);
+
+    assert_tokens_eq!(
+      quote! {
+          #[repr(C)]
+          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
+          pub struct Input0 {
+              pub a: u32,
+              pub b: i32,
+              pub c: f32,
+          }
+          impl Input0 {
+            pub const fn new(a: u32, b: i32, c: f32) -> Self {
+                Self { a, b, c }
+            }
+          }
+          #[repr(C)]
+          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
+          pub struct Nested {
+              pub a: Input0,
+              pub b: f32,
+          }
+          impl Nested {
+            pub const fn new(a: Input0, b: f32) -> Self {
+                Self { a, b }
+            }
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_all_structs_serde_encase() {
+    let source = indoc! {r#"
+            struct Input0 {
+                a: u32,
+                b: i32,
+                c: f32,
+            };
+
+            struct Nested {
+                a: Input0,
+                b: f32
+            }
+
+            var<workgroup> a: Input0;
+            var<uniform> b: Nested;
+
+            @compute
+            @workgroup_size(64)
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+
+    let structs = structs(
+      &module,
+      &WgslBindgenOption {
+        serialization_strategy: WgslTypeSerializeStrategy::Encase,
+        derive_serde: true,
+        type_map: RustWgslTypeMap.build(WgslTypeSerializeStrategy::Encase),
+        ..Default::default()
+      },
+    );
+    let actual = quote!(#(#structs)*);
+
+    assert_tokens_eq!(
+      quote! {
+          #[repr(C)]
+          #[derive(
+              Debug,
+              PartialEq,
+              Clone,
+              Copy,
+              encase::ShaderType,
+              serde::Serialize,
+              serde::Deserialize
+          )]
+          pub struct Input0 {
+              pub a: u32,
+              pub b: i32,
+              pub c: f32,
+          }
+          impl Input0 {
+            pub const fn new(a: u32, b: i32, c: f32) -> Self {
+                Self { a, b, c }
+            }
+          }
+          #[repr(C)]
+          #[derive(
+              Debug,
+              PartialEq,
+              Clone,
+              Copy,
+              encase::ShaderType,
+              serde::Serialize,
+              serde::Deserialize
+          )]
+          pub struct Nested {
+              pub a: Input0,
+              pub b: f32,
+          }
+          impl Nested {
+            pub const fn new(a: Input0, b: f32) -> Self {
+                Self { a, b }
+            }
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_all_structs_derive_default() {
+    let source = indoc! {r#"
+            struct Small {
+                a: u32,
+                b: array<f32, 4>,
+            };
+
+            struct Large {
+                a: array<vec4<f32>, 64>,
+                b: f32
+            }
+
+            var<workgroup> a: Small;
+            var<uniform> b: Large;
+
+            @compute
+            @workgroup_size(64)
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+
+    let structs = structs(
+      &module,
+      &WgslBindgenOption {
+        serialization_strategy: WgslTypeSerializeStrategy::Encase,
+        derive_default: true,
+        type_map: RustWgslTypeMap.build(WgslTypeSerializeStrategy::Encase),
+        ..Default::default()
+      },
+    );
+    let actual = quote!(#(#structs)*);
+
+    assert_tokens_eq!(
+      quote! {
+          #[repr(C)]
+          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType, Default)]
+          pub struct Small {
+              pub a: u32,
+              pub b: [f32; 4],
+          }
+          impl Small {
+            pub const fn new(a: u32, b: [f32; 4]) -> Self {
+                Self { a, b }
             }
           }
           #[repr(C)]
           #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
-          pub struct Nested {
-              pub a: MatricesF32,
-              pub b: VectorsF32,
+          pub struct Large {
+              pub a: [[f32; 4]; 64],
+              pub b: f32,
           }
-          impl Nested {
-            pub const fn new(a: MatricesF32, b: VectorsF32) -> Self {
+          impl Large {
+            pub const fn new(a: [[f32; 4]; 64], b: f32) -> Self {
                 Self { a, b }
             }
           }
+          impl Default for Large {
+            fn default() -> Self {
+                Self {
+                    a: [[Default::default(); 4]; 64],
+                    b: Default::default(),
+                }
+            }
+          }
       },
       actual
     );
   }
 
   #[test]
-  fn write_all_structs_encase() {
+  fn write_all_structs_derive_default_with_custom_derives() {
     let source = indoc! {r#"
-            struct Input0 {
+            struct Small {
                 a: u32,
-                b: i32,
-                c: f32,
             };
 
-            struct Nested {
-                a: Input0,
-                b: f32
+            struct Large {
+                a: array<vec4<f32>, 64>,
             }
 
-            var<uniform> a: Input0;
-            var<storage, read> b: Nested;
+            var<workgroup> a: Small;
+            var<uniform> b: Large;
 
-            @fragment
+            @compute
+            @workgroup_size(64)
             fn main() {}
         "#};
 
@@ -787,36 +2077,46 @@ mod tests {
       &module,
       &WgslBindgenOption {
         serialization_strategy: WgslTypeSerializeStrategy::Encase,
-        derive_serde: false,
+        derive_default: true,
+        custom_derives: vec![(
+          Regex::new(".*").unwrap(),
+          quote!(#[derive(std::default::Default, Eq)]),
+        )],
         type_map: RustWgslTypeMap.build(WgslTypeSerializeStrategy::Encase),
         ..Default::default()
       },
     );
     let actual = quote!(#(#structs)*);
 
+    // The custom `Default` derive is merged with the built-in one, and left out when
+    // `Default` is implemented manually.
     assert_tokens_eq!(
       quote! {
           #[repr(C)]
-          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
-          pub struct Input0 {
+          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType, Default, Eq)]
+          pub struct Small {
               pub a: u32,
-              pub b: i32,
-              pub c: f32,
           }
-          impl Input0 {
-            pub const fn new(a: u32, b: i32, c: f32) -> Self {
-                Self { a, b, c }
+          impl Small {
+            pub const fn new(a: u32) -> Self {
+                Self { a }
             }
           }
           #[repr(C)]
-          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
-          pub struct Nested {
-              pub a: Input0,
-              pub b: f32,
+          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType, Eq)]
+          pub struct Large {
+              pub a: [[f32; 4]; 64],
           }
-          impl Nested {
-            pub const fn new(a: Input0, b: f32) -> Self {
-                Self { a, b }
+          impl Large {
+            pub const fn new(a: [[f32; 4]; 64]) -> Self {
+                Self { a }
+            }
+          }
+          impl Default for Large {
+            fn default() -> Self {
+                Self {
+                    a: [[Default::default(); 4]; 64],
+                }
             }
           }
       },
@@ -825,21 +2125,20 @@ mod tests {
   }
 
   #[test]
-  fn write_all_structs_serde_encase() {
+  fn write_all_structs_size_constants() {
     let source = indoc! {r#"
-            struct Input0 {
-                a: u32,
-                b: i32,
-                c: f32,
+            struct Uniforms {
+                color: vec4<f32>,
+                scale: f32,
             };
 
-            struct Nested {
-                a: Input0,
-                b: f32
-            }
+            struct Particles {
+                count: u32,
+                data: array<vec4<f32>>,
+            };
 
-            var<workgroup> a: Input0;
-            var<uniform> b: Nested;
+            @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+            @group(0) @binding(1) var<storage, read> particles: Particles;
 
             @compute
             @workgroup_size(64)
@@ -852,7 +2151,7 @@ mod tests {
       &module,
       &WgslBindgenOption {
         serialization_strategy: WgslTypeSerializeStrategy::Encase,
-        derive_serde: true,
+        struct_size_constants: true,
         type_map: RustWgslTypeMap.build(WgslTypeSerializeStrategy::Encase),
         ..Default::default()
       },
@@ -862,44 +2161,212 @@ mod tests {
     assert_tokens_eq!(
       quote! {
           #[repr(C)]
-          #[derive(
-              Debug,
-              PartialEq,
-              Clone,
-              Copy,
-              encase::ShaderType,
-              serde::Serialize,
-              serde::Deserialize
-          )]
-          pub struct Input0 {
-              pub a: u32,
-              pub b: i32,
-              pub c: f32,
+          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
+          pub struct Uniforms {
+              pub color: [f32; 4],
+              pub scale: f32,
           }
-          impl Input0 {
-            pub const fn new(a: u32, b: i32, c: f32) -> Self {
-                Self { a, b, c }
+          impl Uniforms {
+            pub const fn new(color: [f32; 4], scale: f32) -> Self {
+                Self { color, scale }
+            }
+          }
+          impl Uniforms {
+            #[doc = " The size of the struct in bytes with the WGSL layout."]
+            pub const SIZE: u64 = 32;
+            #[doc = " The size of the struct rounded up to 256 bytes, the default"]
+            #[doc = " `min_uniform_buffer_offset_alignment` and `min_storage_buffer_offset_alignment`."]
+            pub const ALIGNED_SIZE_256: u64 = Self::aligned_size(256);
+            #[doc = " The size of the struct rounded up to a multiple of `alignment`, i.e. the stride"]
+            #[doc = " of instances placed at aligned offsets in a buffer."]
+            pub const fn aligned_size(alignment: u64) -> u64 {
+                Self::SIZE.next_multiple_of(alignment)
+            }
+            #[doc = " The offset of the instance at `index` in a buffer of instances placed at"]
+            #[doc = " offsets aligned to `alignment`."]
+            pub const fn offset_of_index(index: u64, alignment: u64) -> u64 {
+                index * Self::aligned_size(alignment)
+            }
+          }
+          #[derive(Debug, PartialEq, Clone, encase::ShaderType)]
+          pub struct Particles {
+              pub count: u32,
+              #[size(runtime)]
+              pub data: Vec<[f32; 4]>,
+          }
+          impl Particles {
+            pub const fn new(count: u32, data: Vec<[f32; 4]>) -> Self {
+                Self { count, data }
             }
           }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_all_structs_array_length_constants() {
+    let source = indoc! {r#"
+            const MAX_LIGHTS: u32 = 8;
+
+            struct Lights {
+                lightCount: u32,
+                lights: array<vec4<f32>, MAX_LIGHTS>,
+                weights: array<f32, 4>,
+            };
+
+            @group(0) @binding(0) var<storage, read> lights: Lights;
+
+            @compute
+            @workgroup_size(64)
+            fn main() {}
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+
+    let structs = structs(
+      &module,
+      &WgslBindgenOption {
+        serialization_strategy: WgslTypeSerializeStrategy::Encase,
+        array_length_constants: true,
+        type_map: RustWgslTypeMap.build(WgslTypeSerializeStrategy::Encase),
+        ..Default::default()
+      },
+    );
+    let actual = quote!(#(#structs)*);
+
+    assert_tokens_eq!(
+      quote! {
           #[repr(C)]
-          #[derive(
-              Debug,
-              PartialEq,
-              Clone,
-              Copy,
-              encase::ShaderType,
-              serde::Serialize,
-              serde::Deserialize
-          )]
-          pub struct Nested {
-              pub a: Input0,
-              pub b: f32,
+          #[derive(Debug, PartialEq, Clone, Copy, encase::ShaderType)]
+          pub struct Lights {
+              pub lightCount: u32,
+              pub lights: [[f32; 4]; 8],
+              pub weights: [f32; 4],
           }
-          impl Nested {
-            pub const fn new(a: Input0, b: f32) -> Self {
-                Self { a, b }
+          impl Lights {
+            pub const fn new(
+                lightCount: u32,
+                lights: [[f32; 4]; 8],
+                weights: [f32; 4],
+            ) -> Self {
+                Self { lightCount, lights, weights }
             }
           }
+          impl Lights {
+            #[doc = " The length of the `lights` array."]
+            pub const LIGHTS_LEN: usize = 8;
+            #[doc = " The length of the `weights` array."]
+            pub const WEIGHTS_LEN: usize = 4;
+          }
+      },
+      actual
+    );
+  }
+
+  #[test]
+  fn write_all_structs_padding_field_names() {
+    let source = indoc! {r#"
+            struct Params {
+                scale: f32,
+                _pad0: f32,
+                offset: vec2<f32>,
+                _pad: vec4<f32>,
+                _padding: f32,
+            };
+
+            var<uniform> params: Params;
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+
+    let structs = structs(
+      &module,
+      &WgslBindgenOption {
+        serialization_strategy: WgslTypeSerializeStrategy::Bytemuck,
+        type_map: RustWgslTypeMap.build(WgslTypeSerializeStrategy::Bytemuck),
+        padding_fields_by_name: true,
+        ..Default::default()
+      },
+    );
+    let actual = quote!(#(#structs)*);
+
+    assert_tokens_eq!(
+      quote! {
+          #[repr(C, align(16))]
+          #[derive(Debug, PartialEq, Clone, Copy)]
+          pub struct Params {
+              #[doc = " size: 4, offset: 0x0, type: `f32`"]
+              pub scale: f32,
+              pub _pad0: [u8; 0x4],
+              #[doc = " size: 8, offset: 0x8, type: `vec2<f32>`"]
+              pub offset: [f32; 2],
+              pub _pad: [u8; 0x10],
+              #[doc = " size: 4, offset: 0x20, type: `f32`"]
+              pub _padding: f32,
+              pub _pad__padding: [u8; 0x10 - core::mem::size_of::<f32>()],
+          }
+          impl Params {
+              pub const fn new(scale: f32, offset: [f32; 2], _padding: f32) -> Self {
+                  Self {
+                      scale,
+                      _pad0: [0; 0x4],
+                      offset,
+                      _pad: [0; 0x10],
+                      _padding,
+                      _pad__padding: [0; 0x10 - core::mem::size_of::<f32>()],
+                  }
+              }
+          }
+          #[repr(C)]
+          #[derive(Debug, PartialEq, Clone, Copy)]
+          pub struct ParamsInit {
+              pub scale: f32,
+              pub offset: [f32; 2],
+              pub _padding: f32,
+          }
+          impl ParamsInit {
+              pub const fn build(&self) -> Params {
+                  Params {
+                      scale: self.scale,
+                      _pad0: [0; 0x4],
+                      offset: self.offset,
+                      _pad: [0; 0x10],
+                      _padding: self._padding,
+                      _pad__padding: [0; 0x10 - core::mem::size_of::<f32>()],
+                  }
+              }
+          }
+          impl From<ParamsInit> for Params {
+              fn from(data: ParamsInit) -> Self {
+                  data.build()
+              }
+          }
+          impl From<Params> for ParamsInit {
+              fn from(data: Params) -> Self {
+                  Self {
+                      scale: data.scale,
+                      offset: data.offset,
+                      _padding: data._padding,
+                  }
+              }
+          }
+          impl Params {
+              #[doc = " Overwrites the fields with the fields of `init`, keeping the padding bytes."]
+              pub fn update_from(&mut self, init: &ParamsInit) {
+                  self.scale = init.scale;
+                  self.offset = init.offset;
+                  self._padding = init._padding;
+              }
+          }
+          const PARAMS_ASSERTS: () = {
+              assert!(std::mem::offset_of!(Params, scale) == 0);
+              assert!(std::mem::offset_of!(Params, offset) == 8);
+              assert!(std::mem::offset_of!(Params, _padding) == 32);
+              assert!(std::mem::size_of::<Params>() == 48);
+          };
+          unsafe impl bytemuck::Zeroable for Params {}
+          unsafe impl bytemuck::Pod for Params {}
       },
       actual
     );
@@ -1112,6 +2579,23 @@ mod tests {
                 data.build()
             }
         }
+        impl From<Input0> for Input0Init {
+            fn from(data: Input0) -> Self {
+                Self {
+                    a: data.a,
+                    b: data.b,
+                    c: data.c,
+                }
+            }
+        }
+        impl Input0 {
+            #[doc = " Overwrites the fields with the fields of `init`, keeping the padding bytes."]
+            pub fn update_from(&mut self, init: &Input0Init) {
+                self.a = init.a;
+                self.b = init.b;
+                self.c = init.c;
+            }
+        }
         const INPUT0_ASSERTS: () = {
           assert!(std::mem::offset_of!(Input0, a) == 0);
           assert!(std::mem::offset_of!(Input0, b) == 8);
@@ -1201,6 +2685,61 @@ mod tests {
     );
   }
 
+  #[test]
+  fn write_wrapped_atomic_types() {
+    let source = indoc! {r#"
+            struct Atomics {
+                num: atomic<u32>,
+                numi: atomic<i32>,
+            };
+
+            @group(0) @binding(0)
+            var <storage, read_write> atomics:Atomics;
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+
+    let structs = structs(
+      &module,
+      &WgslBindgenOption {
+        serialization_strategy: WgslTypeSerializeStrategy::Bytemuck,
+        wrap_atomics: true,
+        type_map: RustWgslTypeMap.build(WgslTypeSerializeStrategy::Bytemuck),
+        ..Default::default()
+      },
+    );
+    let actual = quote!(#(#structs)*);
+
+    assert_tokens_eq!(
+      quote! {
+          #[repr(C, align(4))]
+          #[derive(Debug, PartialEq, Clone, Copy)]
+          pub struct Atomics {
+              #[doc = " size: 4, offset: 0x0, type: `atomic<u32>`"]
+              pub num: _root::WgslAtomic<u32>,
+              #[doc = " size: 4, offset: 0x4, type: `atomic<i32>`"]
+              pub numi: _root::WgslAtomic<i32>,
+          }
+          impl Atomics {
+            pub const fn new(
+                num: _root::WgslAtomic<u32>,
+                numi: _root::WgslAtomic<i32>,
+            ) -> Self {
+                Self { num, numi }
+            }
+          }
+          const ATOMICS_ASSERTS: () = {
+              assert!(std::mem::offset_of!(Atomics, num) == 0);
+              assert!(std::mem::offset_of!(Atomics, numi) == 4);
+              assert!(std::mem::size_of::<Atomics>() == 8);
+          };
+          unsafe impl bytemuck::Zeroable for Atomics {}
+          unsafe impl bytemuck::Pod for Atomics {}
+      },
+      actual
+    );
+  }
+
   fn runtime_sized_array_module() -> naga::Module {
     let source = indoc! {r#"
             struct RtsStruct {
@@ -1280,6 +2819,32 @@ mod tests {
         };
         unsafe impl<const N: usize> bytemuck::Zeroable for RtsStruct<N> {}
         unsafe impl<const N: usize> bytemuck::Pod for RtsStruct<N> {}
+        impl RtsStruct<0> {
+            #[doc = " The size in bytes of the fields before the runtime-sized `the_array` array."]
+            pub const FIXED_SIZE: u64 = 4;
+            #[doc = " The stride in bytes of the elements of the runtime-sized `the_array` array."]
+            pub const ELEMENT_STRIDE: u64 = 4;
+            /// The size in bytes of a buffer holding the fields and `element_count` elements.
+            pub const fn buffer_size(element_count: u64) -> u64 {
+                Self::FIXED_SIZE + Self::ELEMENT_STRIDE * element_count
+            }
+            /// Writes the fields of `header` followed by `elements` into `out` with the WGSL
+            /// layout, zeroing any padding.
+            ///
+            /// Panics if `out` is smaller than [`Self::buffer_size`] for the elements.
+            pub fn write_into(header: &Self, elements: &[u32], out: &mut [u8]) {
+                let size = Self::buffer_size(elements.len() as u64) as usize;
+                let out = &mut out[..size];
+                out.fill(0);
+                let header_bytes = bytemuck::bytes_of(header);
+                let offset = std::mem::offset_of!(Self, other_data);
+                out[0..4].copy_from_slice(&header_bytes[offset..offset + 4]);
+                for (i, element) in elements.iter().enumerate() {
+                    let offset = (Self::FIXED_SIZE + Self::ELEMENT_STRIDE * i as u64) as usize;
+                    out[offset..offset + 4].copy_from_slice(&bytemuck::bytes_of(element)[..4]);
+                }
+            }
+        }
       },
       actual
     )
@@ -1309,6 +2874,136 @@ mod tests {
     );
   }
 
+  /// A module with a struct `Foo` containing a sampler, which WGSL itself rejects but
+  /// other frontends can produce, used by a global in `space`.
+  fn unrepresentable_struct_module(space: naga::AddressSpace) -> naga::Module {
+    let mut module = naga::Module::default();
+    let span = naga::Span::UNDEFINED;
+    let f32_ty = module.types.insert(
+      naga::Type {
+        name: None,
+        inner: naga::TypeInner::Scalar(naga::Scalar::F32),
+      },
+      span,
+    );
+    let sampler_ty = module.types.insert(
+      naga::Type {
+        name: None,
+        inner: naga::TypeInner::Sampler { comparison: false },
+      },
+      span,
+    );
+    let member = |name: &str, ty, offset| naga::StructMember {
+      name: Some(name.to_string()),
+      ty,
+      binding: None,
+      offset,
+    };
+    let foo_ty = module.types.insert(
+      naga::Type {
+        name: Some("Foo".to_string()),
+        inner: naga::TypeInner::Struct {
+          members: vec![member("a", f32_ty, 0), member("s", sampler_ty, 4)],
+          span: 4,
+        },
+      },
+      span,
+    );
+    let bar_ty = module.types.insert(
+      naga::Type {
+        name: Some("Bar".to_string()),
+        inner: naga::TypeInner::Struct {
+          members: vec![member("x", f32_ty, 0)],
+          span: 4,
+        },
+      },
+      span,
+    );
+    for (name, ty) in [("foo", foo_ty), ("bar", bar_ty)] {
+      module.global_variables.append(
+        naga::GlobalVariable {
+          name: Some(name.to_string()),
+          space,
+          binding: None,
+          ty,
+          init: None,
+        },
+        span,
+      );
+    }
+    module
+  }
+
+  #[test]
+  fn skip_structs_with_unrepresentable_fields() {
+    let module = unrepresentable_struct_module(naga::AddressSpace::Private);
+
+    let mut warnings = Vec::new();
+    let structs = structs_items(
+      "",
+      &module,
+      &WgslBindgenOption::default(),
+      &Default::default(),
+      &mut warnings,
+    )
+    .unwrap();
+
+    let names = structs
+      .iter()
+      .map(|s| s.path.name.to_string())
+      .collect::<HashSet<_>>();
+    assert_eq!(HashSet::from(["Bar".to_string()]), names);
+    assert_eq!(
+      vec![WgslBindgenWarning::UnrepresentableStructSkipped {
+        name: "Foo".to_string(),
+        field: "s".to_string(),
+        ty: "sampler".to_string(),
+      }],
+      warnings
+    );
+    assert!(check_unrepresentable_types("", &module).is_ok());
+  }
+
+  #[test]
+  fn unrepresentable_struct_in_binding_is_error() {
+    let module = unrepresentable_struct_module(naga::AddressSpace::Uniform);
+
+    assert_eq!(
+      Err(CreateModuleError::UnrepresentableTypeInUse {
+        ty: "sampler".to_string(),
+        used_by: "binding `foo`".to_string(),
+      }),
+      check_unrepresentable_types("", &module)
+    );
+  }
+
+  #[test]
+  fn invalid_dispatch_indirect_structs_are_warnings() {
+    let source = indoc! {r#"
+      struct NotArgs { x: u32, y: f32, z: u32 }
+      var<storage, read_write> a: NotArgs;
+    "#};
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let options = WgslBindgenOption {
+      dispatch_indirect_struct_regex: Some(Regex::new("NotArgs").unwrap()),
+      ..Default::default()
+    };
+
+    let mut warnings = Vec::new();
+    let structs =
+      structs_items("", &module, &options, &Default::default(), &mut warnings).unwrap();
+
+    assert!(structs
+      .iter()
+      .all(|s| !s.item.to_string().contains("DispatchIndirectArgs")));
+    assert_eq!(
+      vec![WgslBindgenWarning::InvalidDispatchIndirectStruct {
+        name: "NotArgs".to_string(),
+      }],
+      warnings
+    );
+  }
+
   #[test]
   fn write_nonpower_of_2_mats_for_bytemuck_option() {
     let source = indoc! {r#"
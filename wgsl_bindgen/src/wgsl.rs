@@ -1,8 +1,12 @@
+use std::collections::{BTreeSet, HashSet};
+
 use naga::StructMember;
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use syn::{Ident, Index};
 
 use crate::quote_gen::RustItemPath;
+use crate::{sanitized_upper_snake_case, CreateModuleError, WgslBindgenOption};
 
 pub fn shader_stages(module: &naga::Module) -> wgpu::ShaderStages {
   module
@@ -16,6 +20,209 @@ pub fn shader_stages(module: &naga::Module) -> wgpu::ShaderStages {
     .collect()
 }
 
+fn module_info(module: &naga::Module) -> Option<naga::valid::ModuleInfo> {
+  naga::valid::Validator::new(
+    naga::valid::ValidationFlags::all(),
+    naga::valid::Capabilities::all(),
+  )
+  .validate(module)
+  .ok()
+}
+
+/// Returns the shader stages of the entry points that use each global variable, indexed by
+/// the global's handle, or `None` if the module fails validation.
+pub fn global_shader_stages(module: &naga::Module) -> Option<Vec<wgpu::ShaderStages>> {
+  let info = module_info(module)?;
+
+  let mut stages = vec![wgpu::ShaderStages::NONE; module.global_variables.len()];
+  for (index, entry) in module.entry_points.iter().enumerate() {
+    let entry_info = info.get_entry_point(index);
+    let stage = match entry.stage {
+      naga::ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
+      naga::ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
+      naga::ShaderStage::Compute => wgpu::ShaderStages::COMPUTE,
+    };
+
+    for (handle, _) in module.global_variables.iter() {
+      if !entry_info[handle].is_empty() {
+        stages[handle.index()] |= stage;
+      }
+    }
+  }
+
+  Some(stages)
+}
+
+/// Returns the bind groups used by each entry point, in entry point order, or `None` if the
+/// module fails validation.
+pub fn entry_point_bind_groups(module: &naga::Module) -> Option<Vec<BTreeSet<u32>>> {
+  let info = module_info(module)?;
+
+  let groups = (0..module.entry_points.len())
+    .map(|index| {
+      let entry_info = info.get_entry_point(index);
+      module
+        .global_variables
+        .iter()
+        .filter(|(handle, _)| !entry_info[*handle].is_empty())
+        .filter_map(|(_, global)| global.binding.as_ref().map(|b| b.group))
+        .collect()
+    })
+    .collect();
+
+  Some(groups)
+}
+
+/// Returns the pipeline overridable constants reachable from the entry point, in declaration
+/// order. This includes overrides used by called functions, overrides used to initialize
+/// the global variables they use and overrides used to initialize other reachable
+/// overrides.
+pub fn entry_overrides<'a>(
+  module: &'a naga::Module,
+  entry: &naga::EntryPoint,
+) -> Vec<&'a naga::Override> {
+  let mut functions = Vec::new();
+  let mut used = HashSet::new();
+  collect_function_overrides(module, &entry.function, &mut functions, &mut used);
+
+  module
+    .overrides
+    .iter()
+    .filter(|(handle, _)| used.contains(handle))
+    .map(|(_, o)| o)
+    .collect()
+}
+
+fn collect_function_overrides(
+  module: &naga::Module,
+  function: &naga::Function,
+  visited: &mut Vec<naga::Handle<naga::Function>>,
+  used: &mut HashSet<naga::Handle<naga::Override>>,
+) {
+  for (_, expression) in function.expressions.iter() {
+    match expression {
+      naga::Expression::Override(handle) => collect_override(module, *handle, used),
+      naga::Expression::GlobalVariable(handle) => {
+        if let Some(init) = module.global_variables[*handle].init {
+          collect_global_expression_overrides(module, init, used);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  let mut called = Vec::new();
+  collect_called_functions(&function.body, &mut called);
+  for handle in called {
+    if !visited.contains(&handle) {
+      visited.push(handle);
+      collect_function_overrides(module, &module.functions[handle], visited, used);
+    }
+  }
+}
+
+fn collect_called_functions(
+  block: &naga::Block,
+  called: &mut Vec<naga::Handle<naga::Function>>,
+) {
+  for statement in block.iter() {
+    match statement {
+      naga::Statement::Call { function, .. } => called.push(*function),
+      naga::Statement::Block(block) => collect_called_functions(block, called),
+      naga::Statement::If { accept, reject, .. } => {
+        collect_called_functions(accept, called);
+        collect_called_functions(reject, called);
+      }
+      naga::Statement::Switch { cases, .. } => {
+        for case in cases {
+          collect_called_functions(&case.body, called);
+        }
+      }
+      naga::Statement::Loop {
+        body, continuing, ..
+      } => {
+        collect_called_functions(body, called);
+        collect_called_functions(continuing, called);
+      }
+      _ => {}
+    }
+  }
+}
+
+fn collect_override(
+  module: &naga::Module,
+  handle: naga::Handle<naga::Override>,
+  used: &mut HashSet<naga::Handle<naga::Override>>,
+) {
+  if used.insert(handle) {
+    if let Some(init) = module.overrides[handle].init {
+      collect_global_expression_overrides(module, init, used);
+    }
+  }
+}
+
+// Override initializers are global expressions, which may refer to other overrides.
+fn collect_global_expression_overrides(
+  module: &naga::Module,
+  handle: naga::Handle<naga::Expression>,
+  used: &mut HashSet<naga::Handle<naga::Override>>,
+) {
+  use naga::Expression as E;
+
+  let children = match &module.global_expressions[handle] {
+    E::Override(o) => return collect_override(module, *o, used),
+    E::Compose { components, .. } => components.clone(),
+    E::Splat { value: expr, .. }
+    | E::Swizzle { vector: expr, .. }
+    | E::AccessIndex { base: expr, .. }
+    | E::Unary { expr, .. }
+    | E::As { expr, .. } => vec![*expr],
+    E::Access { base, index } => vec![*base, *index],
+    E::Binary { left, right, .. } => vec![*left, *right],
+    E::Select {
+      condition,
+      accept,
+      reject,
+    } => vec![*condition, *accept, *reject],
+    E::Math {
+      arg,
+      arg1,
+      arg2,
+      arg3,
+      ..
+    } => std::iter::once(*arg)
+      .chain([arg1, arg2, arg3].into_iter().flatten().copied())
+      .collect(),
+    _ => Vec::new(),
+  };
+
+  for child in children {
+    collect_global_expression_overrides(module, child, used);
+  }
+}
+
+/// The length of a fixed-size array type. Lengths given by WGSL const expressions are
+/// already evaluated by naga, so only runtime-sized arrays have no length.
+pub fn fixed_array_length(ty: &naga::TypeInner) -> Option<u32> {
+  match ty {
+    naga::TypeInner::Array {
+      size: naga::ArraySize::Constant(size),
+      ..
+    }
+    | naga::TypeInner::BindingArray {
+      size: naga::ArraySize::Constant(size),
+      ..
+    } => Some(size.get()),
+    _ => None,
+  }
+}
+
+/// The name of the length constant of an array field or global, e.g. `LIGHTS_LEN` for
+/// `lights`.
+pub fn array_length_const_name(name: &str) -> Ident {
+  Ident::new(&format!("{}_LEN", sanitized_upper_snake_case(name)), Span::call_site())
+}
+
 pub fn buffer_binding_type(storage: naga::AddressSpace) -> TokenStream {
   match storage {
     naga::AddressSpace::Uniform => quote!(wgpu::BufferBindingType::Uniform),
@@ -34,15 +241,81 @@ pub fn buffer_binding_type(storage: naga::AddressSpace) -> TokenStream {
   }
 }
 
-pub fn vertex_format(ty: &naga::Type) -> wgpu::VertexFormat {
+/// The first format of `vertex_format_overrides` matching the field of the struct.
+pub fn vertex_format_override(
+  options: &WgslBindgenOption,
+  fully_qualified_name: &str,
+  member_name: &str,
+) -> Option<wgpu::VertexFormat> {
+  options
+    .vertex_format_overrides
+    .iter()
+    .find(|(struct_regex, field_regex, _)| {
+      struct_regex.is_match(fully_qualified_name) && field_regex.is_match(member_name)
+    })
+    .map(|(_, _, format)| *format)
+}
+
+/// The Rust type holding an attribute of the vertex format in a vertex buffer, and the
+/// number of components the shader sees.
+pub fn vertex_format_rust_type(format: wgpu::VertexFormat) -> (TokenStream, u32) {
+  use wgpu::VertexFormat as F;
+
+  let (scalar, components) = match format {
+    F::Uint8x2 | F::Unorm8x2 => (quote!(u8), 2),
+    F::Uint8x4 | F::Unorm8x4 => (quote!(u8), 4),
+    F::Sint8x2 | F::Snorm8x2 => (quote!(i8), 2),
+    F::Sint8x4 | F::Snorm8x4 => (quote!(i8), 4),
+    F::Uint16x2 | F::Unorm16x2 | F::Float16x2 => (quote!(u16), 2),
+    F::Uint16x4 | F::Unorm16x4 | F::Float16x4 => (quote!(u16), 4),
+    F::Sint16x2 | F::Snorm16x2 => (quote!(i16), 2),
+    F::Sint16x4 | F::Snorm16x4 => (quote!(i16), 4),
+    F::Float32 => (quote!(f32), 1),
+    F::Float32x2 => (quote!(f32), 2),
+    F::Float32x3 => (quote!(f32), 3),
+    F::Float32x4 => (quote!(f32), 4),
+    F::Uint32 => (quote!(u32), 1),
+    F::Uint32x2 => (quote!(u32), 2),
+    F::Uint32x3 => (quote!(u32), 3),
+    F::Uint32x4 => (quote!(u32), 4),
+    F::Sint32 => (quote!(i32), 1),
+    F::Sint32x2 => (quote!(i32), 2),
+    F::Sint32x3 => (quote!(i32), 3),
+    F::Sint32x4 => (quote!(i32), 4),
+    F::Float64 => (quote!(f64), 1),
+    F::Float64x2 => (quote!(f64), 2),
+    F::Float64x3 => (quote!(f64), 3),
+    F::Float64x4 => (quote!(f64), 4),
+    // The components are packed into a single integer.
+    F::Unorm10_10_10_2 => return (quote!(u32), 4),
+  };
+
+  if components == 1 {
+    (scalar, components)
+  } else {
+    let len = Index::from(components as usize);
+    (quote!([#scalar; #len]), components)
+  }
+}
+
+/// The number of components of a vertex attribute of the type.
+pub fn vertex_attribute_components(ty: &naga::Type) -> u32 {
+  match ty.inner {
+    naga::TypeInner::Vector { size, .. } => size as u32,
+    _ => 1,
+  }
+}
+
+/// The vertex format of a vertex attribute of the type, if wgpu has one.
+pub fn vertex_format(ty: &naga::Type) -> Option<wgpu::VertexFormat> {
   // Not all wgsl types work as vertex attributes in wgpu.
-  match &ty.inner {
+  let format = match &ty.inner {
     naga::TypeInner::Scalar(scalar) => match (scalar.kind, scalar.width) {
       (naga::ScalarKind::Sint, 4) => wgpu::VertexFormat::Sint32,
       (naga::ScalarKind::Uint, 4) => wgpu::VertexFormat::Uint32,
       (naga::ScalarKind::Float, 4) => wgpu::VertexFormat::Float32,
       (naga::ScalarKind::Float, 8) => wgpu::VertexFormat::Float64,
-      _ => todo!(),
+      _ => return None,
     },
     naga::TypeInner::Vector { size, scalar } => match size {
       naga::VectorSize::Bi => match (scalar.kind, scalar.width) {
@@ -50,33 +323,49 @@ pub fn vertex_format(ty: &naga::Type) -> wgpu::VertexFormat {
         (naga::ScalarKind::Uint, 1) => wgpu::VertexFormat::Uint8x2,
         (naga::ScalarKind::Sint, 2) => wgpu::VertexFormat::Sint16x2,
         (naga::ScalarKind::Uint, 2) => wgpu::VertexFormat::Uint16x2,
+        (naga::ScalarKind::Float, 2) => wgpu::VertexFormat::Float16x2,
         (naga::ScalarKind::Uint, 4) => wgpu::VertexFormat::Uint32x2,
         (naga::ScalarKind::Sint, 4) => wgpu::VertexFormat::Sint32x2,
         (naga::ScalarKind::Float, 4) => wgpu::VertexFormat::Float32x2,
         (naga::ScalarKind::Float, 8) => wgpu::VertexFormat::Float64x2,
-        _ => todo!(),
+        _ => return None,
       },
       naga::VectorSize::Tri => match (scalar.kind, scalar.width) {
         (naga::ScalarKind::Uint, 4) => wgpu::VertexFormat::Uint32x3,
         (naga::ScalarKind::Sint, 4) => wgpu::VertexFormat::Sint32x3,
         (naga::ScalarKind::Float, 4) => wgpu::VertexFormat::Float32x3,
         (naga::ScalarKind::Float, 8) => wgpu::VertexFormat::Float64x3,
-        _ => todo!(),
+        _ => return None,
       },
       naga::VectorSize::Quad => match (scalar.kind, scalar.width) {
         (naga::ScalarKind::Sint, 1) => wgpu::VertexFormat::Sint8x4,
         (naga::ScalarKind::Uint, 1) => wgpu::VertexFormat::Uint8x4,
         (naga::ScalarKind::Sint, 2) => wgpu::VertexFormat::Sint16x4,
         (naga::ScalarKind::Uint, 2) => wgpu::VertexFormat::Uint16x4,
+        (naga::ScalarKind::Float, 2) => wgpu::VertexFormat::Float16x4,
         (naga::ScalarKind::Uint, 4) => wgpu::VertexFormat::Uint32x4,
         (naga::ScalarKind::Sint, 4) => wgpu::VertexFormat::Sint32x4,
         (naga::ScalarKind::Float, 4) => wgpu::VertexFormat::Float32x4,
         (naga::ScalarKind::Float, 8) => wgpu::VertexFormat::Float64x4,
-        _ => todo!(),
+        _ => return None,
       },
     },
-    _ => todo!(), // are these types even valid as attributes?
-  }
+    _ => return None,
+  };
+  Some(format)
+}
+
+/// The vertex format of the vertex attribute `attribute`, failing for types without one,
+/// like `f16` or `vec3<f16>`.
+pub fn attribute_vertex_format(
+  module: &naga::Module,
+  ty: &naga::Type,
+  attribute: String,
+) -> Result<wgpu::VertexFormat, CreateModuleError> {
+  vertex_format(ty).ok_or_else(|| CreateModuleError::UnsupportedVertexFormat {
+    attribute,
+    ty: ty.inner.to_wgsl(&module.to_ctx()),
+  })
 }
 
 pub struct VertexInput {
@@ -84,57 +373,93 @@ pub struct VertexInput {
   pub fields: Vec<(u32, StructMember)>,
 }
 
-// TODO: Handle errors.
-// Collect the necessary data to generate an equivalent Rust struct.
+/// Returns the vertex input structs of all vertex entry points, once per struct.
 pub fn get_vertex_input_structs(
   invoking_entry_module: &str,
   module: &naga::Module,
 ) -> Vec<VertexInput> {
-  // TODO: Handle multiple entries?
+  let mut seen = HashSet::new();
   module
     .entry_points
     .iter()
-    .find(|e| e.stage == naga::ShaderStage::Vertex)
-    .map(|vertex_entry| {
-      vertex_entry
-        .function
-        .arguments
-        .iter()
-        .filter(|a| a.binding.is_none())
-        .filter_map(|argument| {
-          let arg_type = &module.types[argument.ty];
-          match &arg_type.inner {
-            naga::TypeInner::Struct { members, span: _ } => {
-              let item_path = RustItemPath::from_mangled(
-                arg_type.name.as_ref().unwrap(),
-                invoking_entry_module,
-              );
-
-              let input = VertexInput {
-                item_path,
-                fields: members
-                  .iter()
-                  .filter_map(|member| {
-                    // Skip builtins since they have no location binding.
-                    let location = match member.binding.as_ref().unwrap() {
-                      naga::Binding::BuiltIn(_) => None,
-                      naga::Binding::Location { location, .. } => Some(*location),
-                    }?;
-
-                    Some((location, member.clone()))
-                  })
-                  .collect(),
-              };
-
-              Some(input)
-            }
-            // An argument has to have a binding unless it is a structure.
-            _ => None,
-          }
-        })
-        .collect()
+    .filter(|e| e.stage == naga::ShaderStage::Vertex)
+    .flat_map(|e| get_entry_vertex_input_structs(invoking_entry_module, module, e))
+    .filter(|input| seen.insert(input.item_path.clone()))
+    .collect()
+}
+
+/// Returns the locations and formats of the located parameters of the entry point, which
+/// aren't wrapped in a struct, in argument order.
+pub fn get_entry_located_vertex_inputs(
+  module: &naga::Module,
+  vertex_entry: &naga::EntryPoint,
+) -> Result<Vec<(u32, wgpu::VertexFormat)>, CreateModuleError> {
+  vertex_entry
+    .function
+    .arguments
+    .iter()
+    .filter_map(|argument| match argument.binding.as_ref()? {
+      // Skip builtins since they have no location binding.
+      naga::Binding::BuiltIn(_) => None,
+      naga::Binding::Location { location, .. } => {
+        let attribute = format!(
+          "{}::{}",
+          vertex_entry.name,
+          argument.name.as_deref().unwrap_or_default()
+        );
+        let format =
+          attribute_vertex_format(module, &module.types[argument.ty], attribute);
+        Some(format.map(|format| (*location, format)))
+      }
     })
-    .unwrap_or_default()
+    .collect()
+}
+
+// TODO: Handle errors.
+// Collect the necessary data to generate an equivalent Rust struct.
+/// Returns the vertex input structs of the entry point, in argument order.
+pub fn get_entry_vertex_input_structs(
+  invoking_entry_module: &str,
+  module: &naga::Module,
+  vertex_entry: &naga::EntryPoint,
+) -> Vec<VertexInput> {
+  vertex_entry
+    .function
+    .arguments
+    .iter()
+    .filter(|a| a.binding.is_none())
+    .filter_map(|argument| {
+      let arg_type = &module.types[argument.ty];
+      match &arg_type.inner {
+        naga::TypeInner::Struct { members, span: _ } => {
+          let item_path = RustItemPath::from_mangled(
+            arg_type.name.as_ref().unwrap(),
+            invoking_entry_module,
+          );
+
+          let input = VertexInput {
+            item_path,
+            fields: members
+              .iter()
+              .filter_map(|member| {
+                // Skip builtins since they have no location binding.
+                let location = match member.binding.as_ref().unwrap() {
+                  naga::Binding::BuiltIn(_) => None,
+                  naga::Binding::Location { location, .. } => Some(*location),
+                }?;
+
+                Some((location, member.clone()))
+              })
+              .collect(),
+          };
+
+          Some(input)
+        }
+        // An argument has to have a binding unless it is a structure.
+        _ => None,
+      }
+    })
+    .collect()
 }
 
 #[cfg(test)]
@@ -220,6 +545,53 @@ mod tests {
     assert_eq!(wgpu::ShaderStages::all(), shader_stages(&module));
   }
 
+  #[test]
+  fn vertex_format_f16_vectors() {
+    // The WGSL frontend doesn't parse `f16` yet, so build the types directly.
+    let f16_vector = |size| naga::Type {
+      name: None,
+      inner: naga::TypeInner::Vector {
+        size,
+        scalar: naga::Scalar::float(2),
+      },
+    };
+
+    assert_eq!(
+      Some(wgpu::VertexFormat::Float16x2),
+      vertex_format(&f16_vector(naga::VectorSize::Bi))
+    );
+    assert_eq!(
+      Some(wgpu::VertexFormat::Float16x4),
+      vertex_format(&f16_vector(naga::VectorSize::Quad))
+    );
+  }
+
+  #[test]
+  fn vertex_format_unsupported_f16() {
+    // wgpu has no vertex formats for a single or three f16 components.
+    let f16_scalar = naga::Type {
+      name: None,
+      inner: naga::TypeInner::Scalar(naga::Scalar::float(2)),
+    };
+    let f16_vec3 = naga::Type {
+      name: None,
+      inner: naga::TypeInner::Vector {
+        size: naga::VectorSize::Tri,
+        scalar: naga::Scalar::float(2),
+      },
+    };
+
+    assert_eq!(None, vertex_format(&f16_scalar));
+    assert_eq!(None, vertex_format(&f16_vec3));
+
+    let module = naga::Module::default();
+    assert!(matches!(
+      attribute_vertex_format(&module, &f16_vec3, "VertexInput::normal".to_string()),
+      Err(CreateModuleError::UnsupportedVertexFormat { attribute, ty })
+        if attribute == "VertexInput::normal" && ty == "vec3<f16>"
+    ));
+  }
+
   #[test]
   fn vertex_input_structs_two_structs() {
     let source = indoc! {r#"
@@ -264,4 +636,25 @@ mod tests {
     assert_eq!("in5", vertex_inputs[1].fields[2].1.name.as_ref().unwrap());
     assert_eq!(5, vertex_inputs[1].fields[2].0);
   }
+
+  #[test]
+  fn entry_overrides_of_global_initializers() {
+    let source = indoc! {r#"
+            override scale: f32;
+            override unused: f32 = 1.0;
+            var<private> factor: f32 = scale;
+
+            @compute @workgroup_size(1)
+            fn main() {
+                factor *= 2.0;
+            }
+        "#};
+
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let overrides = entry_overrides(&module, &module.entry_points[0])
+      .into_iter()
+      .map(|o| o.name.clone().unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(vec!["scale".to_string()], overrides);
+  }
 }
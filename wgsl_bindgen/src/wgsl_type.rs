@@ -22,6 +22,9 @@ pub enum WgslVecType {
   Vec2h,
   Vec3h,
   Vec4h,
+  Vec2d,
+  Vec3d,
+  Vec4d,
 }
 
 /// The `WgslType` enum represents various Wgsl matrices.
@@ -46,6 +49,15 @@ pub enum WgslMatType {
   Mat4x2h,
   Mat4x3h,
   Mat4x4h,
+  Mat2x2d,
+  Mat2x3d,
+  Mat2x4d,
+  Mat3x2d,
+  Mat3x3d,
+  Mat3x4d,
+  Mat4x2d,
+  Mat4x3d,
+  Mat4x4d,
 }
 
 pub(crate) trait WgslTypeAlignmentAndSize {
@@ -62,6 +74,9 @@ impl WgslTypeAlignmentAndSize for WgslVecType {
       Vec3h => (8, 6),
       Vec4i | Vec4u | Vec4f => (16, 16),
       Vec4h => (8, 8),
+      Vec2d => (16, 16),
+      Vec3d => (32, 24),
+      Vec4d => (32, 32),
     }
   }
 }
@@ -89,6 +104,15 @@ impl WgslTypeAlignmentAndSize for WgslMatType {
       Mat3x4h => (8, 24),
       Mat4x4f => (16, 64),
       Mat4x4h => (8, 32),
+      Mat2x2d => (16, 32),
+      Mat3x2d => (16, 48),
+      Mat4x2d => (16, 64),
+      Mat2x3d => (32, 64),
+      Mat3x3d => (32, 96),
+      Mat4x3d => (32, 128),
+      Mat2x4d => (32, 64),
+      Mat3x4d => (32, 96),
+      Mat4x4d => (32, 128),
     }
   }
 }
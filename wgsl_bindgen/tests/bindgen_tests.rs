@@ -1,9 +1,30 @@
 use std::fs::read_to_string;
+use std::path::PathBuf;
 
 use miette::{IntoDiagnostic, Result};
 use pretty_assertions::assert_eq;
 use wgsl_bindgen::*;
 
+/// Generates the bindings of `source` as the single entry `main`, with the options
+/// adjusted by `customize`.
+fn generate_main(
+  source: &str,
+  customize: impl FnOnce(&mut WgslBindgenOptionBuilder) -> &mut WgslBindgenOptionBuilder,
+) -> Result<String, WgslBindgenError> {
+  let mut builder = WgslBindgenOptionBuilder::default();
+  builder
+    .add_entry_point("virtual/main.wgsl")
+    .workspace_root("virtual")
+    .import_resolver(
+      InMemoryImportResolver::new().with_source("virtual/main.wgsl", source),
+    )
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true);
+  customize(&mut builder).build()?.generate_string()
+}
+
 #[test]
 fn test_bevy_bindgen() -> Result<()> {
   WgslBindgenOptionBuilder::default()
@@ -112,3 +133,2220 @@ fn test_path_import() -> Result<()> {
 
   Ok(())
 }
+
+#[test]
+fn test_shader_defs_select_bind_groups() -> Result<()> {
+  let generate = |defs: &[(&str, ShaderDefValue)]| -> Result<String> {
+    let mut builder = WgslBindgenOptionBuilder::default();
+    builder
+      .add_entry_point("tests/shaders/shader_defs/main.wgsl")
+      .workspace_root("tests/shaders/shader_defs")
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true);
+
+    for (name, value) in defs {
+      builder.add_shader_def(*name, *value);
+    }
+
+    builder.build()?.generate_string().into_diagnostic()
+  };
+
+  let without_shadows = generate(&[])?;
+  assert!(!without_shadows.contains("WgpuBindGroup1"));
+  assert!(!without_shadows.contains("ShadowParams"));
+
+  let with_shadows = generate(&[("SHADOWS", ShaderDefValue::Bool(true))])?;
+  assert!(with_shadows.contains("WgpuBindGroup1"));
+  assert!(with_shadows.contains("pub struct ShadowParams"));
+
+  Ok(())
+}
+
+#[test]
+fn test_entry_results_record_imports_and_defines() -> Result<()> {
+  let bindgen = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/shader_defs/main.wgsl")
+    .workspace_root("tests/shaders/shader_defs")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?;
+
+  let (_, entries) = bindgen
+    .generate_string_with_entry_results()
+    .into_diagnostic()?;
+
+  assert_eq!(entries.len(), 1);
+  assert_eq!(entries[0].encountered_defines, vec!["SHADOWS", "SHADOW_PCF"]);
+  assert_eq!(
+    entries[0].resolved_imports,
+    vec![PathBuf::from("tests/shaders/shader_defs/common.wgsl")]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_dependencies_of_entry() -> Result<()> {
+  let bindgen = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/shader_defs/main.wgsl")
+    .workspace_root("tests/shaders/shader_defs")
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .build()?;
+
+  let dependencies = bindgen
+    .dependencies("tests/shaders/shader_defs/main.wgsl".as_ref())
+    .into_diagnostic()?;
+  let expected = std::fs::canonicalize("tests/shaders/shader_defs/common.wgsl").unwrap();
+  assert_eq!(dependencies, vec![expected]);
+
+  let unknown = bindgen.dependencies("tests/shaders/minimal.wgsl".as_ref());
+  assert!(matches!(unknown, Err(WgslBindgenError::UnknownSourceFile { .. })));
+
+  Ok(())
+}
+
+#[test]
+fn test_shader_def_permutations() -> Result<()> {
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/shader_defs/main.wgsl")
+    .workspace_root("tests/shaders/shader_defs")
+    .add_shader_def_permutation(Vec::<String>::new())
+    .add_shader_def_permutation(["SHADOWS"])
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub mod default"));
+  assert!(actual.contains("pub mod shadows"));
+  assert!(actual.contains("MainDefault"));
+  assert!(
+    actual.contains("Self::MainShadows => main::shadows::create_pipeline_layout(device)")
+  );
+  assert!(actual.contains("MainShadows::BindGroup1"));
+  assert!(!actual.contains("MainDefault::BindGroup1"));
+
+  Ok(())
+}
+
+#[test]
+fn test_in_memory_import_resolver() -> Result<()> {
+  let resolver = InMemoryImportResolver::new()
+    .with_source(
+      "virtual/main.wgsl",
+      indoc::indoc! {r#"
+        #import common::Uniforms
+
+        @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+          return uniforms.color;
+        }
+      "#},
+    )
+    .with_source("virtual/common.wgsl", "struct Uniforms { color: vec4f }");
+
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("virtual/main.wgsl")
+    .workspace_root("virtual")
+    .import_resolver(resolver)
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub struct Uniforms"));
+  assert!(actual.contains("pub mod main"));
+  Ok(())
+}
+
+#[test]
+fn test_shader_defs_from_env() -> Result<()> {
+  std::env::set_var("WGSL_BINDGEN_TEST_DEF_SHADOWS", "true");
+  std::env::set_var("WGSL_BINDGEN_TEST_QUALITY", "2u");
+
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/shader_defs/main.wgsl")
+    .workspace_root("tests/shaders/shader_defs")
+    .add_shader_defs_from_env_prefix("WGSL_BINDGEN_TEST_DEF_")
+    .add_shader_def_from_env("WGSL_BINDGEN_TEST_QUALITY", true)
+    .add_shader_def_from_env("WGSL_BINDGEN_TEST_UNSET", false)
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub struct ShadowParams"));
+  assert!(actual.contains(
+    "// Shader defines from environment: SHADOWS=true, WGSL_BINDGEN_TEST_QUALITY=2u\n"
+  ));
+
+  let missing = WgslBindgenOptionBuilder::default()
+    .add_entry_point("tests/shaders/shader_defs/main.wgsl")
+    .workspace_root("tests/shaders/shader_defs")
+    .add_shader_def_from_env("WGSL_BINDGEN_TEST_UNSET", true)
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .build();
+
+  assert!(matches!(
+    missing,
+    Err(WgslBindgenError::ShaderDefEnvVarNotFound { name }) if name == "WGSL_BINDGEN_TEST_UNSET"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_if_defined_directives() -> Result<()> {
+  let resolver = InMemoryImportResolver::new().with_source(
+    "virtual/main.wgsl",
+    indoc::indoc! {r#"
+      #if defined(SHADOWS)
+      @group(0) @binding(0) var<uniform> shadow_strength: f32;
+      #endif
+
+      #if !defined(SHADOWS)
+      @group(0) @binding(1) var<uniform> ambient: f32;
+      #endif
+
+      @fragment
+      fn fs_main() -> @location(0) vec4<f32> {
+        return vec4(1.0);
+      }
+    "#},
+  );
+
+  let bindgen = WgslBindgenOptionBuilder::default()
+    .add_entry_point("virtual/main.wgsl")
+    .workspace_root("virtual")
+    .import_resolver(resolver)
+    .add_shader_def("SHADOWS", ShaderDefValue::Bool(true))
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?;
+
+  let (actual, entries) = bindgen
+    .generate_string_with_entry_results()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub shadow_strength"));
+  assert!(!actual.contains("pub ambient"));
+  assert_eq!(entries[0].encountered_defines, vec!["SHADOWS"]);
+  Ok(())
+}
+
+#[test]
+fn test_shader_define_consts() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    #define MAX_LIGHTS 4
+    #define ENABLE_FOG
+
+    const WORKGROUP: u32 = 8u;
+
+    struct Lights {
+      intensities: array<vec4<f32>, #MAX_LIGHTS>,
+    }
+
+    @group(0) @binding(0) var<uniform> lights: Lights;
+
+    @compute @workgroup_size(#WORKGROUP)
+    fn main() {}
+  "#};
+
+  let actual =
+    generate_main(source, |b| b.add_shader_def("WORKGROUP", ShaderDefValue::UInt(8)))
+      .into_diagnostic()?;
+
+  assert!(actual.contains("pub const MAX_LIGHTS: u32 = 4u32;"));
+  assert!(actual.contains("pub const WORKGROUP: u32 = 8u32;"));
+  assert_eq!(actual.matches("pub const WORKGROUP").count(), 1);
+  assert!(!actual.contains("ENABLE_FOG"));
+  Ok(())
+}
+
+#[test]
+fn test_binding_arrays() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    @group(0) @binding(0) var textures: binding_array<texture_2d<f32>, 16>;
+    @group(0) @binding(1) var samplers: binding_array<sampler>;
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+      return textureSample(textures[0], samplers[0], vec2(0.0));
+    }
+  "#};
+
+  let actual = generate_main(source, |b| b.add_binding_array_count("samplers", 4))
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub textures: &'a [&'a wgpu::TextureView]"));
+  assert!(actual.contains("pub samplers: &'a [&'a wgpu::Sampler]"));
+  assert!(actual.contains("wgpu::BindingResource::TextureViewArray(params.textures)"));
+  assert!(actual.contains("wgpu::BindingResource::SamplerArray(params.samplers)"));
+  assert!(actual.contains("count: std::num::NonZeroU32::new(16u32)"));
+  assert!(actual.contains("count: std::num::NonZeroU32::new(4u32)"));
+  Ok(())
+}
+
+#[test]
+fn test_dynamic_offset_bindings() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Camera { view_proj: mat4x4<f32> }
+    struct Object { model: mat4x4<f32> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(0) @binding(1) var<uniform> object: Object;
+    @group(1) @binding(0) var<uniform> light: Object;
+
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+      return camera.view_proj * object.model * light.model * position;
+    }
+  "#};
+
+  let actual = generate_main(source, |b| {
+    b.add_dynamic_offset_binding(Regex::new("^main::object$").unwrap())
+  })
+  .into_diagnostic()?;
+
+  assert_eq!(actual.matches("has_dynamic_offset: true").count(), 1);
+  assert!(actual.contains("offsets: [wgpu::DynamicOffset; 1]"));
+  assert!(actual.contains("render_pass.set_bind_group(0, &self.0, &offsets);"));
+  assert!(actual.contains("render_pass.set_bind_group(1, &self.0, &[]);"));
+  assert!(actual.contains("pub bind_group0_offsets: [wgpu::DynamicOffset; 1]"));
+  assert!(actual.contains("self.bind_group0.set(pass, self.bind_group0_offsets);"));
+  assert!(actual.contains("bind_group0.set(pass, bind_group0_offsets);"));
+  Ok(())
+}
+
+#[test]
+fn test_binding_visibility_from_usage() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Camera { view_proj: mat4x4<f32> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(0) @binding(1) var color_texture: texture_2d<f32>;
+    @group(0) @binding(2) var color_sampler: sampler;
+
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+      return camera.view_proj * position;
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+      return textureSample(color_texture, color_sampler, vec2<f32>(0.5));
+    }
+  "#};
+
+  let generate = |union_binding_visibility: bool| -> Result<String> {
+    generate_main(source, |b| b.union_binding_visibility(union_binding_visibility))
+      .into_diagnostic()
+  };
+
+  let actual = generate(false)?;
+  assert_eq!(
+    actual
+      .matches("visibility: wgpu::ShaderStages::VERTEX,")
+      .count(),
+    1
+  );
+  assert_eq!(
+    actual
+      .matches("visibility: wgpu::ShaderStages::FRAGMENT,")
+      .count(),
+    2
+  );
+
+  let actual = generate(true)?;
+  assert_eq!(
+    actual
+      .matches("visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,")
+      .count(),
+    3
+  );
+  Ok(())
+}
+
+#[test]
+fn test_non_filterable_texture_bindings() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    @group(0) @binding(0) var hdr_texture: texture_2d<f32>;
+    @group(0) @binding(1) var hdr_sampler: sampler;
+    @group(0) @binding(2) var color_texture: texture_2d<f32>;
+    @group(0) @binding(3) var color_sampler: sampler;
+
+    @fragment
+    fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+      return textureSample(hdr_texture, hdr_sampler, uv)
+        + textureSample(color_texture, color_sampler, uv);
+    }
+  "#};
+
+  let actual = generate_main(source, |b| {
+    b.add_non_filterable_texture_binding(Regex::new("^main::hdr_texture$").unwrap())
+  })
+  .into_diagnostic()?;
+
+  assert_eq!(actual.matches("filterable: false").count(), 1);
+  assert_eq!(actual.matches("filterable: true").count(), 1);
+  assert_eq!(
+    actual
+      .matches("wgpu::SamplerBindingType::NonFiltering")
+      .count(),
+    1
+  );
+  assert_eq!(
+    actual
+      .matches("wgpu::SamplerBindingType::Filtering")
+      .count(),
+    1
+  );
+  Ok(())
+}
+
+#[test]
+fn test_sampler_binding_overrides() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    @group(0) @binding(0) var color_texture: texture_2d<f32>;
+    @group(0) @binding(1) var nearest_sampler: sampler;
+    @group(0) @binding(2) var linear_sampler: sampler;
+
+    @fragment
+    fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+      return textureSample(color_texture, nearest_sampler, uv)
+        + textureSample(color_texture, linear_sampler, uv);
+    }
+  "#};
+
+  let actual = generate_main(source, |b| {
+    b.add_sampler_binding_override(
+      Regex::new("nearest").unwrap(),
+      SamplerKind::NonFiltering,
+    )
+    .add_sampler_binding_override(
+      Regex::new("^main::nearest_sampler$").unwrap(),
+      SamplerKind::NonFiltering,
+    )
+  })
+  .into_diagnostic()?;
+
+  assert_eq!(
+    actual
+      .matches("wgpu::SamplerBindingType::NonFiltering")
+      .count(),
+    1
+  );
+  assert_eq!(
+    actual
+      .matches("wgpu::SamplerBindingType::Filtering")
+      .count(),
+    1
+  );
+
+  let conflicting = generate_main(source, |b| {
+    b.add_sampler_binding_override(
+      Regex::new("nearest").unwrap(),
+      SamplerKind::NonFiltering,
+    )
+    .add_sampler_binding_override(
+      Regex::new("_sampler$").unwrap(),
+      SamplerKind::Filtering,
+    )
+  });
+
+  assert!(matches!(
+    conflicting,
+    Err(WgslBindgenError::ModuleCreationError(
+      CreateModuleError::ConflictingSamplerOverrides { binding_name, .. }
+    )) if binding_name == "main::nearest_sampler"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_min_binding_size_overrides() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Light { color: vec4<f32> }
+    struct Camera { view_proj: mat4x4<f32> }
+
+    @group(0) @binding(0) var<storage, read> lights: array<Light>;
+    @group(0) @binding(1) var<uniform> camera: Camera;
+    @group(0) @binding(2) var<uniform> object: Camera;
+
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+      return camera.view_proj * object.view_proj * position * lights[0].color;
+    }
+  "#};
+
+  let actual = generate_main(source, |b| {
+    b.add_min_binding_size_override(Regex::new("^main::lights$").unwrap(), Some(64))
+      .add_min_binding_size_override(Regex::new("^main::camera$").unwrap(), None)
+  })
+  .into_diagnostic()?;
+
+  assert!(actual.contains("min_binding_size: std::num::NonZeroU64::new(64),"));
+  assert!(actual.contains(r#"/// @binding(0): "lights" (min_binding_size overridden)"#));
+  assert!(actual.contains(r#"/// @binding(1): "camera" (min_binding_size overridden)"#));
+  assert!(actual.contains(r#"/// @binding(2): "object""#));
+  assert_eq!(actual.matches("min_binding_size: None,").count(), 1);
+  assert_eq!(
+    actual
+      .matches("std::mem::size_of::<_root::main::Camera>() as _,")
+      .count(),
+    1
+  );
+  Ok(())
+}
+
+#[test]
+fn test_arrayed_textures() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    @group(0) @binding(0) var layers: texture_2d_array<f32>;
+    @group(0) @binding(1) var environment: texture_cube_array<f32>;
+    @group(0) @binding(2) var shadow_maps: texture_depth_2d_array;
+    @group(0) @binding(3) var shadow_sampler: sampler_comparison;
+    @group(0) @binding(4) var output: texture_storage_2d_array<rgba8unorm, write>;
+    @group(0) @binding(5) var color_sampler: sampler;
+
+    @fragment
+    fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+      let shadow = textureSampleCompare(shadow_maps, shadow_sampler, uv, 0, 0.5);
+      textureStore(output, vec2<i32>(0), 0, vec4<f32>(shadow));
+      return textureSample(layers, color_sampler, uv, 0)
+        + textureSample(environment, color_sampler, vec3<f32>(uv, 1.0), 0);
+    }
+  "#};
+
+  let actual = generate_main(source, |b| b).into_diagnostic()?;
+
+  assert_eq!(
+    actual
+      .matches("wgpu::TextureViewDimension::D2Array")
+      .count(),
+    3
+  );
+  assert_eq!(
+    actual
+      .matches("wgpu::TextureViewDimension::CubeArray")
+      .count(),
+    1
+  );
+  assert!(!actual.contains("wgpu::TextureViewDimension::D2,"));
+  Ok(())
+}
+
+#[test]
+fn test_multisampled_textures_are_not_filterable() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    @group(0) @binding(0) var color_msaa: texture_multisampled_2d<f32>;
+    @group(0) @binding(1) var depth_msaa: texture_depth_multisampled_2d;
+
+    @fragment
+    fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+      let coords = vec2<i32>(position.xy);
+      var color = vec4<f32>(0.0);
+      for (var i = 0; i < 4; i++) {
+        color += textureLoad(color_msaa, coords, i);
+      }
+      return color * 0.25 + vec4<f32>(textureLoad(depth_msaa, coords, 0));
+    }
+  "#};
+
+  let actual = generate_main(source, |b| b).into_diagnostic()?;
+
+  assert!(actual.contains("filterable: false"));
+  assert!(!actual.contains("filterable: true"));
+  assert!(actual.contains("wgpu::TextureSampleType::Depth"));
+  assert_eq!(actual.matches("multisampled: true").count(), 2);
+  Ok(())
+}
+
+#[test]
+fn test_bind_groups_from_named() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Camera { view_proj: mat4x4<f32> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(1) @binding(0) var color_texture: texture_2d<f32>;
+    @group(1) @binding(1) var color_sampler: sampler;
+
+    @fragment
+    fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+      return camera.view_proj * textureSample(color_texture, color_sampler, uv);
+    }
+  "#};
+
+  let actual =
+    generate_main(source, |b| b.named_bind_group_resources(true)).into_diagnostic()?;
+
+  assert!(actual.contains("pub struct NamedResourcesError"));
+  assert!(actual.contains("pub const BIND_GROUP_ENTRY_NAMES: [&str; 3] = ["));
+  assert!(actual.contains(r#""main::color_texture","#));
+  assert!(actual
+    .contains("Some(wgpu::BindingResource::TextureView(resource)) => Some(*resource),"));
+  assert!(
+    actual.contains("Result<(WgpuBindGroup0, WgpuBindGroup1), NamedResourcesError>")
+  );
+  Ok(())
+}
+
+#[test]
+fn test_bind_group_entries_builders() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Camera { view_proj: mat4x4<f32> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(0) @binding(1) var color_texture: texture_2d<f32>;
+    @group(0) @binding(2) var color_sampler: sampler;
+    @group(0) @binding(3) var debug_textures: binding_array<texture_2d<f32>, 2>;
+
+    @fragment
+    fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+      return camera.view_proj * textureSample(color_texture, color_sampler, uv)
+        + textureSample(debug_textures[1], color_sampler, uv);
+    }
+  "#};
+
+  let actual =
+    generate_main(source, |b| b.bind_group_entries_builders(true)).into_diagnostic()?;
+
+  assert!(actual.contains("pub struct BindGroupEntryDefaults<'a>"));
+  assert!(actual.contains("pub struct MissingBindGroupEntries(pub Vec<&'static str>);"));
+  assert!(actual.contains("pub struct WgpuBindGroup0EntriesBuilder<'a>"));
+  assert!(actual
+    .contains("pub fn color_texture(mut self, color_texture: &'a wgpu::TextureView)"));
+  assert!(actual.contains("self.camera.or_else(|| self.defaults.buffer.clone())"));
+  assert!(actual.contains("self.color_sampler.or(self.defaults.sampler)"));
+  assert!(actual.contains("let debug_textures = self.debug_textures;"));
+  assert!(actual.contains("missing.push(\"debug_textures\");"));
+  Ok(())
+}
+
+#[test]
+fn test_omit_unused_bindings() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Camera { view_proj: mat4x4<f32> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(0) @binding(1) var<uniform> debug_camera: Camera;
+    @group(0) @binding(2) var<uniform> object: Camera;
+    @group(1) @binding(0) var debug_texture: texture_2d<f32>;
+
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+      return camera.view_proj * object.view_proj * position;
+    }
+  "#};
+
+  let actual =
+    generate_main(source, |b| b.omit_unused_bindings(true)).into_diagnostic()?;
+
+  assert!(!actual.contains("pub debug_camera:"));
+  assert!(!actual.contains("pub struct WgpuBindGroup1("));
+  assert!(actual.contains(r#"/// @binding(2): "object""#));
+  assert!(actual.contains(
+    r#"pub const OMITTED_BINDINGS: &[&str] = &["debug_camera", "debug_texture"];"#
+  ));
+  assert!(actual.contains("/// - `debug_texture`"));
+
+  // A group left without bindings is a gap.
+  let source = indoc::indoc! {r#"
+    @group(0) @binding(0) var<uniform> color: vec4<f32>;
+    @group(1) @binding(0) var<uniform> unused: vec4<f32>;
+    @group(2) @binding(0) var<uniform> scale: vec4<f32>;
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+      return color * scale;
+    }
+  "#};
+
+  let with_gap = generate_main(source, |b| b.omit_unused_bindings(true));
+
+  assert!(matches!(
+    with_gap,
+    Err(WgslBindgenError::ModuleCreationError(
+      CreateModuleError::NonConsecutiveBindGroups
+    ))
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_buffer_wrappers() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Camera { view_proj: mat4x4<f32> }
+    struct Light { color: vec4<f32> }
+    struct Lights { count: u32, items: array<Light> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(0) @binding(1) var<storage, read> lights: array<Light>;
+    @group(0) @binding(2) var<storage, read> packed_lights: Lights;
+    @group(0) @binding(3) var color_texture: texture_2d<f32>;
+
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+      let size = textureDimensions(color_texture);
+      return camera.view_proj * position * lights[0].color * packed_lights.items[0].color;
+    }
+  "#};
+
+  let actual = generate_main(source, |b| b.buffer_wrappers(true)).into_diagnostic()?;
+
+  assert!(actual.contains("pub struct CameraBuffer(pub wgpu::Buffer);"));
+  assert!(actual.contains("pub struct LightsBuffer(pub wgpu::Buffer);"));
+  assert!(!actual.contains("PackedLightsBuffer"));
+  assert!(!actual.contains("ColorTextureBuffer"));
+  assert!(actual
+    .contains("pub fn new(device: &wgpu::Device, data: &_root::main::Camera) -> Self"));
+  assert!(
+    actual.contains("pub fn new_with_capacity(device: &wgpu::Device, len: u64) -> Self")
+  );
+  assert!(actual.contains("size: len * 16,"));
+  assert!(actual.contains("wgpu::BufferUsages::UNIFORM"));
+  assert!(actual.contains("wgpu::BufferUsages::STORAGE"));
+  Ok(())
+}
+
+#[test]
+fn test_allow_bind_group_gaps() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Camera { view_proj: mat4x4<f32> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(2) @binding(0) var<uniform> object: Camera;
+
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+      return camera.view_proj * object.view_proj * position;
+    }
+  "#};
+
+  let strict = generate_main(source, |b| b);
+
+  assert!(matches!(
+    strict,
+    Err(WgslBindgenError::ModuleCreationError(
+      CreateModuleError::NonConsecutiveBindGroups
+    ))
+  ));
+
+  let actual =
+    generate_main(source, |b| b.allow_bind_group_gaps(true).cache_layouts(true))
+      .into_diagnostic()?;
+
+  assert!(actual.contains("pub struct WgpuBindGroup2("));
+  assert!(!actual.contains("pub struct WgpuBindGroup1("));
+  assert!(actual.contains(r#"label: Some("Main::BindGroup1::LayoutDescriptor"),"#));
+  assert!(actual.contains("entries: &[],"));
+  assert!(actual.contains("entries: [wgpu::BindGroupLayout; 3],"));
+  assert!(actual.contains("pub bind_group_layouts: [&'static wgpu::BindGroupLayout; 3],"));
+  assert!(actual.contains("self.bind_group2.set_with_offsets(pass, offsets[1]);"));
+  assert!(actual.contains("pub const BIND_GROUP_COUNT: u32 = 3;"));
+  assert!(actual.contains("Group0 = 0,"));
+  assert!(actual.contains("Group2 = 2,"));
+  assert!(!actual.contains("Group1 = 1,"));
+  assert!(actual.contains("Self::Group2 => &WgpuBindGroup2::LAYOUT_DESCRIPTOR"));
+  Ok(())
+}
+
+#[test]
+fn test_cache_layouts() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Camera { view_proj: mat4x4<f32> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(1) @binding(0) var<uniform> object: Camera;
+
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+      return camera.view_proj * object.view_proj * position;
+    }
+  "#};
+
+  let actual = generate_main(source, |b| b.cache_layouts(true)).into_diagnostic()?;
+
+  assert_eq!(
+    actual
+      .matches("pub fn get_cached_bind_group_layout(")
+      .count(),
+    2
+  );
+  assert_eq!(
+    actual
+      .matches("pub fn from_bindings_with_cached_layout(")
+      .count(),
+    2
+  );
+  assert!(actual.contains("pub bind_group_layouts: [&'static wgpu::BindGroupLayout; 2],"));
+  assert!(actual.contains("pub pipeline_layout: &'static wgpu::PipelineLayout,"));
+  Ok(())
+}
+
+#[test]
+fn test_binding_reflection_table() -> Result<()> {
+  let shader = r#"
+    struct Camera { view_proj: mat4x4<f32> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(0) @binding(1) var color_texture: texture_2d<f32>;
+
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+      let size = textureDimensions(color_texture);
+      return camera.view_proj * position;
+    }
+  "#;
+  let resolver = InMemoryImportResolver::new()
+    .with_source("virtual/a.wgsl", shader)
+    .with_source("virtual/b.wgsl", shader);
+
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("virtual/a.wgsl")
+    .add_entry_point("virtual/b.wgsl")
+    .workspace_root("virtual")
+    .import_resolver(resolver)
+    .binding_reflection_table(true)
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub struct BindingDesc"));
+  assert!(actual.contains(
+    "pub const ALL_BINDINGS: &[&[BindingDesc]] = &[a::BINDINGS, b::BINDINGS];"
+  ));
+  assert_eq!(
+    actual
+      .matches("pub const BINDINGS: &[BindingDesc] = &[")
+      .count(),
+    2
+  );
+  assert!(actual.contains(r#"module: "a","#));
+  assert!(actual.contains(r#"module: "b","#));
+  assert!(actual.contains(r#"name: "a::color_texture","#));
+  assert!(actual.contains(r#"name: "b::color_texture","#));
+  assert!(actual.contains("entry: WgpuBindGroup0::LAYOUT_DESCRIPTOR.entries[1],"));
+  Ok(())
+}
+
+#[test]
+fn test_conflicting_binding_types() -> Result<()> {
+  let generate = |b_settings: &str, allow: bool| {
+    let a = indoc::indoc! {r#"
+      struct Settings { scale: vec4<f32> }
+      @group(0) @binding(0) var<uniform> settings: Settings;
+
+      @vertex
+      fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+        return settings.scale * position;
+      }
+    "#};
+    let b = format!(
+      r#"
+      struct Settings {{ scale: vec4<f32> }}
+      {b_settings}
+
+      @fragment
+      fn fs_main() -> @location(0) vec4<f32> {{
+        return settings.scale;
+      }}
+      "#
+    );
+    let resolver = InMemoryImportResolver::new()
+      .with_source("virtual/a.wgsl", a)
+      .with_source("virtual/b.wgsl", b);
+
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("virtual/a.wgsl")
+      .add_entry_point("virtual/b.wgsl")
+      .workspace_root("virtual")
+      .import_resolver(resolver)
+      .allow_conflicting_binding_types(allow)
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .build()
+      .unwrap()
+      .generate_string_with_warnings()
+  };
+
+  // Only the visibility differs.
+  let (_, warnings) =
+    generate("@group(0) @binding(0) var<uniform> settings: Settings;", false)
+      .into_diagnostic()?;
+  assert!(warnings.is_empty());
+
+  let storage_settings = "@group(0) @binding(0) var<storage, read> settings: Settings;";
+  let conflicting = generate(storage_settings, false);
+  assert!(matches!(
+    conflicting,
+    Err(WgslBindgenError::ModuleCreationError(
+      CreateModuleError::ConflictingBindingTypes { name, modules }
+    )) if name == "settings" && modules == ["a", "b"]
+  ));
+
+  let (_, warnings) = generate(storage_settings, true).into_diagnostic()?;
+  assert_eq!(
+    warnings,
+    vec![WgslBindgenWarning::ConflictingBindingTypes {
+      name: "settings".to_string(),
+      modules: vec!["a".to_string(), "b".to_string()],
+    }]
+  );
+  Ok(())
+}
+
+#[test]
+fn test_share_identical_bind_groups() -> Result<()> {
+  let shader = |body: &str| {
+    format!(
+      r#"
+      struct Camera {{ view_proj: mat4x4<f32> }}
+
+      @group(0) @binding(0) var<uniform> camera: Camera;
+      @group(1) @binding(0) var color_texture: texture_2d<f32>;
+
+      @vertex
+      fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {{
+        {body}
+        return camera.view_proj * position;
+      }}
+      "#
+    )
+  };
+  let resolver = InMemoryImportResolver::new()
+    .with_source("virtual/a.wgsl", shader(""))
+    .with_source("virtual/b.wgsl", shader("let size = textureDimensions(color_texture);"))
+    .with_source(
+      "virtual/c.wgsl",
+      shader("let size = textureDimensions(color_texture);"),
+    );
+
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("virtual/a.wgsl")
+    .add_entry_point("virtual/b.wgsl")
+    .add_entry_point("virtual/c.wgsl")
+    .workspace_root("virtual")
+    .import_resolver(resolver)
+    .share_identical_bind_groups(true)
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub mod shared_bind_groups"));
+  assert_eq!(
+    actual
+      .matches("pub struct WgpuBindGroup1(wgpu::BindGroup);")
+      .count(),
+    1
+  );
+  assert_eq!(
+    actual
+      .matches("pub use _root::shared_bind_groups::layout0::*;")
+      .count(),
+    3
+  );
+  // Each entry declares its own `Camera`, so the uniform buffers differ in type.
+  assert_eq!(
+    actual
+      .matches("pub struct WgpuBindGroup0(wgpu::BindGroup);")
+      .count(),
+    3
+  );
+  Ok(())
+}
+
+#[test]
+fn test_label_format() -> Result<()> {
+  let generate = |label_format: WgslLabelFormat| -> Result<String> {
+    let source = indoc::indoc! {r#"
+      @group(0) @binding(0) var<uniform> scale: f32;
+
+      @vertex
+      fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+        return scale * position;
+      }
+    "#};
+    generate_main(source, |b| b.label_format(label_format)).into_diagnostic()
+  };
+
+  let actual = generate(WgslLabelFormat::Default)?;
+  assert!(actual.contains(r#"label: Some("Main::BindGroup0::LayoutDescriptor"),"#));
+  assert!(actual.contains(r#"label: Some("Main::BindGroup0"),"#));
+  assert!(actual.contains(r#"label: Some("Main::PipelineLayout"),"#));
+
+  let actual = generate(WgslLabelFormat::Custom(
+    "my_crate {path} {module} {kind}{group}".to_string(),
+  ))?;
+  assert!(actual
+    .contains(r#"label: Some("my_crate virtual/main.wgsl Main BindGroupLayout0"),"#));
+  assert!(
+    actual.contains(r#"label: Some("my_crate virtual/main.wgsl Main BindGroup0"),"#)
+  );
+  assert!(
+    actual.contains(r#"label: Some("my_crate virtual/main.wgsl Main PipelineLayout"),"#)
+  );
+
+  let actual = generate(WgslLabelFormat::None)?;
+  assert_eq!(actual.matches("label: None,").count(), 3);
+  Ok(())
+}
+
+#[test]
+fn test_push_constants() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct PushConstants { color: vec4<f32>, scale: f32 }
+
+    var<push_constant> pc: PushConstants;
+
+    @vertex
+    fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+      return position;
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+      return pc.color * pc.scale;
+    }
+  "#};
+
+  let actual = generate_main(source, |b| {
+    b.ir_capabilities(naga::valid::Capabilities::PUSH_CONSTANT)
+  })
+  .into_diagnostic()?;
+
+  assert!(actual.contains("pub struct PushConstants"));
+  assert!(actual.contains("push_constant_ranges: PUSH_CONSTANT_RANGES,"));
+  assert!(actual.contains("stages: wgpu::ShaderStages::FRAGMENT,"));
+  assert!(actual.contains("range: 0..32,"));
+  assert!(actual.contains("pass: &mut wgpu::RenderPass<'_>,"));
+  assert!(actual.contains("data: &_root::main::PushConstants,"));
+  assert!(actual.contains("&bytemuck::bytes_of(data)[..32]"));
+  Ok(())
+}
+
+#[test]
+fn test_dyn_entry_structs() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct VertexInput { @location(0) position: vec4<f32> }
+
+    @vertex
+    fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+      return in.position;
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+      return vec4<f32>(1.0);
+    }
+  "#};
+
+  let generate = |dyn_entry_structs: bool| -> Result<String> {
+    generate_main(source, |b| b.dyn_entry_structs(dyn_entry_structs)).into_diagnostic()
+  };
+
+  let actual = generate(true)?;
+  assert!(actual.contains("pub struct VertexEntryDyn {"));
+  assert!(actual.contains("pub buffers: Vec<wgpu::VertexBufferLayout<'static>>,"));
+  assert!(
+    actual.contains("impl<const N: usize> From<VertexEntry<N>> for VertexEntryDyn {")
+  );
+  assert!(actual.contains("pub fn vertex_state_dyn<'a>("));
+  assert!(actual.contains("pub struct FragmentEntryDyn {"));
+  assert!(actual.contains("pub targets: Vec<Option<wgpu::ColorTargetState>>,"));
+  assert!(actual.contains("pub fn fragment_state_dyn<'a>("));
+
+  let actual = generate(false)?;
+  assert!(!actual.contains("VertexEntryDyn"));
+  assert!(!actual.contains("FragmentEntryDyn"));
+  Ok(())
+}
+
+#[test]
+fn test_wgpu_version() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct VertexInput { @location(0) position: vec4<f32> }
+
+    @vertex
+    fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+      return in.position;
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+      return vec4<f32>(1.0);
+    }
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {}
+  "#};
+
+  let generate = |wgpu_version: WgpuVersion| -> Result<String> {
+    generate_main(source, |b| b.wgpu_version(wgpu_version).dyn_entry_structs(true))
+      .into_diagnostic()
+  };
+
+  // The vertex, fragment and dyn states and the dyn `From` impls.
+  let actual = generate(WgpuVersion::V22)?;
+  assert_eq!(actual.matches("entry_point: entry.entry_point,").count(), 6);
+  assert!(actual.contains("entry_point: ENTRY_CS_MAIN,"));
+  assert!(actual.contains(r#"entry_point: "cs_main","#));
+  assert!(!actual.contains("entry_point: Some("));
+
+  // Only the wgpu descriptors take an optional entry point, the generated structs don't.
+  let actual = generate(WgpuVersion::V23)?;
+  assert_eq!(
+    actual
+      .matches("entry_point: Some(entry.entry_point),")
+      .count(),
+    4
+  );
+  assert_eq!(actual.matches("entry_point: entry.entry_point,").count(), 2);
+  assert!(actual.contains("entry_point: Some(ENTRY_CS_MAIN),"));
+  assert!(actual.contains(r#"entry_point: Some("cs_main"),"#));
+  assert!(actual.contains("pub entry_point: &'static str,"));
+  Ok(())
+}
+
+#[test]
+fn test_dispatch_indirect_args() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct DispatchArgs { x: u32, y: u32, z: u32 }
+    struct PaddedCounts { groups_x: u32, groups_y: u32, groups_z: u32, extra: u32 }
+    struct NotArgs { x: u32, y: u32, w: u32 }
+
+    @group(0) @binding(0) var<storage, read_write> args: array<DispatchArgs>;
+    @group(0) @binding(1) var<storage, read_write> counts: PaddedCounts;
+    @group(0) @binding(2) var<storage, read_write> not_args: NotArgs;
+
+    @compute
+    @workgroup_size(1)
+    fn cs_main() {
+      args[0] = DispatchArgs(1u, 1u, 1u);
+      counts.groups_x = 1u;
+      not_args.x = 1u;
+    }
+  "#};
+
+  let actual = generate_main(source, |b| {
+    b.dispatch_indirect_struct_regex(Regex::new("PaddedCounts").unwrap())
+  })
+  .into_diagnostic()?;
+
+  assert!(
+    actual.contains("impl From<DispatchArgs> for wgpu::util::DispatchIndirectArgs {")
+  );
+  assert!(
+    actual.contains("impl From<wgpu::util::DispatchIndirectArgs> for DispatchArgs {")
+  );
+  assert!(actual.contains("const MAIN_DISPATCH_ARGS_DISPATCH_INDIRECT_ASSERTS: () = {"));
+
+  // The extra member can't be set from the indirect args and fails the size assertion.
+  assert!(
+    actual.contains("impl From<PaddedCounts> for wgpu::util::DispatchIndirectArgs {")
+  );
+  assert!(!actual.contains("for PaddedCounts {"));
+  assert!(actual.contains("x: args.groups_x,"));
+  assert!(actual.contains("const MAIN_PADDED_COUNTS_DISPATCH_INDIRECT_ASSERTS: () = {"));
+
+  assert!(!actual.contains("NotArgs> for"));
+
+  assert!(actual.contains("pub fn dispatch_indirect_cs_main("));
+  Ok(())
+}
+
+#[test]
+fn test_skip_workgroup_memory_zero_init() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    @vertex
+    fn vs_main() -> @builtin(position) vec4<f32> {
+      return vec4<f32>(0.0);
+    }
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {}
+  "#};
+
+  let generate = |skip: bool| -> Result<String> {
+    generate_main(source, |b| b.skip_workgroup_memory_zero_init(skip)).into_diagnostic()
+  };
+
+  let actual = generate(false)?;
+  assert!(actual.contains("zero_initialize_workgroup_memory: true,"));
+  assert!(!actual.contains("zero_initialize_workgroup_memory: false,"));
+  assert!(actual.contains("create_cs_main_pipeline_with_zero_init(device, module, true)"));
+
+  let actual = generate(true)?;
+  assert!(!actual.contains("zero_initialize_workgroup_memory: true,"));
+  // The vertex entry and the compute module pipelines.
+  assert_eq!(
+    actual
+      .matches("zero_initialize_workgroup_memory: false,")
+      .count(),
+    2
+  );
+  assert!(
+    actual.contains("create_cs_main_pipeline_with_zero_init(device, module, false)")
+  );
+  // The state functions use the value of the entry.
+  assert!(actual.contains(
+    "zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_skip_layout_assertions() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Particle { position: vec3<f32>, mass: f32 }
+
+    @group(0) @binding(0) var<storage, read_write> particles: array<Particle>;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      particles[0].mass = 1.0;
+    }
+  "#};
+
+  let generate = |skip: bool| -> Result<String> {
+    generate_main(source, |b| b.skip_layout_assertions(skip)).into_diagnostic()
+  };
+
+  let actual = generate(false)?;
+  assert!(actual.contains("const WGSL_BASE_TYPE_ASSERTS: () = {"));
+  assert!(actual.contains("const MAIN_PARTICLE_ASSERTS: () = {"));
+  assert!(actual.contains("std::mem::offset_of!(main::Particle, mass) == 12"));
+
+  let actual = generate(true)?;
+  assert!(!actual.contains("WGSL_BASE_TYPE_ASSERTS"));
+  assert!(!actual.contains("MAIN_PARTICLE_ASSERTS"));
+  assert!(!actual.contains("offset_of!"));
+  Ok(())
+}
+
+#[test]
+fn test_derive_default() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Light { position: vec4<f32>, intensity: f32 }
+
+    struct Settings { tint: vec4<f32>, scale: f32 }
+
+    struct Lights { settings: Settings, lights: array<Light, 64> }
+
+    struct Particles { count: u32, data: array<vec4<f32>> }
+
+    struct Bounds { min: vec4<f32>, max: vec4<f32> }
+
+    @group(0) @binding(0) var<uniform> lights: Lights;
+    @group(0) @binding(1) var<storage, read_write> particles: Particles;
+    @group(0) @binding(2) var<uniform> bounds: Bounds;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      particles.data[0] = lights.settings.tint * lights.lights[0].intensity + bounds.min;
+    }
+  "#};
+
+  let actual = generate_main(source, |b| b.derive_default(true)).into_diagnostic()?;
+
+  // Padded structs can not derive `Default` and get a manual impl instead.
+  assert!(actual.contains("impl Default for Light {"));
+  assert!(actual.contains("_pad_intensity: [0;"));
+  assert!(actual.contains("lights: [Default::default(); 64]"));
+  assert!(actual.contains("impl<const N: usize> Default for Particles<N> {"));
+  assert!(actual.contains("data: [Default::default(); N]"));
+  assert!(actual.contains("#[derive(Debug, PartialEq, Clone, Copy, Default)]"));
+  Ok(())
+}
+
+#[test]
+fn test_custom_derives_and_field_attributes() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Particle { position: vec4<f32>, mass: f32 }
+
+    @group(0) @binding(0) var<storage, read_write> particles: array<Particle>;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      particles[0].mass = 1.0;
+    }
+  "#};
+
+  let actual = generate_main(source, |b| {
+    b.add_custom_derive(
+      Regex::new("Particle$").unwrap(),
+      quote::quote!(#[derive(serde::Serialize, PartialEq)]),
+    )
+    .add_custom_derive(
+      Regex::new("main::.*").unwrap(),
+      quote::quote!(#[derive(serde::Serialize)]),
+    )
+    .add_custom_derive(
+      Regex::new("main::.*").unwrap(),
+      quote::quote!(#[serde(rename_all = "camelCase")]),
+    )
+    .add_custom_derive(
+      Regex::new("Particle").unwrap(),
+      quote::quote!(#[serde(rename_all = "camelCase")]),
+    )
+    .add_custom_field_attribute(
+      Regex::new("Particle").unwrap(),
+      Regex::new("mass").unwrap(),
+      quote::quote!(#[serde(skip)]),
+    )
+    .add_custom_field_attribute(
+      Regex::new(".*").unwrap(),
+      Regex::new("mass").unwrap(),
+      quote::quote!(#[serde(skip)]),
+    )
+  })
+  .into_diagnostic()?;
+
+  let derives = "#[derive(Debug, PartialEq, Clone, Copy, serde::Serialize)]\n    \
+                 #[serde(rename_all = \"camelCase\")]\n    pub struct Particle";
+  assert!(actual.contains(derives), "{actual}");
+  let derives = "#[derive(Debug, PartialEq, Clone, Copy, serde::Serialize)]\n    \
+                 #[serde(rename_all = \"camelCase\")]\n    pub struct ParticleInit";
+  assert!(actual.contains(derives), "{actual}");
+  assert_eq!(actual.matches("#[serde(skip)]").count(), 2);
+  assert!(actual.contains("#[serde(skip)]\n        pub mass: f32,"));
+  Ok(())
+}
+
+#[test]
+fn test_wrap_atomics() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Counters { hits: atomic<u32>, balance: atomic<i32>, scale: f32 }
+
+    @group(0) @binding(0) var<storage, read_write> counters: Counters;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      atomicAdd(&counters.hits, 1u);
+      atomicSub(&counters.balance, i32(counters.scale));
+    }
+  "#};
+
+  let generate = |wrap: bool| -> Result<String> {
+    generate_main(source, |b| b.wrap_atomics(wrap)).into_diagnostic()
+  };
+
+  let actual = generate(false)?;
+  assert!(actual.contains("pub hits: u32,"));
+  assert!(actual.contains("pub balance: i32,"));
+  assert!(!actual.contains("WgslAtomic"));
+
+  let actual = generate(true)?;
+  assert!(actual.contains("pub struct WgslAtomic<T>(pub T);"));
+  assert!(actual.contains("pub hits: _root::WgslAtomic<u32>,"));
+  assert!(actual.contains("pub balance: _root::WgslAtomic<i32>,"));
+  assert!(actual.contains("assert!(std::mem::offset_of!(main::Counters, balance) == 4);"));
+  Ok(())
+}
+
+#[test]
+fn test_runtime_sized_array_helpers() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Particle { position: vec3<f32>, mass: f32 }
+
+    struct Particles { origin: vec3<f32>, count: u32, data: array<Particle> }
+
+    @group(0) @binding(0) var<storage, read_write> particles: Particles;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      particles.data[0].position = particles.origin * f32(particles.count);
+    }
+  "#};
+
+  let actual = generate_main(source, |b| b).into_diagnostic()?;
+
+  assert!(actual.contains("impl Particles<0> {"));
+  assert!(actual.contains("pub const FIXED_SIZE: u64 = 16;"));
+  assert!(actual.contains("pub const ELEMENT_STRIDE: u64 = 16;"));
+  assert!(actual.contains("elements: &[_root::main::Particle],"));
+  // Only the 12 bytes of the `vec3<f32>` are written for the `glam::Vec3A` field.
+  assert!(actual.contains("let offset = std::mem::offset_of!(Self, origin);"));
+  assert!(
+    actual.contains("out[0..12].copy_from_slice(&header_bytes[offset..offset + 12]);")
+  );
+  assert!(
+    actual.contains("out[12..16].copy_from_slice(&header_bytes[offset..offset + 4]);")
+  );
+  // The minimum binding size covers the fields and one element.
+  assert!(actual.contains("min_binding_size: std::num::NonZeroU64::new(32),"));
+  Ok(())
+}
+
+#[test]
+fn test_override_struct_alignment_trailing_padding() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Uniforms { color: vec4<f32>, width: f32 }
+
+    @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+
+    @compute
+    @workgroup_size(1)
+    fn cs_main() {
+      let width = uniforms.width;
+    }
+  "#};
+
+  let actual = generate_main(source, |b| {
+    b.override_struct_alignment([(".*::Uniforms", 256)].map(Into::into))
+  })
+  .into_diagnostic()?;
+
+  assert!(actual.contains("#[repr(C, align(256))]"));
+  // The last member is padded up to the overridden alignment instead of leaving implicit
+  // padding, so that the struct is `Pod` and matches the stride of arrays of it.
+  assert!(actual.contains("pub _pad_width: [u8; 0xF0 - core::mem::size_of::<f32>()],"));
+  assert!(actual.contains("assert!(std::mem::size_of:: < main::Uniforms > () == 256);"));
+  Ok(())
+}
+
+#[test]
+fn test_struct_buffer_helpers() -> Result<()> {
+  let resolver = InMemoryImportResolver::new()
+    .with_source(
+      "virtual/a.wgsl",
+      indoc::indoc! {r#"
+        #import common::Params
+
+        struct Camera { view_proj: mat4x4<f32> }
+
+        @group(0) @binding(0) var<uniform> params: Params;
+        @group(0) @binding(1) var<uniform> camera: Camera;
+
+        @vertex
+        fn vs_main(@location(0) position: vec4<f32>) -> @builtin(position) vec4<f32> {
+          return camera.view_proj * position * params.scale;
+        }
+      "#},
+    )
+    .with_source(
+      "virtual/b.wgsl",
+      indoc::indoc! {r#"
+        #import common::Params
+
+        @group(0) @binding(0) var<storage, read_write> output_params: Params;
+
+        @compute
+        @workgroup_size(64)
+        fn cs_main() {
+          output_params.scale = 2.0;
+        }
+      "#},
+    )
+    .with_source("virtual/common.wgsl", "struct Params { scale: f32, count: u32 }");
+
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("virtual/a.wgsl")
+    .add_entry_point("virtual/b.wgsl")
+    .workspace_root("virtual")
+    .import_resolver(resolver)
+    .struct_buffer_helpers(true)
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert_eq!(actual.matches("pub const BUFFER_USAGES").count(), 2);
+  assert!(actual.contains(
+    "pub const BUFFER_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::COPY_DST\n            .union(wgpu::BufferUsages::UNIFORM)\n            .union(wgpu::BufferUsages::STORAGE);"
+  ));
+  assert!(actual.contains(
+    "pub const BUFFER_USAGES: wgpu::BufferUsages = wgpu::BufferUsages::COPY_DST\n            .union(wgpu::BufferUsages::UNIFORM);"
+  ));
+  assert_eq!(actual.matches("pub fn create_buffer_init(").count(), 2);
+  assert!(actual.contains(
+    "pub fn write(&self, queue: &wgpu::Queue, buffer: &wgpu::Buffer, offset: u64) {"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_vertex_format_overrides() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct VertexInput {
+      @location(0) position: vec3<f32>,
+      @location(1) color: vec4<f32>,
+      @location(2) normal: vec2<f32>,
+    }
+
+    @vertex
+    fn vs_main(in: VertexInput) -> @builtin(position) vec4<f32> {
+      return vec4<f32>(in.position, 1.0) * in.color * in.normal.x;
+    }
+  "#};
+
+  let generate = |normal_format: wgpu_types::VertexFormat| {
+    generate_main(source, |b| {
+      b.add_vertex_format_override(
+        Regex::new("VertexInput$").unwrap(),
+        Regex::new("^color$").unwrap(),
+        wgpu_types::VertexFormat::Unorm8x4,
+      )
+      .add_vertex_format_override(
+        Regex::new("VertexInput$").unwrap(),
+        Regex::new("^normal$").unwrap(),
+        normal_format,
+      )
+    })
+    .into_diagnostic()
+  };
+
+  let actual = generate(wgpu_types::VertexFormat::Snorm16x2)?;
+  assert!(actual.contains("pub position: glam::Vec3A,"));
+  assert!(actual.contains("pub color: [u8; 4],"));
+  assert!(actual.contains("pub normal: [i16; 2],"));
+  assert!(actual.contains("format: wgpu::VertexFormat::Float32x3,"));
+  assert!(actual.contains("format: wgpu::VertexFormat::Unorm8x4,"));
+  assert!(actual.contains("format: wgpu::VertexFormat::Snorm16x2,"));
+
+  let error = generate(wgpu_types::VertexFormat::Snorm16x4).unwrap_err();
+  assert!(error.to_string().contains(
+    "vertex format `Snorm16x4` of `main::VertexInput::normal` has 4 components, but the attribute has 2"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_skip_struct_regexps() -> Result<()> {
+  let utils = indoc::indoc! {r#"
+    struct Helper { a: vec3<f32>, b: f32 }
+    struct Params { scale: f32, count: u32 }
+
+    fn helper_sum(h: Helper) -> f32 { return h.a.x + h.b; }
+  "#};
+  let generate = |main: &str| {
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("virtual/main.wgsl")
+      .workspace_root("virtual")
+      .import_resolver(
+        InMemoryImportResolver::new()
+          .with_source("virtual/main.wgsl", main)
+          .with_source("virtual/utils.wgsl", utils),
+      )
+      .add_skip_struct_regexp(Regex::new("^utils::Helper$").unwrap())
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .build()?
+      .generate_string()
+      .into_diagnostic()
+  };
+
+  let actual = generate(indoc::indoc! {r#"
+    #import utils::{Helper, Params, helper_sum}
+
+    @group(0) @binding(0) var<uniform> params: Params;
+    var<workgroup> helper: Helper;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      helper.b = helper_sum(helper) * params.scale;
+    }
+  "#})?;
+  assert!(actual.contains("pub struct Params"));
+  assert!(!actual.contains("pub struct Helper"));
+  assert!(!actual.contains("UTILS_HELPER_ASSERTS"));
+
+  let error = generate(indoc::indoc! {r#"
+    #import utils::{Helper, helper_sum}
+
+    @group(0) @binding(0) var<storage, read_write> helpers: array<Helper>;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      helpers[0].b = helper_sum(helpers[1]);
+    }
+  "#})
+  .unwrap_err();
+  assert!(error.to_string().contains(
+    "struct `utils::Helper` is skipped by `skip_struct_regexps` but used by binding `main::helpers`"
+  ));
+
+  let error = generate(indoc::indoc! {r#"
+    #import utils::{Helper, helper_sum}
+
+    struct Wrapper { helper: Helper }
+
+    @group(0) @binding(0) var<uniform> wrapper: Wrapper;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      let sum = helper_sum(wrapper.helper);
+    }
+  "#})
+  .unwrap_err();
+  assert!(error.to_string().contains(
+    "struct `utils::Helper` is skipped by `skip_struct_regexps` but used by field `main::Wrapper::helper`"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_mint_type_map() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Uniforms {
+      a: vec2<f32>,
+      b: vec3<f32>,
+      c: vec4<u32>,
+      d: mat3x3<f32>,
+      e: mat4x2<f32>,
+      f: mat4x4<f32>,
+    }
+
+    @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+      return uniforms.f[0];
+    }
+  "#};
+
+  let generate = |strategy: WgslTypeSerializeStrategy| -> Result<String> {
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("virtual/main.wgsl")
+      .workspace_root("virtual")
+      .import_resolver(
+        InMemoryImportResolver::new().with_source("virtual/main.wgsl", source),
+      )
+      .serialization_strategy(strategy)
+      .type_map(MintWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .build()?
+      .generate_string()
+      .into_diagnostic()
+  };
+
+  let actual = generate(WgslTypeSerializeStrategy::Bytemuck)?;
+  assert!(actual.contains("pub a: mint::Vector2<f32>,"));
+  assert!(actual.contains("pub b: [f32; 4],"));
+  assert!(actual.contains("pub c: mint::Vector4<u32>,"));
+  assert!(actual.contains("pub d: [[f32; 4]; 3],"));
+  assert!(actual.contains("pub e: mint::ColumnMatrix2x4<f32>,"));
+  assert!(actual.contains("pub f: mint::ColumnMatrix4<f32>,"));
+  assert!(
+    actual.contains("assert!(std::mem::size_of:: < mint::Vector2 < f32 > > () == 8);")
+  );
+  assert!(
+    actual.contains("assert!(std::mem::align_of:: < mint::Vector2 < f32 > > () <= 8);")
+  );
+  assert!(!actual.contains("mint::Vector3"));
+  assert!(!actual.contains("mint::ColumnMatrix3<"));
+
+  let actual = generate(WgslTypeSerializeStrategy::Encase)?;
+  assert!(actual.contains("pub b: mint::Vector3<f32>,"));
+  assert!(actual.contains("pub d: mint::ColumnMatrix3<f32>,"));
+  assert!(!actual.contains("WGSL_BASE_TYPE_ASSERTS"));
+  Ok(())
+}
+
+#[test]
+fn test_serde_struct_regexps() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Config { exposure: f32, samples: u32 }
+    struct Scratch { value: f32 }
+
+    @group(0) @binding(0) var<uniform> config: Config;
+    @group(0) @binding(1) var<storage, read_write> scratch: Scratch;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      scratch.value = config.exposure * f32(config.samples);
+    }
+  "#};
+
+  let generate = |feature: Option<&str>| -> Result<String> {
+    generate_main(source, |b| {
+      b.add_serde_struct_regexp(Regex::new("::Config$").unwrap())
+        .always_generate_init_struct(true);
+      if let Some(feature) = feature {
+        b.serde_feature(feature);
+      }
+      b
+    })
+    .into_diagnostic()
+  };
+
+  let actual = generate(None)?;
+  assert_eq!(
+    actual
+      .matches(
+        "#[derive(Debug, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]"
+      )
+      .count(),
+    2
+  );
+  assert!(actual.contains(
+    "#[derive(Debug, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]\n    pub struct ConfigInit {"
+  ));
+  assert!(
+    actual.contains("#[derive(Debug, PartialEq, Clone, Copy)]\n    pub struct Scratch {")
+  );
+  assert!(actual
+    .contains("#[derive(Debug, PartialEq, Clone, Copy)]\n    pub struct ScratchInit {"));
+
+  let actual = generate(Some("serde"))?;
+  assert_eq!(
+    actual
+      .matches(
+        "#[cfg_attr(feature = \"serde\", derive(serde::Serialize, serde::Deserialize))]"
+      )
+      .count(),
+    2
+  );
+  assert!(!actual.contains("Copy, serde::Serialize"));
+  Ok(())
+}
+
+#[test]
+fn test_standard_derives() -> Result<()> {
+  let int_source = indoc::indoc! {r#"
+    struct Flags { mask: u32, ids: array<vec4<u32>, 2> }
+
+    @group(0) @binding(0) var<uniform> flags: Flags;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      let mask = flags.mask;
+    }
+  "#};
+  let float_source = indoc::indoc! {r#"
+    struct Params { scale: f32 }
+
+    @group(0) @binding(0) var<uniform> params: Params;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      let scale = params.scale;
+    }
+  "#};
+
+  let generate =
+    |source: &str, derives: enumflags2::BitFlags<StdDerive>| -> Result<String> {
+      generate_main(source, |b| b.standard_derives(derives)).into_diagnostic()
+    };
+
+  let actual = generate(float_source, StdDerive::Clone | StdDerive::Copy)?;
+  assert!(actual.contains("#[derive(Clone, Copy)]\n    pub struct Params {"));
+
+  let derives =
+    enumflags2::BitFlags::<StdDerive>::default() | StdDerive::Eq | StdDerive::Hash;
+  let actual = generate(int_source, derives)?;
+  assert!(actual.contains(
+    "#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]\n    pub struct Flags {"
+  ));
+
+  let error = generate(float_source, derives).unwrap_err();
+  assert!(error.to_string().contains(
+    "cannot derive `Eq` for struct `main::Params`: field `scale` contains floats"
+  ));
+
+  let error = generate(int_source, StdDerive::Debug | StdDerive::Clone).unwrap_err();
+  assert!(error.to_string().contains(
+    "cannot derive `Copy` for struct `main::Flags`: `Copy` is required by `bytemuck::Pod`, but not derived"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_wgsl_bool_fields() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Settings { enabled: u32, count: u32, scale: vec3<f32> }
+
+    @group(0) @binding(0) var<uniform> settings: Settings;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      let scale = settings.scale * f32(settings.enabled * settings.count);
+    }
+  "#};
+
+  let actual = generate_main(source, |b| {
+    b.add_wgsl_bool_field(
+      Regex::new("::Settings$").unwrap(),
+      Regex::new("^enabled$").unwrap(),
+    )
+  })
+  .into_diagnostic()?;
+
+  assert!(actual.contains("pub struct WgslBool(pub u32);"));
+  assert!(actual.contains("impl From<bool> for WgslBool {"));
+  assert!(actual.contains("impl From<WgslBool> for bool {"));
+  assert!(actual.contains("unsafe impl bytemuck::Pod for WgslBool {}"));
+  assert!(actual.contains("assert!(std::mem::size_of:: < WgslBool > () == 4);"));
+  assert!(actual.contains("pub enabled: _root::WgslBool,"));
+  assert!(actual.contains("pub count: u32,"));
+
+  // The Init struct takes a plain bool, since the struct has padding.
+  assert!(actual.contains("pub struct SettingsInit {\n        pub enabled: bool,"));
+  assert!(actual.contains("enabled: _root::WgslBool::new(self.enabled),"));
+  assert!(actual.contains("assert!(std::mem::offset_of!(main::Settings, count) == 4);"));
+  Ok(())
+}
+
+#[test]
+fn test_array_length_constants() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    const MAX_LIGHTS: u32 = 16;
+
+    struct Light { color: vec4<f32> }
+    struct Lights { lights: array<Light, MAX_LIGHTS>, weights: array<vec4<f32>, 4> }
+
+    @group(0) @binding(0) var<uniform> scene: Lights;
+    @group(0) @binding(1) var<storage, read> kernel: array<vec4<f32>, 9>;
+    @group(0) @binding(2) var<storage, read> particles: array<vec4<f32>>;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      let color = scene.lights[0].color * scene.weights[0] + kernel[0] + particles[0];
+    }
+  "#};
+
+  let actual =
+    generate_main(source, |b| b.array_length_constants(true)).into_diagnostic()?;
+
+  assert!(actual.contains("pub const LIGHTS_LEN: usize = 16;"));
+  assert!(actual.contains("pub const WEIGHTS_LEN: usize = 4;"));
+  assert!(actual.contains("pub const KERNEL_LEN: usize = 9;"));
+  assert!(!actual.contains("PARTICLES_LEN"));
+  Ok(())
+}
+
+#[test]
+fn test_field_offset_constants() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Light { position: vec3<f32>, intensity: f32 }
+    struct Camera { view_proj: mat4x4<f32>, eyePosition: vec3<f32>, light: Light, exposure: f32 }
+    struct Particles { count: u32, data: array<vec4<f32>> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(0) @binding(1) var<storage, read> particles: Particles;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      let position = camera.view_proj * vec4(camera.eyePosition + camera.light.position, camera.exposure);
+      let data = particles.data[particles.count];
+    }
+  "#};
+
+  let actual =
+    generate_main(source, |b| b.field_offset_constants(true)).into_diagnostic()?;
+
+  assert!(actual.contains(
+    r#"pub const FIELD_OFFSETS: &[(&str, u64)] = &[
+            ("view_proj", 0),
+            ("eyePosition", 64),
+            ("light", 80),
+            ("exposure", 96),
+        ];"#
+  ));
+  assert!(actual.contains("pub const VIEW_PROJ_OFFSET: u64 = 0;"));
+  assert!(actual.contains("pub const EYE_POSITION_OFFSET: u64 = 64;"));
+  assert!(actual.contains("pub const LIGHT_OFFSET: u64 = 80;"));
+  assert!(actual.contains("pub const EXPOSURE_OFFSET: u64 = 96;"));
+  assert!(actual.contains("pub const GPU_SIZE: u64 = 112;"));
+  assert!(actual.contains("pub const INTENSITY_OFFSET: u64 = 12;"));
+
+  // The size of a struct ending in a runtime-sized array isn't fixed.
+  assert!(actual.contains("pub const DATA_OFFSET: u64 = 16;"));
+  assert_eq!(actual.matches("pub const GPU_SIZE").count(), 2);
+  Ok(())
+}
+
+#[test]
+fn test_emit_layout_tests() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Camera { view_proj: mat4x4<f32>, position: vec4<f32>, exposure: f32 }
+    struct VertexInput { @location(0) position: vec4<f32> }
+    struct Particles { count: u32, data: array<vec4<f32>> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(0) @binding(1) var<storage, read> particles: Particles;
+
+    @vertex
+    fn vs_main(input: VertexInput) -> @builtin(position) vec4<f32> {
+      let data = particles.data[particles.count];
+      return camera.view_proj * (input.position + camera.position) * camera.exposure;
+    }
+  "#};
+
+  let generate = |serialization_strategy| {
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("virtual/main.wgsl")
+      .workspace_root("virtual")
+      .import_resolver(
+        InMemoryImportResolver::new().with_source("virtual/main.wgsl", source),
+      )
+      .emit_layout_tests(true)
+      .serialization_strategy(serialization_strategy)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .build()?
+      .generate_string()
+      .into_diagnostic()
+  };
+
+  let actual = generate(WgslTypeSerializeStrategy::Bytemuck)?;
+  assert!(actual.contains("#[cfg(test)]\nmod generated_layout_tests {"));
+  assert!(actual.contains("fn main_camera_layout() {"));
+  assert!(actual.contains("assert_eq!(std::mem::size_of:: < main::Camera > (), 96);"));
+  assert!(actual.contains("assert_eq!(std::mem::align_of:: < main::Camera > (), 16);"));
+  assert!(
+    actual.contains("assert_eq!(std::mem::offset_of!(main::Camera, exposure), 80);")
+  );
+  assert!(actual.contains("bytemuck::pod_read_unaligned::<main::Camera>(bytes);"));
+
+  // Vertex inputs aren't host sharable and runtime-sized structs have no fixed size.
+  assert!(!actual.contains("fn main_vertex_input_layout()"));
+  assert!(!actual.contains("fn main_particles_layout()"));
+
+  let actual = generate(WgslTypeSerializeStrategy::Encase)?;
+  assert!(actual.contains(
+    "assert_eq!(< main::Camera as encase::ShaderSize > ::SHADER_SIZE.get(), 96);"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_override_struct_field_type_exact() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Light { color: vec4<f32> }
+    struct PointLight { color: vec4<f32>, radius: f32 }
+
+    @group(0) @binding(0) var<uniform> light: Light;
+    @group(0) @binding(1) var<uniform> point_light: PointLight;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      let color = light.color + point_light.color * point_light.radius;
+    }
+  "#};
+
+  let generate = |field_path: &str| {
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("virtual/main.wgsl")
+      .workspace_root("virtual")
+      .import_resolver(
+        InMemoryImportResolver::new().with_source("virtual/main.wgsl", source),
+      )
+      .override_struct_field_type(vec![
+        ("Light$", "color", quote::quote!(glam::Vec4)).into()
+      ])
+      .add_override_struct_field_type_exact((field_path, quote::quote!(crate::Color)))
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(RustWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .build()?
+      .generate_string()
+      .into_diagnostic()
+  };
+
+  // The exact path takes precedence over the regex, which also matches `PointLight`.
+  let actual = generate("main::Light.color")?;
+  assert!(actual.contains("pub color: crate::Color,"));
+  assert!(actual.contains("pub struct PointLightInit {\n        pub color: glam::Vec4,"));
+
+  let error = generate("main::Lihgt.color").unwrap_err();
+  assert!(error.to_string().contains(
+    "struct field type override `main::Lihgt.color` doesn't match any struct field, did you mean `main::Light.color`?"
+  ));
+
+  let error = generate("Light.color").unwrap_err();
+  assert!(error.to_string().contains(
+    "struct field type override `Light.color` doesn't match any struct field, did you mean `main::Light.color`?"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_init_struct_conversions() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    struct Material { color: vec4<f32>, roughness: f32 }
+    struct Object { material: Material, position: vec2<f32>, enabled: u32 }
+
+    @group(0) @binding(0) var<uniform> object: Object;
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      let color = object.material.color.xy * object.material.roughness + object.position * f32(object.enabled);
+    }
+  "#};
+
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("virtual/main.wgsl")
+    .workspace_root("virtual")
+    .import_resolver(
+      InMemoryImportResolver::new().with_source("virtual/main.wgsl", source),
+    )
+    .always_generate_init_struct(true)
+    .add_wgsl_bool_field(
+      Regex::new("::Object$").unwrap(),
+      Regex::new("^enabled$").unwrap(),
+    )
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(RustWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("impl From<Object> for ObjectInit {"));
+  assert!(actual.contains("enabled: data.enabled.get(),"));
+  assert!(actual.contains("pub fn update_from(&mut self, init: &ObjectInit) {"));
+  assert!(actual.contains("self.material.update_from(&init.material.into());"));
+  assert!(actual.contains("self.position = init.position;"));
+  assert!(actual.contains("self.enabled = _root::WgslBool::new(init.enabled);"));
+  assert!(actual.contains("pub fn update_from(&mut self, init: &MaterialInit) {"));
+  assert!(actual.contains("self.roughness = init.roughness;"));
+  Ok(())
+}
+
+#[test]
+fn test_f64_structs_and_consts() -> Result<()> {
+  let source = indoc::indoc! {r#"
+    const SCALE: f64 = 0.1lf;
+
+    struct Params {
+      offset: f64,
+      origin: vec2<f64>,
+      position: vec3<f64>,
+      color: vec4<f64>,
+      transform: mat4x4<f64>,
+      count: u32,
+    }
+
+    @group(0) @binding(0) var<uniform> params: Params;
+    @group(0) @binding(1) var<storage, read_write> output: array<vec4<f64>>;
+
+    struct VertexInput {
+      @location(0) position: vec2<f64>,
+      @location(1) weight: f64,
+    }
+
+    @compute
+    @workgroup_size(64)
+    fn cs_main() {
+      output[params.count] = params.transform * params.color * (params.offset + params.origin.x + params.position.z) * SCALE;
+    }
+
+    @vertex
+    fn vs_main(input: VertexInput) -> @builtin(position) vec4<f32> {
+      return vec4<f32>(vec2<f32>(input.position * input.weight), 0.0, 1.0);
+    }
+  "#};
+
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("virtual/main.wgsl")
+    .workspace_root("virtual")
+    .import_resolver(
+      InMemoryImportResolver::new().with_source("virtual/main.wgsl", source),
+    )
+    .ir_capabilities(naga::valid::Capabilities::FLOAT64)
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(RustWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub const SCALE: f64 = 0.1f64;"));
+  assert!(actual.contains("#[repr(C, align(32))]"));
+  assert!(actual.contains("pub origin: [f64; 2],"));
+  assert!(actual.contains("pub position: [f64; 4],"));
+  assert!(actual.contains("pub transform: [[f64; 4]; 4],"));
+  assert!(actual.contains("assert!(std::mem::offset_of!(main::Params, count) == 224);"));
+  assert!(actual.contains("assert!(std::mem::size_of:: < main::Params > () == 256);"));
+  assert!(actual.contains("format: wgpu::VertexFormat::Float64x2,"));
+  assert!(actual.contains("format: wgpu::VertexFormat::Float64,"));
+  Ok(())
+}
+
+#[test]
+fn test_share_identical_structs() -> Result<()> {
+  let shared = indoc::indoc! {r#"
+    struct Light { color: vec4<f32> }
+    struct Camera { view_proj: mat4x4<f32>, light: Light }
+    struct VertexInput { @location(0) position: vec4<f32> }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+
+    @vertex
+    fn vs_main(input: VertexInput) -> @builtin(position) vec4<f32> {
+      return camera.view_proj * input.position;
+    }
+  "#};
+  let resolver = InMemoryImportResolver::new()
+    .with_source("virtual/a.wgsl", shared)
+    .with_source("virtual/b.wgsl", shared)
+    .with_source("virtual/c.wgsl", shared.replace("@location(0)", "@location(1)"));
+
+  let actual = WgslBindgenOptionBuilder::default()
+    .add_entry_point("virtual/a.wgsl")
+    .add_entry_point("virtual/b.wgsl")
+    .add_entry_point("virtual/c.wgsl")
+    .workspace_root("virtual")
+    .import_resolver(resolver)
+    .share_identical_structs(true)
+    .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+    .type_map(GlamWgslTypeMap)
+    .emit_rerun_if_change(false)
+    .skip_header_comments(true)
+    .build()?
+    .generate_string()
+    .into_diagnostic()?;
+
+  assert!(actual.contains("pub mod common_types"));
+  assert_eq!(actual.matches("pub struct Light {").count(), 1);
+  assert_eq!(actual.matches("pub struct Camera {").count(), 1);
+  assert_eq!(
+    actual
+      .matches("pub use _root::common_types::{Camera, Light};")
+      .count(),
+    3
+  );
+  // `c` reads the vertex input from another location, so each entry keeps its own
+  // `VertexInput` with a note.
+  assert_eq!(actual.matches("pub struct VertexInput {").count(), 3);
+  assert_eq!(
+    actual
+      .matches(
+        "/// `VertexInput` is defined differently by `c`, so it isn't shared in `common_types`."
+      )
+      .count(),
+    2
+  );
+  assert!(actual.contains(
+    "/// `VertexInput` is defined differently by `a`, `b`, so it isn't shared in `common_types`."
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_conflicting_struct_definitions() -> Result<()> {
+  let generate = |disambiguate: bool| {
+    let shader = |settings: &str| {
+      format!(
+        r#"
+        struct Settings {{ {settings} }}
+        @group(0) @binding(0) var<uniform> settings: Settings;
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {{
+          return settings.a;
+        }}
+        "#
+      )
+    };
+    let resolver = InMemoryImportResolver::new()
+      .with_source("virtual/a.wgsl", shader("a: vec4<f32>"))
+      .with_source("virtual/b.wgsl", shader("a: vec4<f32>, b: vec4<f32>"))
+      .with_source("virtual/c.wgsl", shader("a: vec4<f32>"));
+
+    WgslBindgenOptionBuilder::default()
+      .add_entry_point("virtual/a.wgsl")
+      .add_entry_point("virtual/b.wgsl")
+      .add_entry_point("virtual/c.wgsl")
+      .workspace_root("virtual")
+      .import_resolver(resolver)
+      .disambiguate_conflicting_structs(disambiguate)
+      .allow_conflicting_binding_types(true)
+      .serialization_strategy(WgslTypeSerializeStrategy::Bytemuck)
+      .type_map(GlamWgslTypeMap)
+      .emit_rerun_if_change(false)
+      .skip_header_comments(true)
+      .build()
+      .unwrap()
+      .generate_string_with_warnings()
+  };
+
+  let conflicting = generate(false);
+  assert!(matches!(
+    conflicting,
+    Err(WgslBindgenError::ModuleCreationError(
+      CreateModuleError::ConflictingStructDefinitions { name, modules }
+    )) if name == "Settings" && modules == ["a", "b", "c"]
+  ));
+
+  let (actual, warnings) = generate(true).into_diagnostic()?;
+  assert!(warnings.contains(&WgslBindgenWarning::ConflictingStructDefinitions {
+    name: "Settings".to_string(),
+    modules: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+  }));
+  assert!(!actual.contains("pub struct Settings {"));
+  assert_eq!(actual.matches("pub struct SettingsA {").count(), 2);
+  assert_eq!(actual.matches("pub struct SettingsB {").count(), 1);
+  assert_eq!(
+    actual
+      .matches(
+        "/// Renamed from the WGSL struct `Settings`, which is defined with a different layout by `b` as `SettingsB`."
+      )
+      .count(),
+    2
+  );
+  assert!(actual.contains(
+    "/// Renamed from the WGSL struct `Settings`, which is defined with a different layout by `a` as `SettingsA`, `c` as `SettingsA`."
+  ));
+  // The bindings use the renamed structs, while the shader sources keep the WGSL name.
+  assert!(actual.contains("std::mem::size_of::<_root::b::SettingsB>()"));
+  assert_eq!(actual.matches("struct Settings {").count(), 3);
+  Ok(())
+}
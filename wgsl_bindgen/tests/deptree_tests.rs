@@ -3,7 +3,7 @@ use std::sync::OnceLock;
 use indexmap::{indexmap, indexset, IndexMap};
 use miette::IntoDiagnostic;
 use pretty_assertions::assert_eq;
-use wgsl_bindgen::bevy_util::DependencyTree;
+use wgsl_bindgen::bevy_util::{DependencyTree, DependencyTreeError};
 use wgsl_bindgen::SourceFilePath;
 
 pub type SourceDependencyMap =
@@ -140,3 +140,78 @@ fn test_bevy_mesh_wgsl_dependency_order() {
     ]
   );
 }
+
+#[test]
+fn test_circular_import_is_reported() {
+  let result = DependencyTree::try_build(
+    "tests/shaders/circular".into(),
+    None,
+    vec![SourceFilePath::new("tests/shaders/circular/a.wgsl")],
+    vec![],
+  );
+
+  let Err(DependencyTreeError::CircularImport { chain }) = result else {
+    panic!("expected a circular import error");
+  };
+
+  assert_eq!(
+    chain,
+    vec![
+      "tests/shaders/circular/a.wgsl",
+      "tests/shaders/circular/b.wgsl",
+      "tests/shaders/circular/a.wgsl",
+    ]
+  );
+}
+
+fn build_scan_order_deptree(entry: &str) -> Result<DependencyTree, DependencyTreeError> {
+  DependencyTree::try_build(
+    "tests/shaders/scan_order".into(),
+    None,
+    vec![SourceFilePath::new(entry)],
+    vec![
+      (None, "tests/shaders/scan_order/first").into(),
+      (None, "tests/shaders/scan_order/second").into(),
+    ],
+  )
+}
+
+#[test]
+fn test_first_additional_scan_dir_wins() {
+  let deptree = build_scan_order_deptree("tests/shaders/scan_order/main.wgsl")
+    .into_diagnostic()
+    .expect("build_scan_order_deptree error");
+
+  let deps = deptree
+    .get_full_dependency_for(&SourceFilePath::new("tests/shaders/scan_order/main.wgsl"))
+    .into_iter()
+    .collect::<Vec<_>>();
+
+  assert_eq!(
+    deps,
+    vec![SourceFilePath::new(
+      "tests/shaders/scan_order/first/common/math.wgsl"
+    )]
+  );
+}
+
+#[test]
+fn test_missing_import_lists_searched_paths() {
+  let result = build_scan_order_deptree("tests/shaders/scan_order/unresolved.wgsl");
+
+  let Err(DependencyTreeError::ImportPathNotFound { searched_paths, .. }) = result else {
+    panic!("expected an import path not found error");
+  };
+
+  assert_eq!(
+    searched_paths,
+    vec![
+      "tests/shaders/scan_order/missing/ITEM.wgsl",
+      "tests/shaders/scan_order/missing.wgsl",
+      "tests/shaders/scan_order/first/missing/ITEM.wgsl",
+      "tests/shaders/scan_order/first/missing.wgsl",
+      "tests/shaders/scan_order/second/missing/ITEM.wgsl",
+      "tests/shaders/scan_order/second/missing.wgsl",
+    ]
+  );
+}
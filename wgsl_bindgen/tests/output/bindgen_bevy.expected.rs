@@ -304,6 +304,31 @@ pub mod bevy_pbr {
                     data.build()
                 }
             }
+            impl From<StandardMaterial> for StandardMaterialInit {
+                fn from(data: StandardMaterial) -> Self {
+                    Self {
+                        base_color: data.base_color,
+                        emissive: data.emissive,
+                        perceptual_roughness: data.perceptual_roughness,
+                        metallic: data.metallic,
+                        reflectance: data.reflectance,
+                        flags: data.flags,
+                        alpha_cutoff: data.alpha_cutoff,
+                    }
+                }
+            }
+            impl StandardMaterial {
+                /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+                pub fn update_from(&mut self, init: &StandardMaterialInit) {
+                    self.base_color = init.base_color;
+                    self.emissive = init.emissive;
+                    self.perceptual_roughness = init.perceptual_roughness;
+                    self.metallic = init.metallic;
+                    self.reflectance = init.reflectance;
+                    self.flags = init.flags;
+                    self.alpha_cutoff = init.alpha_cutoff;
+                }
+            }
             pub const STANDARD_MATERIAL_FLAGS_UNLIT_BIT: u32 = 32u32;
             pub const STANDARD_MATERIAL_FLAGS_DOUBLE_SIDED_BIT: u32 = 16u32;
             pub const STANDARD_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE: u32 = 64u32;
@@ -398,6 +423,35 @@ pub mod bevy_pbr {
                 data.build()
             }
         }
+        impl From<View> for ViewInit {
+            fn from(data: View) -> Self {
+                Self {
+                    view_proj: data.view_proj,
+                    inverse_view_proj: data.inverse_view_proj,
+                    view: data.view,
+                    inverse_view: data.inverse_view,
+                    projection: data.projection,
+                    inverse_projection: data.inverse_projection,
+                    world_position: data.world_position,
+                    width: data.width,
+                    height: data.height,
+                }
+            }
+        }
+        impl View {
+            /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+            pub fn update_from(&mut self, init: &ViewInit) {
+                self.view_proj = init.view_proj;
+                self.inverse_view_proj = init.inverse_view_proj;
+                self.view = init.view;
+                self.inverse_view = init.inverse_view;
+                self.projection = init.projection;
+                self.inverse_projection = init.inverse_projection;
+                self.world_position = init.world_position;
+                self.width = init.width;
+                self.height = init.height;
+            }
+        }
         #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct DirectionalLight {
@@ -468,6 +522,29 @@ pub mod bevy_pbr {
                 data.build()
             }
         }
+        impl From<DirectionalLight> for DirectionalLightInit {
+            fn from(data: DirectionalLight) -> Self {
+                Self {
+                    view_projection: data.view_projection,
+                    color: data.color,
+                    direction_to_light: data.direction_to_light,
+                    flags: data.flags,
+                    shadow_depth_bias: data.shadow_depth_bias,
+                    shadow_normal_bias: data.shadow_normal_bias,
+                }
+            }
+        }
+        impl DirectionalLight {
+            /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+            pub fn update_from(&mut self, init: &DirectionalLightInit) {
+                self.view_projection = init.view_projection;
+                self.color = init.color;
+                self.direction_to_light = init.direction_to_light;
+                self.flags = init.flags;
+                self.shadow_depth_bias = init.shadow_depth_bias;
+                self.shadow_normal_bias = init.shadow_normal_bias;
+            }
+        }
         #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct Lights {
@@ -536,6 +613,29 @@ pub mod bevy_pbr {
                 data.build()
             }
         }
+        impl From<Lights> for LightsInit {
+            fn from(data: Lights) -> Self {
+                Self {
+                    directional_lights: data.directional_lights,
+                    ambient_color: data.ambient_color,
+                    cluster_dimensions: data.cluster_dimensions,
+                    cluster_factors: data.cluster_factors,
+                    n_directional_lights: data.n_directional_lights,
+                    spot_light_shadowmap_offset: data.spot_light_shadowmap_offset,
+                }
+            }
+        }
+        impl Lights {
+            /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+            pub fn update_from(&mut self, init: &LightsInit) {
+                self.directional_lights = init.directional_lights;
+                self.ambient_color = init.ambient_color;
+                self.cluster_dimensions = init.cluster_dimensions;
+                self.cluster_factors = init.cluster_factors;
+                self.n_directional_lights = init.n_directional_lights;
+                self.spot_light_shadowmap_offset = init.spot_light_shadowmap_offset;
+            }
+        }
         #[repr(C, align(16))]
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct PointLight {
@@ -587,6 +687,36 @@ pub mod bevy_pbr {
                 Self { data }
             }
         }
+        impl PointLights<0> {
+            /// The size in bytes of the fields before the runtime-sized `data` array.
+            pub const FIXED_SIZE: u64 = 0;
+            /// The stride in bytes of the elements of the runtime-sized `data` array.
+            pub const ELEMENT_STRIDE: u64 = 64;
+            /// The size in bytes of a buffer holding the fields and `element_count` elements.
+            pub const fn buffer_size(element_count: u64) -> u64 {
+                Self::FIXED_SIZE + Self::ELEMENT_STRIDE * element_count
+            }
+            /// Writes the fields of `header` followed by `elements` into `out` with the WGSL
+            /// layout, zeroing any padding.
+            ///
+            /// Panics if `out` is smaller than [`Self::buffer_size`] for the elements.
+            pub fn write_into(
+                header: &Self,
+                elements: &[_root::bevy_pbr::mesh_view_types::PointLight],
+                out: &mut [u8],
+            ) {
+                let size = Self::buffer_size(elements.len() as u64) as usize;
+                let out = &mut out[..size];
+                out.fill(0);
+                let header_bytes = bytemuck::bytes_of(header);
+                for (i, element) in elements.iter().enumerate() {
+                    let offset = (Self::FIXED_SIZE + Self::ELEMENT_STRIDE * i as u64)
+                        as usize;
+                    out[offset..offset + 64]
+                        .copy_from_slice(&bytemuck::bytes_of(element)[..64]);
+                }
+            }
+        }
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct ClusterLightIndexLists<const N: usize> {
             /// size: 4, offset: 0x0, type: `array<u32>`
@@ -597,6 +727,32 @@ pub mod bevy_pbr {
                 Self { data }
             }
         }
+        impl ClusterLightIndexLists<0> {
+            /// The size in bytes of the fields before the runtime-sized `data` array.
+            pub const FIXED_SIZE: u64 = 0;
+            /// The stride in bytes of the elements of the runtime-sized `data` array.
+            pub const ELEMENT_STRIDE: u64 = 4;
+            /// The size in bytes of a buffer holding the fields and `element_count` elements.
+            pub const fn buffer_size(element_count: u64) -> u64 {
+                Self::FIXED_SIZE + Self::ELEMENT_STRIDE * element_count
+            }
+            /// Writes the fields of `header` followed by `elements` into `out` with the WGSL
+            /// layout, zeroing any padding.
+            ///
+            /// Panics if `out` is smaller than [`Self::buffer_size`] for the elements.
+            pub fn write_into(header: &Self, elements: &[u32], out: &mut [u8]) {
+                let size = Self::buffer_size(elements.len() as u64) as usize;
+                let out = &mut out[..size];
+                out.fill(0);
+                let header_bytes = bytemuck::bytes_of(header);
+                for (i, element) in elements.iter().enumerate() {
+                    let offset = (Self::FIXED_SIZE + Self::ELEMENT_STRIDE * i as u64)
+                        as usize;
+                    out[offset..offset + 4]
+                        .copy_from_slice(&bytemuck::bytes_of(element)[..4]);
+                }
+            }
+        }
         #[derive(Debug, PartialEq, Clone, Copy)]
         pub struct ClusterOffsetsAndCounts<const N: usize> {
             /// size: 16, offset: 0x0, type: `array<vec4<u32>>`
@@ -607,6 +763,32 @@ pub mod bevy_pbr {
                 Self { data }
             }
         }
+        impl ClusterOffsetsAndCounts<0> {
+            /// The size in bytes of the fields before the runtime-sized `data` array.
+            pub const FIXED_SIZE: u64 = 0;
+            /// The stride in bytes of the elements of the runtime-sized `data` array.
+            pub const ELEMENT_STRIDE: u64 = 16;
+            /// The size in bytes of a buffer holding the fields and `element_count` elements.
+            pub const fn buffer_size(element_count: u64) -> u64 {
+                Self::FIXED_SIZE + Self::ELEMENT_STRIDE * element_count
+            }
+            /// Writes the fields of `header` followed by `elements` into `out` with the WGSL
+            /// layout, zeroing any padding.
+            ///
+            /// Panics if `out` is smaller than [`Self::buffer_size`] for the elements.
+            pub fn write_into(header: &Self, elements: &[[u32; 4]], out: &mut [u8]) {
+                let size = Self::buffer_size(elements.len() as u64) as usize;
+                let out = &mut out[..size];
+                out.fill(0);
+                let header_bytes = bytemuck::bytes_of(header);
+                for (i, element) in elements.iter().enumerate() {
+                    let offset = (Self::FIXED_SIZE + Self::ELEMENT_STRIDE * i as u64)
+                        as usize;
+                    out[offset..offset + 16]
+                        .copy_from_slice(&bytemuck::bytes_of(element)[..16]);
+                }
+            }
+        }
         pub const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32 = 2u32;
         pub const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u32;
         pub const DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u32;
@@ -660,6 +842,23 @@ pub mod bevy_pbr {
                 data.build()
             }
         }
+        impl From<Mesh> for MeshInit {
+            fn from(data: Mesh) -> Self {
+                Self {
+                    model: data.model,
+                    inverse_transpose_model: data.inverse_transpose_model,
+                    flags: data.flags,
+                }
+            }
+        }
+        impl Mesh {
+            /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+            pub fn update_from(&mut self, init: &MeshInit) {
+                self.model = init.model;
+                self.inverse_transpose_model = init.inverse_transpose_model;
+                self.flags = init.flags;
+            }
+        }
         pub const MESH_FLAGS_SHADOW_RECEIVER_BIT: u32 = 1u32;
     }
     pub mod utils {
@@ -702,25 +901,25 @@ pub mod pbr {
     pub struct WgpuBindGroup0EntriesParams<'a> {
         pub view: wgpu::BufferBinding<'a>,
         pub lights: wgpu::BufferBinding<'a>,
-        pub point_lights: wgpu::BufferBinding<'a>,
-        pub cluster_light_index_lists: wgpu::BufferBinding<'a>,
-        pub cluster_offsets_and_counts: wgpu::BufferBinding<'a>,
         pub point_shadow_textures: &'a wgpu::TextureView,
         pub point_shadow_textures_sampler: &'a wgpu::Sampler,
         pub directional_shadow_textures: &'a wgpu::TextureView,
         pub directional_shadow_textures_sampler: &'a wgpu::Sampler,
+        pub point_lights: wgpu::BufferBinding<'a>,
+        pub cluster_light_index_lists: wgpu::BufferBinding<'a>,
+        pub cluster_offsets_and_counts: wgpu::BufferBinding<'a>,
     }
     #[derive(Clone, Debug)]
     pub struct WgpuBindGroup0Entries<'a> {
         pub view: wgpu::BindGroupEntry<'a>,
         pub lights: wgpu::BindGroupEntry<'a>,
-        pub point_lights: wgpu::BindGroupEntry<'a>,
-        pub cluster_light_index_lists: wgpu::BindGroupEntry<'a>,
-        pub cluster_offsets_and_counts: wgpu::BindGroupEntry<'a>,
         pub point_shadow_textures: wgpu::BindGroupEntry<'a>,
         pub point_shadow_textures_sampler: wgpu::BindGroupEntry<'a>,
         pub directional_shadow_textures: wgpu::BindGroupEntry<'a>,
         pub directional_shadow_textures_sampler: wgpu::BindGroupEntry<'a>,
+        pub point_lights: wgpu::BindGroupEntry<'a>,
+        pub cluster_light_index_lists: wgpu::BindGroupEntry<'a>,
+        pub cluster_offsets_and_counts: wgpu::BindGroupEntry<'a>,
     }
     impl<'a> WgpuBindGroup0Entries<'a> {
         pub fn new(params: WgpuBindGroup0EntriesParams<'a>) -> Self {
@@ -733,22 +932,6 @@ pub mod pbr {
                     binding: 1,
                     resource: wgpu::BindingResource::Buffer(params.lights),
                 },
-                point_lights: wgpu::BindGroupEntry {
-                    binding: 6,
-                    resource: wgpu::BindingResource::Buffer(params.point_lights),
-                },
-                cluster_light_index_lists: wgpu::BindGroupEntry {
-                    binding: 7,
-                    resource: wgpu::BindingResource::Buffer(
-                        params.cluster_light_index_lists,
-                    ),
-                },
-                cluster_offsets_and_counts: wgpu::BindGroupEntry {
-                    binding: 8,
-                    resource: wgpu::BindingResource::Buffer(
-                        params.cluster_offsets_and_counts,
-                    ),
-                },
                 point_shadow_textures: wgpu::BindGroupEntry {
                     binding: 2,
                     resource: wgpu::BindingResource::TextureView(
@@ -773,19 +956,35 @@ pub mod pbr {
                         params.directional_shadow_textures_sampler,
                     ),
                 },
+                point_lights: wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::Buffer(params.point_lights),
+                },
+                cluster_light_index_lists: wgpu::BindGroupEntry {
+                    binding: 7,
+                    resource: wgpu::BindingResource::Buffer(
+                        params.cluster_light_index_lists,
+                    ),
+                },
+                cluster_offsets_and_counts: wgpu::BindGroupEntry {
+                    binding: 8,
+                    resource: wgpu::BindingResource::Buffer(
+                        params.cluster_offsets_and_counts,
+                    ),
+                },
             }
         }
         pub fn as_array(self) -> [wgpu::BindGroupEntry<'a>; 9] {
             [
                 self.view,
                 self.lights,
-                self.point_lights,
-                self.cluster_light_index_lists,
-                self.cluster_offsets_and_counts,
                 self.point_shadow_textures,
                 self.point_shadow_textures_sampler,
                 self.directional_shadow_textures,
                 self.directional_shadow_textures_sampler,
+                self.point_lights,
+                self.cluster_light_index_lists,
+                self.cluster_offsets_and_counts,
             ]
         }
         pub fn collect<B: FromIterator<wgpu::BindGroupEntry<'a>>>(self) -> B {
@@ -827,6 +1026,42 @@ pub mod pbr {
                     },
                     count: None,
                 },
+                /// @binding(2): "_root::bevy_pbr::mesh_view_bindings::point_shadow_textures"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::CubeArray,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                /// @binding(3): "_root::bevy_pbr::mesh_view_bindings::point_shadow_textures_sampler"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                    count: None,
+                },
+                /// @binding(4): "_root::bevy_pbr::mesh_view_bindings::directional_shadow_textures"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                /// @binding(5): "_root::bevy_pbr::mesh_view_bindings::directional_shadow_textures_sampler"
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                    count: None,
+                },
                 /// @binding(6): "_root::bevy_pbr::mesh_view_bindings::point_lights"
                 wgpu::BindGroupLayoutEntry {
                     binding: 6,
@@ -836,7 +1071,7 @@ pub mod pbr {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(64),
                     },
                     count: None,
                 },
@@ -849,7 +1084,7 @@ pub mod pbr {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
+                        min_binding_size: std::num::NonZeroU64::new(4),
                     },
                     count: None,
                 },
@@ -862,46 +1097,10 @@ pub mod pbr {
                             read_only: true,
                         },
                         has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                /// @binding(2): "_root::bevy_pbr::mesh_view_bindings::point_shadow_textures"
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Depth,
-                        view_dimension: wgpu::TextureViewDimension::Cube,
-                        multisampled: false,
-                    },
-                    count: None,
-                },
-                /// @binding(3): "_root::bevy_pbr::mesh_view_bindings::point_shadow_textures_sampler"
-                wgpu::BindGroupLayoutEntry {
-                    binding: 3,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
-                    count: None,
-                },
-                /// @binding(4): "_root::bevy_pbr::mesh_view_bindings::directional_shadow_textures"
-                wgpu::BindGroupLayoutEntry {
-                    binding: 4,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Depth,
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
+                        min_binding_size: std::num::NonZeroU64::new(16),
                     },
                     count: None,
                 },
-                /// @binding(5): "_root::bevy_pbr::mesh_view_bindings::directional_shadow_textures_sampler"
-                wgpu::BindGroupLayoutEntry {
-                    binding: 5,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
-                    count: None,
-                },
             ],
         };
         pub fn get_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
@@ -926,6 +1125,13 @@ pub mod pbr {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(0, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(0, &self.0, offsets);
+        }
     }
     #[derive(Debug)]
     pub struct WgpuBindGroup1EntriesParams<'a> {
@@ -996,6 +1202,13 @@ pub mod pbr {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(1, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(1, &self.0, offsets);
+        }
     }
     #[derive(Debug)]
     pub struct WgpuBindGroup2EntriesParams<'a> {
@@ -1064,6 +1277,13 @@ pub mod pbr {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
             render_pass.set_bind_group(2, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::RenderPass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(2, &self.0, offsets);
+        }
     }
     #[derive(Debug, Copy, Clone)]
     pub struct WgpuBindGroups<'a> {
@@ -1077,6 +1297,37 @@ pub mod pbr {
             self.bind_group1.set(pass);
             self.bind_group2.set(pass);
         }
+        /// Sets all the bind groups with the given dynamic offsets, in group order.
+        pub fn set_with_offsets(
+            &self,
+            pass: &mut wgpu::RenderPass<'a>,
+            offsets: [&[wgpu::DynamicOffset]; 3],
+        ) {
+            self.bind_group0.set_with_offsets(pass, offsets[0]);
+            self.bind_group1.set_with_offsets(pass, offsets[1]);
+            self.bind_group2.set_with_offsets(pass, offsets[2]);
+        }
+    }
+    /// The number of bind group slots in the pipeline layout.
+    pub const BIND_GROUP_COUNT: u32 = 3;
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(u32)]
+    pub enum BindGroupIndex {
+        Group0 = 0,
+        Group1 = 1,
+        Group2 = 2,
+    }
+    impl BindGroupIndex {
+        pub const ALL: [Self; 3] = [Self::Group0, Self::Group1, Self::Group2];
+        pub fn layout_descriptor(
+            self,
+        ) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+            match self {
+                Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+                Self::Group1 => &WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+                Self::Group2 => &WgpuBindGroup2::LAYOUT_DESCRIPTOR,
+            }
+        }
     }
     pub fn set_bind_groups<'a>(
         pass: &mut wgpu::RenderPass<'a>,
@@ -1088,12 +1339,101 @@ pub mod pbr {
         bind_group1.set(pass);
         bind_group2.set(pass);
     }
+    pub fn set_bind_groups_with_offsets<'a>(
+        pass: &mut wgpu::RenderPass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+        bind_group0_offsets: &[wgpu::DynamicOffset],
+        bind_group1: &'a WgpuBindGroup1,
+        bind_group1_offsets: &[wgpu::DynamicOffset],
+        bind_group2: &'a WgpuBindGroup2,
+        bind_group2_offsets: &[wgpu::DynamicOffset],
+    ) {
+        bind_group0.set_with_offsets(pass, bind_group0_offsets);
+        bind_group1.set_with_offsets(pass, bind_group1_offsets);
+        bind_group2.set_with_offsets(pass, bind_group2_offsets);
+    }
+    /// Creates the layouts and all the bind groups from their entries, in group order.
+    pub fn create_bind_groups(
+        device: &wgpu::Device,
+        bind_group0: WgpuBindGroup0Entries,
+        bind_group1: WgpuBindGroup1Entries,
+        bind_group2: WgpuBindGroup2Entries,
+    ) -> (WgpuBindGroup0, WgpuBindGroup1, WgpuBindGroup2) {
+        (
+            WgpuBindGroup0::from_bindings(device, bind_group0),
+            WgpuBindGroup1::from_bindings(device, bind_group1),
+            WgpuBindGroup2::from_bindings(device, bind_group2),
+        )
+    }
+    pub const VIEW_GROUP: u32 = 0;
+    pub const VIEW_BINDING: u32 = 0;
+    pub const LIGHTS_GROUP: u32 = 0;
+    pub const LIGHTS_BINDING: u32 = 1;
+    pub const POINT_SHADOW_TEXTURES_GROUP: u32 = 0;
+    pub const POINT_SHADOW_TEXTURES_BINDING: u32 = 2;
+    pub const POINT_SHADOW_TEXTURES_SAMPLER_GROUP: u32 = 0;
+    pub const POINT_SHADOW_TEXTURES_SAMPLER_BINDING: u32 = 3;
+    pub const DIRECTIONAL_SHADOW_TEXTURES_GROUP: u32 = 0;
+    pub const DIRECTIONAL_SHADOW_TEXTURES_BINDING: u32 = 4;
+    pub const DIRECTIONAL_SHADOW_TEXTURES_SAMPLER_GROUP: u32 = 0;
+    pub const DIRECTIONAL_SHADOW_TEXTURES_SAMPLER_BINDING: u32 = 5;
+    pub const POINT_LIGHTS_GROUP: u32 = 0;
+    pub const POINT_LIGHTS_BINDING: u32 = 6;
+    pub const CLUSTER_LIGHT_INDEX_LISTS_GROUP: u32 = 0;
+    pub const CLUSTER_LIGHT_INDEX_LISTS_BINDING: u32 = 7;
+    pub const CLUSTER_OFFSETS_AND_COUNTS_GROUP: u32 = 0;
+    pub const CLUSTER_OFFSETS_AND_COUNTS_BINDING: u32 = 8;
+    pub const MATERIAL_GROUP: u32 = 1;
+    pub const MATERIAL_BINDING: u32 = 0;
+    pub const MESH_GROUP: u32 = 2;
+    pub const MESH_BINDING: u32 = 0;
+    /// Returns the group and binding index of the binding with the given name.
+    pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+        match name {
+            "view" => Some((0, 0)),
+            "lights" => Some((0, 1)),
+            "point_shadow_textures" => Some((0, 2)),
+            "point_shadow_textures_sampler" => Some((0, 3)),
+            "directional_shadow_textures" => Some((0, 4)),
+            "directional_shadow_textures_sampler" => Some((0, 5)),
+            "point_lights" => Some((0, 6)),
+            "cluster_light_index_lists" => Some((0, 7)),
+            "cluster_offsets_and_counts" => Some((0, 8)),
+            "material" => Some((1, 0)),
+            "mesh" => Some((2, 0)),
+            _ => None,
+        }
+    }
     pub const ENTRY_FRAGMENT: &str = "fragment";
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum EntryPoint {
+        Fragment,
+    }
+    impl EntryPoint {
+        pub const ALL: &'static [Self] = &[Self::Fragment];
+        pub fn name(self) -> &'static str {
+            match self {
+                Self::Fragment => ENTRY_FRAGMENT,
+            }
+        }
+        pub fn stage(self) -> wgpu::ShaderStages {
+            match self {
+                Self::Fragment => wgpu::ShaderStages::FRAGMENT,
+            }
+        }
+        /// The workgroup size of compute entry points.
+        pub fn workgroup_size(self) -> Option<[u32; 3]> {
+            match self {
+                Self::Fragment => None,
+            }
+        }
+    }
     #[derive(Debug)]
     pub struct FragmentEntry<const N: usize> {
         pub entry_point: &'static str,
         pub targets: [Option<wgpu::ColorTargetState>; N],
         pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
     }
     pub fn fragment_state<'a, const N: usize>(
         module: &'a wgpu::ShaderModule,
@@ -1105,6 +1445,7 @@ pub mod pbr {
             targets: &entry.targets,
             compilation_options: wgpu::PipelineCompilationOptions {
                 constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                 ..Default::default()
             },
         }
@@ -1116,8 +1457,57 @@ pub mod pbr {
             entry_point: ENTRY_FRAGMENT,
             targets,
             constants: Default::default(),
+            zero_initialize_workgroup_memory: true,
         }
     }
+    /// Calls [`fragment_entry`] with a single color target of `format`, without blending and writing all channels.
+    pub fn fragment_entry_single(format: wgpu::TextureFormat) -> FragmentEntry<1> {
+        fragment_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// Calls [`fragment_entry`] with a single color target of `format` blended with `blend`, writing all channels.
+    pub fn fragment_entry_single_blend(
+        format: wgpu::TextureFormat,
+        blend: wgpu::BlendState,
+    ) -> FragmentEntry<1> {
+        fragment_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// The output locations of the entry point in ascending order, with their scalar kinds:
+    /// - `0`: float
+    pub const FRAGMENT_OUTPUT_LOCATIONS: [u32; 1] = [0];
+    /// Returns the color targets of the entry point with the same format for every output,
+    /// without blending and writing all channels.
+    pub fn fragment_targets(
+        format: wgpu::TextureFormat,
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        fragment_targets_with_formats([format; 1])
+    }
+    /// Returns the color targets of the entry point with one format per output, in the order
+    /// of the output locations, without blending and writing all channels.
+    pub fn fragment_targets_with_formats(
+        formats: [wgpu::TextureFormat; 1],
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        [
+            Some(wgpu::ColorTargetState {
+                format: formats[0],
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ]
+    }
+    /// Whether the entry point writes `@builtin(frag_depth)`.
+    pub const FRAGMENT_WRITES_DEPTH: bool = false;
     #[derive(Debug)]
     pub struct WgpuPipelineLayout;
     impl WgpuPipelineLayout {
@@ -1127,18 +1517,42 @@ pub mod pbr {
             entries
         }
     }
+    /// The descriptor of the pipeline layout of the module with the given bind group layouts,
+    /// for callers that create or share the bind group layouts themselves.
+    pub fn pipeline_layout_descriptor<'a>(
+        bind_group_layouts: &'a [&'a wgpu::BindGroupLayout],
+    ) -> wgpu::PipelineLayoutDescriptor<'a> {
+        wgpu::PipelineLayoutDescriptor {
+            label: Some("Pbr::PipelineLayout"),
+            bind_group_layouts,
+            push_constant_ranges: &[],
+        }
+    }
     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
         device
             .create_pipeline_layout(
-                &wgpu::PipelineLayoutDescriptor {
-                    label: Some("Pbr::PipelineLayout"),
-                    bind_group_layouts: &[
+                &pipeline_layout_descriptor(
+                    &[
                         &WgpuBindGroup0::get_bind_group_layout(device),
                         &WgpuBindGroup1::get_bind_group_layout(device),
                         &WgpuBindGroup2::get_bind_group_layout(device),
                     ],
-                    push_constant_ranges: &[],
-                },
+                ),
+            )
+    }
+    /// Creates a pipeline layout with only the bind groups used by `fragment`.
+    pub fn create_pipeline_layout_for_fragment(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &pipeline_layout_descriptor(
+                    &[
+                        &WgpuBindGroup0::get_bind_group_layout(device),
+                        &WgpuBindGroup1::get_bind_group_layout(device),
+                        &WgpuBindGroup2::get_bind_group_layout(device),
+                    ],
+                ),
             )
     }
     pub fn create_shader_module_embed_source(
@@ -67,14 +67,14 @@ pub mod main {
         pub color: glam::Vec4,
         /// size: 4, offset: 0x10, type: `f32`
         pub width: f32,
-        pub _pad_width: [u8; 0x10 - core::mem::size_of::<f32>()],
+        pub _pad_width: [u8; 0xF0 - core::mem::size_of::<f32>()],
     }
     impl Style {
         pub const fn new(color: glam::Vec4, width: f32) -> Self {
             Self {
                 color,
                 width,
-                _pad_width: [0; 0x10 - core::mem::size_of::<f32>()],
+                _pad_width: [0; 0xF0 - core::mem::size_of::<f32>()],
             }
         }
     }
@@ -89,7 +89,7 @@ pub mod main {
             Style {
                 color: self.color,
                 width: self.width,
-                _pad_width: [0; 0x10 - core::mem::size_of::<f32>()],
+                _pad_width: [0; 0xF0 - core::mem::size_of::<f32>()],
             }
         }
     }
@@ -98,6 +98,21 @@ pub mod main {
             data.build()
         }
     }
+    impl From<Style> for StyleInit {
+        fn from(data: Style) -> Self {
+            Self {
+                color: data.color,
+                width: data.width,
+            }
+        }
+    }
+    impl Style {
+        /// Overwrites the fields with the fields of `init`, keeping the padding bytes.
+        pub fn update_from(&mut self, init: &StyleInit) {
+            self.color = init.color;
+            self.width = init.width;
+        }
+    }
     #[derive(Debug)]
     pub struct WgpuBindGroup0EntriesParams<'a> {
         pub buffer: wgpu::BufferBinding<'a>,
@@ -218,6 +233,13 @@ pub mod main {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
             render_pass.set_bind_group(0, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::ComputePass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(0, &self.0, offsets);
+        }
     }
     #[derive(Debug)]
     pub struct WgpuBindGroup1EntriesParams<'a> {
@@ -286,6 +308,13 @@ pub mod main {
         pub fn set<'a>(&'a self, render_pass: &mut wgpu::ComputePass<'a>) {
             render_pass.set_bind_group(1, &self.0, &[]);
         }
+        pub fn set_with_offsets<'a>(
+            &'a self,
+            render_pass: &mut wgpu::ComputePass<'a>,
+            offsets: &[wgpu::DynamicOffset],
+        ) {
+            render_pass.set_bind_group(1, &self.0, offsets);
+        }
     }
     #[derive(Debug, Copy, Clone)]
     pub struct WgpuBindGroups<'a> {
@@ -297,6 +326,34 @@ pub mod main {
             self.bind_group0.set(pass);
             self.bind_group1.set(pass);
         }
+        /// Sets all the bind groups with the given dynamic offsets, in group order.
+        pub fn set_with_offsets(
+            &self,
+            pass: &mut wgpu::ComputePass<'a>,
+            offsets: [&[wgpu::DynamicOffset]; 2],
+        ) {
+            self.bind_group0.set_with_offsets(pass, offsets[0]);
+            self.bind_group1.set_with_offsets(pass, offsets[1]);
+        }
+    }
+    /// The number of bind group slots in the pipeline layout.
+    pub const BIND_GROUP_COUNT: u32 = 2;
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(u32)]
+    pub enum BindGroupIndex {
+        Group0 = 0,
+        Group1 = 1,
+    }
+    impl BindGroupIndex {
+        pub const ALL: [Self; 2] = [Self::Group0, Self::Group1];
+        pub fn layout_descriptor(
+            self,
+        ) -> &'static wgpu::BindGroupLayoutDescriptor<'static> {
+            match self {
+                Self::Group0 => &WgpuBindGroup0::LAYOUT_DESCRIPTOR,
+                Self::Group1 => &WgpuBindGroup1::LAYOUT_DESCRIPTOR,
+            }
+        }
     }
     pub fn set_bind_groups<'a>(
         pass: &mut wgpu::ComputePass<'a>,
@@ -306,8 +363,72 @@ pub mod main {
         bind_group0.set(pass);
         bind_group1.set(pass);
     }
+    pub fn set_bind_groups_with_offsets<'a>(
+        pass: &mut wgpu::ComputePass<'a>,
+        bind_group0: &'a WgpuBindGroup0,
+        bind_group0_offsets: &[wgpu::DynamicOffset],
+        bind_group1: &'a WgpuBindGroup1,
+        bind_group1_offsets: &[wgpu::DynamicOffset],
+    ) {
+        bind_group0.set_with_offsets(pass, bind_group0_offsets);
+        bind_group1.set_with_offsets(pass, bind_group1_offsets);
+    }
+    /// Creates the layouts and all the bind groups from their entries, in group order.
+    pub fn create_bind_groups(
+        device: &wgpu::Device,
+        bind_group0: WgpuBindGroup0Entries,
+        bind_group1: WgpuBindGroup1Entries,
+    ) -> (WgpuBindGroup0, WgpuBindGroup1) {
+        (
+            WgpuBindGroup0::from_bindings(device, bind_group0),
+            WgpuBindGroup1::from_bindings(device, bind_group1),
+        )
+    }
+    pub const BUFFER_GROUP: u32 = 0;
+    pub const BUFFER_BINDING: u32 = 0;
+    pub const TEXTURE_FLOAT_GROUP: u32 = 0;
+    pub const TEXTURE_FLOAT_BINDING: u32 = 1;
+    pub const TEXTURE_SINT_GROUP: u32 = 0;
+    pub const TEXTURE_SINT_BINDING: u32 = 2;
+    pub const TEXTURE_UINT_GROUP: u32 = 0;
+    pub const TEXTURE_UINT_BINDING: u32 = 3;
+    pub const O_N_E_GROUP: u32 = 1;
+    pub const O_N_E_BINDING: u32 = 0;
+    /// Returns the group and binding index of the binding with the given name.
+    pub fn binding_index_of(name: &str) -> Option<(u32, u32)> {
+        match name {
+            "buffer" => Some((0, 0)),
+            "texture_float" => Some((0, 1)),
+            "texture_sint" => Some((0, 2)),
+            "texture_uint" => Some((0, 3)),
+            "ONE" => Some((1, 0)),
+            _ => None,
+        }
+    }
     pub mod compute {
         pub const MAIN_WORKGROUP_SIZE: [u32; 3] = [1, 1, 1];
+        /// Returns the number of workgroups needed to cover `work_items`, rounding up.
+        pub const fn main_dispatch_count(work_items: [u32; 3]) -> [u32; 3] {
+            [
+                work_items[0].div_ceil(MAIN_WORKGROUP_SIZE[0]),
+                work_items[1].div_ceil(MAIN_WORKGROUP_SIZE[1]),
+                work_items[2].div_ceil(MAIN_WORKGROUP_SIZE[2]),
+            ]
+        }
+        /// Dispatches enough workgroups to cover `work_items` with the pipeline set on `pass`.
+        pub fn dispatch_main(pass: &mut wgpu::ComputePass<'_>, work_items: [u32; 3]) {
+            let [x, y, z] = main_dispatch_count(work_items);
+            pass.dispatch_workgroups(x, y, z);
+        }
+        /// Dispatches the workgroup counts stored in `buffer` at `offset`, laid out like
+        /// `wgpu::util::DispatchIndirectArgs`, with the pipeline set on `pass`.
+        pub fn dispatch_indirect_main(
+            pass: &mut wgpu::ComputePass<'_>,
+            buffer: &wgpu::Buffer,
+            offset: wgpu::BufferAddress,
+        ) {
+            pass.dispatch_workgroups_indirect(buffer, offset);
+        }
         pub fn create_main_pipeline_embed_source(
             device: &wgpu::Device,
         ) -> wgpu::ComputePipeline {
@@ -320,7 +441,10 @@ pub mod main {
                         layout: Some(&layout),
                         module: &module,
                         entry_point: "main",
-                        compilation_options: Default::default(),
+                        compilation_options: wgpu::PipelineCompilationOptions {
+                            zero_initialize_workgroup_memory: true,
+                            ..Default::default()
+                        },
                         cache: None,
                     },
                 )
@@ -342,13 +466,69 @@ pub mod main {
                         layout: Some(&layout),
                         module: &module,
                         entry_point: "main",
-                        compilation_options: Default::default(),
+                        compilation_options: wgpu::PipelineCompilationOptions {
+                            zero_initialize_workgroup_memory: true,
+                            ..Default::default()
+                        },
                         cache: None,
                     },
                 )
         }
     }
     pub const ENTRY_MAIN: &str = "main";
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum EntryPoint {
+        Main,
+    }
+    impl EntryPoint {
+        pub const ALL: &'static [Self] = &[Self::Main];
+        pub fn name(self) -> &'static str {
+            match self {
+                Self::Main => ENTRY_MAIN,
+            }
+        }
+        pub fn stage(self) -> wgpu::ShaderStages {
+            match self {
+                Self::Main => wgpu::ShaderStages::COMPUTE,
+            }
+        }
+        /// The workgroup size of compute entry points.
+        pub fn workgroup_size(self) -> Option<[u32; 3]> {
+            match self {
+                Self::Main => Some(compute::MAIN_WORKGROUP_SIZE),
+            }
+        }
+    }
+    /// Calls [`create_main_pipeline_with_zero_init`] with `zero_initialize_workgroup_memory` set to `true`.
+    pub fn create_main_pipeline(
+        device: &wgpu::Device,
+        module: &wgpu::ShaderModule,
+    ) -> wgpu::ComputePipeline {
+        create_main_pipeline_with_zero_init(device, module, true)
+    }
+    pub fn create_main_pipeline_with_zero_init(
+        device: &wgpu::Device,
+        module: &wgpu::ShaderModule,
+        zero_initialize_workgroup_memory: bool,
+    ) -> wgpu::ComputePipeline {
+        let layout = create_pipeline_layout(device);
+        let constants: std::collections::HashMap<String, f64> = Default::default();
+        device
+            .create_compute_pipeline(
+                &wgpu::ComputePipelineDescriptor {
+                    label: Some("Main::main::ComputePipeline"),
+                    layout: Some(&layout),
+                    module,
+                    entry_point: ENTRY_MAIN,
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: &constants,
+                        zero_initialize_workgroup_memory,
+                        ..Default::default()
+                    },
+                    cache: None,
+                },
+            )
+    }
     #[derive(Debug)]
     pub struct WgpuPipelineLayout;
     impl WgpuPipelineLayout {
@@ -358,24 +538,56 @@ pub mod main {
             entries
         }
     }
+    /// The descriptor of the pipeline layout of the module with the given bind group layouts,
+    /// for callers that create or share the bind group layouts themselves.
+    pub fn pipeline_layout_descriptor<'a>(
+        bind_group_layouts: &'a [&'a wgpu::BindGroupLayout],
+    ) -> wgpu::PipelineLayoutDescriptor<'a> {
+        wgpu::PipelineLayoutDescriptor {
+            label: Some("Main::PipelineLayout"),
+            bind_group_layouts,
+            push_constant_ranges: PUSH_CONSTANT_RANGES,
+        }
+    }
     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
         device
             .create_pipeline_layout(
-                &wgpu::PipelineLayoutDescriptor {
-                    label: Some("Main::PipelineLayout"),
-                    bind_group_layouts: &[
+                &pipeline_layout_descriptor(
+                    &[
                         &WgpuBindGroup0::get_bind_group_layout(device),
                         &WgpuBindGroup1::get_bind_group_layout(device),
                     ],
-                    push_constant_ranges: &[
-                        wgpu::PushConstantRange {
-                            stages: wgpu::ShaderStages::COMPUTE,
-                            range: 0..32,
-                        },
+                ),
+            )
+    }
+    /// Creates a pipeline layout with only the bind groups used by `main`.
+    pub fn create_pipeline_layout_for_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device
+            .create_pipeline_layout(
+                &pipeline_layout_descriptor(
+                    &[
+                        &WgpuBindGroup0::get_bind_group_layout(device),
+                        &WgpuBindGroup1::get_bind_group_layout(device),
                     ],
-                },
+                ),
             )
     }
+    pub const PUSH_CONSTANT_RANGES: &[wgpu::PushConstantRange] = &[
+        wgpu::PushConstantRange {
+            stages: wgpu::ShaderStages::COMPUTE,
+            range: 0..32,
+        },
+    ];
+    /// Sets the push constants from the start of `data`, leaving out any padding the
+    /// Rust type has past the size of the shader type.
+    pub fn set_push_constants(
+        pass: &mut wgpu::ComputePass<'_>,
+        data: &_root::main::Style,
+    ) {
+        pass.set_push_constants(0, &bytemuck::bytes_of(data)[..32]);
+    }
     pub fn create_shader_module_embed_source(
         device: &wgpu::Device,
     ) -> wgpu::ShaderModule {
@@ -492,6 +704,18 @@ fn main(@builtin(global_invocation_id) id: vec3<u32>) {
                 }),
         )
     }
+    /// Creates the shader module from the shader files if they all exist, e.g. to hot reload
+    /// them during development, or else from the embedded source, which ignores `shader_defs`.
+    pub fn create_shader_module_from_path_or_embedded(
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<String, naga_oil::compose::ShaderDefValue>,
+    ) -> Result<wgpu::ShaderModule, naga_oil::compose::ComposerError> {
+        if SHADER_PATHS.iter().all(|path| std::path::Path::new(path).exists()) {
+            create_shader_module_from_path(device, shader_defs)
+        } else {
+            Ok(create_shader_module_embed_source(device))
+        }
+    }
 }
 pub mod bytemuck_impls {
     use super::{_root, _root::*};
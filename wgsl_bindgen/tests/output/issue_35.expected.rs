@@ -73,11 +73,39 @@ pub mod clear {
     use super::{_root, _root::*};
     pub const ENTRY_VERTEX_MAIN: &str = "vertex_main";
     pub const ENTRY_FRAGMENT_MAIN: &str = "fragment_main";
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum EntryPoint {
+        VertexMain,
+        FragmentMain,
+    }
+    impl EntryPoint {
+        pub const ALL: &'static [Self] = &[Self::VertexMain, Self::FragmentMain];
+        pub fn name(self) -> &'static str {
+            match self {
+                Self::VertexMain => ENTRY_VERTEX_MAIN,
+                Self::FragmentMain => ENTRY_FRAGMENT_MAIN,
+            }
+        }
+        pub fn stage(self) -> wgpu::ShaderStages {
+            match self {
+                Self::VertexMain => wgpu::ShaderStages::VERTEX,
+                Self::FragmentMain => wgpu::ShaderStages::FRAGMENT,
+            }
+        }
+        /// The workgroup size of compute entry points.
+        pub fn workgroup_size(self) -> Option<[u32; 3]> {
+            match self {
+                Self::VertexMain => None,
+                Self::FragmentMain => None,
+            }
+        }
+    }
     #[derive(Debug)]
     pub struct VertexEntry<const N: usize> {
         pub entry_point: &'static str,
         pub buffers: [wgpu::VertexBufferLayout<'static>; N],
         pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
     }
     pub fn vertex_state<'a, const N: usize>(
         module: &'a wgpu::ShaderModule,
@@ -89,6 +117,7 @@ pub mod clear {
             buffers: &entry.buffers,
             compilation_options: wgpu::PipelineCompilationOptions {
                 constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                 ..Default::default()
             },
         }
@@ -98,13 +127,20 @@ pub mod clear {
             entry_point: ENTRY_VERTEX_MAIN,
             buffers: [vertices::VertexIn::vertex_buffer_layout(vertex_in)],
             constants: Default::default(),
+            zero_initialize_workgroup_memory: true,
         }
     }
+    /// Calls [`vertex_main_entry`] with `Instance` step mode for the buffers whose names match `(?i)instance`, and `Vertex` for the others:
+    /// - `VertexIn`: `Vertex`
+    pub fn vertex_main_entry_default() -> VertexEntry<1> {
+        vertex_main_entry(wgpu::VertexStepMode::Vertex)
+    }
     #[derive(Debug)]
     pub struct FragmentEntry<const N: usize> {
         pub entry_point: &'static str,
         pub targets: [Option<wgpu::ColorTargetState>; N],
         pub constants: std::collections::HashMap<String, f64>,
+        pub zero_initialize_workgroup_memory: bool,
     }
     pub fn fragment_state<'a, const N: usize>(
         module: &'a wgpu::ShaderModule,
@@ -116,6 +152,7 @@ pub mod clear {
             targets: &entry.targets,
             compilation_options: wgpu::PipelineCompilationOptions {
                 constants: &entry.constants,
+                zero_initialize_workgroup_memory: entry.zero_initialize_workgroup_memory,
                 ..Default::default()
             },
         }
@@ -127,8 +164,97 @@ pub mod clear {
             entry_point: ENTRY_FRAGMENT_MAIN,
             targets,
             constants: Default::default(),
+            zero_initialize_workgroup_memory: true,
         }
     }
+    /// Calls [`fragment_main_entry`] with a single color target of `format`, without blending and writing all channels.
+    pub fn fragment_main_entry_single(format: wgpu::TextureFormat) -> FragmentEntry<1> {
+        fragment_main_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// Calls [`fragment_main_entry`] with a single color target of `format` blended with `blend`, writing all channels.
+    pub fn fragment_main_entry_single_blend(
+        format: wgpu::TextureFormat,
+        blend: wgpu::BlendState,
+    ) -> FragmentEntry<1> {
+        fragment_main_entry([
+            Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ])
+    }
+    /// The output locations of the entry point in ascending order, with their scalar kinds:
+    /// - `0`: float
+    pub const FRAGMENT_MAIN_OUTPUT_LOCATIONS: [u32; 1] = [0];
+    /// Returns the color targets of the entry point with the same format for every output,
+    /// without blending and writing all channels.
+    pub fn fragment_main_targets(
+        format: wgpu::TextureFormat,
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        fragment_main_targets_with_formats([format; 1])
+    }
+    /// Returns the color targets of the entry point with one format per output, in the order
+    /// of the output locations, without blending and writing all channels.
+    pub fn fragment_main_targets_with_formats(
+        formats: [wgpu::TextureFormat; 1],
+    ) -> [Option<wgpu::ColorTargetState>; 1] {
+        [
+            Some(wgpu::ColorTargetState {
+                format: formats[0],
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }),
+        ]
+    }
+    /// Whether the entry point writes `@builtin(frag_depth)`.
+    pub const FRAGMENT_MAIN_WRITES_DEPTH: bool = false;
+    /// The entries and states of a render pipeline. The optional states default to a
+    /// triangle list without culling, no depth stencil and no multisampling.
+    #[derive(Debug)]
+    pub struct CreatePipelineArgs<const N: usize, const M: usize> {
+        pub vertex: VertexEntry<N>,
+        pub fragment: FragmentEntry<M>,
+        pub primitive: Option<wgpu::PrimitiveState>,
+        pub depth_stencil: Option<wgpu::DepthStencilState>,
+        pub multisample: Option<wgpu::MultisampleState>,
+    }
+    pub fn render_pipeline_descriptor<'a, const N: usize, const M: usize>(
+        layout: &'a wgpu::PipelineLayout,
+        module: &'a wgpu::ShaderModule,
+        args: &'a CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipelineDescriptor<'a> {
+        wgpu::RenderPipelineDescriptor {
+            label: Some("Clear::RenderPipeline"),
+            layout: Some(layout),
+            vertex: vertex_state(module, &args.vertex),
+            primitive: args.primitive.unwrap_or_default(),
+            depth_stencil: args.depth_stencil.clone(),
+            multisample: args.multisample.unwrap_or_default(),
+            fragment: Some(fragment_state(module, &args.fragment)),
+            multiview: None,
+            cache: None,
+        }
+    }
+    pub fn create_pipeline_embedded<const N: usize, const M: usize>(
+        device: &wgpu::Device,
+        shader_defs: std::collections::HashMap<
+            String,
+            naga_oil::compose::ShaderDefValue,
+        >,
+        args: CreatePipelineArgs<N, M>,
+    ) -> wgpu::RenderPipeline {
+        let module = create_shader_module_embedded(device, shader_defs);
+        let layout = create_pipeline_layout(device);
+        device
+            .create_render_pipeline(&render_pipeline_descriptor(&layout, &module, &args))
+    }
     #[derive(Debug)]
     pub struct WgpuPipelineLayout;
     impl WgpuPipelineLayout {
@@ -138,15 +264,31 @@ pub mod clear {
             entries
         }
     }
+    /// The descriptor of the pipeline layout of the module with the given bind group layouts,
+    /// for callers that create or share the bind group layouts themselves.
+    pub fn pipeline_layout_descriptor<'a>(
+        bind_group_layouts: &'a [&'a wgpu::BindGroupLayout],
+    ) -> wgpu::PipelineLayoutDescriptor<'a> {
+        wgpu::PipelineLayoutDescriptor {
+            label: Some("Clear::PipelineLayout"),
+            bind_group_layouts,
+            push_constant_ranges: &[],
+        }
+    }
     pub fn create_pipeline_layout(device: &wgpu::Device) -> wgpu::PipelineLayout {
-        device
-            .create_pipeline_layout(
-                &wgpu::PipelineLayoutDescriptor {
-                    label: Some("Clear::PipelineLayout"),
-                    bind_group_layouts: &[],
-                    push_constant_ranges: &[],
-                },
-            )
+        device.create_pipeline_layout(&pipeline_layout_descriptor(&[]))
+    }
+    /// Creates a pipeline layout with only the bind groups used by `vertex_main`.
+    pub fn create_pipeline_layout_for_vertex_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device.create_pipeline_layout(&pipeline_layout_descriptor(&[]))
+    }
+    /// Creates a pipeline layout with only the bind groups used by `fragment_main`.
+    pub fn create_pipeline_layout_for_fragment_main(
+        device: &wgpu::Device,
+    ) -> wgpu::PipelineLayout {
+        device.create_pipeline_layout(&pipeline_layout_descriptor(&[]))
     }
     pub fn load_shader_modules_embedded(
         composer: &mut naga_oil::compose::Composer,
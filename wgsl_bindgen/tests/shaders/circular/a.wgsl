@@ -0,0 +1,5 @@
+#import b;
+
+fn a_value() -> f32 {
+  return b::b_value();
+}
@@ -0,0 +1,5 @@
+#import a;
+
+fn b_value() -> f32 {
+  return 1.0;
+}
@@ -0,0 +1 @@
+const ONE: f32 = 1.0;
@@ -0,0 +1,6 @@
+#import common::math::{ONE}
+
+@compute @workgroup_size(1)
+fn main() {
+  let one = ONE;
+}
@@ -0,0 +1 @@
+const ONE: f32 = 2.0;
@@ -0,0 +1,6 @@
+#import missing::{ITEM}
+
+@compute @workgroup_size(1)
+fn main() {
+  let item = ITEM;
+}
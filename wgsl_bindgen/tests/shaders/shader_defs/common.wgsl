@@ -0,0 +1,11 @@
+struct Uniforms {
+  color: vec4f,
+}
+
+fn shadow_factor(strength: f32) -> f32 {
+#ifdef SHADOW_PCF
+  return strength * 0.5;
+#else
+  return strength;
+#endif
+}
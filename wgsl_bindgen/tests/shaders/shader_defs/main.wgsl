@@ -0,0 +1,22 @@
+#import common::{Uniforms, shadow_factor}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+#ifdef SHADOWS
+struct ShadowParams {
+  strength: f32,
+}
+
+@group(1) @binding(0)
+var<uniform> shadow_params: ShadowParams;
+#endif
+
+@fragment
+fn fs_main() -> @location(0) vec4f {
+#ifdef SHADOWS
+  return uniforms.color * shadow_factor(shadow_params.strength);
+#else
+  return uniforms.color;
+#endif
+}